@@ -0,0 +1,1639 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::backtesting::metrics::{
+    AdvancedRiskMetrics, GeneralPerformanceMetrics, MarketConditionMetrics, OptionsSpecificMetrics,
+};
+use crate::backtesting::results::BacktestResult;
+use crate::backtesting::types::{
+    CapitalUtilization, DrawdownAnalysis, DrawdownEvent, ExitReason, TimeSeriesData, TradeRecord,
+    TradeStatistics, VolatilityData,
+};
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::{MetricsError, PositionError};
+use crate::greeks::{Greeks, GreeksSnapshot, side_sign};
+use crate::model::Position;
+use crate::pricing::black_scholes;
+use crate::risk::{MarginCalculator, MarginMethod};
+use crate::series::OptionSeries;
+use crate::strategies::base::Positionable;
+use crate::utils::stats::{mean, quantile};
+use crate::{ExpirationDate, OptionStyle, Options, Positive, Side};
+use chrono::{DateTime, Utc};
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::error::Error;
+use tracing::{debug, trace};
+use uuid::Uuid;
+
+const SECONDS_PER_DAY: Decimal = dec!(86400);
+const DAYS_PER_YEAR: Decimal = dec!(365);
+
+/// A point-in-time view of the market that the backtest engine replays.
+///
+/// A snapshot holds the underlying price and every option chain available at
+/// `timestamp`. Positions are matched against the chain whose expiration falls on
+/// the same calendar day as the option, and against the row with the same strike.
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    /// The moment in time the snapshot represents.
+    pub timestamp: DateTime<Utc>,
+    /// Price of the underlying asset at `timestamp`.
+    pub underlying_price: Positive,
+    /// Option chains quoted at `timestamp`, one per expiration.
+    pub chains: Vec<OptionChain>,
+}
+
+impl MarketSnapshot {
+    /// Creates a snapshot from a single option chain.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The moment in time the chain was observed.
+    /// * `chain` - The option chain observed at `timestamp`.
+    pub fn from_chain(timestamp: DateTime<Utc>, chain: OptionChain) -> Self {
+        MarketSnapshot {
+            timestamp,
+            underlying_price: chain.underlying_price,
+            chains: vec![chain],
+        }
+    }
+
+    /// Creates a snapshot from every chain contained in an option series.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The moment in time the series was observed.
+    /// * `series` - The option series observed at `timestamp`.
+    pub fn from_series(timestamp: DateTime<Utc>, series: &OptionSeries) -> Self {
+        MarketSnapshot {
+            timestamp,
+            underlying_price: series.underlying_price,
+            chains: series.chains.values().cloned().collect(),
+        }
+    }
+
+    /// Finds the chain whose expiration falls on the same calendar day as `expiration`.
+    ///
+    /// # Returns
+    ///
+    /// The matching chain, or `None` if no chain in the snapshot expires that day.
+    pub fn get_chain(&self, expiration: &ExpirationDate) -> Option<&OptionChain> {
+        let target = expiration.get_date().ok()?.date_naive();
+        self.chains.iter().find(|chain| {
+            chain
+                .get_expiration()
+                .and_then(|exp| exp.get_date().ok())
+                .is_some_and(|date| date.date_naive() == target)
+        })
+    }
+
+    /// Finds the quoted row for the strike and expiration of `option`.
+    ///
+    /// # Returns
+    ///
+    /// The `OptionData` quoted for the option, or `None` if the market does not list it.
+    pub fn get_option_data(&self, option: &Options) -> Option<&OptionData> {
+        self.get_chain(&option.expiration_date)?
+            .get_optiondata_with_strike(&option.strike_price)
+            .ok()
+    }
+
+    /// Returns the at-the-money implied volatility of the nearest chain in the snapshot.
+    pub fn get_atm_implied_volatility(&self) -> Option<Positive> {
+        self.chains
+            .iter()
+            .find_map(|chain| chain.get_atm_implied_volatility().ok().copied())
+    }
+}
+
+/// Settings that control how the backtest engine fills orders and accounts for capital.
+#[derive(Debug, Clone)]
+pub struct BacktestConfig {
+    /// Name reported in `BacktestResult::strategy_name`.
+    pub strategy_name: String,
+    /// Cash available at the start of the backtest.
+    pub initial_capital: Positive,
+    /// When `true`, positions are closed at the bid (longs) or ask (shorts) and the
+    /// distance to the mid price is recorded as slippage. When `false` they close at mid.
+    pub fill_at_touch: bool,
+    /// Fraction of the underlying notional reserved as margin for each short option,
+    /// on top of the option's current market value. Only applied when `margin` is `None`.
+    #[deprecated(note = "set `margin` instead; this rate only applies when `margin` is `None`")]
+    pub short_margin_rate: Decimal,
+    /// Rules used to size the margin of the open positions, which also backs
+    /// `OptionsSpecificMetrics::return_on_margin`. `None` falls back to
+    /// `short_margin_rate`.
+    pub margin: Option<MarginMethod>,
+    /// Annual risk-free rate used for the Sharpe and Sortino ratios.
+    pub risk_free_rate: Decimal,
+    /// When `true`, positions still open on the last snapshot are closed at market.
+    pub close_positions_at_end: bool,
+}
+
+impl Default for BacktestConfig {
+    #[allow(deprecated)]
+    fn default() -> Self {
+        BacktestConfig {
+            strategy_name: "Backtest".to_string(),
+            initial_capital: Positive::HUNDRED * Positive::THOUSAND,
+            fill_at_touch: false,
+            short_margin_rate: dec!(0.2),
+            margin: Some(MarginMethod::default()),
+            risk_free_rate: Decimal::ZERO,
+            close_positions_at_end: true,
+        }
+    }
+}
+
+/// A position currently held by the backtest engine.
+#[derive(Debug, Clone)]
+pub struct OpenPosition {
+    /// Identifier shared with the `TradeRecord` of this position.
+    pub id: Uuid,
+    /// The position as it was opened.
+    pub position: Position,
+    /// The latest per-contract market price of the option.
+    pub mark_price: Positive,
+}
+
+impl OpenPosition {
+    /// Signed market value of the position: positive for longs, negative for shorts.
+    pub fn market_value(&self) -> Decimal {
+        let value = self.mark_price * self.position.option.quantity;
+        match self.position.option.side {
+            Side::Long => value.to_dec(),
+            Side::Short => -value.to_dec(),
+        }
+    }
+
+    /// Profit or loss of the position if it were closed at the current mark, fees included.
+    pub fn unrealized_pnl(&self) -> Result<Decimal, PositionError> {
+        self.position.unrealized_pnl(self.mark_price)
+    }
+}
+
+/// Read-only view of the engine state handed to a [`BacktestPolicy`] on every step.
+#[derive(Debug)]
+pub struct BacktestContext<'a> {
+    /// Index of the current snapshot.
+    pub step: usize,
+    /// The market data for the current step.
+    pub snapshot: &'a MarketSnapshot,
+    /// Positions open after marking to market and settling expirations.
+    pub positions: &'a [OpenPosition],
+    /// Cash balance, including premiums received and paid.
+    pub cash: Decimal,
+    /// Cash plus the market value of the open positions.
+    pub equity: Decimal,
+}
+
+/// An instruction returned by a [`BacktestPolicy`].
+#[derive(Debug, Clone)]
+pub enum BacktestAction {
+    /// Opens a new position. Its `date` is overwritten with the snapshot timestamp.
+    Open(Position),
+    /// Closes the open position with the given identifier.
+    Close {
+        /// Identifier of the position to close.
+        id: Uuid,
+        /// Why the position is being closed.
+        reason: ExitReason,
+    },
+    /// Replaces an open position with a new one, closing the former as a roll.
+    Adjust {
+        /// Identifier of the position to replace.
+        id: Uuid,
+        /// The position opened in its place.
+        position: Position,
+    },
+    /// Closes every open position.
+    CloseAll {
+        /// Why the positions are being closed.
+        reason: ExitReason,
+    },
+}
+
+/// Trading logic driven by the backtest engine.
+///
+/// The engine calls `on_step` once per snapshot, after open positions have been
+/// marked to market and expired options have been settled. The returned actions are
+/// executed against the same snapshot, in order.
+pub trait BacktestPolicy {
+    /// Decides which positions to open, adjust or close at the current step.
+    ///
+    /// # Arguments
+    ///
+    /// * `context` - The current market snapshot and the state of the portfolio.
+    ///
+    /// # Returns
+    ///
+    /// The actions to execute, or an error that aborts the backtest.
+    fn on_step(
+        &mut self,
+        context: &BacktestContext<'_>,
+    ) -> Result<Vec<BacktestAction>, Box<dyn Error>>;
+}
+
+/// Event-driven engine that replays market snapshots through a [`BacktestPolicy`].
+///
+/// At every step the engine marks the open positions to market using the mid price
+/// quoted in the snapshot (falling back to Black-Scholes when the option is not listed),
+/// settles expired options at intrinsic value, executes the policy's actions and
+/// records the equity, margin and Greek exposure of the portfolio. Once the last
+/// snapshot is processed the collected data is summarised into a [`BacktestResult`].
+///
+/// Options should carry an `ExpirationDate::DateTime` expiration so that time to
+/// expiry is measured from each snapshot rather than from the wall clock.
+#[derive(Debug, Clone)]
+pub struct BacktestEngine {
+    config: BacktestConfig,
+    snapshots: Vec<MarketSnapshot>,
+}
+
+impl BacktestEngine {
+    /// Creates an engine over the given snapshots, sorted by timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Capital and execution settings.
+    /// * `snapshots` - The market data to replay.
+    pub fn new(config: BacktestConfig, mut snapshots: Vec<MarketSnapshot>) -> Self {
+        snapshots.sort_by_key(|snapshot| snapshot.timestamp);
+        BacktestEngine { config, snapshots }
+    }
+
+    /// Creates an engine that replays a sequence of option chains.
+    pub fn from_chains(config: BacktestConfig, chains: Vec<(DateTime<Utc>, OptionChain)>) -> Self {
+        let snapshots = chains
+            .into_iter()
+            .map(|(timestamp, chain)| MarketSnapshot::from_chain(timestamp, chain))
+            .collect();
+        Self::new(config, snapshots)
+    }
+
+    /// Creates an engine that replays a sequence of option series.
+    pub fn from_series(config: BacktestConfig, series: &[(DateTime<Utc>, OptionSeries)]) -> Self {
+        let snapshots = series
+            .iter()
+            .map(|(timestamp, series)| MarketSnapshot::from_series(*timestamp, series))
+            .collect();
+        Self::new(config, snapshots)
+    }
+
+    /// Returns the engine configuration.
+    pub fn get_config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    /// Returns the snapshots replayed by the engine, in chronological order.
+    pub fn get_snapshots(&self) -> &[MarketSnapshot] {
+        &self.snapshots
+    }
+
+    /// Runs the backtest.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The trading logic deciding which positions to open and close.
+    ///
+    /// # Returns
+    ///
+    /// A `BacktestResult` with performance, trade, drawdown, capital, volatility,
+    /// market-condition and risk metrics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there are no snapshots, if the policy fails, if it
+    /// references an unknown position or opens an invalid one, or if a position
+    /// cannot be priced.
+    pub fn run<P: BacktestPolicy + ?Sized>(
+        &self,
+        policy: &mut P,
+    ) -> Result<BacktestResult, Box<dyn Error>> {
+        if self.snapshots.is_empty() {
+            return Err("Backtest requires at least one market snapshot".into());
+        }
+        let mut state = BacktestState::new(self.config.initial_capital.to_dec());
+        let last_step = self.snapshots.len() - 1;
+
+        for (step, snapshot) in self.snapshots.iter().enumerate() {
+            state.mark_to_market(snapshot)?;
+            state.settle_expirations(snapshot)?;
+
+            let actions = {
+                let context = BacktestContext {
+                    step,
+                    snapshot,
+                    positions: &state.open,
+                    cash: state.cash,
+                    equity: state.equity(),
+                };
+                policy.on_step(&context)?
+            };
+            trace!("Step {} produced {} actions", step, actions.len());
+            state.execute(actions, snapshot, &self.config)?;
+
+            if step == last_step && self.config.close_positions_at_end {
+                state.close_all(
+                    snapshot,
+                    ExitReason::Other("End of backtest".to_string()),
+                    &self.config,
+                )?;
+            }
+            state.record(snapshot, &self.config)?;
+        }
+
+        Ok(state.into_result(&self.config, &self.snapshots))
+    }
+}
+
+/// Bid, ask and mid price of an option at a given snapshot.
+struct Quote {
+    bid: Option<Positive>,
+    ask: Option<Positive>,
+    mid: Positive,
+}
+
+/// Mutable portfolio state accumulated while replaying snapshots.
+struct BacktestState {
+    cash: Decimal,
+    open: Vec<OpenPosition>,
+    trades: Vec<TradeRecord>,
+    trade_index: HashMap<Uuid, usize>,
+    time_series: TimeSeriesData,
+    peak_equity: Decimal,
+    capital_used: Vec<Decimal>,
+    position_sizes: Vec<Decimal>,
+    spread_trades: usize,
+    atm_ivs: Vec<Option<Positive>>,
+    total_fees: Decimal,
+}
+
+impl BacktestState {
+    fn new(initial_capital: Decimal) -> Self {
+        BacktestState {
+            cash: initial_capital,
+            open: Vec::new(),
+            trades: Vec::new(),
+            trade_index: HashMap::new(),
+            time_series: TimeSeriesData {
+                delta_exposure: Some(Vec::new()),
+                gamma_exposure: Some(Vec::new()),
+                theta_exposure: Some(Vec::new()),
+                vega_exposure: Some(Vec::new()),
+                ..Default::default()
+            },
+            peak_equity: initial_capital,
+            capital_used: Vec::new(),
+            position_sizes: Vec::new(),
+            spread_trades: 0,
+            atm_ivs: Vec::new(),
+            total_fees: Decimal::ZERO,
+        }
+    }
+
+    fn equity(&self) -> Decimal {
+        self.cash
+            + self
+                .open
+                .iter()
+                .map(OpenPosition::market_value)
+                .sum::<Decimal>()
+    }
+
+    fn mark_to_market(&mut self, snapshot: &MarketSnapshot) -> Result<(), Box<dyn Error>> {
+        for open in self.open.iter_mut() {
+            open.mark_price = quote(snapshot, &open.position.option)?.mid;
+        }
+        Ok(())
+    }
+
+    fn settle_expirations(&mut self, snapshot: &MarketSnapshot) -> Result<(), Box<dyn Error>> {
+        let expired: Vec<Uuid> = self
+            .open
+            .iter()
+            .filter(|open| {
+                open.position
+                    .option
+                    .expiration_date
+                    .get_date()
+                    .is_ok_and(|date| date <= snapshot.timestamp)
+            })
+            .map(|open| open.id)
+            .collect();
+        for id in expired {
+            let index = self.open_index(&id)?;
+            let mut option = self.open[index].position.option.clone();
+            option.side = Side::Long;
+            option.quantity = Positive::ONE;
+            let intrinsic = Positive::new_decimal(
+                option
+                    .intrinsic_value(snapshot.underlying_price)?
+                    .max(Decimal::ZERO),
+            )?;
+            debug!("Position {} expired with intrinsic value {}", id, intrinsic);
+            self.close_at(index, intrinsic, None, snapshot, ExitReason::Expiration)?;
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &mut self,
+        actions: Vec<BacktestAction>,
+        snapshot: &MarketSnapshot,
+        config: &BacktestConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        let legs_opened = actions
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action,
+                    BacktestAction::Open(_) | BacktestAction::Adjust { .. }
+                )
+            })
+            .count();
+        let first_new_trade = self.trades.len();
+
+        for action in actions {
+            match action {
+                BacktestAction::Open(position) => self.open_position(position, snapshot, config)?,
+                BacktestAction::Close { id, reason } => {
+                    self.close_position(&id, snapshot, reason, config)?
+                }
+                BacktestAction::Adjust { id, position } => {
+                    self.close_position(&id, snapshot, ExitReason::RollOver, config)?;
+                    self.open_position(position, snapshot, config)?;
+                }
+                BacktestAction::CloseAll { reason } => self.close_all(snapshot, reason, config)?,
+            }
+        }
+
+        if legs_opened > 1 {
+            self.spread_trades += self.trades.len() - first_new_trade;
+        }
+        Ok(())
+    }
+
+    fn open_position(
+        &mut self,
+        mut position: Position,
+        snapshot: &MarketSnapshot,
+        config: &BacktestConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        position.date = snapshot.timestamp;
+        if !position.validate() {
+            return Err(Box::new(PositionError::invalid_position(
+                "Backtest policy tried to open an invalid position",
+            )));
+        }
+        let equity_before = self.equity();
+        let quantity = position.option.quantity;
+        let premium = (position.premium * quantity).to_dec();
+        let open_fees = (position.open_fee * quantity).to_dec();
+        match position.option.side {
+            Side::Long => self.cash -= premium + open_fees,
+            Side::Short => self.cash += premium - open_fees,
+        }
+        self.total_fees += open_fees;
+
+        let open = OpenPosition {
+            id: Uuid::new_v4(),
+            mark_price: quote(snapshot, &position.option)?.mid,
+            position,
+        };
+        let margin = margin_requirement(std::slice::from_ref(&open), snapshot, config)?;
+        if equity_before > Decimal::ZERO {
+            let size = match open.position.option.side {
+                Side::Long => premium + open_fees,
+                Side::Short => margin,
+            };
+            self.position_sizes
+                .push(size / equity_before * Decimal::ONE_HUNDRED);
+        }
+
+        let record = TradeRecord {
+            id: open.id,
+            entry_date: snapshot.timestamp,
+            position: open.position.clone(),
+            margin_required: Some(margin),
+            entry_greeks: greeks_snapshot(&open.position, snapshot),
+            ..Default::default()
+        };
+        self.trade_index.insert(open.id, self.trades.len());
+        self.trades.push(record);
+        self.open.push(open);
+        Ok(())
+    }
+
+    fn close_position(
+        &mut self,
+        id: &Uuid,
+        snapshot: &MarketSnapshot,
+        reason: ExitReason,
+        config: &BacktestConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        let index = self.open_index(id)?;
+        let quote = quote(snapshot, &self.open[index].position.option)?;
+        let (price, slippage) = if config.fill_at_touch {
+            let touch = match self.open[index].position.option.side {
+                Side::Long => quote.bid,
+                Side::Short => quote.ask,
+            }
+            .unwrap_or(quote.mid);
+            let slippage = (touch.to_dec() - quote.mid.to_dec()).abs()
+                * self.open[index].position.option.quantity;
+            (touch, Some(slippage))
+        } else {
+            (quote.mid, None)
+        };
+        self.close_at(index, price, slippage, snapshot, reason)
+    }
+
+    fn close_all(
+        &mut self,
+        snapshot: &MarketSnapshot,
+        reason: ExitReason,
+        config: &BacktestConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        let ids: Vec<Uuid> = self.open.iter().map(|open| open.id).collect();
+        for id in ids {
+            self.close_position(&id, snapshot, reason.clone(), config)?;
+        }
+        Ok(())
+    }
+
+    fn close_at(
+        &mut self,
+        index: usize,
+        price: Positive,
+        slippage: Option<Decimal>,
+        snapshot: &MarketSnapshot,
+        reason: ExitReason,
+    ) -> Result<(), Box<dyn Error>> {
+        let open = self.open.remove(index);
+        let quantity = open.position.option.quantity;
+        let proceeds = (price * quantity).to_dec();
+        let close_fees = (open.position.close_fee * quantity).to_dec();
+        match open.position.option.side {
+            Side::Long => self.cash += proceeds - close_fees,
+            Side::Short => self.cash -= proceeds + close_fees,
+        }
+        self.total_fees += close_fees;
+
+        let profit_loss = open.position.unrealized_pnl(price)?;
+        let exit_greeks = greeks_snapshot(&open.position, snapshot);
+        let record = &mut self.trades[self.trade_index[&open.id]];
+        let basis = match open.position.option.side {
+            Side::Long => (open.position.premium * quantity).to_dec(),
+            Side::Short => record.margin_required.unwrap_or(Decimal::ZERO),
+        };
+        record.exit_date = Some(snapshot.timestamp);
+        record.duration = Some(days_between(record.entry_date, snapshot.timestamp));
+        record.exit_price = Some(price.to_dec());
+        record.slippage = slippage;
+        record.profit_loss = Some(profit_loss);
+        record.return_percentage = (basis > Decimal::ZERO).then(|| profit_loss / basis);
+        record.exit_reason = Some(reason);
+        record.exit_greeks = exit_greeks;
+        Ok(())
+    }
+
+    fn open_index(&self, id: &Uuid) -> Result<usize, PositionError> {
+        self.open
+            .iter()
+            .position(|open| open.id == *id)
+            .ok_or_else(|| {
+                PositionError::invalid_position(&format!("No open position with id {id}"))
+            })
+    }
+
+    fn record(
+        &mut self,
+        snapshot: &MarketSnapshot,
+        config: &BacktestConfig,
+    ) -> Result<(), Box<dyn Error>> {
+        let equity = self.equity();
+        self.peak_equity = self.peak_equity.max(equity);
+        let drawdown = if self.peak_equity > Decimal::ZERO {
+            (self.peak_equity - equity) / self.peak_equity
+        } else {
+            Decimal::ZERO
+        };
+
+        let margin = margin_requirement(&self.open, snapshot, config)?;
+        // The margin calculators already charge long options their market value
+        let capital = match config.margin {
+            Some(_) => margin,
+            None => {
+                margin
+                    + self
+                        .open
+                        .iter()
+                        .filter(|open| open.position.option.side == Side::Long)
+                        .map(OpenPosition::market_value)
+                        .sum::<Decimal>()
+            }
+        };
+        let mut exposure = [Decimal::ZERO; 4];
+        for open in &self.open {
+            if let Some(greeks) = greeks_snapshot(&open.position, snapshot) {
+                let sign = side_sign(&open.position.option);
+                exposure[0] += greeks.delta;
+                exposure[1] += greeks.gamma * sign;
+                exposure[2] += greeks.theta * sign;
+                exposure[3] += greeks.vega * sign;
+            }
+        }
+
+        let series = &mut self.time_series;
+        series.timestamps.push(snapshot.timestamp);
+        series.equity_curve.push(equity);
+        series.drawdown_curve.push(drawdown);
+        series.margin_usage.push(margin);
+        series.position_count.push(self.open.len());
+        for (values, value) in [
+            &mut series.delta_exposure,
+            &mut series.gamma_exposure,
+            &mut series.theta_exposure,
+            &mut series.vega_exposure,
+        ]
+        .into_iter()
+        .zip(exposure)
+        {
+            if let Some(values) = values {
+                values.push(value);
+            }
+        }
+        self.capital_used.push(capital);
+        self.atm_ivs.push(snapshot.get_atm_implied_volatility());
+        Ok(())
+    }
+
+    fn into_result(self, config: &BacktestConfig, snapshots: &[MarketSnapshot]) -> BacktestResult {
+        let initial_capital = config.initial_capital.to_dec();
+        let final_capital = self
+            .time_series
+            .equity_curve
+            .last()
+            .copied()
+            .unwrap_or(initial_capital);
+        let start = self.time_series.timestamps[0];
+        let end = *self.time_series.timestamps.last().unwrap_or(&start);
+
+        let closed: Vec<&TradeRecord> = self
+            .trades
+            .iter()
+            .filter(|trade| trade.profit_loss.is_some())
+            .collect();
+        let drawdown_analysis = drawdown_analysis(&self.time_series);
+        let general_performance = general_performance(
+            &self.time_series,
+            &closed,
+            initial_capital,
+            final_capital,
+            &drawdown_analysis,
+            config.risk_free_rate,
+        );
+        let trade_statistics = trade_statistics(&closed, self.spread_trades);
+        let capital_utilization = capital_utilization(
+            &self.trades,
+            &self.time_series,
+            &self.capital_used,
+            &self.position_sizes,
+            final_capital - initial_capital,
+        );
+        let options_metrics = options_metrics(
+            &self.trades,
+            &self.time_series,
+            &capital_utilization,
+            final_capital - initial_capital,
+        );
+        let volatility_data = volatility_data(&self.trades, &self.atm_ivs);
+        let market_conditions = market_conditions(snapshots, &self.atm_ivs);
+        let risk_metrics = risk_metrics(&self.time_series, &closed);
+
+        let mut custom_metrics = HashMap::new();
+        custom_metrics.insert("total_fees".to_string(), self.total_fees);
+        custom_metrics.insert(
+            "open_positions_at_end".to_string(),
+            Decimal::from(self.open.len()),
+        );
+
+        BacktestResult {
+            general_performance,
+            options_metrics,
+            trade_statistics,
+            drawdown_analysis,
+            capital_utilization,
+            time_series: self.time_series,
+            trades: self.trades,
+            market_conditions: Some(market_conditions),
+            volatility_data: Some(volatility_data),
+            risk_metrics: Some(risk_metrics),
+            monte_carlo_simulation: None,
+            strategy_name: config.strategy_name.clone(),
+            test_period_start: start,
+            test_period_end: end,
+            initial_capital,
+            final_capital,
+            custom_metrics,
+        }
+    }
+}
+
+/// Fraction of a day elapsed between two instants, floored at zero.
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> Positive {
+    let seconds = Decimal::from((to - from).num_seconds());
+    Positive::new_decimal(seconds / SECONDS_PER_DAY).unwrap_or(Positive::ZERO)
+}
+
+/// Clones `option` as seen from `snapshot`: time to expiry is measured from the
+/// snapshot timestamp and the underlying price is taken from the snapshot.
+fn option_at_snapshot(option: &Options, snapshot: &MarketSnapshot) -> Options {
+    let mut repriced = option.clone();
+    let days = option
+        .expiration_date
+        .get_date()
+        .map(|date| days_between(snapshot.timestamp, date))
+        .unwrap_or(Positive::ZERO);
+    repriced.expiration_date = ExpirationDate::Days(days);
+    repriced.underlying_price = snapshot.underlying_price;
+    if let Some(data) = snapshot.get_option_data(option) {
+        repriced.implied_volatility = data.implied_volatility;
+    }
+    repriced
+}
+
+/// Quotes a single contract of `option` at `snapshot`, using the listed bid/ask when
+/// available and the theoretical value otherwise.
+fn quote(snapshot: &MarketSnapshot, option: &Options) -> Result<Quote, Box<dyn Error>> {
+    let (bid, ask, middle) = match snapshot.get_option_data(option) {
+        Some(data) => match option.option_style {
+            OptionStyle::Call => (data.call_bid, data.call_ask, data.call_middle),
+            OptionStyle::Put => (data.put_bid, data.put_ask, data.put_middle),
+        },
+        None => (None, None, None),
+    };
+    let mid = match (middle, bid, ask) {
+        (Some(mid), _, _) => mid,
+        (None, Some(bid), Some(ask)) => (bid + ask) / Positive::TWO,
+        (None, Some(price), None) | (None, None, Some(price)) => price,
+        (None, None, None) => theoretical_price(option, snapshot)?,
+    };
+    Ok(Quote { bid, ask, mid })
+}
+
+/// Black-Scholes value of a single long contract, or intrinsic value at expiration.
+fn theoretical_price(
+    option: &Options,
+    snapshot: &MarketSnapshot,
+) -> Result<Positive, Box<dyn Error>> {
+    let mut repriced = option_at_snapshot(option, snapshot);
+    repriced.side = Side::Long;
+    repriced.quantity = Positive::ONE;
+    let price = if repriced.expiration_date.get_days()? == Positive::ZERO {
+        repriced.intrinsic_value(snapshot.underlying_price)?
+    } else {
+        black_scholes(&repriced)?
+    };
+    Ok(Positive::new_decimal(price.max(Decimal::ZERO))?)
+}
+
+/// Open positions repriced at a snapshot, so the margin calculators can treat them as
+/// one strategy.
+struct MarginBook {
+    positions: Vec<Position>,
+}
+
+impl Positionable for MarginBook {
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.positions.iter().collect())
+    }
+}
+
+/// Buying-power requirement of `open` at `snapshot` under the configured margin method.
+/// Each position is valued at its current mark, so long options tie up their market
+/// value and hedged shorts are charged as spreads. Without a margin method, each short
+/// option reserves `short_margin_rate` of the underlying notional plus its market value.
+fn margin_requirement(
+    open: &[OpenPosition],
+    snapshot: &MarketSnapshot,
+    config: &BacktestConfig,
+) -> Result<Decimal, MetricsError> {
+    let Some(method) = &config.margin else {
+        #[allow(deprecated)]
+        let rate = config.short_margin_rate;
+        return Ok(open
+            .iter()
+            .filter(|open| open.position.option.side == Side::Short)
+            .map(|open| {
+                let quantity = open.position.option.quantity;
+                (snapshot.underlying_price * quantity).to_dec() * rate
+                    + (open.mark_price * quantity).to_dec()
+            })
+            .sum());
+    };
+    let book = MarginBook {
+        positions: open
+            .iter()
+            .map(|open| Position {
+                option: option_at_snapshot(&open.position.option, snapshot),
+                premium: open.mark_price,
+                ..open.position.clone()
+            })
+            .collect(),
+    };
+    book.margin_requirement(method)
+}
+
+fn greeks_snapshot(position: &Position, snapshot: &MarketSnapshot) -> Option<GreeksSnapshot> {
+    let repriced = Position {
+        option: option_at_snapshot(&position.option, snapshot),
+        ..position.clone()
+    };
+    let greeks = repriced.greeks().ok()?;
+    Some(GreeksSnapshot {
+        delta: greeks.delta,
+        gamma: greeks.gamma,
+        theta: greeks.theta,
+        vega: greeks.vega,
+        rho: Some(greeks.rho),
+        rho_d: Some(greeks.rho_d),
+        alpha: Some(greeks.alpha),
+        vanna: Some(greeks.vanna),
+        vomma: Some(greeks.vomma),
+        charm: Some(greeks.charm),
+        veta: Some(greeks.veta),
+        speed: Some(greeks.speed),
+        zomma: Some(greeks.zomma),
+        color: Some(greeks.color),
+        ultima: Some(greeks.ultima),
+    })
+}
+
+fn median(values: &[Decimal]) -> Option<Decimal> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    let middle = sorted.len() / 2;
+    if sorted.len().is_multiple_of(2) {
+        Some((sorted[middle - 1] + sorted[middle]) / Decimal::TWO)
+    } else {
+        Some(sorted[middle])
+    }
+}
+
+fn std_dev(values: &[Decimal]) -> Option<Decimal> {
+    if values.len() < 2 {
+        return None;
+    }
+    let avg = mean(values)?;
+    let variance = values
+        .iter()
+        .map(|value| (value - avg).powi(2))
+        .sum::<Decimal>()
+        / Decimal::from(values.len() - 1);
+    variance.sqrt()
+}
+
+fn step_returns(equity: &[Decimal]) -> Vec<Decimal> {
+    equity
+        .windows(2)
+        .filter(|pair| pair[0] > Decimal::ZERO)
+        .map(|pair| pair[1] / pair[0] - Decimal::ONE)
+        .collect()
+}
+
+fn general_performance(
+    series: &TimeSeriesData,
+    closed: &[&TradeRecord],
+    initial_capital: Decimal,
+    final_capital: Decimal,
+    drawdowns: &DrawdownAnalysis,
+    risk_free_rate: Decimal,
+) -> GeneralPerformanceMetrics {
+    let total_return = if initial_capital > Decimal::ZERO {
+        final_capital / initial_capital - Decimal::ONE
+    } else {
+        Decimal::ZERO
+    };
+    let years = match (series.timestamps.first(), series.timestamps.last()) {
+        (Some(start), Some(end)) => days_between(*start, *end).to_dec() / DAYS_PER_YEAR,
+        _ => Decimal::ZERO,
+    };
+    let annualized_return = if years > Decimal::ZERO && total_return > Decimal::NEGATIVE_ONE {
+        (Decimal::ONE + total_return)
+            .checked_powd(Decimal::ONE / years)
+            .map(|growth| growth - Decimal::ONE)
+            .unwrap_or(total_return)
+    } else {
+        total_return
+    };
+
+    let returns = step_returns(&series.equity_curve);
+    let annualization = if years > Decimal::ZERO && !returns.is_empty() {
+        (Decimal::from(returns.len()) / years).sqrt()
+    } else {
+        None
+    };
+    let volatility = std_dev(&returns)
+        .zip(annualization)
+        .map(|(std, factor)| std * factor);
+    let downside_deviation = if returns.is_empty() {
+        None
+    } else {
+        let downside = returns
+            .iter()
+            .map(|value| (*value).min(Decimal::ZERO).powi(2))
+            .sum::<Decimal>()
+            / Decimal::from(returns.len());
+        downside
+            .sqrt()
+            .zip(annualization)
+            .map(|(dev, factor)| dev * factor)
+    };
+    let excess_return = annualized_return - risk_free_rate;
+    let ratio = |denominator: Option<Decimal>| {
+        denominator
+            .filter(|value| *value > Decimal::ZERO)
+            .map(|value| excess_return / value)
+    };
+    let sharpe_ratio = ratio(volatility);
+    let sortino_ratio = ratio(downside_deviation);
+    let calmar_ratio = (drawdowns.max_drawdown > Decimal::ZERO)
+        .then(|| annualized_return / drawdowns.max_drawdown);
+
+    let pnls: Vec<Decimal> = closed
+        .iter()
+        .filter_map(|trade| trade.profit_loss)
+        .collect();
+    let gains: Vec<Decimal> = pnls
+        .iter()
+        .copied()
+        .filter(|pnl| *pnl > Decimal::ZERO)
+        .collect();
+    let losses: Vec<Decimal> = pnls
+        .iter()
+        .copied()
+        .filter(|pnl| *pnl < Decimal::ZERO)
+        .collect();
+    let gross_profit: Decimal = gains.iter().sum();
+    let gross_loss: Decimal = losses.iter().sum::<Decimal>().abs();
+    let avg_gain = mean(&gains);
+    let avg_loss = mean(&losses);
+
+    GeneralPerformanceMetrics {
+        total_return,
+        annualized_return,
+        volatility: volatility.and_then(|value| Positive::new_decimal(value).ok()),
+        downside_deviation: downside_deviation.and_then(|value| Positive::new_decimal(value).ok()),
+        sharpe_ratio,
+        sortino_ratio,
+        calmar_ratio,
+        win_rate: (!pnls.is_empty())
+            .then(|| Decimal::from(gains.len()) / Decimal::from(pnls.len())),
+        profit_factor: (gross_loss > Decimal::ZERO).then(|| gross_profit / gross_loss),
+        avg_gain,
+        avg_loss,
+        gain_loss_ratio: avg_gain
+            .zip(avg_loss)
+            .filter(|(_, loss)| !loss.is_zero())
+            .map(|(gain, loss)| gain / loss.abs()),
+    }
+}
+
+fn trade_statistics(closed: &[&TradeRecord], spread_trades: usize) -> TradeStatistics {
+    let pnls: Vec<Decimal> = closed
+        .iter()
+        .filter_map(|trade| trade.profit_loss)
+        .collect();
+    let holding: Vec<Decimal> = closed
+        .iter()
+        .filter_map(|trade| trade.duration.map(|duration| duration.to_dec()))
+        .collect();
+    let to_positive = |value: Option<Decimal>| {
+        value
+            .and_then(|value| Positive::new_decimal(value).ok())
+            .unwrap_or(Positive::ZERO)
+    };
+    let options: Vec<&Options> = closed.iter().map(|trade| &trade.position.option).collect();
+
+    TradeStatistics {
+        number_of_trades: closed.len(),
+        winners: pnls.iter().filter(|pnl| **pnl > Decimal::ZERO).count(),
+        losers: pnls.iter().filter(|pnl| **pnl < Decimal::ZERO).count(),
+        break_even: pnls.iter().filter(|pnl| pnl.is_zero()).count(),
+        average_trade_return: mean(&pnls).unwrap_or(Decimal::ZERO),
+        median_trade_return: median(&pnls).unwrap_or(Decimal::ZERO),
+        largest_win: pnls
+            .iter()
+            .copied()
+            .filter(|pnl| *pnl > Decimal::ZERO)
+            .max(),
+        largest_loss: pnls
+            .iter()
+            .copied()
+            .filter(|pnl| *pnl < Decimal::ZERO)
+            .min(),
+        average_holding_period: to_positive(mean(&holding)),
+        median_holding_period: to_positive(median(&holding)),
+        min_holding_period: to_positive(holding.iter().copied().min()),
+        max_holding_period: to_positive(holding.iter().copied().max()),
+        long_trades: options.iter().filter(|option| option.is_long()).count(),
+        short_trades: options.iter().filter(|option| option.is_short()).count(),
+        call_trades: options
+            .iter()
+            .filter(|option| option.option_style == OptionStyle::Call)
+            .count(),
+        put_trades: options
+            .iter()
+            .filter(|option| option.option_style == OptionStyle::Put)
+            .count(),
+        spread_trades: spread_trades.min(closed.len()),
+    }
+}
+
+fn drawdown_analysis(series: &TimeSeriesData) -> DrawdownAnalysis {
+    let timestamps = &series.timestamps;
+    let equity = &series.equity_curve;
+    if equity.is_empty() {
+        return DrawdownAnalysis::default();
+    }
+
+    let mut events: Vec<DrawdownEvent> = Vec::new();
+    let mut peak_index = 0;
+    let mut trough_index: Option<usize> = None;
+    let mut underwater_days = Decimal::ZERO;
+    let close_event = |peak: usize, trough: usize, recovery: Option<usize>| {
+        let magnitude = (equity[peak] - equity[trough]) / equity[peak];
+        DrawdownEvent {
+            start_date: timestamps[peak].naive_utc(),
+            bottom_date: timestamps[trough].naive_utc(),
+            recovery_date: recovery.map(|index| timestamps[index].naive_utc()),
+            magnitude,
+            duration: days_between(timestamps[peak], timestamps[trough]),
+            recovery_duration: recovery
+                .map(|index| days_between(timestamps[trough], timestamps[index])),
+        }
+    };
+
+    for index in 1..equity.len() {
+        if equity[index - 1] < equity[peak_index] {
+            underwater_days += days_between(timestamps[index - 1], timestamps[index]).to_dec();
+        }
+        if equity[index] >= equity[peak_index] {
+            if let Some(trough) = trough_index.take() {
+                events.push(close_event(peak_index, trough, Some(index)));
+            }
+            peak_index = index;
+        } else if equity[peak_index] > Decimal::ZERO
+            && trough_index.is_none_or(|trough| equity[index] < equity[trough])
+        {
+            trough_index = Some(index);
+        }
+    }
+    if let Some(trough) = trough_index {
+        events.push(close_event(peak_index, trough, None));
+    }
+
+    let total_days = days_between(timestamps[0], *timestamps.last().unwrap_or(&timestamps[0]));
+    let worst = events
+        .iter()
+        .max_by(|a, b| a.magnitude.cmp(&b.magnitude))
+        .cloned()
+        .unwrap_or_default();
+    let recoveries: Vec<Decimal> = events
+        .iter()
+        .filter_map(|event| event.recovery_duration.map(|days| days.to_dec()))
+        .collect();
+    let magnitudes: Vec<Decimal> = events.iter().map(|event| event.magnitude).collect();
+
+    DrawdownAnalysis {
+        max_drawdown: worst.magnitude,
+        max_drawdown_duration: worst.duration,
+        recovery_duration: worst.recovery_duration,
+        time_to_max_drawdown: if events.is_empty() {
+            Positive::ZERO
+        } else {
+            days_between(timestamps[0], worst.start_date.and_utc())
+        },
+        avg_drawdown: mean(&magnitudes).unwrap_or(Decimal::ZERO),
+        avg_recovery_time: mean(&recoveries).and_then(|days| Positive::new_decimal(days).ok()),
+        total_underwater_days: Positive::new_decimal(underwater_days).unwrap_or(Positive::ZERO),
+        underwater_percentage: if total_days > Positive::ZERO {
+            underwater_days / total_days.to_dec() * Decimal::ONE_HUNDRED
+        } else {
+            Decimal::ZERO
+        },
+        drawdowns: events,
+    }
+}
+
+fn capital_utilization(
+    trades: &[TradeRecord],
+    series: &TimeSeriesData,
+    capital_used: &[Decimal],
+    position_sizes: &[Decimal],
+    total_pnl: Decimal,
+) -> CapitalUtilization {
+    let premium = |side: Side| -> Decimal {
+        trades
+            .iter()
+            .filter(|trade| trade.position.option.side == side)
+            .map(|trade| (trade.position.premium * trade.position.option.quantity).to_dec())
+            .sum()
+    };
+    let total_premium_paid = premium(Side::Long);
+    let total_premium_received = premium(Side::Short);
+    let avg_capital_used = mean(capital_used).unwrap_or(Decimal::ZERO);
+
+    CapitalUtilization {
+        max_capital_used: capital_used.iter().copied().max().unwrap_or(Decimal::ZERO),
+        avg_capital_used,
+        capital_efficiency: if avg_capital_used > Decimal::ZERO {
+            total_pnl / avg_capital_used
+        } else {
+            Decimal::ZERO
+        },
+        total_margin_used: trades
+            .iter()
+            .filter_map(|trade| trade.margin_required)
+            .sum(),
+        max_margin_used: series
+            .margin_usage
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(Decimal::ZERO),
+        avg_margin_used: mean(&series.margin_usage).unwrap_or(Decimal::ZERO),
+        total_premium_paid,
+        total_premium_received,
+        net_premium: total_premium_received - total_premium_paid,
+        max_position_size: position_sizes
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(Decimal::ZERO),
+        avg_position_size: mean(position_sizes).unwrap_or(Decimal::ZERO),
+    }
+}
+
+fn options_metrics(
+    trades: &[TradeRecord],
+    series: &TimeSeriesData,
+    capital: &CapitalUtilization,
+    total_pnl: Decimal,
+) -> OptionsSpecificMetrics {
+    let count = Decimal::from(trades.len());
+    let share = |predicate: &dyn Fn(&Options) -> bool| {
+        (!trades.is_empty()).then(|| {
+            Decimal::from(
+                trades
+                    .iter()
+                    .filter(|trade| predicate(&trade.position.option))
+                    .count(),
+            ) / count
+                * Decimal::ONE_HUNDRED
+        })
+    };
+    let short_pnl: Decimal = trades
+        .iter()
+        .filter(|trade| trade.position.option.is_short())
+        .filter_map(|trade| trade.profit_loss)
+        .sum();
+    let average = |values: &Option<Vec<Decimal>>| values.as_deref().and_then(mean);
+
+    OptionsSpecificMetrics {
+        return_on_margin: (capital.max_margin_used > Decimal::ZERO)
+            .then(|| total_pnl / capital.max_margin_used),
+        return_on_premium: (capital.net_premium != Decimal::ZERO)
+            .then(|| total_pnl / capital.net_premium.abs()),
+        premium_capture: (capital.total_premium_received > Decimal::ZERO)
+            .then(|| short_pnl / capital.total_premium_received),
+        avg_delta_exposure: average(&series.delta_exposure),
+        avg_gamma_exposure: average(&series.gamma_exposure),
+        avg_theta_exposure: average(&series.theta_exposure),
+        avg_vega_exposure: average(&series.vega_exposure),
+        calls_percentage: share(&|option| option.option_style == OptionStyle::Call),
+        puts_percentage: share(&|option| option.option_style == OptionStyle::Put),
+        long_percentage: share(&|option| option.is_long()),
+        short_percentage: share(&|option| option.is_short()),
+    }
+}
+
+fn volatility_data(trades: &[TradeRecord], atm_ivs: &[Option<Positive>]) -> VolatilityData {
+    let traded: Vec<Decimal> = trades
+        .iter()
+        .map(|trade| trade.position.option.implied_volatility.to_dec())
+        .collect();
+    let history: Vec<Decimal> = atm_ivs.iter().flatten().map(|iv| iv.to_dec()).collect();
+    let avg_iv_traded = mean(&traded);
+    let (low, high) = (history.iter().min(), history.iter().max());
+
+    VolatilityData {
+        implied_volatility_used: !traded.is_empty(),
+        avg_iv_traded,
+        iv_percentile_traded: avg_iv_traded.filter(|_| !history.is_empty()).map(|iv| {
+            Decimal::from(history.iter().filter(|value| **value <= iv).count())
+                / Decimal::from(history.len())
+                * Decimal::ONE_HUNDRED
+        }),
+        iv_rank_traded: match (avg_iv_traded, low, high) {
+            (Some(iv), Some(low), Some(high)) if high > low => {
+                Some((iv - low) / (high - low) * Decimal::ONE_HUNDRED)
+            }
+            _ => None,
+        },
+    }
+}
+
+fn market_conditions(
+    snapshots: &[MarketSnapshot],
+    atm_ivs: &[Option<Positive>],
+) -> MarketConditionMetrics {
+    let flat_band = dec!(0.001);
+    let history: Vec<Decimal> = atm_ivs.iter().flatten().map(|iv| iv.to_dec()).collect();
+    let median_iv = median(&history);
+    let mut days = [Decimal::ZERO; 5];
+
+    for (index, pair) in snapshots.windows(2).enumerate() {
+        let elapsed = days_between(pair[0].timestamp, pair[1].timestamp).to_dec();
+        let change =
+            pair[1].underlying_price.to_dec() / pair[0].underlying_price.to_dec() - Decimal::ONE;
+        let trend = if change > flat_band {
+            0
+        } else if change < -flat_band {
+            1
+        } else {
+            2
+        };
+        days[trend] += elapsed;
+        if let (Some(iv), Some(median_iv)) = (atm_ivs[index], median_iv) {
+            days[if iv.to_dec() > median_iv { 3 } else { 4 }] += elapsed;
+        }
+    }
+    let to_positive = |value: Decimal| Positive::new_decimal(value).unwrap_or(Positive::ZERO);
+
+    MarketConditionMetrics {
+        bull_market_days: to_positive(days[0]),
+        bear_market_days: to_positive(days[1]),
+        sideways_market_days: to_positive(days[2]),
+        high_volatility_days: to_positive(days[3]),
+        low_volatility_days: to_positive(days[4]),
+        avg_market_volatility: mean(&history),
+    }
+}
+
+fn risk_metrics(series: &TimeSeriesData, closed: &[&TradeRecord]) -> AdvancedRiskMetrics {
+    let returns = step_returns(&series.equity_curve);
+    let var = |level: Decimal| quantile(&returns, level).map(|q| (-q).max(Decimal::ZERO));
+    let expected_shortfall = quantile(&returns, dec!(0.05)).and_then(|threshold| {
+        let tail: Vec<Decimal> = returns
+            .iter()
+            .copied()
+            .filter(|r| *r <= threshold)
+            .collect();
+        mean(&tail).map(|loss| (-loss).max(Decimal::ZERO))
+    });
+    let tail_ratio = quantile(&returns, dec!(0.95))
+        .zip(quantile(&returns, dec!(0.05)))
+        .filter(|(_, low)| !low.is_zero())
+        .map(|(high, low)| high.abs() / low.abs());
+
+    let mut exits: Vec<&&TradeRecord> = closed.iter().collect();
+    exits.sort_by_key(|trade| trade.exit_date);
+    let mut max_consecutive_losses = 0;
+    let mut streak = 0;
+    for trade in exits {
+        if trade.profit_loss.is_some_and(|pnl| pnl < Decimal::ZERO) {
+            streak += 1;
+            max_consecutive_losses = max_consecutive_losses.max(streak);
+        } else {
+            streak = 0;
+        }
+    }
+
+    let drawdown_pct: Vec<Decimal> = series
+        .drawdown_curve
+        .iter()
+        .map(|dd| dd * Decimal::ONE_HUNDRED)
+        .collect();
+    let squared: Vec<Decimal> = drawdown_pct.iter().map(|dd| dd * dd).collect();
+
+    AdvancedRiskMetrics {
+        value_at_risk_95: var(dec!(0.05)),
+        value_at_risk_99: var(dec!(0.01)),
+        expected_shortfall,
+        tail_ratio,
+        max_consecutive_losses,
+        ulcer_index: mean(&squared).and_then(|value| value.sqrt()),
+        pain_index: mean(&drawdown_pct),
+    }
+}
+
+#[cfg(test)]
+mod tests_backtest_engine {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use crate::model::types::OptionType;
+    use crate::pos;
+    use crate::risk::PortfolioMarginParameters;
+    use chrono::{Duration, TimeZone};
+
+    fn expiration() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2030, 1, 18, 18, 30, 0).unwrap()
+    }
+
+    fn chain(underlying: Positive, call_bid: Positive, call_ask: Positive) -> OptionChain {
+        let mut chain = OptionChain::new(
+            "TEST",
+            underlying,
+            "2030-01-18".to_string(),
+            Some(dec!(0.05)),
+            Some(Positive::ZERO),
+        );
+        chain.add_option(
+            pos!(100.0),
+            Some(call_bid),
+            Some(call_ask),
+            Some(pos!(1.0)),
+            Some(pos!(1.2)),
+            pos!(0.2),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        chain
+    }
+
+    fn snapshots() -> Vec<(DateTime<Utc>, OptionChain)> {
+        let start = Utc.with_ymd_and_hms(2030, 1, 1, 16, 0, 0).unwrap();
+        vec![
+            (start, chain(pos!(100.0), pos!(4.9), pos!(5.1))),
+            (
+                start + Duration::days(1),
+                chain(pos!(103.0), pos!(6.9), pos!(7.1)),
+            ),
+            (
+                start + Duration::days(2),
+                chain(pos!(98.0), pos!(3.9), pos!(4.1)),
+            ),
+            (
+                start + Duration::days(3),
+                chain(pos!(101.0), pos!(5.9), pos!(6.1)),
+            ),
+        ]
+    }
+
+    fn call(side: Side, premium: Positive) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "TEST".to_string(),
+            pos!(100.0),
+            ExpirationDate::DateTime(expiration()),
+            pos!(0.2),
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    /// Buys one call on the first step and sells it on the third.
+    struct BuyAndSell;
+
+    impl BacktestPolicy for BuyAndSell {
+        fn on_step(
+            &mut self,
+            context: &BacktestContext<'_>,
+        ) -> Result<Vec<BacktestAction>, Box<dyn Error>> {
+            Ok(match context.step {
+                0 => vec![BacktestAction::Open(call(Side::Long, pos!(5.1)))],
+                2 => vec![BacktestAction::Close {
+                    id: context.positions[0].id,
+                    reason: ExitReason::StopLoss,
+                }],
+                _ => vec![],
+            })
+        }
+    }
+
+    struct HoldShort;
+
+    impl BacktestPolicy for HoldShort {
+        fn on_step(
+            &mut self,
+            context: &BacktestContext<'_>,
+        ) -> Result<Vec<BacktestAction>, Box<dyn Error>> {
+            if context.step == 0 {
+                Ok(vec![BacktestAction::Open(call(Side::Short, pos!(4.9)))])
+            } else {
+                Ok(vec![])
+            }
+        }
+    }
+
+    struct CloseUnknown;
+
+    impl BacktestPolicy for CloseUnknown {
+        fn on_step(
+            &mut self,
+            _: &BacktestContext<'_>,
+        ) -> Result<Vec<BacktestAction>, Box<dyn Error>> {
+            Ok(vec![BacktestAction::Close {
+                id: Uuid::new_v4(),
+                reason: ExitReason::ManualClose,
+            }])
+        }
+    }
+
+    fn config() -> BacktestConfig {
+        BacktestConfig {
+            strategy_name: "Test".to_string(),
+            initial_capital: pos!(1000.0),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_round_trip_trade_is_recorded() {
+        let engine = BacktestEngine::from_chains(config(), snapshots());
+        let result = engine.run(&mut BuyAndSell).unwrap();
+
+        assert_eq!(result.strategy_name, "Test");
+        assert_eq!(result.trades.len(), 1);
+        let trade = &result.trades[0];
+        assert_eq!(trade.exit_price, Some(dec!(4.0)));
+        assert_eq!(trade.profit_loss, Some(dec!(-1.1)));
+        assert_eq!(trade.exit_reason, Some(ExitReason::StopLoss));
+        assert_eq!(trade.duration, Some(pos!(2.0)));
+        assert!(trade.entry_greeks.is_some());
+
+        assert_eq!(result.final_capital, dec!(998.9));
+        assert_eq!(result.trade_statistics.number_of_trades, 1);
+        assert_eq!(result.trade_statistics.losers, 1);
+        assert_eq!(result.trade_statistics.largest_loss, Some(dec!(-1.1)));
+        assert_eq!(result.capital_utilization.total_premium_paid, dec!(5.1));
+        assert_eq!(result.general_performance.win_rate, Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_equity_curve_marks_to_mid() {
+        let engine = BacktestEngine::from_chains(config(), snapshots());
+        let result = engine.run(&mut BuyAndSell).unwrap();
+
+        let expected = vec![dec!(999.9), dec!(1001.9), dec!(998.9), dec!(998.9)];
+        assert_eq!(result.time_series.equity_curve, expected);
+        assert_eq!(result.time_series.position_count, vec![1, 1, 0, 0]);
+        assert_eq!(result.time_series.timestamps.len(), 4);
+        assert_eq!(result.drawdown_analysis.drawdowns.len(), 1);
+        assert!(result.drawdown_analysis.max_drawdown > Decimal::ZERO);
+        assert!(result.drawdown_analysis.recovery_duration.is_none());
+    }
+
+    #[test]
+    fn test_open_positions_are_closed_at_end() {
+        let engine = BacktestEngine::from_chains(config(), snapshots());
+        let result = engine.run(&mut HoldShort).unwrap();
+
+        let trade = &result.trades[0];
+        assert_eq!(trade.exit_price, Some(dec!(6.0)));
+        assert_eq!(trade.profit_loss, Some(dec!(-1.1)));
+        assert!(matches!(trade.exit_reason, Some(ExitReason::Other(_))));
+        assert!(result.capital_utilization.max_margin_used > Decimal::ZERO);
+        assert!(result.options_metrics.return_on_margin.is_some());
+        assert_eq!(result.options_metrics.short_percentage, Some(dec!(100)));
+    }
+
+    #[test]
+    fn test_margin_follows_margin_method() {
+        let engine = BacktestEngine::from_chains(config(), snapshots());
+        let result = engine.run(&mut HoldShort).unwrap();
+
+        // Naked call: 20% of the underlying less the out-of-the-money amount under the
+        // default Reg-T rates
+        assert_eq!(result.trades[0].margin_required, Some(dec!(20.0)));
+        assert_eq!(
+            result.time_series.margin_usage,
+            vec![dec!(20.0), dec!(20.6), dec!(17.6), Decimal::ZERO]
+        );
+        assert_eq!(result.capital_utilization.max_margin_used, dec!(20.6));
+
+        let config = BacktestConfig {
+            margin: Some(MarginMethod::Portfolio(PortfolioMarginParameters::default())),
+            ..config()
+        };
+        let engine = BacktestEngine::from_chains(config, snapshots());
+        let portfolio = engine.run(&mut HoldShort).unwrap();
+        let margin = portfolio.trades[0].margin_required.unwrap();
+        assert!(margin > Decimal::ZERO && margin != dec!(20.0));
+        assert_eq!(
+            portfolio.options_metrics.return_on_margin,
+            Some(
+                (portfolio.final_capital - dec!(1000.0))
+                    / portfolio.capital_utilization.max_margin_used
+            )
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_short_margin_rate_without_margin_method() {
+        let config = BacktestConfig {
+            short_margin_rate: dec!(0.3),
+            margin: None,
+            ..config()
+        };
+        let engine = BacktestEngine::from_chains(config, snapshots());
+        let result = engine.run(&mut HoldShort).unwrap();
+
+        // 30% of the underlying notional plus the market value of the call
+        assert_eq!(result.trades[0].margin_required, Some(dec!(35.0)));
+        assert_eq!(
+            result.time_series.margin_usage,
+            vec![dec!(35.0), dec!(37.9), dec!(33.4), Decimal::ZERO]
+        );
+        assert_eq!(result.capital_utilization.max_capital_used, dec!(37.9));
+    }
+
+    #[test]
+    fn test_fill_at_touch_records_slippage() {
+        let config = BacktestConfig {
+            fill_at_touch: true,
+            ..config()
+        };
+        let engine = BacktestEngine::from_chains(config, snapshots());
+        let result = engine.run(&mut BuyAndSell).unwrap();
+
+        let trade = &result.trades[0];
+        assert_eq!(trade.exit_price, Some(dec!(3.9)));
+        assert_eq!(trade.slippage, Some(dec!(0.1)));
+    }
+
+    #[test]
+    fn test_expired_positions_settle_at_intrinsic() {
+        let start = expiration() - Duration::days(1);
+        let engine = BacktestEngine::from_chains(
+            config(),
+            vec![
+                (start, chain(pos!(100.0), pos!(4.9), pos!(5.1))),
+                (
+                    expiration(),
+                    OptionChain::new("TEST", pos!(110.0), "2030-01-25".to_string(), None, None),
+                ),
+            ],
+        );
+        let result = engine.run(&mut HoldShort).unwrap();
+
+        let trade = &result.trades[0];
+        assert_eq!(trade.exit_reason, Some(ExitReason::Expiration));
+        assert_eq!(trade.exit_price, Some(dec!(10)));
+        assert_eq!(trade.profit_loss, Some(dec!(-5.1)));
+    }
+
+    #[test]
+    fn test_unlisted_option_uses_theoretical_price() {
+        let snapshot = MarketSnapshot::from_chain(
+            expiration() - Duration::days(30),
+            OptionChain::new("TEST", pos!(100.0), "2030-02-15".to_string(), None, None),
+        );
+        let option = call(Side::Long, pos!(1.0)).option;
+        let quote = quote(&snapshot, &option).unwrap();
+        assert!(quote.bid.is_none());
+        assert!(quote.mid > pos!(2.0) && quote.mid < pos!(4.0));
+    }
+
+    #[test]
+    fn test_unknown_position_is_an_error() {
+        let engine = BacktestEngine::from_chains(config(), snapshots());
+        assert!(engine.run(&mut CloseUnknown).is_err());
+    }
+
+    #[test]
+    fn test_empty_backtest_is_an_error() {
+        let engine = BacktestEngine::new(config(), vec![]);
+        assert!(engine.run(&mut BuyAndSell).is_err());
+    }
+}
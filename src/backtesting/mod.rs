@@ -343,6 +343,16 @@
 //! ```
 //!
 
+/// This module contains the event-driven engine that replays market snapshots through a
+/// user-supplied policy and produces a fully populated `BacktestResult`.
+///
+/// It includes:
+/// `MarketSnapshot`: underlying price and option chains observed at a point in time, built from an `OptionChain` or an `OptionSeries`.
+/// `BacktestPolicy`: the trait implemented by trading logic, returning `BacktestAction`s (open, adjust, close) at every step.
+/// `BacktestEngine`: marks positions to market, settles expirations, executes actions and records equity, margin and Greek exposure.
+/// `BacktestConfig`: initial capital, fill model, margin method and end-of-test behaviour.
+pub mod engine;
+
 /// GeneralPerformanceMetrics
 ///
 /// Purpose:
@@ -10,7 +10,7 @@ use crate::chains::utils::{
 use crate::chains::{OptionData, OptionsInStrike, RNDAnalysis, RNDParameters, RNDResult};
 use crate::curves::{BasicCurves, Curve, Point2D};
 use crate::error::chains::{ChainError, OptionDataErrorKind};
-use crate::error::{CurveError, SurfaceError};
+use crate::error::{CurveError, GreeksError, SurfaceError};
 use crate::geometrics::LinearInterpolation;
 use crate::greeks::Greeks;
 use crate::model::{
@@ -950,6 +950,33 @@ impl OptionChain {
         self.options = modified_options;
     }
 
+    /// Implies the volatility of every strike in the chain from its bid/ask quotes.
+    ///
+    /// Each strike is solved independently with `OptionData::calculate_implied_volatility`.
+    /// Strikes without a usable quote, or for which the solver fails, keep their previous
+    /// implied volatility.
+    ///
+    /// # Returns
+    ///
+    /// The number of strikes whose implied volatility was updated.
+    pub fn update_implied_volatilities(&mut self) -> usize {
+        let mut updated = 0;
+        let modified_options: BTreeSet<OptionData> = self
+            .options
+            .iter()
+            .map(|option| {
+                let mut option = option.clone();
+                match option.calculate_implied_volatility() {
+                    Ok(_) => updated += 1,
+                    Err(e) => debug!("Implied volatility not updated: {}", e),
+                }
+                option
+            })
+            .collect();
+        self.options = modified_options;
+        updated
+    }
+
     /// Saves the option chain data to a CSV file.
     ///
     /// This method writes the option chain data to a CSV file at the specified path.
@@ -1861,6 +1888,128 @@ impl OptionChain {
         self.curve(&BasicAxisTypes::Theta, &OptionStyle::Call, &Side::Long)
     }
 
+    /// Generates a vanna curve for visualization and analysis.
+    ///
+    /// Creates a curve of vanna values across strike prices for long call options in
+    /// the chain. Vanna is the sensitivity of delta to implied volatility, so the curve shows
+    /// where the delta of the chain reacts most to a volatility shock.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing vanna data points;
+    ///   strikes whose vanna cannot be calculated are skipped
+    pub fn vanna_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.vanna())
+    }
+
+    /// Generates a vomma (volga) curve for visualization and analysis.
+    ///
+    /// Creates a curve of vomma (volga) values across strike prices for long call options in
+    /// the chain. Vomma is the sensitivity of vega to implied volatility and peaks on the wings
+    /// of the chain.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing vomma (volga) data points;
+    ///   strikes whose vomma cannot be calculated are skipped
+    pub fn vomma_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.vomma())
+    }
+
+    /// Generates a charm curve for visualization and analysis.
+    ///
+    /// Creates a curve of charm values across strike prices for long call options in
+    /// the chain. Charm is the daily change in delta as expiration approaches.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing charm data points;
+    ///   strikes whose charm cannot be calculated are skipped
+    pub fn charm_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.charm())
+    }
+
+    /// Generates a veta curve for visualization and analysis.
+    ///
+    /// Creates a curve of veta values across strike prices for long call options in
+    /// the chain. Veta is the daily change in vega as expiration approaches.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing veta data points;
+    ///   strikes whose veta cannot be calculated are skipped
+    pub fn veta_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.veta())
+    }
+
+    /// Generates a speed curve for visualization and analysis.
+    ///
+    /// Creates a curve of speed values across strike prices for long call options in
+    /// the chain. Speed is the sensitivity of gamma to the underlying price.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing speed data points;
+    ///   strikes whose speed cannot be calculated are skipped
+    pub fn speed_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.speed())
+    }
+
+    /// Generates a zomma curve for visualization and analysis.
+    ///
+    /// Creates a curve of zomma values across strike prices for long call options in
+    /// the chain. Zomma is the sensitivity of gamma to implied volatility.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing zomma data points;
+    ///   strikes whose zomma cannot be calculated are skipped
+    pub fn zomma_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.zomma())
+    }
+
+    /// Generates a color curve for visualization and analysis.
+    ///
+    /// Creates a curve of color values across strike prices for long call options in
+    /// the chain. Color is the daily change in gamma as expiration approaches.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing color data points;
+    ///   strikes whose color cannot be calculated are skipped
+    pub fn color_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.color())
+    }
+
+    /// Generates a ultima curve for visualization and analysis.
+    ///
+    /// Creates a curve of ultima values across strike prices for long call options in
+    /// the chain. Ultima is the sensitivity of vomma to implied volatility.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Curve, CurveError>` - A curve object containing ultima data points;
+    ///   strikes whose ultima cannot be calculated are skipped
+    pub fn ultima_curve(&self) -> Result<Curve, CurveError> {
+        self.greek_curve(|option| option.ultima())
+    }
+
+    /// Builds a strike versus Greek curve for the long calls of the chain.
+    fn greek_curve<F>(&self, greek: F) -> Result<Curve, CurveError>
+    where
+        F: Fn(&Options) -> Result<Decimal, GreeksError>,
+    {
+        let points: BTreeSet<Point2D> = self
+            .get_single_iter()
+            .filter_map(|option_data| {
+                let option = option_data.get_option(Side::Long, OptionStyle::Call).ok()?;
+                let value = greek(&option).ok()?;
+                Some(Point2D::new(option.strike_price.to_dec(), value))
+            })
+            .collect();
+        Ok(Curve::new(points))
+    }
+
     /// Updates the expiration date for the option chain and recalculates Greeks.
     ///
     /// This method changes the expiration date of the option chain to the provided value
@@ -6304,6 +6453,32 @@ mod tests_gamma_calculations {
         assert_eq!(curve.x_range.1, last_strike.to_dec());
     }
 
+    #[test]
+    fn test_higher_order_curves() {
+        let chain = create_test_chain_with_gamma();
+        let curves = [
+            chain.vanna_curve(),
+            chain.vomma_curve(),
+            chain.charm_curve(),
+            chain.veta_curve(),
+            chain.speed_curve(),
+            chain.zomma_curve(),
+            chain.color_curve(),
+            chain.ultima_curve(),
+        ];
+        for curve in curves {
+            let curve = curve.unwrap();
+            assert_eq!(curve.points.len(), chain.options.len());
+        }
+
+        let first = chain.options.iter().next().unwrap();
+        let option = first.get_option(Side::Long, OptionStyle::Call).unwrap();
+        let vanna_curve = chain.vanna_curve().unwrap();
+        let point = vanna_curve.points.iter().next().unwrap();
+        assert_eq!(point.x, first.strike_price.to_dec());
+        assert_eq!(point.y, option.vanna().unwrap());
+    }
+
     #[test]
     fn test_gamma_curve_empty_chain() {
         let chain = OptionChain::new("TEST", pos!(100.0), "2024-12-31".to_string(), None, None);
@@ -8590,3 +8765,92 @@ mod tests_get_strikes_and_optiondata {
         assert_eq!(strikes[2], pos!(105.0));
     }
 }
+
+#[cfg(test)]
+mod tests_update_implied_volatilities {
+    use super::*;
+    use crate::model::ExpirationDate;
+    use crate::{OptionStyle, OptionType, Side, assert_pos_relative_eq, pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn quote(style: OptionStyle, strike: Positive, iv: Positive) -> Positive {
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos!(30.0)),
+            iv,
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Positive(option.calculate_price_black_scholes().unwrap())
+    }
+
+    fn chain_with_smile() -> OptionChain {
+        let mut chain = OptionChain::new(
+            "TEST",
+            pos!(100.0),
+            "30".to_string(),
+            Some(dec!(0.05)),
+            None,
+        );
+        for (strike, iv) in [(90.0, 0.28), (100.0, 0.22), (110.0, 0.25)] {
+            let strike = pos!(strike);
+            let iv = pos!(iv);
+            let call = quote(OptionStyle::Call, strike, iv);
+            let put = quote(OptionStyle::Put, strike, iv);
+            chain.add_option(
+                strike,
+                Some(call),
+                Some(call),
+                Some(put),
+                Some(put),
+                pos!(0.5),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        chain
+    }
+
+    #[test]
+    fn test_update_implied_volatilities_recovers_smile() {
+        let mut chain = chain_with_smile();
+        assert_eq!(chain.update_implied_volatilities(), 3);
+        let expected = [pos!(0.28), pos!(0.22), pos!(0.25)];
+        for (option, iv) in chain.options.iter().zip(expected) {
+            assert_pos_relative_eq!(option.implied_volatility, iv, pos!(1e-3));
+        }
+    }
+
+    #[test]
+    fn test_update_implied_volatilities_skips_missing_quotes() {
+        let mut chain = chain_with_smile();
+        chain.add_option(
+            pos!(120.0),
+            None,
+            spos!(0.2),
+            None,
+            None,
+            pos!(0.4),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert_eq!(chain.update_implied_volatilities(), 3);
+        let untouched = chain.options.iter().last().unwrap();
+        assert_eq!(untouched.implied_volatility, pos!(0.4));
+    }
+}
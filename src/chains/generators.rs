@@ -95,6 +95,10 @@ pub fn generator_optionchain(
             walk_params.walker.heston(walk_params).unwrap(),
             Some(*volatility),
         ),
+        WalkType::LocalVolatility { surface, .. } => (
+            walk_params.walker.local_volatility(walk_params).unwrap(),
+            Some(surface.atm_volatility()),
+        ),
         WalkType::Custom { volatility, .. } => (
             walk_params.walker.custom(walk_params).unwrap(),
             Some(*volatility),
@@ -183,6 +187,9 @@ pub fn generator_positive(
         WalkType::JumpDiffusion { .. } => walk_params.walker.jump_diffusion(walk_params).unwrap(),
         WalkType::Garch { .. } => walk_params.walker.garch(walk_params).unwrap(),
         WalkType::Heston { .. } => walk_params.walker.heston(walk_params).unwrap(),
+        WalkType::LocalVolatility { .. } => {
+            walk_params.walker.local_volatility(walk_params).unwrap()
+        }
         WalkType::Custom { .. } => walk_params.walker.custom(walk_params).unwrap(),
         WalkType::Historical { .. } => walk_params.walker.historical(walk_params).unwrap(),
     };
@@ -9,12 +9,15 @@ use std::fmt::{Display, Formatter, Result};
 /// Represents the various configurations of option strategy legs with different complexities.
 ///
 /// This enum provides structured representations for common option strategies that can consist
-/// of two, three, four, or six legs. Each variant holds references to the corresponding `OptionData`
+/// of one, two, three, four, or six legs. Each variant holds references to the corresponding `OptionData`
 /// objects that make up the strategy. This allows for organized storage and manipulation of
 /// multi-leg option strategies with varying complexity.
 ///
 /// # Variants
 ///
+/// * `OneLeg` - Represents strategies with a single option leg, possibly combined with a
+///   position in the underlying, such as covered calls or protective puts.
+///
 /// * `TwoLegs` - Represents option strategies with two legs, such as vertical spreads,
 ///   straddles, or strangles.
 ///
@@ -34,6 +37,15 @@ use std::fmt::{Display, Formatter, Result};
 /// approach.
 #[derive(Debug, Clone)]
 pub enum StrategyLegs<'a> {
+    /// Single-option strategy configuration
+    ///
+    /// Common examples include covered calls and protective puts, where the other leg is the
+    /// underlying asset itself.
+    OneLeg {
+        /// The only option contract in the strategy
+        first: &'a OptionData,
+    },
+
     /// Two-legged option strategy configuration
     ///
     /// Common examples include vertical spreads (bull/bear spreads), straddles, and strangles.
@@ -92,6 +104,9 @@ pub enum StrategyLegs<'a> {
 impl Display for StrategyLegs<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
+            StrategyLegs::OneLeg { first } => {
+                write!(f, "One Leg Strategy:\n1st Leg: {}", first)
+            }
             StrategyLegs::TwoLegs { first, second } => {
                 write!(
                     f,
@@ -274,6 +289,17 @@ mod tests {
         assert!(display_string.contains("2nd Leg"));
     }
 
+    #[test]
+    fn test_display_one_leg() {
+        let option = create_test_option(dec!(100.0));
+        let strategy = StrategyLegs::OneLeg { first: &option };
+
+        let display_string = format!("{}", strategy);
+        assert!(display_string.contains("One Leg Strategy"));
+        assert!(display_string.contains("1st Leg"));
+        assert!(!display_string.contains("2nd Leg"));
+    }
+
     #[test]
     fn test_display_four_legs() {
         let option1 = create_test_option(dec!(100.0));
@@ -9,6 +9,7 @@ use crate::error::ChainError;
 use crate::greeks::{delta, gamma};
 use crate::model::Position;
 use crate::strategies::{BasicAble, FindOptimalSide};
+use crate::volatility::solve_implied_volatility;
 use crate::{ExpirationDate, OptionStyle, Options, Positive, Side, pos};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -482,7 +483,6 @@ impl OptionData {
     /// * An `Options` instance configured with the specified parameters
     /// * A `ChainError` if there was a problem creating the option
     ///
-    #[allow(dead_code)]
     fn get_option_for_iv(
         &self,
         side: Side,
@@ -830,6 +830,60 @@ impl OptionData {
         }
     }
 
+    /// Implies the volatility of this strike from its bid/ask quotes and stores it.
+    ///
+    /// The mid price of the out-of-the-money side is used (calls at or above the underlying
+    /// price, puts below it), since it carries the most time value. When that side has no
+    /// two-sided quote the other side is used instead.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Positive)` - The implied volatility, also written to `implied_volatility`.
+    /// * `Err(ChainError)` - If the pricing parameters are missing, there is no two-sided
+    ///   quote, or the solver fails. `implied_volatility` is left unchanged in that case.
+    pub fn calculate_implied_volatility(&mut self) -> Result<Positive, ChainError> {
+        if self.symbol.is_none() || self.expiration_date.is_none() {
+            return Err(ChainError::invalid_parameters(
+                "price_params",
+                "Symbol and expiration date are required to imply a volatility",
+            ));
+        }
+        let underlying_price = match &self.underlying_price {
+            Some(price) => **price,
+            None => {
+                return Err(ChainError::invalid_parameters(
+                    "underlying_price",
+                    "Underlying price is required to imply a volatility",
+                ));
+            }
+        };
+        let mid = |bid: Option<Positive>, ask: Option<Positive>| match (bid, ask) {
+            (Some(bid), Some(ask)) if ask > Positive::ZERO => Some((bid + ask) / Positive::TWO),
+            _ => None,
+        };
+        let call = mid(self.call_bid, self.call_ask).map(|price| (OptionStyle::Call, price));
+        let put = mid(self.put_bid, self.put_ask).map(|price| (OptionStyle::Put, price));
+        let quote = if self.strike_price >= underlying_price {
+            call.or(put)
+        } else {
+            put.or(call)
+        };
+        let (option_style, market_price) = quote.ok_or_else(|| {
+            ChainError::invalid_prices(None, None, "No bid/ask quote to imply a volatility from")
+        })?;
+
+        let option = self.get_option_for_iv(Side::Long, option_style, self.implied_volatility)?;
+        let result = solve_implied_volatility(&option, market_price.to_dec()).map_err(|e| {
+            ChainError::invalid_volatility(None, &format!("Strike {}: {}", self.strike_price, e))
+        })?;
+        trace!(
+            "Implied volatility {} for strike {} in {} {} iterations",
+            result.volatility, self.strike_price, result.iterations, result.method
+        );
+        self.implied_volatility = result.volatility;
+        Ok(result.volatility)
+    }
+
     /// Retrieves delta values for options at the current strike price.
     ///
     /// Delta measures the rate of change of the option price with respect to changes
@@ -71,6 +71,22 @@ pub enum VolatilityError {
         /// The last volatility value that was calculated before giving up.
         last_volatility: Positive,
     },
+    /// Error indicating that a volatility model could not be calibrated to market data.
+    ///
+    /// This occurs when there are not enough quotes to fit the model, or when the
+    /// fitted parameters violate the constraints of the model.
+    CalibrationError {
+        /// A description of why the calibration failed.
+        reason: String,
+    },
+    /// Error indicating that market data cannot be used for a volatility estimate.
+    ///
+    /// This occurs when OHLCV candles are missing, out of order, have non-positive
+    /// prices, or cannot be grouped into the requested time frame.
+    MarketDataError {
+        /// A description of what is wrong with the data.
+        reason: String,
+    },
 }
 
 impl Error for VolatilityError {}
@@ -103,6 +119,12 @@ impl fmt::Display for VolatilityError {
                     iterations, last_volatility
                 )
             }
+            VolatilityError::CalibrationError { reason } => {
+                write!(f, "Calibration error: {}", reason)
+            }
+            VolatilityError::MarketDataError { reason } => {
+                write!(f, "Market data error: {}", reason)
+            }
         }
     }
 }
@@ -181,6 +203,24 @@ mod tests_volatility_errors {
         assert_eq!(error.to_string(), "Option error: Invalid option parameters");
     }
 
+    #[test]
+    fn test_calibration_error() {
+        let error = VolatilityError::CalibrationError {
+            reason: "Not enough quotes".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "Calibration error: Not enough quotes");
+    }
+
+    #[test]
+    fn test_market_data_error() {
+        let error = VolatilityError::MarketDataError {
+            reason: "Not enough candles".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "Market data error: Not enough candles");
+    }
+
     #[test]
     fn test_no_convergence_error() {
         let error = VolatilityError::NoConvergence {
@@ -7,8 +7,10 @@ use crate::constants::ZERO;
 use crate::error::greeks::GreeksError;
 use crate::greeks::utils::{big_n, d1, d2, n};
 use crate::model::types::OptionStyle;
+use crate::volatility::SABRParameters;
 use crate::{Options, Positive, Side};
 use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 
 /// Represents a complete set of option Greeks, which measure the sensitivity of an option's
@@ -28,9 +30,11 @@ use serde::{Deserialize, Serialize};
 /// * `rho`: Measures the rate of change in the option price with respect to the risk-free interest rate
 /// * `rho_d`: Measures the rate of change in the option price with respect to the dividend yield
 /// * `alpha`: Represents a measure of an option's excess return relative to what would be predicted by models
+/// * `vanna`, `vomma`, `charm`, `veta`: Second-order sensitivities of delta and vega to volatility and time
+/// * `speed`, `zomma`, `color`, `ultima`: Third-order sensitivities of gamma and vomma
 ///
 /// These metrics help traders understand and manage the various dimensions of risk in option positions.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Greek {
     /// Measures sensitivity to changes in the underlying asset's price (first derivative)
     pub delta: Decimal,
@@ -46,6 +50,22 @@ pub struct Greek {
     pub rho_d: Decimal,
     /// Measures the option's theoretical value not explained by other Greeks
     pub alpha: Decimal,
+    /// Sensitivity of delta to changes in implied volatility
+    pub vanna: Decimal,
+    /// Sensitivity of vega to changes in implied volatility (volga)
+    pub vomma: Decimal,
+    /// Change in delta as time passes (delta decay)
+    pub charm: Decimal,
+    /// Change in vega as time passes
+    pub veta: Decimal,
+    /// Sensitivity of gamma to changes in the underlying price
+    pub speed: Decimal,
+    /// Sensitivity of gamma to changes in implied volatility
+    pub zomma: Decimal,
+    /// Change in gamma as time passes (gamma decay)
+    pub color: Decimal,
+    /// Sensitivity of vomma to changes in implied volatility
+    pub ultima: Decimal,
 }
 
 /// A struct representing a snapshot of the Greeks, financial measures used to assess risk and
@@ -71,6 +91,22 @@ pub struct GreeksSnapshot {
     pub rho_d: Option<Decimal>,
     /// Measures the option's theoretical value not explained by other Greeks
     pub alpha: Option<Decimal>,
+    /// Sensitivity of delta to changes in implied volatility
+    pub vanna: Option<Decimal>,
+    /// Sensitivity of vega to changes in implied volatility (volga)
+    pub vomma: Option<Decimal>,
+    /// Change in delta as time passes (delta decay)
+    pub charm: Option<Decimal>,
+    /// Change in vega as time passes
+    pub veta: Option<Decimal>,
+    /// Sensitivity of gamma to changes in the underlying price
+    pub speed: Option<Decimal>,
+    /// Sensitivity of gamma to changes in implied volatility
+    pub zomma: Option<Decimal>,
+    /// Change in gamma as time passes (gamma decay)
+    pub color: Option<Decimal>,
+    /// Sensitivity of vomma to changes in implied volatility
+    pub ultima: Option<Decimal>,
 }
 
 /// Trait that provides option Greeks calculation functionality for financial instruments.
@@ -92,6 +128,8 @@ pub struct GreeksSnapshot {
 /// - Rho: Sensitivity to changes in interest rates
 /// - Rho_d: Sensitivity to changes in dividend yield
 /// - Alpha: Ratio between gamma and theta
+/// - Vanna, Vomma, Charm and Veta: Second-order sensitivities
+/// - Speed, Zomma, Color and Ultima: Third-order sensitivities
 ///
 /// # Usage
 ///
@@ -138,6 +176,14 @@ pub trait Greeks {
             rho,
             rho_d,
             alpha,
+            vanna: self.vanna()?,
+            vomma: self.vomma()?,
+            charm: self.charm()?,
+            veta: self.veta()?,
+            speed: self.speed()?,
+            zomma: self.zomma()?,
+            color: self.color()?,
+            ultima: self.ultima()?,
         })
     }
 
@@ -259,6 +305,172 @@ pub trait Greeks {
         }
         Ok(alpha_value)
     }
+
+    /// Calculates the aggregate vanna value for all options.
+    ///
+    /// Vanna measures the sensitivity of delta to changes in implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or vanna calculation fails.
+    fn vanna(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut vanna_value = Decimal::ZERO;
+        for option in options {
+            vanna_value += vanna(option)?;
+        }
+        Ok(vanna_value)
+    }
+
+    /// Calculates the aggregate vomma value for all options.
+    ///
+    /// Vomma (volga) measures the sensitivity of vega to changes in implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or vomma calculation fails.
+    fn vomma(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut vomma_value = Decimal::ZERO;
+        for option in options {
+            vomma_value += vomma(option)?;
+        }
+        Ok(vomma_value)
+    }
+
+    /// Calculates the aggregate charm value for all options.
+    ///
+    /// Charm measures the change in delta per day as expiration approaches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or charm calculation fails.
+    fn charm(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut charm_value = Decimal::ZERO;
+        for option in options {
+            charm_value += charm(option)?;
+        }
+        Ok(charm_value)
+    }
+
+    /// Calculates the aggregate veta value for all options.
+    ///
+    /// Veta measures the change in vega per day as expiration approaches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or veta calculation fails.
+    fn veta(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut veta_value = Decimal::ZERO;
+        for option in options {
+            veta_value += veta(option)?;
+        }
+        Ok(veta_value)
+    }
+
+    /// Calculates the aggregate speed value for all options.
+    ///
+    /// Speed measures the rate of change of gamma with respect to
+    /// changes in the underlying asset's price.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or speed calculation fails.
+    fn speed(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut speed_value = Decimal::ZERO;
+        for option in options {
+            speed_value += speed(option)?;
+        }
+        Ok(speed_value)
+    }
+
+    /// Calculates the aggregate zomma value for all options.
+    ///
+    /// Zomma measures the sensitivity of gamma to changes in implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or zomma calculation fails.
+    fn zomma(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut zomma_value = Decimal::ZERO;
+        for option in options {
+            zomma_value += zomma(option)?;
+        }
+        Ok(zomma_value)
+    }
+
+    /// Calculates the aggregate color value for all options.
+    ///
+    /// Color measures the change in gamma per day as expiration approaches.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or color calculation fails.
+    fn color(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut color_value = Decimal::ZERO;
+        for option in options {
+            color_value += color(option)?;
+        }
+        Ok(color_value)
+    }
+
+    /// Calculates the aggregate ultima value for all options.
+    ///
+    /// Ultima measures the sensitivity of vomma to changes in implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or ultima calculation fails.
+    fn ultima(&self) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut ultima_value = Decimal::ZERO;
+        for option in options {
+            ultima_value += ultima(option)?;
+        }
+        Ok(ultima_value)
+    }
+
+    /// Calculates the aggregate SABR-consistent (Bartlett) delta for all options.
+    ///
+    /// Unlike `delta`, which holds volatility fixed, the SABR delta accounts for the
+    /// move of each option's volatility along the calibrated smile when the underlying
+    /// moves.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or an option cannot be
+    /// priced with the SABR volatility.
+    fn sabr_delta(&self, parameters: &SABRParameters) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut delta_value = Decimal::ZERO;
+        for option in options {
+            delta_value += parameters.bartlett_delta(option)?;
+        }
+        Ok(delta_value)
+    }
+
+    /// Calculates the aggregate SABR vega for all options.
+    ///
+    /// SABR vega measures the change in value when the SABR `alpha` moves enough to
+    /// raise the at-the-money volatility by one percentage point.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the options can't be retrieved or an option cannot be
+    /// priced with the SABR volatility.
+    fn sabr_vega(&self, parameters: &SABRParameters) -> Result<Decimal, GreeksError> {
+        let options = self.get_options()?;
+        let mut vega_value = Decimal::ZERO;
+        for option in options {
+            vega_value += parameters.vega(option)?;
+        }
+        Ok(vega_value)
+    }
 }
 
 /// Calculates the delta of an option.
@@ -1079,6 +1291,311 @@ pub fn alpha(option: &Options) -> Result<Decimal, GreeksError> {
     }
 }
 
+/// Black-Scholes inputs shared by the second- and third-order Greeks.
+///
+/// Built only when the option has both time value and volatility; every higher-order
+/// Greek is zero otherwise. As in [`gamma`] and [`vega`], the Greeks are scaled by the
+/// option quantity but not signed by side.
+///
+/// `d1` and `d2` come from the shared [`d1`]/[`d2`] helpers, which drift at the risk-free
+/// rate; the time derivatives use that drift so that they differentiate the crate's own
+/// [`delta`], [`gamma`] and [`vega`].
+struct HigherOrderInputs {
+    s: Decimal,
+    t: Decimal,
+    sigma: Decimal,
+    r: Decimal,
+    q: Decimal,
+    d1: Decimal,
+    d2: Decimal,
+    nd1: Decimal,
+    exp_minus_qt: Decimal,
+    quantity: Decimal,
+}
+
+impl HigherOrderInputs {
+    fn new(option: &Options) -> Result<Option<Self>, GreeksError> {
+        if option.implied_volatility == ZERO {
+            return Ok(None);
+        }
+        let t = option.expiration_date.get_years()?;
+        if t == Decimal::ZERO {
+            return Ok(None);
+        }
+        let d1 = d1(
+            option.underlying_price,
+            option.strike_price,
+            option.risk_free_rate,
+            t,
+            option.implied_volatility,
+        )?;
+        let d2 = d2(
+            option.underlying_price,
+            option.strike_price,
+            option.risk_free_rate,
+            t,
+            option.implied_volatility,
+        )?;
+        let q = option.dividend_yield.to_dec();
+        Ok(Some(HigherOrderInputs {
+            s: option.underlying_price.to_dec(),
+            t: t.to_dec(),
+            sigma: option.implied_volatility.to_dec(),
+            r: option.risk_free_rate,
+            q,
+            d1,
+            d2,
+            nd1: n(d1)?,
+            exp_minus_qt: (-q * t.to_dec()).exp(),
+            quantity: option.quantity.to_dec(),
+        }))
+    }
+
+    fn sqrt_t(&self) -> Decimal {
+        self.t.sqrt().unwrap_or(Decimal::ZERO)
+    }
+
+    /// Vega per unit of volatility, before the percentage scaling used by [`vega`].
+    fn raw_vega(&self) -> Decimal {
+        self.s * self.exp_minus_qt * self.nd1 * self.sqrt_t()
+    }
+
+    /// Gamma per unit of the underlying, as returned by [`gamma`] for one contract.
+    fn raw_gamma(&self) -> Decimal {
+        self.exp_minus_qt * self.nd1 / (self.s * self.sigma * self.sqrt_t())
+    }
+}
+
+/// Computes the vanna of an option.
+///
+/// Vanna is the sensitivity of delta to changes in implied volatility, or equivalently
+/// the sensitivity of vega to changes in the underlying price. It is scaled, like
+/// [`vega`], to a 1% change in volatility.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Vanna} = -e^{-qT} \cdot n(d1) \cdot \frac{d2}{\sigma}
+/// ```
+///
+/// The result is divided by 100 and multiplied by the option quantity. Options with zero
+/// volatility or at expiration have a vanna of zero.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn vanna(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let vanna = -inputs.exp_minus_qt * inputs.nd1 * inputs.d2 / inputs.sigma;
+    Ok(vanna * inputs.quantity / Decimal::ONE_HUNDRED)
+}
+
+/// Computes the vomma (also called volga) of an option.
+///
+/// Vomma is the sensitivity of vega to changes in implied volatility. Because [`vega`] is
+/// already expressed per 1% of volatility, vomma is the change in that vega for a further
+/// 1% change in volatility.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Vomma} = S \cdot e^{-qT} \cdot n(d1) \cdot \sqrt{T} \cdot \frac{d1 \cdot d2}{\sigma}
+/// ```
+///
+/// The result is divided by 100² and multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn vomma(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let vomma = inputs.raw_vega() * inputs.d1 * inputs.d2 / inputs.sigma;
+    Ok(vomma * inputs.quantity / dec!(10000))
+}
+
+/// Computes the charm (delta decay) of an option.
+///
+/// Charm measures how delta changes as time passes, all else equal. Like [`theta`], it is
+/// expressed per calendar day, so a positive charm means delta increases by that amount
+/// by tomorrow.
+///
+/// # Formula
+///
+/// **Call Options:**
+///
+/// ```math
+/// \text{Charm}_{\text{call}} = q e^{-qT} N(d1)
+/// - e^{-qT} n(d1) \frac{2rT - d2 \sigma \sqrt{T}}{2T\sigma\sqrt{T}}
+/// ```
+///
+/// **Put Options:**
+///
+/// ```math
+/// \text{Charm}_{\text{put}} = -q e^{-qT} N(-d1)
+/// - e^{-qT} n(d1) \frac{2rT - d2 \sigma \sqrt{T}}{2T\sigma\sqrt{T}}
+/// ```
+///
+/// The result is divided by 365 and multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration, `d1`/`d2` or the normal CDF cannot
+/// be computed.
+pub fn charm(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let vol_sqrt_t = inputs.sigma * inputs.sqrt_t();
+    let common = inputs.exp_minus_qt
+        * inputs.nd1
+        * (Decimal::TWO * inputs.r * inputs.t - inputs.d2 * vol_sqrt_t)
+        / (Decimal::TWO * inputs.t * vol_sqrt_t);
+    let charm = match option.option_style {
+        OptionStyle::Call => inputs.q * inputs.exp_minus_qt * big_n(inputs.d1)? - common,
+        OptionStyle::Put => -inputs.q * inputs.exp_minus_qt * big_n(-inputs.d1)? - common,
+    };
+    Ok(charm * inputs.quantity / Decimal::from(365))
+}
+
+/// Computes the veta of an option.
+///
+/// Veta measures how vega changes as time passes. It is expressed as the change in the
+/// per-1% [`vega`] over one calendar day.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Veta} = S e^{-qT} n(d1) \sqrt{T}
+/// \left( q + \frac{r \cdot d1}{\sigma\sqrt{T}} - \frac{1 + d1 \cdot d2}{2T} \right)
+/// ```
+///
+/// The result is divided by 100 and by 365, then multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn veta(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let veta = inputs.raw_vega()
+        * (inputs.q + inputs.r * inputs.d1 / (inputs.sigma * inputs.sqrt_t())
+            - (Decimal::ONE + inputs.d1 * inputs.d2) / (Decimal::TWO * inputs.t));
+    Ok(veta * inputs.quantity / (Decimal::ONE_HUNDRED * Decimal::from(365)))
+}
+
+/// Computes the speed of an option.
+///
+/// Speed is the third derivative of the option price with respect to the underlying price,
+/// that is, the rate at which [`gamma`] changes as the underlying moves.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Speed} = -\frac{\Gamma}{S} \left( \frac{d1}{\sigma\sqrt{T}} + 1 \right)
+/// ```
+///
+/// The result is multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1` cannot be computed.
+pub fn speed(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let speed = -inputs.raw_gamma() / inputs.s
+        * (inputs.d1 / (inputs.sigma * inputs.sqrt_t()) + Decimal::ONE);
+    Ok(speed * inputs.quantity)
+}
+
+/// Computes the zomma of an option.
+///
+/// Zomma is the sensitivity of [`gamma`] to changes in implied volatility, scaled to a
+/// 1% change in volatility.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Zomma} = \Gamma \cdot \frac{d1 \cdot d2 - 1}{\sigma}
+/// ```
+///
+/// The result is divided by 100 and multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn zomma(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let zomma = inputs.raw_gamma() * (inputs.d1 * inputs.d2 - Decimal::ONE) / inputs.sigma;
+    Ok(zomma * inputs.quantity / Decimal::ONE_HUNDRED)
+}
+
+/// Computes the color (gamma decay) of an option.
+///
+/// Color measures how [`gamma`] changes as time passes, expressed per calendar day.
+///
+/// # Formula
+///
+/// ```math
+/// \text{Color} = \frac{e^{-qT} n(d1)}{2ST\sigma\sqrt{T}}
+/// \left( 2qT + 1 + \frac{2rT - d2\sigma\sqrt{T}}{\sigma\sqrt{T}} d1 \right)
+/// ```
+///
+/// The result is divided by 365 and multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn color(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let vol_sqrt_t = inputs.sigma * inputs.sqrt_t();
+    let color = inputs.exp_minus_qt * inputs.nd1
+        / (Decimal::TWO * inputs.s * inputs.t * vol_sqrt_t)
+        * (Decimal::TWO * inputs.q * inputs.t
+            + Decimal::ONE
+            + (Decimal::TWO * inputs.r * inputs.t - inputs.d2 * vol_sqrt_t) / vol_sqrt_t
+                * inputs.d1);
+    Ok(color * inputs.quantity / Decimal::from(365))
+}
+
+/// Computes the ultima of an option.
+///
+/// Ultima is the sensitivity of [`vomma`] to changes in implied volatility, the third
+/// derivative of the option price with respect to volatility. It is scaled to 1% changes
+/// in volatility, consistently with [`vega`] and [`vomma`].
+///
+/// # Formula
+///
+/// ```math
+/// \text{Ultima} = -\frac{\text{Vega}}{\sigma^2}
+/// \left( d1 \cdot d2 (1 - d1 \cdot d2) + d1^2 + d2^2 \right)
+/// ```
+///
+/// where Vega is taken per unit of volatility. The result is divided by 100³ and
+/// multiplied by the option quantity.
+///
+/// # Errors
+///
+/// Returns a `GreeksError` if the time to expiration or `d1`/`d2` cannot be computed.
+pub fn ultima(option: &Options) -> Result<Decimal, GreeksError> {
+    let Some(inputs) = HigherOrderInputs::new(option)? else {
+        return Ok(Decimal::ZERO);
+    };
+    let d1d2 = inputs.d1 * inputs.d2;
+    let ultima = -inputs.raw_vega() / (inputs.sigma * inputs.sigma)
+        * (d1d2 * (Decimal::ONE - d1d2) + inputs.d1 * inputs.d1 + inputs.d2 * inputs.d2);
+    Ok(ultima * inputs.quantity / dec!(1000000))
+}
+
 #[cfg(test)]
 pub mod tests_delta_equations {
     use super::*;
@@ -1950,6 +2467,24 @@ mod tests_greeks_trait {
         )
     }
 
+    #[test]
+    fn test_higher_order_greeks_aggregate() {
+        let call = create_test_option(Side::Long, OptionStyle::Call, pos!(1.0));
+        let put = create_test_option(Side::Long, OptionStyle::Put, pos!(2.0));
+        let expected_vanna = vanna(&call).unwrap() + vanna(&put).unwrap();
+        let expected_charm = charm(&call).unwrap() + charm(&put).unwrap();
+        let expected_color = color(&call).unwrap() + color(&put).unwrap();
+        let collection = TestOptionCollection {
+            options: vec![call, put],
+        };
+
+        let greeks = collection.greeks().unwrap();
+        assert_decimal_eq!(greeks.vanna, expected_vanna, dec!(1e-12));
+        assert_decimal_eq!(greeks.charm, expected_charm, dec!(1e-12));
+        assert_decimal_eq!(greeks.color, expected_color, dec!(1e-12));
+        assert_decimal_eq!(collection.speed().unwrap(), greeks.speed, dec!(1e-12));
+    }
+
     #[test]
     fn test_greeks_single_option() {
         let option = create_test_option(Side::Long, OptionStyle::Call, pos!(1.0));
@@ -2157,3 +2692,160 @@ mod tests_greeks_trait {
         assert!(greeks.rho_d.abs() > dec!(0.0));
     }
 }
+
+#[cfg(test)]
+mod tests_higher_order_greeks {
+    use super::*;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::{ExpirationDate, assert_decimal_eq, pos};
+
+    const H_VOL: Decimal = dec!(0.0001);
+    const H_SPOT: Decimal = dec!(0.01);
+    const H_DAYS: Decimal = dec!(0.01);
+
+    fn create_option(style: OptionStyle, strike: Positive, dividend_yield: Positive) -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos!(45.0)),
+            pos!(0.25),
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            style,
+            dividend_yield,
+            None,
+        )
+    }
+
+    fn bump_vol(option: &Options, h: Decimal) -> Options {
+        let mut bumped = option.clone();
+        bumped.implied_volatility = Positive(option.implied_volatility.to_dec() + h);
+        bumped
+    }
+
+    fn bump_spot(option: &Options, h: Decimal) -> Options {
+        let mut bumped = option.clone();
+        bumped.underlying_price = Positive(option.underlying_price.to_dec() + h);
+        bumped
+    }
+
+    fn bump_days(option: &Options, h: Decimal) -> Options {
+        let mut bumped = option.clone();
+        let days = option.expiration_date.get_days().unwrap();
+        bumped.expiration_date = ExpirationDate::Days(Positive(days.to_dec() + h));
+        bumped
+    }
+
+    /// Central difference of `greek` with respect to volatility, per 1% of volatility.
+    fn d_vol(option: &Options, greek: fn(&Options) -> Result<Decimal, GreeksError>) -> Decimal {
+        let up = greek(&bump_vol(option, H_VOL)).unwrap();
+        let down = greek(&bump_vol(option, -H_VOL)).unwrap();
+        (up - down) / (Decimal::TWO * H_VOL) / Decimal::ONE_HUNDRED
+    }
+
+    /// Central difference of `greek` with respect to the passage of one calendar day.
+    fn d_day(option: &Options, greek: fn(&Options) -> Result<Decimal, GreeksError>) -> Decimal {
+        let sooner = greek(&bump_days(option, -H_DAYS)).unwrap();
+        let later = greek(&bump_days(option, H_DAYS)).unwrap();
+        (sooner - later) / (Decimal::TWO * H_DAYS)
+    }
+
+    #[test]
+    fn test_volatility_derivatives_match_finite_differences() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            for strike in [pos!(90.0), pos!(100.0), pos!(112.0)] {
+                let option = create_option(style, strike, pos!(0.02));
+                assert_decimal_eq!(vanna(&option).unwrap(), d_vol(&option, delta), dec!(1e-6));
+                assert_decimal_eq!(vomma(&option).unwrap(), d_vol(&option, vega), dec!(1e-6));
+                assert_decimal_eq!(zomma(&option).unwrap(), d_vol(&option, gamma), dec!(1e-6));
+                assert_decimal_eq!(ultima(&option).unwrap(), d_vol(&option, vomma), dec!(1e-6));
+            }
+        }
+    }
+
+    #[test]
+    fn test_speed_matches_finite_difference() {
+        let option = create_option(OptionStyle::Call, pos!(105.0), pos!(0.02));
+        let up = gamma(&bump_spot(&option, H_SPOT)).unwrap();
+        let down = gamma(&bump_spot(&option, -H_SPOT)).unwrap();
+        let expected = (up - down) / (Decimal::TWO * H_SPOT);
+        assert_decimal_eq!(speed(&option).unwrap(), expected, dec!(1e-7));
+    }
+
+    #[test]
+    fn test_time_derivatives_match_finite_differences() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            for dividend_yield in [Positive::ZERO, pos!(0.02), pos!(0.05)] {
+                for strike in [pos!(95.0), pos!(105.0)] {
+                    let option = create_option(style, strike, dividend_yield);
+                    assert_decimal_eq!(charm(&option).unwrap(), d_day(&option, delta), dec!(1e-6));
+                    assert_decimal_eq!(veta(&option).unwrap(), d_day(&option, vega), dec!(1e-6));
+                    assert_decimal_eq!(color(&option).unwrap(), d_day(&option, gamma), dec!(1e-7));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_position_matches_long() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            let long = create_option(style, pos!(97.0), pos!(0.02));
+            let mut short = long.clone();
+            short.side = Side::Short;
+            // Only delta is signed by side
+            assert_ne!(delta(&long).unwrap(), Decimal::ZERO);
+            assert_eq!(delta(&short).unwrap(), -delta(&long).unwrap());
+            for greek in [
+                gamma, vega, theta, vanna, vomma, charm, veta, speed, zomma, color, ultima,
+            ] {
+                assert_ne!(greek(&long).unwrap(), Decimal::ZERO);
+                assert_eq!(greek(&short).unwrap(), greek(&long).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_volatility_and_expiration() {
+        let mut option = create_option(OptionStyle::Call, pos!(100.0), Positive::ZERO);
+        option.implied_volatility = Positive::ZERO;
+        for greek in [vanna, vomma, charm, veta, speed, zomma, color, ultima] {
+            assert_eq!(greek(&option).unwrap(), Decimal::ZERO);
+        }
+        let mut option = create_option(OptionStyle::Put, pos!(100.0), Positive::ZERO);
+        option.expiration_date = ExpirationDate::Days(Positive::ZERO);
+        for greek in [vanna, vomma, charm, veta, speed, zomma, color, ultima] {
+            assert_eq!(greek(&option).unwrap(), Decimal::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_scaled_by_quantity() {
+        let single = create_option(OptionStyle::Call, pos!(100.0), pos!(0.01));
+        let mut triple = single.clone();
+        triple.quantity = pos!(3.0);
+        for greek in [vanna, vomma, charm, veta, speed, zomma, color, ultima] {
+            assert_decimal_eq!(
+                greek(&triple).unwrap(),
+                greek(&single).unwrap() * Decimal::from(3),
+                dec!(1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn test_greeks_trait_includes_higher_orders() {
+        let option = create_option(OptionStyle::Put, pos!(98.0), pos!(0.01));
+        let greeks = option.greeks().unwrap();
+        assert_eq!(greeks.vanna, vanna(&option).unwrap());
+        assert_eq!(greeks.vomma, vomma(&option).unwrap());
+        assert_eq!(greeks.charm, charm(&option).unwrap());
+        assert_eq!(greeks.veta, veta(&option).unwrap());
+        assert_eq!(greeks.speed, speed(&option).unwrap());
+        assert_eq!(greeks.zomma, zomma(&option).unwrap());
+        assert_eq!(greeks.color, color(&option).unwrap());
+        assert_eq!(greeks.ultima, ultima(&option).unwrap());
+    }
+}
@@ -25,6 +25,13 @@
 //! * Rho   (ρ) - Measures the rate of change in option value with respect to the risk-free rate
 //! * Rho_d     - Measures sensitivity to dividend yield changes
 //!
+//! Higher-order Greeks are available as well:
+//!
+//! * Vanna, Vomma (Volga) - Sensitivity of delta and vega to volatility
+//! * Charm, Veta          - Decay of delta and vega per day
+//! * Speed, Zomma, Color  - Sensitivity of gamma to the underlying, volatility and time
+//! * Ultima               - Sensitivity of vomma to volatility
+//!
 //! ## Utilities Included
 //!
 //! The utilities module provides essential mathematical functions for Greek calculations:
@@ -88,7 +95,10 @@
 mod equations;
 mod utils;
 
-pub use equations::{Greek, Greeks, GreeksSnapshot, delta, gamma, rho, rho_d, theta, vega};
-pub(crate) use utils::calculate_d_values;
+pub use equations::{
+    Greek, Greeks, GreeksSnapshot, charm, color, delta, gamma, rho, rho_d, speed, theta, ultima,
+    vanna, vega, veta, vomma, zomma,
+};
 pub use utils::calculate_delta_neutral_sizes;
 pub use utils::{big_n, d1, d2, n};
+pub(crate) use utils::{calculate_d_values, side_sign};
@@ -424,6 +424,19 @@ pub(crate) fn calculate_d_values(option: &Options) -> Result<(Decimal, Decimal),
     Ok((d1_value?, d2_value?))
 }
 
+/// Sign of the exposure of `option`: one for long options and minus one for short ones.
+///
+/// Only [`delta`](crate::greeks::delta) is signed by side; the other Greeks describe a long
+/// contract of the same quantity, and are multiplied by this sign when the exposure of a
+/// position is aggregated.
+pub(crate) fn side_sign(option: &Options) -> Decimal {
+    if option.is_short() {
+        Decimal::NEGATIVE_ONE
+    } else {
+        Decimal::ONE
+    }
+}
+
 /// Calculates the optimal position sizes for two positions to achieve delta neutrality
 /// while maintaining a specified total position size.
 ///
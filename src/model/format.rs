@@ -80,6 +80,14 @@ impl fmt::Display for ExoticParams {
             fields.push(format!("Spot Max: {:.2}", max));
         }
 
+        if let Some(volatility) = self.second_asset_volatility {
+            fields.push(format!("Second Asset Volatility: {}", volatility));
+        }
+
+        if let Some(correlation) = self.correlation {
+            fields.push(format!("Correlation: {:.2}", correlation));
+        }
+
         write!(f, "{}", fields.join(", "))
     }
 }
@@ -397,6 +405,8 @@ mod tests_options {
             spot_prices: None,
             spot_min: None,
             spot_max: None,
+            second_asset_volatility: None,
+            correlation: None,
         };
         let naive_date = NaiveDate::from_ymd_opt(2024, 8, 8)
             .expect("Invalid date")
@@ -431,7 +441,7 @@ mod tests_options {
             Quantity: 5\n\
             Risk-free Rate: 1.50%\n\
             Dividend Yield: 1%\n\
-            Exotic Parameters: ExoticParams { spot_prices: None, spot_min: None, spot_max: None }";
+            Exotic Parameters: ExoticParams { spot_prices: None, spot_min: None, spot_max: None, second_asset_volatility: None, correlation: None }";
 
         assert_eq!(display_output, expected_output);
     }
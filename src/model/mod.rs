@@ -17,6 +17,7 @@
 //! * `utils` - Utility functions for model operations and calculations
 //! * `format` - Display and Debug implementations for model types
 //! * `profit_range` - Calculations for profit/loss ranges
+//! * `underlying` - Positions held directly in the underlying asset
 //!
 //! ## Key Features
 //!
@@ -126,6 +127,9 @@ mod axis;
 mod expiration;
 mod trade;
 
+/// Positions held directly in the underlying asset, used as the stock leg of covered strategies.
+mod underlying;
+
 pub use axis::BasicAxisTypes;
 pub use expiration::ExpirationDate;
 pub use option::Options;
@@ -133,3 +137,4 @@ pub use position::Position;
 pub use profit_range::ProfitLossRange;
 pub use trade::{Trade, TradeAble, TradeStatus, TradeStatusAble, save_trades};
 pub use types::{OptionStyle, OptionType, Side};
+pub use underlying::UnderlyingPosition;
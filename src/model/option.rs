@@ -1,5 +1,5 @@
 use crate::chains::OptionData;
-use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV, ZERO};
+use crate::constants::ZERO;
 use crate::error::{GreeksError, OptionsError, OptionsResult, StrategyError, VolatilityError};
 use crate::greeks::Greeks;
 use crate::model::types::{OptionBasicType, OptionStyle, OptionType, Side};
@@ -14,10 +14,10 @@ use crate::strategies::base::BasicAble;
 use crate::visualization::{
     ColorScheme, Graph, GraphConfig, GraphData, LineStyle, Series2D, TraceMode,
 };
-use crate::{ExpirationDate, Positive, pos};
+use crate::volatility::solve_implied_volatility;
+use crate::{ExpirationDate, Positive};
 use num_traits::FromPrimitive;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::error::Error;
@@ -49,6 +49,14 @@ pub struct ExoticParams {
     /// Maximum observed spot price during the option's lifetime,
     /// used for lookback option pricing.
     pub spot_max: Option<Decimal>, // Lookback
+
+    /// Volatility of the second asset, used for exchange option pricing.
+    /// Defaults to the option's implied volatility when absent.
+    pub second_asset_volatility: Option<Positive>, // Exchange
+
+    /// Correlation between the underlying and the second asset, used for
+    /// exchange option pricing. Defaults to zero when absent.
+    pub correlation: Option<Decimal>, // Exchange
 }
 
 /// Represents a financial option contract with its essential parameters and characteristics.
@@ -539,45 +547,32 @@ impl Options {
 
     /// **calculate_implied_volatility**:
     ///
-    /// This function estimates the implied volatility of an option based on its market price
-    /// using binary search. Implied volatility is a key metric in options trading that reflects
-    /// the market's view of the expected volatility of the underlying asset.
+    /// This function estimates the implied volatility of an option based on its market price.
+    /// Implied volatility is a key metric in options trading that reflects the market's view
+    /// of the expected volatility of the underlying asset.
     ///
     /// ### Parameters:
     ///
     /// - `market_price`: The market price of the option as a `Decimal`. This represents the cost
-    ///   at which the option is traded in the market.
+    ///   at which the option is traded in the market. Short positions may pass a negative price.
     ///
     /// ### Returns:
     ///
-    /// - `Ok(Positive)`: A `Positive` value representing the calculated implied volatility as a percentage.
-    /// - `Err(ImpliedVolatilityError)`: An error indicating the reason calculation failed, such as:
-    ///     - No convergence within the maximum number of iterations.
-    ///     - Invalid option parameters.
+    /// - `Ok(Positive)`: The implied volatility as a decimal fraction (e.g. `0.2` for 20%).
+    /// - `Err(VolatilityError)`: An error indicating the reason calculation failed.
     ///
     /// ### Implementation Details:
     ///
-    /// - **Binary Search**: The function uses a binary search approach to iteratively find
-    ///   the implied volatility (`volatility`) that narrows the difference between the calculated
-    ///   option price (via Black-Scholes) and the target `market_price`.
-    ///
-    /// - **Short Options Adjustment**: For short options, the market price is inverted (negated),
-    ///   and this adjustment ensures proper calculation of implied volatility.
-    ///
-    /// - **Bounds and Iteration**: The method starts with a maximum bound (`5.0`, representing 500%
-    ///   volatility) and a lower bound (`0.0`). It adjusts these bounds based on whether the computed
-    ///   price is above or below the target and repeats until convergence or the maximum number of
-    ///   iterations is reached (`MAX_ITERATIONS_IV`).
-    ///
-    /// - **Convergence Tolerance**: The function stops iterating when the computed price is within `IV_TOLERANCE`
-    ///   of the target market price or when the difference between the high and low bounds is smaller
-    ///   than a threshold (`0.0001`).
+    /// The calculation is delegated to [`crate::volatility::solve_implied_volatility`], which
+    /// starts from a Corrado–Miller estimate, runs Newton–Raphson on the analytic vega and
+    /// falls back to Brent's method when Newton leaves the bracketing interval. Use that
+    /// function directly when the convergence diagnostics are needed.
     ///
     /// ### Error Cases:
-    /// - **No Convergence**: If the binary search exhausts the allowed number of iterations (`MAX_ITERATIONS_IV`)
-    ///   without sufficiently narrowing down the implied volatility, the function returns an `ImpliedVolatilityError::NoConvergence`.
-    /// - **Invalid Parameters**: Bounds violations or invalid market inputs can potentially cause other errors
-    ///   during calculations.
+    /// - **Invalid Price**: The market price lies outside the no-arbitrage bounds of the option.
+    /// - **Invalid Option**: The option is expired, or the price is zero.
+    /// - **No Convergence**: Neither Newton nor Brent reached `IV_TOLERANCE` within
+    ///   `MAX_ITERATIONS_IV` iterations.
     ///
     /// ### Example Usage:
     /// ```rust
@@ -611,55 +606,7 @@ impl Options {
         &self,
         market_price: Decimal,
     ) -> Result<Positive, VolatilityError> {
-        let is_short = self.is_short();
-        let target_price = if is_short {
-            -market_price
-        } else {
-            market_price
-        };
-
-        // Initialize high and low bounds for volatility
-        let mut high = pos!(5.0); // 500% max volatility
-        let mut low = pos!(0.0);
-
-        // Binary search through volatilities until we find one that gives us our target price
-        // or until we reach maximum iterations
-        for _ in 0..MAX_ITERATIONS_IV {
-            // Calculate midpoint volatility
-            let mid_vol = (high.to_dec() + low.to_dec()) / Decimal::TWO;
-            let volatility = Positive(mid_vol);
-
-            // Calculate option price at this volatility
-            let mut option_copy = self.clone();
-            option_copy.implied_volatility = volatility;
-            let price = option_copy.calculate_price_black_scholes()?;
-
-            // Adjust price for short positions
-            let actual_price = if is_short { -price } else { price };
-
-            // Check if we're close enough to the target price
-            if (actual_price - target_price).abs() < IV_TOLERANCE {
-                return Ok(volatility);
-            }
-
-            // Update bounds based on whether this price was too high or too low
-            if actual_price > target_price {
-                high = volatility;
-            } else {
-                low = volatility;
-            }
-
-            // Check if our range is too small (meaning we've converged)
-            if (high - low).to_dec() < dec!(0.0001) {
-                return Ok(volatility);
-            }
-        }
-
-        // If we haven't found a solution after max iterations
-        Err(VolatilityError::NoConvergence {
-            iterations: MAX_ITERATIONS_IV,
-            last_volatility: (high + low) / Positive::TWO,
-        })
+        solve_implied_volatility(self, market_price).map(|result| result.volatility)
     }
 }
 
@@ -1729,8 +1676,8 @@ mod tests_greeks {
 #[cfg(test)]
 mod tests_greek_trait {
     use super::*;
-    use crate::assert_decimal_eq;
     use crate::model::utils::create_sample_option_simplest;
+    use crate::{assert_decimal_eq, pos};
     use rust_decimal_macros::dec;
 
     const EPSILON: Decimal = dec!(1e-6);
@@ -2075,6 +2022,7 @@ mod tests_options_black_scholes {
 #[cfg(test)]
 mod tests_calculate_implied_volatility {
     use super::*;
+    use crate::constants::IV_TOLERANCE;
     use crate::error::VolatilityError;
     use crate::{assert_pos_relative_eq, pos};
     use rust_decimal_macros::dec;
@@ -2099,7 +2047,7 @@ mod tests_calculate_implied_volatility {
         let market_price = dec!(60.30);
         let iv = option.calculate_implied_volatility(market_price).unwrap();
 
-        assert_pos_relative_eq!(iv, pos!(0.111618041), Positive(IV_TOLERANCE));
+        assert_pos_relative_eq!(iv, pos!(0.111686771), Positive(IV_TOLERANCE));
     }
 
     #[test]
@@ -2121,7 +2069,7 @@ mod tests_calculate_implied_volatility {
 
         let market_price = dec!(132.16);
         let iv = option.calculate_implied_volatility(market_price).unwrap();
-        assert_pos_relative_eq!(iv, pos!(0.125961), Positive(IV_TOLERANCE));
+        assert_pos_relative_eq!(iv, pos!(0.125937707), Positive(IV_TOLERANCE));
     }
 
     #[test]
@@ -2144,7 +2092,7 @@ mod tests_calculate_implied_volatility {
         let market_price = dec!(-114.16);
         let iv = option.calculate_implied_volatility(market_price).unwrap();
 
-        assert_pos_relative_eq!(iv, pos!(0.1258087), Positive(IV_TOLERANCE));
+        assert_pos_relative_eq!(iv, pos!(0.125753127), Positive(IV_TOLERANCE));
     }
 
     #[test]
@@ -2166,7 +2114,7 @@ mod tests_calculate_implied_volatility {
 
         let market_price = dec!(-132.27);
         let iv = option.calculate_implied_volatility(market_price).unwrap();
-        assert_pos_relative_eq!(iv, pos!(0.12611389), Positive(IV_TOLERANCE));
+        assert_pos_relative_eq!(iv, pos!(0.126050506), Positive(IV_TOLERANCE));
     }
 
     #[test]
@@ -2228,6 +2176,7 @@ mod tests_calculate_implied_volatility {
 mod tests_serialize_deserialize {
     use super::*;
     use crate::model::utils::create_sample_option_simplest_strike;
+    use crate::pos;
 
     #[test]
     fn test_serialize_deserialize_options() {
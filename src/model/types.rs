@@ -227,7 +227,7 @@ impl Payoff for OptionType {
             } => calculate_barrier_payoff(barrier_type, barrier_level, info),
             OptionType::Binary { binary_type } => calculate_binary_payoff(binary_type, info),
             OptionType::Lookback { lookback_type } => match lookback_type {
-                LookbackType::FixedStrike => standard_payoff(info),
+                LookbackType::FixedStrike => calculate_fixed_strike_payoff(info),
                 LookbackType::FloatingStrike => calculate_floating_strike_payoff(info),
             },
             OptionType::Compound { underlying_option } => underlying_option.payoff(info),
@@ -239,7 +239,7 @@ impl Payoff for OptionType {
                         .into(),
                 )
                 .to_f64(),
-            OptionType::Cliquet { .. } => standard_payoff(info),
+            OptionType::Cliquet { .. } => calculate_cliquet_payoff(info),
             OptionType::Rainbow { .. }
             | OptionType::Spread { .. }
             | OptionType::Exchange { .. } => standard_payoff(info),
@@ -421,7 +421,10 @@ fn calculate_asian_payoff(averaging_type: &AsianAveragingType, info: &PayoffInfo
 ///
 /// # Behavior
 ///
-/// 1. Evaluates whether the current spot price satisfies the barrier condition based on the given `barrier_type` and `barrier_level`.
+/// 1. Evaluates whether the spot price satisfies the barrier condition based on the given `barrier_type` and `barrier_level`.
+///    When the path extremes `spot_max` (up barriers) or `spot_min` (down barriers) are available they are
+///    used as well, so a barrier touched during the option's life is detected even if the final spot is back
+///    on the other side.
 /// 2. If the condition for an "In" type (`UpAndIn` or `DownAndIn`) barrier is met, the standard payoff is returned; otherwise, it returns `0.0`.
 /// 3. If the condition for an "Out" type (`UpAndOut` or `DownAndOut`) barrier is met, the payoff is `0.0`; otherwise, it returns the standard payoff.
 ///
@@ -438,9 +441,14 @@ fn calculate_barrier_payoff(
     barrier_level: &f64,
     info: &PayoffInfo,
 ) -> f64 {
+    let spot = info.spot.to_f64();
     let barrier_condition = match barrier_type {
-        BarrierType::UpAndIn | BarrierType::UpAndOut => info.spot >= *barrier_level,
-        BarrierType::DownAndIn | BarrierType::DownAndOut => info.spot <= *barrier_level,
+        BarrierType::UpAndIn | BarrierType::UpAndOut => {
+            info.spot_max.map_or(spot, |max| max.max(spot)) >= *barrier_level
+        }
+        BarrierType::DownAndIn | BarrierType::DownAndOut => {
+            info.spot_min.map_or(spot, |min| min.min(spot)) <= *barrier_level
+        }
     };
     let std_payoff = standard_payoff(info);
     match barrier_type {
@@ -569,6 +577,52 @@ fn calculate_floating_strike_payoff(info: &PayoffInfo) -> f64 {
     }
 }
 
+/// Calculates the payoff of a fixed strike lookback option.
+///
+/// A call pays `max(S_max - K, 0)` and a put pays `max(K - S_min, 0)`, where the extremes are
+/// taken over `info.spot_max` / `info.spot_min` and the final spot. When no extremes are
+/// provided the payoff reduces to the standard payoff at `info.spot`.
+fn calculate_fixed_strike_payoff(info: &PayoffInfo) -> f64 {
+    let spot = info.spot.to_f64();
+    let strike = info.strike.to_f64();
+    let payoff = match info.style {
+        OptionStyle::Call => (info.spot_max.map_or(spot, |max| max.max(spot)) - strike).max(ZERO),
+        OptionStyle::Put => (strike - info.spot_min.map_or(spot, |min| min.min(spot))).max(ZERO),
+    };
+    signed_payoff(payoff, info)
+}
+
+/// Calculates the payoff of a cliquet (ratchet) option.
+///
+/// `info.spot_prices` holds the spot fixings observed on each reset date, in order. The strike
+/// of the first period is `info.strike` and each reset sets the strike of the next period to
+/// the fixing, so the payoff is the sum of the vanilla payoffs of every period, the last one
+/// ending at `info.spot`. Without fixings the payoff is the standard payoff.
+fn calculate_cliquet_payoff(info: &PayoffInfo) -> f64 {
+    let fixings = match &info.spot_prices {
+        Some(fixings) if !fixings.is_empty() => fixings,
+        _ => return standard_payoff(info),
+    };
+    let period_payoff = |start: f64, end: f64| match info.style {
+        OptionStyle::Call => (end - start).max(ZERO),
+        OptionStyle::Put => (start - end).max(ZERO),
+    };
+    let mut start = info.strike.to_f64();
+    let mut payoff = ZERO;
+    for &fixing in fixings.iter().chain(std::iter::once(&info.spot.to_f64())) {
+        payoff += period_payoff(start, fixing);
+        start = fixing;
+    }
+    signed_payoff(payoff, info)
+}
+
+fn signed_payoff(payoff: f64, info: &PayoffInfo) -> f64 {
+    match info.side {
+        Side::Long => payoff,
+        Side::Short => -payoff,
+    }
+}
+
 #[cfg(test)]
 mod tests_payoff {
     use super::*;
@@ -662,6 +716,22 @@ mod tests_payoff {
         assert_eq!(option.payoff(&info), 10.0);
     }
 
+    #[test]
+    fn test_lookback_fixed_strike_call_uses_path_maximum() {
+        let option = OptionType::Lookback {
+            lookback_type: LookbackType::FixedStrike,
+        };
+        let info = PayoffInfo {
+            spot: pos!(105.0),
+            strike: pos!(100.0),
+            style: OptionStyle::Call,
+            side: Side::Long,
+            spot_max: Some(125.0),
+            ..Default::default()
+        };
+        assert_eq!(option.payoff(&info), 25.0);
+    }
+
     #[test]
     fn test_quanto_call() {
         let option = OptionType::Quanto { exchange_rate: 1.5 };
@@ -1035,6 +1105,23 @@ mod test_barrier_options {
         };
         assert_eq!(option.payoff(&info), 0.0);
     }
+
+    #[test]
+    fn test_barrier_up_and_out_call_touched_during_path() {
+        let option = OptionType::Barrier {
+            barrier_type: BarrierType::UpAndOut,
+            barrier_level: 110.0,
+        };
+        let info = PayoffInfo {
+            spot: pos!(105.0),
+            strike: pos!(100.0),
+            style: OptionStyle::Call,
+            side: Side::Long,
+            spot_max: Some(112.0),
+            ..Default::default()
+        };
+        assert_eq!(option.payoff(&info), 0.0);
+    }
 }
 
 #[cfg(test)]
@@ -1058,6 +1145,22 @@ mod test_cliquet_options {
         };
         assert_eq!(option.payoff(&info), 20.0);
     }
+
+    #[test]
+    fn test_cliquet_option_with_fixings() {
+        let option = OptionType::Cliquet {
+            reset_dates: vec![30.0, 60.0],
+        };
+        let info = PayoffInfo {
+            spot: pos!(115.0),
+            strike: pos!(100.0),
+            style: OptionStyle::Call,
+            side: Side::Long,
+            spot_prices: Some(vec![110.0, 105.0]),
+            ..Default::default()
+        };
+        assert_eq!(option.payoff(&info), 20.0);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,273 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::model::types::Side;
+use crate::pnl::PnLCalculator;
+use crate::pnl::utils::PnL;
+use crate::{ExpirationDate, Positive};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use tracing::debug;
+
+/// A position held directly in the underlying asset (shares, units of an ETF, etc.).
+///
+/// Strategies such as the covered call, protective put and collar combine option
+/// legs with a holding in the underlying itself. Unlike an option `Position`, this leg
+/// has no strike or expiration: its payoff is linear in the underlying price and its
+/// delta is simply the signed quantity held.
+///
+/// Fees are expressed per unit, in the same way as for option positions, so the total
+/// fees of the leg are `(open_fee + close_fee) * quantity`.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::Utc;
+/// use optionstratlib::model::UnderlyingPosition;
+/// use optionstratlib::{pos, Side};
+///
+/// let shares = UnderlyingPosition::new(
+///     "AAPL".to_string(),
+///     Side::Long,
+///     pos!(100.0), // quantity
+///     pos!(150.0), // entry price
+///     Utc::now(),
+///     pos!(0.01),  // open fee per unit
+///     pos!(0.01),  // close fee per unit
+/// );
+///
+/// assert_eq!(shares.delta(), rust_decimal_macros::dec!(100.0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UnderlyingPosition {
+    /// Symbol of the underlying asset.
+    pub symbol: String,
+    /// Whether the underlying is held long or sold short.
+    pub side: Side,
+    /// Number of units held.
+    pub quantity: Positive,
+    /// Price per unit at which the position was opened.
+    pub price: Positive,
+    /// Date and time when the position was opened.
+    pub date: DateTime<Utc>,
+    /// Fee per unit paid to open the position.
+    pub open_fee: Positive,
+    /// Fee per unit paid to close the position.
+    pub close_fee: Positive,
+}
+
+impl UnderlyingPosition {
+    /// Creates a new position in the underlying asset.
+    ///
+    /// # Parameters
+    /// * `symbol` - Symbol of the underlying asset
+    /// * `side` - Long or short
+    /// * `quantity` - Number of units
+    /// * `price` - Entry price per unit
+    /// * `date` - Opening date of the position
+    /// * `open_fee` - Opening fee per unit
+    /// * `close_fee` - Closing fee per unit
+    pub fn new(
+        symbol: String,
+        side: Side,
+        quantity: Positive,
+        price: Positive,
+        date: DateTime<Utc>,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Self {
+        UnderlyingPosition {
+            symbol,
+            side,
+            quantity,
+            price,
+            date,
+            open_fee,
+            close_fee,
+        }
+    }
+
+    /// Total transaction fees of the position, `(open_fee + close_fee) * quantity`.
+    pub fn fees(&self) -> Positive {
+        (self.open_fee + self.close_fee) * self.quantity
+    }
+
+    /// Cash paid to open the position.
+    ///
+    /// A long holding pays the purchase price plus fees; a short sale only pays fees,
+    /// mirroring how option positions report their cost.
+    pub fn total_cost(&self) -> Positive {
+        match self.side {
+            Side::Long => self.price * self.quantity + self.fees(),
+            Side::Short => self.fees(),
+        }
+    }
+
+    /// Cash received when opening the position (the sale proceeds of a short sale).
+    pub fn proceeds(&self) -> Positive {
+        match self.side {
+            Side::Long => Positive::ZERO,
+            Side::Short => self.price * self.quantity,
+        }
+    }
+
+    /// Net cash outlay of the position: cost minus proceeds. Negative for short sales.
+    pub fn net_cost(&self) -> Decimal {
+        self.total_cost().to_dec() - self.proceeds().to_dec()
+    }
+
+    /// Profit or loss of the position, fees included, if it is closed at `price`.
+    pub fn pnl_at(&self, price: &Positive) -> Decimal {
+        let move_per_unit = price.to_dec() - self.price.to_dec();
+        let gross = match self.side {
+            Side::Long => move_per_unit,
+            Side::Short => -move_per_unit,
+        } * self.quantity;
+        gross - self.fees().to_dec()
+    }
+
+    /// Delta of the position: `+quantity` when long, `-quantity` when short.
+    pub fn delta(&self) -> Decimal {
+        match self.side {
+            Side::Long => self.quantity.to_dec(),
+            Side::Short => -self.quantity.to_dec(),
+        }
+    }
+
+    /// Checks that the position holds a non-zero quantity at a non-zero price.
+    pub fn validate(&self) -> bool {
+        if self.quantity == Positive::ZERO {
+            debug!("Underlying position quantity must be greater than zero.");
+            return false;
+        }
+        if self.price == Positive::ZERO {
+            debug!("Underlying position price must be greater than zero.");
+            return false;
+        }
+        true
+    }
+}
+
+impl Default for UnderlyingPosition {
+    fn default() -> Self {
+        UnderlyingPosition {
+            symbol: String::new(),
+            side: Side::Long,
+            quantity: Positive::ZERO,
+            price: Positive::ZERO,
+            date: Utc::now(),
+            open_fee: Positive::ZERO,
+            close_fee: Positive::ZERO,
+        }
+    }
+}
+
+impl PnLCalculator for UnderlyingPosition {
+    /// The underlying has no time value or volatility exposure, so only the market price
+    /// matters: the unrealized result is the mark-to-market move since entry.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        _expiration_date: ExpirationDate,
+        _implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        let initial_cost = self.total_cost();
+        let initial_income = self.proceeds();
+        let unrealized = self.pnl_at(market_price) + self.fees().to_dec();
+        Ok(PnL::new(
+            Some(initial_income.to_dec() - initial_cost.to_dec()),
+            Some(unrealized),
+            initial_cost,
+            initial_income,
+            self.date,
+        ))
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(PnL::new(
+            Some(self.pnl_at(underlying_price)),
+            Some(Decimal::ZERO),
+            self.total_cost(),
+            self.proceeds(),
+            self.date,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests_underlying_position {
+    use super::*;
+    use crate::pos;
+    use rust_decimal_macros::dec;
+
+    fn create_position(side: Side) -> UnderlyingPosition {
+        UnderlyingPosition::new(
+            "AAPL".to_string(),
+            side,
+            pos!(10.0),
+            pos!(100.0),
+            Utc::now(),
+            pos!(0.1),
+            pos!(0.1),
+        )
+    }
+
+    #[test]
+    fn test_costs_long() {
+        let position = create_position(Side::Long);
+        assert_eq!(position.fees(), pos!(2.0));
+        assert_eq!(position.total_cost(), pos!(1002.0));
+        assert_eq!(position.proceeds(), Positive::ZERO);
+        assert_eq!(position.net_cost(), dec!(1002.0));
+    }
+
+    #[test]
+    fn test_costs_short() {
+        let position = create_position(Side::Short);
+        assert_eq!(position.total_cost(), pos!(2.0));
+        assert_eq!(position.proceeds(), pos!(1000.0));
+        assert_eq!(position.net_cost(), dec!(-998.0));
+    }
+
+    #[test]
+    fn test_pnl_at() {
+        let long = create_position(Side::Long);
+        let short = create_position(Side::Short);
+        assert_eq!(long.pnl_at(&pos!(110.0)), dec!(98.0));
+        assert_eq!(long.pnl_at(&pos!(90.0)), dec!(-102.0));
+        assert_eq!(short.pnl_at(&pos!(110.0)), dec!(-102.0));
+        assert_eq!(short.pnl_at(&pos!(90.0)), dec!(98.0));
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!(create_position(Side::Long).delta(), dec!(10.0));
+        assert_eq!(create_position(Side::Short).delta(), dec!(-10.0));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(create_position(Side::Long).validate());
+        assert!(!UnderlyingPosition::default().validate());
+    }
+
+    #[test]
+    fn test_pnl_calculator() {
+        let position = create_position(Side::Long);
+        let pnl = position
+            .calculate_pnl(&pos!(105.0), ExpirationDate::Days(pos!(10.0)), &pos!(0.2))
+            .unwrap();
+        assert_eq!(pnl.unrealized, Some(dec!(50.0)));
+        assert_eq!(pnl.initial_costs, pos!(1002.0));
+
+        let pnl = position.calculate_pnl_at_expiration(&pos!(105.0)).unwrap();
+        assert_eq!(pnl.realized, Some(dec!(48.0)));
+    }
+}
@@ -0,0 +1,407 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::greeks::{big_n, n};
+use crate::model::types::OptionStyle;
+use crate::{Options, Positive};
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use std::error::Error;
+use tracing::trace;
+
+/// Maximum number of Newton iterations used to locate the critical exercise price.
+const MAX_ITERATIONS_CRITICAL_PRICE: usize = 100;
+
+/// Relative tolerance (to the strike) accepted for the critical exercise price.
+const CRITICAL_PRICE_TOLERANCE: Decimal = dec!(0.000001);
+
+/// Market inputs shared by the Barone-Adesi–Whaley formulas.
+struct BawInputs {
+    spot: Decimal,
+    strike: Decimal,
+    time: Decimal,
+    rate: Decimal,
+    carry: Decimal,
+    volatility: Decimal,
+}
+
+impl BawInputs {
+    fn from_option(option: &Options) -> Result<Self, Box<dyn Error>> {
+        Ok(BawInputs {
+            spot: option.underlying_price.to_dec(),
+            strike: option.strike_price.to_dec(),
+            time: option.time_to_expiration()?.to_dec(),
+            rate: option.risk_free_rate,
+            carry: option.risk_free_rate - option.dividend_yield.to_dec(),
+            volatility: option.implied_volatility.to_dec(),
+        })
+    }
+
+    fn vol_sqrt_time(&self) -> Decimal {
+        self.volatility * self.time.sqrt().unwrap_or(Decimal::ZERO)
+    }
+
+    /// `e^((b - r)T)`, the dividend discount factor.
+    fn carry_discount(&self) -> Decimal {
+        ((self.carry - self.rate) * self.time).exp()
+    }
+
+    fn d1(&self, spot: Decimal) -> Decimal {
+        ((spot / self.strike).ln()
+            + (self.carry + self.volatility * self.volatility / Decimal::TWO) * self.time)
+            / self.vol_sqrt_time()
+    }
+
+    /// Generalized Black-Scholes-Merton price of the European counterpart at `spot`.
+    fn european(&self, style: &OptionStyle, spot: Decimal) -> Result<Decimal, Box<dyn Error>> {
+        let forward_spot = spot * self.carry_discount();
+        let discounted_strike = self.strike * (-self.rate * self.time).exp();
+        if self.time == Decimal::ZERO || self.volatility == Decimal::ZERO {
+            let value = match style {
+                OptionStyle::Call => forward_spot - discounted_strike,
+                OptionStyle::Put => discounted_strike - forward_spot,
+            };
+            return Ok(value.max(Decimal::ZERO));
+        }
+        let d1 = self.d1(spot);
+        let d2 = d1 - self.vol_sqrt_time();
+        Ok(match style {
+            OptionStyle::Call => forward_spot * big_n(d1)? - discounted_strike * big_n(d2)?,
+            OptionStyle::Put => discounted_strike * big_n(-d2)? - forward_spot * big_n(-d1)?,
+        })
+    }
+
+    fn intrinsic(&self, style: &OptionStyle) -> Decimal {
+        match style {
+            OptionStyle::Call => (self.spot - self.strike).max(Decimal::ZERO),
+            OptionStyle::Put => (self.strike - self.spot).max(Decimal::ZERO),
+        }
+    }
+
+    /// Returns `(N - 1, 4M / K)` from the Barone-Adesi–Whaley quadratic.
+    ///
+    /// When the rate is zero `M / K` is replaced by its limit `2 / (σ²T)`.
+    fn quadratic_terms(&self) -> (Decimal, Decimal) {
+        let variance = self.volatility * self.volatility;
+        let n_minus_one = Decimal::TWO * self.carry / variance - Decimal::ONE;
+        let m_over_k = if self.rate == Decimal::ZERO {
+            Decimal::TWO / (variance * self.time)
+        } else {
+            Decimal::TWO * self.rate / (variance * (Decimal::ONE - (-self.rate * self.time).exp()))
+        };
+        (n_minus_one, dec!(4) * m_over_k)
+    }
+}
+
+/// Prices an American option with the Barone-Adesi–Whaley quadratic approximation.
+///
+/// The American value is split into the generalized Black-Scholes price of the
+/// European counterpart plus an early-exercise premium. The premium is driven by
+/// the critical underlying price at which immediate exercise becomes optimal,
+/// which is found with Newton's method. The cost of carry is `r - q`, so
+/// `dividend_yield` is fully taken into account.
+///
+/// # Arguments
+///
+/// * `option` - The option to price. Its `option_type` is not inspected, and the
+///   price is computed for a single long contract.
+///
+/// # Returns
+///
+/// * `Ok(Decimal)` - The per-contract price of the long American option.
+/// * `Err(Box<dyn Error>)` - If the time to expiration cannot be computed or a
+///   numerical operation fails.
+///
+/// # Notes
+///
+/// * An American call on an asset without dividends is never exercised early, so
+///   its European value is returned. The same holds for a put when the
+///   risk-free rate is not positive.
+/// * The result is never below the intrinsic value of the option.
+pub fn barone_adesi_whaley(option: &Options) -> Result<Decimal, Box<dyn Error>> {
+    let inputs = BawInputs::from_option(option)?;
+    let style = &option.option_style;
+    let intrinsic = inputs.intrinsic(style);
+    if inputs.time == Decimal::ZERO || inputs.volatility == Decimal::ZERO {
+        return Ok(inputs.european(style, inputs.spot)?.max(intrinsic));
+    }
+
+    let european = inputs.european(style, inputs.spot)?;
+    let price = match style {
+        OptionStyle::Call if inputs.carry >= inputs.rate => european,
+        OptionStyle::Put if inputs.rate <= Decimal::ZERO => european,
+        OptionStyle::Call => baw_call(&inputs, european)?,
+        OptionStyle::Put => baw_put(&inputs, european)?,
+    };
+    trace!(
+        "Barone-Adesi-Whaley {:?}: european {} american {}",
+        style, european, price
+    );
+    Ok(price.max(intrinsic))
+}
+
+fn baw_call(inputs: &BawInputs, european: Decimal) -> Result<Decimal, Box<dyn Error>> {
+    let (n_minus_one, four_m_over_k) = inputs.quadratic_terms();
+    let root = (n_minus_one * n_minus_one + four_m_over_k)
+        .sqrt()
+        .ok_or("Negative discriminant in Barone-Adesi-Whaley call")?;
+    let q2 = (-n_minus_one + root) / Decimal::TWO;
+    let critical = critical_call_price(inputs, q2)?;
+
+    if inputs.spot >= critical {
+        return Ok(inputs.spot - inputs.strike);
+    }
+    let a2 = critical / q2 * (Decimal::ONE - inputs.carry_discount() * big_n(inputs.d1(critical))?);
+    Ok(european + a2 * (inputs.spot / critical).powd(q2))
+}
+
+fn baw_put(inputs: &BawInputs, european: Decimal) -> Result<Decimal, Box<dyn Error>> {
+    let (n_minus_one, four_m_over_k) = inputs.quadratic_terms();
+    let root = (n_minus_one * n_minus_one + four_m_over_k)
+        .sqrt()
+        .ok_or("Negative discriminant in Barone-Adesi-Whaley put")?;
+    let q1 = (-n_minus_one - root) / Decimal::TWO;
+    let critical = critical_put_price(inputs, q1)?;
+
+    if inputs.spot <= critical {
+        return Ok(inputs.strike - inputs.spot);
+    }
+    let a1 =
+        -critical / q1 * (Decimal::ONE - inputs.carry_discount() * big_n(-inputs.d1(critical))?);
+    Ok(european + a1 * (inputs.spot / critical).powd(q1))
+}
+
+/// Solves `S* - K = c(S*) + (1 - e^((b-r)T) N(d1(S*))) S* / q2` for the call critical price.
+fn critical_call_price(inputs: &BawInputs, q2: Decimal) -> Result<Decimal, Box<dyn Error>> {
+    let strike = inputs.strike;
+    let vol_sqrt_time = inputs.vol_sqrt_time();
+    let carry_discount = inputs.carry_discount();
+
+    // Seed from the perpetual option (Barone-Adesi & Whaley, 1987).
+    let (n_minus_one, _) = inputs.quadratic_terms();
+    let perpetual_root = (n_minus_one * n_minus_one
+        + dec!(8) * inputs.rate / (inputs.volatility * inputs.volatility))
+        .sqrt()
+        .ok_or("Negative discriminant in perpetual call")?;
+    let q2_infinite = (-n_minus_one + perpetual_root) / Decimal::TWO;
+    let critical_infinite = strike / (Decimal::ONE - Decimal::ONE / q2_infinite);
+    let h2 = -(inputs.carry * inputs.time + Decimal::TWO * vol_sqrt_time) * strike
+        / (critical_infinite - strike);
+    let mut critical = strike + (critical_infinite - strike) * (Decimal::ONE - h2.exp());
+
+    for _ in 0..MAX_ITERATIONS_CRITICAL_PRICE {
+        let d1 = inputs.d1(critical);
+        let lhs = critical - strike;
+        let rhs = inputs.european(&OptionStyle::Call, critical)?
+            + (Decimal::ONE - carry_discount * big_n(d1)?) * critical / q2;
+        if ((lhs - rhs) / strike).abs() <= CRITICAL_PRICE_TOLERANCE {
+            break;
+        }
+        let slope = carry_discount * big_n(d1)? * (Decimal::ONE - Decimal::ONE / q2)
+            + (Decimal::ONE - carry_discount * n(d1)? / vol_sqrt_time) / q2;
+        critical = (strike + rhs - slope * critical) / (Decimal::ONE - slope);
+    }
+    Ok(critical.max(strike))
+}
+
+/// Solves `K - S** = p(S**) - (1 - e^((b-r)T) N(-d1(S**))) S** / q1` for the put critical price.
+fn critical_put_price(inputs: &BawInputs, q1: Decimal) -> Result<Decimal, Box<dyn Error>> {
+    let strike = inputs.strike;
+    let vol_sqrt_time = inputs.vol_sqrt_time();
+    let carry_discount = inputs.carry_discount();
+
+    let (n_minus_one, _) = inputs.quadratic_terms();
+    let perpetual_root = (n_minus_one * n_minus_one
+        + dec!(8) * inputs.rate / (inputs.volatility * inputs.volatility))
+        .sqrt()
+        .ok_or("Negative discriminant in perpetual put")?;
+    let q1_infinite = (-n_minus_one - perpetual_root) / Decimal::TWO;
+    let critical_infinite = strike / (Decimal::ONE - Decimal::ONE / q1_infinite);
+    let h1 = (inputs.carry * inputs.time - Decimal::TWO * vol_sqrt_time) * strike
+        / (strike - critical_infinite);
+    let mut critical = critical_infinite + (strike - critical_infinite) * h1.exp();
+
+    for _ in 0..MAX_ITERATIONS_CRITICAL_PRICE {
+        let d1 = inputs.d1(critical);
+        let lhs = strike - critical;
+        let rhs = inputs.european(&OptionStyle::Put, critical)?
+            - (Decimal::ONE - carry_discount * big_n(-d1)?) * critical / q1;
+        if ((lhs - rhs) / strike).abs() <= CRITICAL_PRICE_TOLERANCE {
+            break;
+        }
+        let slope = -carry_discount * big_n(-d1)? * (Decimal::ONE - Decimal::ONE / q1)
+            - (Decimal::ONE + carry_discount * n(-d1)? / vol_sqrt_time) / q1;
+        critical = (strike - rhs + slope * critical) / (Decimal::ONE + slope);
+    }
+    Ok(Positive::new_decimal(critical.min(strike))
+        .unwrap_or(Positive::ZERO)
+        .to_dec())
+}
+
+#[cfg(test)]
+mod tests_barone_adesi_whaley {
+    use super::*;
+    use crate::model::types::{OptionType, Side};
+    use crate::pricing::{BinomialPricingParams, black_scholes, price_binomial};
+    use crate::{ExpirationDate, assert_decimal_eq, pos};
+
+    fn american(
+        style: OptionStyle,
+        spot: Positive,
+        strike: Positive,
+        rate: Decimal,
+        dividend_yield: Positive,
+        days: Positive,
+    ) -> Options {
+        Options::new(
+            OptionType::American,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos!(0.25),
+            Positive::ONE,
+            spot,
+            rate,
+            style,
+            dividend_yield,
+            None,
+        )
+    }
+
+    fn binomial(option: &Options, no_steps: usize) -> Decimal {
+        price_binomial(BinomialPricingParams {
+            asset: option.underlying_price,
+            volatility: option.implied_volatility,
+            int_rate: option.risk_free_rate,
+            strike: option.strike_price,
+            expiry: option.time_to_expiration().unwrap(),
+            no_steps,
+            option_type: &OptionType::American,
+            option_style: &option.option_style,
+            side: &option.side,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_put_matches_binomial() {
+        for spot in [pos!(90.0), pos!(100.0), pos!(110.0)] {
+            let option = american(
+                OptionStyle::Put,
+                spot,
+                pos!(100.0),
+                dec!(0.08),
+                Positive::ZERO,
+                pos!(91.25),
+            );
+            let analytic = barone_adesi_whaley(&option).unwrap();
+            let tree = binomial(&option, 500);
+            // The quadratic approximation stays within 2% of the lattice for short maturities.
+            assert_decimal_eq!(analytic, tree, tree * dec!(0.02));
+        }
+    }
+
+    #[test]
+    fn test_dividend_call_matches_binomial_by_symmetry() {
+        // With r = 0, an American call with yield q equals an American put with
+        // spot and strike swapped, priced at rate q and no dividend.
+        let call = american(
+            OptionStyle::Call,
+            pos!(110.0),
+            pos!(100.0),
+            Decimal::ZERO,
+            pos!(0.08),
+            pos!(182.5),
+        );
+        let symmetric_put = american(
+            OptionStyle::Put,
+            pos!(100.0),
+            pos!(110.0),
+            dec!(0.08),
+            Positive::ZERO,
+            pos!(182.5),
+        );
+        let analytic = barone_adesi_whaley(&call).unwrap();
+        let tree = binomial(&symmetric_put, 500);
+        assert_decimal_eq!(analytic, tree, dec!(0.1));
+    }
+
+    #[test]
+    fn test_call_without_dividends_is_european() {
+        let option = american(
+            OptionStyle::Call,
+            pos!(100.0),
+            pos!(100.0),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(365.0),
+        );
+        let mut european = option.clone();
+        european.option_type = OptionType::European;
+        assert_decimal_eq!(
+            barone_adesi_whaley(&option).unwrap(),
+            black_scholes(&european).unwrap(),
+            dec!(0.000001)
+        );
+    }
+
+    #[test]
+    fn test_early_exercise_premium_is_positive() {
+        let option = american(
+            OptionStyle::Call,
+            pos!(100.0),
+            pos!(100.0),
+            dec!(0.03),
+            pos!(0.1),
+            pos!(365.0),
+        );
+        let inputs = BawInputs::from_option(&option).unwrap();
+        let european = inputs.european(&OptionStyle::Call, inputs.spot).unwrap();
+        assert!(barone_adesi_whaley(&option).unwrap() > european);
+    }
+
+    #[test]
+    fn test_deep_in_the_money_put_is_exercised() {
+        let option = american(
+            OptionStyle::Put,
+            pos!(50.0),
+            pos!(100.0),
+            dec!(0.1),
+            Positive::ZERO,
+            pos!(91.25),
+        );
+        assert_eq!(barone_adesi_whaley(&option).unwrap(), dec!(50.0));
+    }
+
+    #[test]
+    fn test_expired_option_returns_intrinsic() {
+        let option = american(
+            OptionStyle::Put,
+            pos!(95.0),
+            pos!(100.0),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert_eq!(barone_adesi_whaley(&option).unwrap(), dec!(5.0));
+    }
+
+    #[test]
+    fn test_black_scholes_dispatches_american() {
+        let mut option = american(
+            OptionStyle::Put,
+            pos!(100.0),
+            pos!(100.0),
+            dec!(0.05),
+            pos!(0.02),
+            pos!(91.25),
+        );
+        let long = black_scholes(&option).unwrap();
+        assert_eq!(long, barone_adesi_whaley(&option).unwrap());
+        option.side = Side::Short;
+        assert_eq!(black_scholes(&option).unwrap(), -long);
+    }
+}
@@ -6,6 +6,11 @@
 use crate::Options;
 use crate::greeks::{big_n, calculate_d_values};
 use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::american::barone_adesi_whaley;
+use crate::pricing::exotic::{
+    asian_price, barrier_price, binary_price, chooser_price, exchange_price, lookback_price,
+    power_price,
+};
 use rust_decimal::{Decimal, MathematicalOps};
 use std::error::Error;
 use tracing::trace;
@@ -35,24 +40,50 @@ use tracing::trace;
 ///
 /// The function returns the computed price based on the type of option provided.
 ///
+/// American options are priced with the Barone-Adesi–Whaley approximation
+/// (see [`barone_adesi_whaley`]), which accounts for the dividend yield and
+/// the early-exercise premium.
+///
+/// Asian, barrier, binary, lookback, chooser, exchange and power options are
+/// priced with their closed-form solutions from [`crate::pricing::exotic`].
+/// The remaining exotic types are not supported yet and return zero.
+///
 pub fn black_scholes(option: &Options) -> Result<Decimal, Box<dyn Error>> {
-    let (d1, d2, expiry_time) = calculate_d1_d2_and_time(option)?;
     match option.option_type {
-        OptionType::European => calculate_european_option_price(option, d1, d2, expiry_time),
-        OptionType::American => Ok(Decimal::ZERO), // TODO: calculate this
+        OptionType::European => {
+            let (d1, d2, expiry_time) = calculate_d1_d2_and_time(option)?;
+            calculate_european_option_price(option, d1, d2, expiry_time)
+        }
+        OptionType::American => Ok(apply_side(option, barone_adesi_whaley(option)?)),
         OptionType::Bermuda { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Asian { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Barrier { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Binary { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Lookback { .. } => Ok(Decimal::ZERO), // TODO: calculate this
+        OptionType::Asian { ref averaging_type } => {
+            Ok(apply_side(option, asian_price(option, averaging_type)?))
+        }
+        OptionType::Barrier {
+            ref barrier_type,
+            barrier_level,
+        } => Ok(apply_side(
+            option,
+            barrier_price(option, barrier_type, barrier_level)?,
+        )),
+        OptionType::Binary { ref binary_type } => {
+            Ok(apply_side(option, binary_price(option, binary_type)?))
+        }
+        OptionType::Lookback { ref lookback_type } => {
+            Ok(apply_side(option, lookback_price(option, lookback_type)?))
+        }
         OptionType::Compound { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Chooser { .. } => Ok(Decimal::ZERO), // TODO: calculate this
+        OptionType::Chooser { choice_date } => {
+            Ok(apply_side(option, chooser_price(option, choice_date)?))
+        }
         OptionType::Cliquet { .. } => Ok(Decimal::ZERO), // TODO: calculate this
         OptionType::Rainbow { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Spread { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Quanto { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Exchange { .. } => Ok(Decimal::ZERO), // TODO: calculate this
-        OptionType::Power { .. } => Ok(Decimal::ZERO), // TODO: calculate this
+        OptionType::Spread { .. } => Ok(Decimal::ZERO),  // TODO: calculate this
+        OptionType::Quanto { .. } => Ok(Decimal::ZERO),  // TODO: calculate this
+        OptionType::Exchange { second_asset } => {
+            Ok(apply_side(option, exchange_price(option, second_asset)?))
+        }
+        OptionType::Power { exponent } => Ok(apply_side(option, power_price(option, exponent)?)),
     }
 }
 
@@ -85,6 +116,14 @@ fn calculate_european_option_price(
     }
 }
 
+/// Signs a long-contract price according to the side of the option.
+fn apply_side(option: &Options, price: Decimal) -> Decimal {
+    match option.side {
+        Side::Long => price,
+        Side::Short => -price,
+    }
+}
+
 /// Calculates the price of a long position in an option based on its style (Call or Put).
 ///
 /// # Arguments
@@ -0,0 +1,752 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::greeks::big_n;
+use crate::model::types::{AsianAveragingType, BarrierType, BinaryType, LookbackType};
+use crate::{OptionStyle, Options, d2f, f2d};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::error::Error;
+
+/// Smallest cost of carry used by the lookback formulas, which are singular at `b = 0`.
+const MIN_CARRY: f64 = 1e-6;
+
+/// Market inputs of the generalized Black-Scholes-Merton model.
+///
+/// `carry` is the cost of carry `b = r - q`, so `e^((b - r)T)` discounts the spot
+/// for the dividend yield.
+struct GbsInputs {
+    spot: f64,
+    strike: f64,
+    time: f64,
+    rate: f64,
+    carry: f64,
+    volatility: f64,
+}
+
+impl GbsInputs {
+    fn from_option(option: &Options) -> Result<Self, Box<dyn Error>> {
+        let volatility = option.implied_volatility.to_f64();
+        if volatility <= 0.0 {
+            return Err("Closed-form exotic pricing requires a positive volatility".into());
+        }
+        Ok(GbsInputs {
+            spot: option.underlying_price.to_f64(),
+            strike: option.strike_price.to_f64(),
+            time: option.time_to_expiration()?.to_f64(),
+            rate: d2f!(option.risk_free_rate),
+            carry: d2f!(option.risk_free_rate - option.dividend_yield.to_dec()),
+            volatility,
+        })
+    }
+
+    fn vol_sqrt_time(&self) -> f64 {
+        self.volatility * self.time.sqrt()
+    }
+
+    fn spot_discount(&self) -> f64 {
+        ((self.carry - self.rate) * self.time).exp()
+    }
+
+    fn rate_discount(&self) -> f64 {
+        (-self.rate * self.time).exp()
+    }
+
+    fn d1(&self) -> f64 {
+        ((self.spot / self.strike).ln()
+            + (self.carry + self.volatility * self.volatility / 2.0) * self.time)
+            / self.vol_sqrt_time()
+    }
+
+    fn d2(&self) -> f64 {
+        self.d1() - self.vol_sqrt_time()
+    }
+
+    /// Generalized Black-Scholes-Merton price of a vanilla option.
+    fn vanilla(&self, style: &OptionStyle) -> f64 {
+        let (d1, d2) = (self.d1(), self.d2());
+        let forward = self.spot * self.spot_discount();
+        let strike = self.strike * self.rate_discount();
+        match style {
+            OptionStyle::Call => forward * cnd(d1) - strike * cnd(d2),
+            OptionStyle::Put => strike * cnd(-d2) - forward * cnd(-d1),
+        }
+    }
+}
+
+/// Standard normal cumulative distribution function, through [`big_n`]. Arguments beyond
+/// the `Decimal` range are fully in one tail.
+fn cnd(x: f64) -> f64 {
+    Decimal::from_f64(x)
+        .and_then(|x| big_n(x).ok())
+        .and_then(|n| n.to_f64())
+        .unwrap_or(if x > 0.0 { 1.0 } else { 0.0 })
+}
+
+fn phi(style: &OptionStyle) -> f64 {
+    match style {
+        OptionStyle::Call => 1.0,
+        OptionStyle::Put => -1.0,
+    }
+}
+
+/// Prices a single-barrier option with the Reiner–Rubinstein formulas (no rebate).
+///
+/// If the spot has already crossed the barrier the option is treated as knocked:
+/// knock-in options are worth the vanilla option and knock-out options are worthless.
+///
+/// # Arguments
+///
+/// * `option` - The option to price, for a single long contract.
+/// * `barrier_type` - Direction of the barrier and whether it knocks in or out.
+/// * `barrier_level` - The barrier price.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn barrier_price(
+    option: &Options,
+    barrier_type: &BarrierType,
+    barrier_level: f64,
+) -> Result<Decimal, Box<dyn Error>> {
+    let inputs = GbsInputs::from_option(option)?;
+    let style = &option.option_style;
+    if barrier_level <= 0.0 {
+        return Err(format!("Invalid barrier level: {barrier_level}").into());
+    }
+    let (spot, strike, barrier) = (inputs.spot, inputs.strike, barrier_level);
+
+    let breached = match barrier_type {
+        BarrierType::DownAndIn | BarrierType::DownAndOut => spot <= barrier,
+        BarrierType::UpAndIn | BarrierType::UpAndOut => spot >= barrier,
+    };
+    if breached || inputs.time == 0.0 {
+        let knocked_in = matches!(barrier_type, BarrierType::DownAndIn | BarrierType::UpAndIn);
+        return if breached == knocked_in {
+            Ok(f2d!(vanilla_or_intrinsic(&inputs, style)))
+        } else {
+            Ok(Decimal::ZERO)
+        };
+    }
+
+    let sigma = inputs.volatility;
+    let vol_sqrt_time = inputs.vol_sqrt_time();
+    let mu = (inputs.carry - sigma * sigma / 2.0) / (sigma * sigma);
+    let phi = phi(style);
+    let eta = match barrier_type {
+        BarrierType::DownAndIn | BarrierType::DownAndOut => 1.0,
+        BarrierType::UpAndIn | BarrierType::UpAndOut => -1.0,
+    };
+    let forward = spot * inputs.spot_discount();
+    let discounted_strike = strike * inputs.rate_discount();
+    let ratio = barrier / spot;
+
+    let x1 = (spot / strike).ln() / vol_sqrt_time + (1.0 + mu) * vol_sqrt_time;
+    let x2 = (spot / barrier).ln() / vol_sqrt_time + (1.0 + mu) * vol_sqrt_time;
+    let y1 =
+        (barrier * barrier / (spot * strike)).ln() / vol_sqrt_time + (1.0 + mu) * vol_sqrt_time;
+    let y2 = (barrier / spot).ln() / vol_sqrt_time + (1.0 + mu) * vol_sqrt_time;
+
+    let a = phi * forward * cnd(phi * x1)
+        - phi * discounted_strike * cnd(phi * x1 - phi * vol_sqrt_time);
+    let b = phi * forward * cnd(phi * x2)
+        - phi * discounted_strike * cnd(phi * x2 - phi * vol_sqrt_time);
+    let c = phi * forward * ratio.powf(2.0 * (mu + 1.0)) * cnd(eta * y1)
+        - phi * discounted_strike * ratio.powf(2.0 * mu) * cnd(eta * y1 - eta * vol_sqrt_time);
+    let d = phi * forward * ratio.powf(2.0 * (mu + 1.0)) * cnd(eta * y2)
+        - phi * discounted_strike * ratio.powf(2.0 * mu) * cnd(eta * y2 - eta * vol_sqrt_time);
+
+    let above = strike >= barrier;
+    let price = match (barrier_type, style, above) {
+        (BarrierType::DownAndIn, OptionStyle::Call, true) => c,
+        (BarrierType::DownAndIn, OptionStyle::Call, false) => a - b + d,
+        (BarrierType::UpAndIn, OptionStyle::Call, true) => a,
+        (BarrierType::UpAndIn, OptionStyle::Call, false) => b - c + d,
+        (BarrierType::DownAndIn, OptionStyle::Put, true) => b - c + d,
+        (BarrierType::DownAndIn, OptionStyle::Put, false) => a,
+        (BarrierType::UpAndIn, OptionStyle::Put, true) => a - b + d,
+        (BarrierType::UpAndIn, OptionStyle::Put, false) => c,
+        (BarrierType::DownAndOut, OptionStyle::Call, true) => a - c,
+        (BarrierType::DownAndOut, OptionStyle::Call, false) => b - d,
+        (BarrierType::UpAndOut, OptionStyle::Call, true) => 0.0,
+        (BarrierType::UpAndOut, OptionStyle::Call, false) => a - b + c - d,
+        (BarrierType::DownAndOut, OptionStyle::Put, true) => a - b + c - d,
+        (BarrierType::DownAndOut, OptionStyle::Put, false) => 0.0,
+        (BarrierType::UpAndOut, OptionStyle::Put, true) => b - d,
+        (BarrierType::UpAndOut, OptionStyle::Put, false) => a - c,
+    };
+    Ok(f2d!(price.max(0.0)))
+}
+
+/// Prices a binary (digital) option.
+///
+/// * `CashOrNothing` pays one unit of cash when the option finishes in the money.
+/// * `AssetOrNothing` pays the underlying when the option finishes in the money.
+/// * `Gap` pays the distance between spot and strike when in the money, which is
+///   the payoff of a vanilla option.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn binary_price(option: &Options, binary_type: &BinaryType) -> Result<Decimal, Box<dyn Error>> {
+    let inputs = GbsInputs::from_option(option)?;
+    let style = &option.option_style;
+    if inputs.time == 0.0 {
+        let in_the_money = phi(style) * (inputs.spot - inputs.strike) > 0.0;
+        let payoff = match (binary_type, in_the_money) {
+            (_, false) => 0.0,
+            (BinaryType::CashOrNothing, true) => 1.0,
+            (BinaryType::AssetOrNothing, true) => inputs.spot,
+            (BinaryType::Gap, true) => (inputs.spot - inputs.strike).abs(),
+        };
+        return Ok(f2d!(payoff));
+    }
+    let phi = phi(style);
+    let price = match binary_type {
+        BinaryType::CashOrNothing => inputs.rate_discount() * cnd(phi * inputs.d2()),
+        BinaryType::AssetOrNothing => inputs.spot * inputs.spot_discount() * cnd(phi * inputs.d1()),
+        BinaryType::Gap => inputs.vanilla(style),
+    };
+    Ok(f2d!(price.max(0.0)))
+}
+
+/// Prices a lookback option.
+///
+/// * Floating strike options use the Goldman–Sosin–Gatto formula: the call pays
+///   `S_T - S_min` and the put pays `S_max - S_T`.
+/// * Fixed strike options use the Conze–Viswanathan formula: the call pays
+///   `max(S_max - K, 0)` and the put pays `max(K - S_min, 0)`.
+///
+/// The running minimum and maximum are read from `exotic_params.spot_min` and
+/// `exotic_params.spot_max`, and default to the current underlying price for a
+/// newly issued option.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn lookback_price(
+    option: &Options,
+    lookback_type: &LookbackType,
+) -> Result<Decimal, Box<dyn Error>> {
+    let mut inputs = GbsInputs::from_option(option)?;
+    let style = &option.option_style;
+    let spot = inputs.spot;
+    let params = option.exotic_params.as_ref();
+    let spot_min = match params.and_then(|params| params.spot_min) {
+        Some(value) => d2f!(value).min(spot),
+        None => spot,
+    };
+    let spot_max = match params.and_then(|params| params.spot_max) {
+        Some(value) => d2f!(value).max(spot),
+        None => spot,
+    };
+    if inputs.time == 0.0 {
+        let payoff = match (lookback_type, style) {
+            (LookbackType::FloatingStrike, OptionStyle::Call) => spot - spot_min,
+            (LookbackType::FloatingStrike, OptionStyle::Put) => spot_max - spot,
+            (LookbackType::FixedStrike, OptionStyle::Call) => (spot_max - inputs.strike).max(0.0),
+            (LookbackType::FixedStrike, OptionStyle::Put) => (inputs.strike - spot_min).max(0.0),
+        };
+        return Ok(f2d!(payoff));
+    }
+    if inputs.carry.abs() < MIN_CARRY {
+        inputs.carry = MIN_CARRY;
+    }
+
+    let (sigma, carry, time) = (inputs.volatility, inputs.carry, inputs.time);
+    let vol_sqrt_time = inputs.vol_sqrt_time();
+    let forward = spot * inputs.spot_discount();
+    let discount = inputs.rate_discount();
+    let carry_term = spot * discount * sigma * sigma / (2.0 * carry);
+    let exponent = -2.0 * carry / (sigma * sigma);
+    let shift = 2.0 * carry * time.sqrt() / sigma;
+    let d = |reference: f64| {
+        ((spot / reference).ln() + (carry + sigma * sigma / 2.0) * time) / vol_sqrt_time
+    };
+
+    let price = match (lookback_type, style) {
+        (LookbackType::FloatingStrike, OptionStyle::Call) => {
+            let a1 = d(spot_min);
+            forward * cnd(a1) - spot_min * discount * cnd(a1 - vol_sqrt_time)
+                + carry_term
+                    * ((spot / spot_min).powf(exponent) * cnd(-a1 + shift)
+                        - (carry * time).exp() * cnd(-a1))
+        }
+        (LookbackType::FloatingStrike, OptionStyle::Put) => {
+            let b1 = d(spot_max);
+            spot_max * discount * cnd(-(b1 - vol_sqrt_time)) - forward * cnd(-b1)
+                + carry_term
+                    * (-(spot / spot_max).powf(exponent) * cnd(b1 - shift)
+                        + (carry * time).exp() * cnd(b1))
+        }
+        (LookbackType::FixedStrike, OptionStyle::Call) => {
+            let (reference, locked) = if inputs.strike > spot_max {
+                (inputs.strike, 0.0)
+            } else {
+                (spot_max, discount * (spot_max - inputs.strike))
+            };
+            let e1 = d(reference);
+            locked + forward * cnd(e1) - reference * discount * cnd(e1 - vol_sqrt_time)
+                + carry_term
+                    * (-(spot / reference).powf(exponent) * cnd(e1 - shift)
+                        + (carry * time).exp() * cnd(e1))
+        }
+        (LookbackType::FixedStrike, OptionStyle::Put) => {
+            let (reference, locked) = if inputs.strike < spot_min {
+                (inputs.strike, 0.0)
+            } else {
+                (spot_min, discount * (inputs.strike - spot_min))
+            };
+            let f1 = d(reference);
+            locked - forward * cnd(-f1)
+                + reference * discount * cnd(-(f1 - vol_sqrt_time))
+                + carry_term
+                    * ((spot / reference).powf(exponent) * cnd(-f1 + shift)
+                        - (carry * time).exp() * cnd(-f1))
+        }
+    };
+    Ok(f2d!(price.max(0.0)))
+}
+
+/// Prices an Asian option whose averaging period starts today.
+///
+/// * Geometric averages use the exact Kemna–Vorst formula, which prices the option
+///   as a vanilla with volatility `σ/√3` and cost of carry `(b - σ²/6)/2`.
+/// * Arithmetic averages use the Turnbull–Wakeman approximation, which matches the
+///   first two moments of the average to a lognormal distribution.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn asian_price(
+    option: &Options,
+    averaging_type: &AsianAveragingType,
+) -> Result<Decimal, Box<dyn Error>> {
+    let mut inputs = GbsInputs::from_option(option)?;
+    let style = &option.option_style;
+    if inputs.time == 0.0 {
+        return Ok(f2d!(vanilla_or_intrinsic(&inputs, style)));
+    }
+    let (sigma, carry, time) = (inputs.volatility, inputs.carry, inputs.time);
+    match averaging_type {
+        AsianAveragingType::Geometric => {
+            inputs.carry = (carry - sigma * sigma / 6.0) / 2.0;
+            inputs.volatility = sigma / 3.0_f64.sqrt();
+        }
+        AsianAveragingType::Arithmetic => {
+            let variance = sigma * sigma;
+            let (m1, m2) = if carry.abs() < MIN_CARRY {
+                (
+                    1.0,
+                    2.0 * ((variance * time).exp() - 1.0 - variance * time)
+                        / (variance * variance * time * time),
+                )
+            } else {
+                (
+                    ((carry * time).exp() - 1.0) / (carry * time),
+                    2.0 * ((2.0 * carry + variance) * time).exp()
+                        / ((carry + variance) * (2.0 * carry + variance) * time * time)
+                        + 2.0 / (carry * time * time)
+                            * (1.0 / (2.0 * carry + variance)
+                                - (carry * time).exp() / (carry + variance)),
+                )
+            };
+            inputs.carry = m1.ln() / time;
+            inputs.volatility = (m2.ln() / time - 2.0 * inputs.carry).max(0.0).sqrt();
+            if inputs.volatility == 0.0 {
+                return Err("Degenerate Turnbull-Wakeman volatility".into());
+            }
+        }
+    }
+    Ok(f2d!(inputs.vanilla(style).max(0.0)))
+}
+
+/// Prices an exchange option with the Margrabe formula.
+///
+/// A call gives the right to receive the underlying in exchange for the second
+/// asset, paying `max(S1 - S2, 0)`; a put pays `max(S2 - S1, 0)`. The strike is not
+/// used. The second asset's volatility and its correlation with the underlying are
+/// read from `exotic_params`, defaulting to the option's implied volatility and to
+/// zero. The second asset is assumed to pay no dividends.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn exchange_price(option: &Options, second_asset: f64) -> Result<Decimal, Box<dyn Error>> {
+    let inputs = GbsInputs::from_option(option)?;
+    if second_asset <= 0.0 {
+        return Err(format!("Invalid second asset price: {second_asset}").into());
+    }
+    let params = option.exotic_params.as_ref();
+    let sigma_1 = inputs.volatility;
+    let sigma_2 = params
+        .and_then(|params| params.second_asset_volatility)
+        .map_or(sigma_1, |volatility| volatility.to_f64());
+    let rho = match params.and_then(|params| params.correlation) {
+        Some(correlation) => d2f!(correlation).clamp(-1.0, 1.0),
+        None => 0.0,
+    };
+    let spread_volatility = (sigma_1 * sigma_1 + sigma_2 * sigma_2 - 2.0 * rho * sigma_1 * sigma_2)
+        .max(0.0)
+        .sqrt();
+    let exchange = GbsInputs {
+        spot: inputs.spot,
+        strike: second_asset,
+        time: inputs.time,
+        rate: 0.0,
+        carry: inputs.carry - inputs.rate,
+        volatility: spread_volatility,
+    };
+    let style = &option.option_style;
+    if exchange.time == 0.0 || spread_volatility == 0.0 {
+        let forward = phi(style) * (exchange.spot * exchange.spot_discount() - second_asset);
+        return Ok(f2d!(forward.max(0.0)));
+    }
+    Ok(f2d!(exchange.vanilla(style).max(0.0)))
+}
+
+/// Prices a simple chooser option with the Rubinstein formula.
+///
+/// At the choice date the holder decides whether the option becomes a call or a put
+/// with the same strike and expiration.
+///
+/// # Arguments
+///
+/// * `option` - The option to price, for a single long contract. `option_style` is ignored.
+/// * `choice_date` - Days from today until the choice is made. Values beyond the
+///   expiration are capped at the expiration, where the chooser is worth a straddle.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the inputs are invalid.
+pub fn chooser_price(option: &Options, choice_date: f64) -> Result<Decimal, Box<dyn Error>> {
+    let inputs = GbsInputs::from_option(option)?;
+    if inputs.time == 0.0 {
+        return Ok(f2d!((inputs.spot - inputs.strike).abs()));
+    }
+    let choice_time = (choice_date / DAYS_IN_A_YEAR.to_f64()).clamp(0.0, inputs.time);
+    if choice_time == 0.0 {
+        let call = inputs.vanilla(&OptionStyle::Call);
+        let put = inputs.vanilla(&OptionStyle::Put);
+        return Ok(f2d!(call.max(put)));
+    }
+    let sigma = inputs.volatility;
+    let forward = inputs.spot * inputs.spot_discount();
+    let discounted_strike = inputs.strike * inputs.rate_discount();
+    let d = inputs.d1();
+    let y = ((inputs.spot / inputs.strike).ln()
+        + inputs.carry * inputs.time
+        + sigma * sigma * choice_time / 2.0)
+        / (sigma * choice_time.sqrt());
+    let price =
+        forward * cnd(d) - discounted_strike * cnd(d - inputs.vol_sqrt_time()) - forward * cnd(-y)
+            + discounted_strike * cnd(-y + sigma * choice_time.sqrt());
+    Ok(f2d!(price.max(0.0)))
+}
+
+/// Prices an asymmetric power option, whose call pays `max(S^i - K, 0)` and put pays
+/// `max(K - S^i, 0)` for exponent `i`.
+///
+/// # Returns
+///
+/// The per-contract price of the long option, or an error if the exponent is not positive.
+pub fn power_price(option: &Options, exponent: f64) -> Result<Decimal, Box<dyn Error>> {
+    if exponent <= 0.0 {
+        return Err(format!("Power option exponent must be positive, got {exponent}").into());
+    }
+    let inputs = GbsInputs::from_option(option)?;
+    let style = &option.option_style;
+    let powered_spot = inputs.spot.powf(exponent);
+    if inputs.time == 0.0 {
+        return Ok(f2d!((phi(style) * (powered_spot - inputs.strike)).max(0.0)));
+    }
+    let (sigma, rate, carry, time) = (inputs.volatility, inputs.rate, inputs.carry, inputs.time);
+    let d1 = ((inputs.spot / inputs.strike.powf(1.0 / exponent)).ln()
+        + (carry + (exponent - 0.5) * sigma * sigma) * time)
+        / inputs.vol_sqrt_time();
+    let d2 = d1 - exponent * inputs.vol_sqrt_time();
+    let growth = (((exponent - 1.0) * (rate + exponent * sigma * sigma / 2.0)
+        - exponent * (rate - carry))
+        * time)
+        .exp();
+    let discounted_strike = inputs.strike * inputs.rate_discount();
+    let price = match style {
+        OptionStyle::Call => powered_spot * growth * cnd(d1) - discounted_strike * cnd(d2),
+        OptionStyle::Put => discounted_strike * cnd(-d2) - powered_spot * growth * cnd(-d1),
+    };
+    Ok(f2d!(price.max(0.0)))
+}
+
+/// Vanilla price before expiration, intrinsic value at expiration.
+fn vanilla_or_intrinsic(inputs: &GbsInputs, style: &OptionStyle) -> f64 {
+    if inputs.time == 0.0 {
+        (phi(style) * (inputs.spot - inputs.strike)).max(0.0)
+    } else {
+        inputs.vanilla(style).max(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests_exotic_pricing {
+    use super::*;
+    use crate::model::option::ExoticParams;
+    use crate::model::types::OptionType;
+    use crate::pricing::black_scholes;
+    use crate::{ExpirationDate, Positive, Side, assert_decimal_eq, pos};
+    use rust_decimal::MathematicalOps;
+    use rust_decimal_macros::dec;
+
+    #[allow(clippy::too_many_arguments)]
+    fn option(
+        option_type: OptionType,
+        style: OptionStyle,
+        spot: Positive,
+        strike: Positive,
+        years: f64,
+        rate: Decimal,
+        dividend_yield: Positive,
+        volatility: Positive,
+    ) -> Options {
+        Options::new(
+            option_type,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos!(years * 365.0)),
+            volatility,
+            Positive::ONE,
+            spot,
+            rate,
+            style,
+            dividend_yield,
+            None,
+        )
+    }
+
+    fn standard(option_type: OptionType, style: OptionStyle) -> Options {
+        option(
+            option_type,
+            style,
+            pos!(100.0),
+            pos!(100.0),
+            0.5,
+            dec!(0.05),
+            pos!(0.02),
+            pos!(0.25),
+        )
+    }
+
+    fn vanilla(style: OptionStyle) -> Decimal {
+        let inputs = GbsInputs::from_option(&standard(OptionType::European, style)).unwrap();
+        Decimal::try_from(inputs.vanilla(&style)).unwrap()
+    }
+
+    #[test]
+    fn test_barrier_in_out_parity() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            for (knock_in, knock_out, level) in [
+                (BarrierType::DownAndIn, BarrierType::DownAndOut, 90.0),
+                (BarrierType::DownAndIn, BarrierType::DownAndOut, 95.0),
+                (BarrierType::UpAndIn, BarrierType::UpAndOut, 110.0),
+                (BarrierType::UpAndIn, BarrierType::UpAndOut, 130.0),
+            ] {
+                let mut base = standard(OptionType::European, style);
+                base.strike_price = if level < 100.0 {
+                    pos!(92.0)
+                } else {
+                    pos!(115.0)
+                };
+                let vanilla =
+                    Decimal::try_from(GbsInputs::from_option(&base).unwrap().vanilla(&style))
+                        .unwrap();
+                let price_in = barrier_price(&base, &knock_in, level).unwrap();
+                let price_out = barrier_price(&base, &knock_out, level).unwrap();
+                assert_decimal_eq!(price_in + price_out, vanilla, dec!(0.000001));
+            }
+        }
+    }
+
+    #[test]
+    fn test_breached_barrier() {
+        let base = standard(OptionType::European, OptionStyle::Call);
+        assert_eq!(
+            barrier_price(&base, &BarrierType::DownAndOut, 105.0).unwrap(),
+            Decimal::ZERO
+        );
+        assert_decimal_eq!(
+            barrier_price(&base, &BarrierType::DownAndIn, 105.0).unwrap(),
+            vanilla(OptionStyle::Call),
+            dec!(0.000001)
+        );
+    }
+
+    #[test]
+    fn test_cash_or_nothing_reference_value() {
+        // Haug, cash-or-nothing put paying 10: 2.6710
+        let option = option(
+            OptionType::European,
+            OptionStyle::Put,
+            pos!(100.0),
+            pos!(80.0),
+            0.75,
+            dec!(0.06),
+            pos!(0.06),
+            pos!(0.35),
+        );
+        let price = binary_price(&option, &BinaryType::CashOrNothing).unwrap();
+        assert_decimal_eq!(price * dec!(10), dec!(2.6710), dec!(0.0001));
+    }
+
+    #[test]
+    fn test_asset_or_nothing_reference_value() {
+        // Haug, asset-or-nothing put: 20.2069
+        let option = option(
+            OptionType::European,
+            OptionStyle::Put,
+            pos!(70.0),
+            pos!(65.0),
+            0.5,
+            dec!(0.07),
+            pos!(0.05),
+            pos!(0.27),
+        );
+        let price = binary_price(&option, &BinaryType::AssetOrNothing).unwrap();
+        assert_decimal_eq!(price, dec!(20.2069), dec!(0.0001));
+    }
+
+    #[test]
+    fn test_digitals_replicate_vanilla() {
+        let base = standard(OptionType::European, OptionStyle::Call);
+        let asset = binary_price(&base, &BinaryType::AssetOrNothing).unwrap();
+        let cash = binary_price(&base, &BinaryType::CashOrNothing).unwrap();
+        assert_decimal_eq!(
+            asset - cash * dec!(100),
+            vanilla(OptionStyle::Call),
+            dec!(0.000001)
+        );
+        assert_decimal_eq!(
+            binary_price(&base, &BinaryType::Gap).unwrap(),
+            vanilla(OptionStyle::Call),
+            dec!(0.000001)
+        );
+    }
+
+    #[test]
+    fn test_floating_lookback_reference_value() {
+        // Goldman-Sosin-Gatto floating strike call, evaluated independently: 26.2842
+        let mut option = option(
+            OptionType::European,
+            OptionStyle::Call,
+            pos!(120.0),
+            pos!(120.0),
+            0.5,
+            dec!(0.10),
+            pos!(0.04),
+            pos!(0.30),
+        );
+        option.exotic_params = Some(ExoticParams {
+            spot_min: Some(dec!(100)),
+            ..Default::default()
+        });
+        let price = lookback_price(&option, &LookbackType::FloatingStrike).unwrap();
+        assert_decimal_eq!(price, dec!(26.2842), dec!(0.0001));
+    }
+
+    #[test]
+    fn test_fixed_lookback_dominates_vanilla() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            let base = standard(OptionType::European, style);
+            let price = lookback_price(&base, &LookbackType::FixedStrike).unwrap();
+            assert!(price > vanilla(style));
+        }
+    }
+
+    #[test]
+    fn test_lookback_without_carry() {
+        let base = option(
+            OptionType::European,
+            OptionStyle::Put,
+            pos!(100.0),
+            pos!(100.0),
+            0.5,
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos!(0.2),
+        );
+        let price = lookback_price(&base, &LookbackType::FloatingStrike).unwrap();
+        assert!(price > Decimal::ZERO && price < dec!(20));
+    }
+
+    #[test]
+    fn test_asian_prices() {
+        let base = standard(OptionType::European, OptionStyle::Call);
+        let geometric = asian_price(&base, &AsianAveragingType::Geometric).unwrap();
+        let arithmetic = asian_price(&base, &AsianAveragingType::Arithmetic).unwrap();
+        assert!(geometric < arithmetic);
+        assert!(arithmetic < vanilla(OptionStyle::Call));
+    }
+
+    #[test]
+    fn test_chooser_reference_value() {
+        // Haug, simple chooser: 6.1071
+        let option = option(
+            OptionType::European,
+            OptionStyle::Call,
+            pos!(50.0),
+            pos!(50.0),
+            0.5,
+            dec!(0.08),
+            Positive::ZERO,
+            pos!(0.25),
+        );
+        let price = chooser_price(&option, 0.25 * 365.0).unwrap();
+        assert_decimal_eq!(price, dec!(6.1071), dec!(0.0001));
+    }
+
+    #[test]
+    fn test_exchange_parity() {
+        let mut base = standard(OptionType::European, OptionStyle::Call);
+        base.exotic_params = Some(ExoticParams {
+            second_asset_volatility: Some(pos!(0.3)),
+            correlation: Some(dec!(0.4)),
+            ..Default::default()
+        });
+        let call = exchange_price(&base, 95.0).unwrap();
+        base.option_style = OptionStyle::Put;
+        let put = exchange_price(&base, 95.0).unwrap();
+        let forward = dec!(100) * (dec!(-0.02) * dec!(0.5)).exp() - dec!(95);
+        assert_decimal_eq!(call - put, forward, dec!(0.000001));
+    }
+
+    #[test]
+    fn test_power_with_unit_exponent_is_vanilla() {
+        let base = standard(OptionType::European, OptionStyle::Put);
+        assert_decimal_eq!(
+            power_price(&base, 1.0).unwrap(),
+            vanilla(OptionStyle::Put),
+            dec!(0.000001)
+        );
+        assert!(power_price(&base, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_black_scholes_dispatches_exotics() {
+        let mut barrier = standard(
+            OptionType::Barrier {
+                barrier_type: BarrierType::UpAndOut,
+                barrier_level: 130.0,
+            },
+            OptionStyle::Call,
+        );
+        let price = black_scholes(&barrier).unwrap();
+        assert!(price > Decimal::ZERO);
+        barrier.side = Side::Short;
+        assert_eq!(black_scholes(&barrier).unwrap(), -price);
+
+        let chooser = standard(OptionType::Chooser { choice_date: 30.0 }, OptionStyle::Call);
+        assert!(chooser.calculate_price_black_scholes().unwrap() > vanilla(OptionStyle::Call));
+    }
+}
@@ -0,0 +1,424 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::{Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::f64::consts::PI;
+use std::ops::{Add, Div, Mul, Sub};
+use std::sync::OnceLock;
+
+/// Number of Gauss-Laguerre nodes used to integrate the Lewis formula.
+const LAGUERRE_NODES: usize = 64;
+
+/// Parameters of the Heston (1993) stochastic volatility model:
+///
+/// `dS = (r - q) S dt + sqrt(v) S dW₁`, `dv = κ(θ - v) dt + ξ sqrt(v) dW₂`,
+/// `dW₁ dW₂ = ρ dt`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HestonParameters {
+    /// Speed of mean reversion of the variance `κ`.
+    pub kappa: Positive,
+    /// Long-term variance `θ`.
+    pub theta: Positive,
+    /// Volatility of the variance `ξ`.
+    pub xi: Positive,
+    /// Correlation `ρ` between the asset and its variance.
+    pub rho: Decimal,
+    /// Initial variance `v₀`.
+    pub v0: Positive,
+}
+
+impl HestonParameters {
+    /// Creates a new set of Heston parameters.
+    pub fn new(kappa: Positive, theta: Positive, xi: Positive, rho: Decimal, v0: Positive) -> Self {
+        HestonParameters {
+            kappa,
+            theta,
+            xi,
+            rho,
+            v0,
+        }
+    }
+
+    /// Whether `2κθ > ξ²`, in which case the variance never reaches zero.
+    pub fn feller_condition(&self) -> bool {
+        Decimal::TWO * self.kappa.to_dec() * self.theta.to_dec() > (self.xi * self.xi).to_dec()
+    }
+
+    pub(crate) fn as_f64(&self) -> [f64; 5] {
+        [
+            self.kappa.to_f64(),
+            self.theta.to_f64(),
+            self.xi.to_f64(),
+            self.rho.to_f64().unwrap_or(f64::NAN),
+            self.v0.to_f64(),
+        ]
+    }
+}
+
+/// Minimal complex arithmetic for the characteristic function.
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    fn exp(self) -> Self {
+        let scale = self.re.exp();
+        Complex::new(scale * self.im.cos(), scale * self.im.sin())
+    }
+
+    fn ln(self) -> Self {
+        Complex::new(self.re.hypot(self.im).ln(), self.im.atan2(self.re))
+    }
+
+    fn sqrt(self) -> Self {
+        let modulus = self.re.hypot(self.im).sqrt();
+        let argument = self.im.atan2(self.re) / 2.0;
+        Complex::new(modulus * argument.cos(), modulus * argument.sin())
+    }
+
+    fn scale(self, factor: f64) -> Self {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+/// Characteristic function of `ln(S_T / F_T)` at the complex argument `u`, in the
+/// "little Heston trap" form of Albrecher et al. (2007), which avoids branch cuts.
+fn characteristic_function(u: Complex, years: f64, p: &[f64; 5]) -> Complex {
+    let [kappa, theta, xi, rho, v0] = *p;
+    let one = Complex::new(1.0, 0.0);
+    let iu = Complex::new(-u.im, u.re);
+    let beta = Complex::new(kappa, 0.0) - iu.scale(rho * xi);
+    let d = (beta * beta + (iu + u * u).scale(xi * xi)).sqrt();
+    let g = (beta - d) / (beta + d);
+    let decay = d.scale(-years).exp();
+    let c = ((beta - d).scale(years) - ((one - g * decay) / (one - g)).ln().scale(2.0))
+        .scale(kappa * theta / (xi * xi));
+    let dv = (beta - d).scale(1.0 / (xi * xi)) * ((one - decay) / (one - g * decay));
+    (c + dv.scale(v0)).exp()
+}
+
+/// Gauss-Laguerre nodes with weights pre-multiplied by `e^x`, so that
+/// `∫₀^∞ f(x) dx ≈ Σ wᵢ f(xᵢ)`.
+fn laguerre_rule() -> &'static [(f64, f64)] {
+    static RULE: OnceLock<Vec<(f64, f64)>> = OnceLock::new();
+    RULE.get_or_init(|| {
+        let n = LAGUERRE_NODES;
+        let nf = n as f64;
+        let mut nodes: Vec<(f64, f64)> = Vec::with_capacity(n);
+        let mut z = 0.0_f64;
+        for i in 0..n {
+            z = match i {
+                0 => 3.0 / (1.0 + 2.4 * nf),
+                1 => z + 15.0 / (1.0 + 2.5 * nf),
+                _ => {
+                    let ai = (i - 1) as f64;
+                    z + (1.0 + 2.55 * ai) / (1.9 * ai) * (z - nodes[i - 2].0)
+                }
+            };
+            let mut derivative = 0.0;
+            let mut previous = 0.0;
+            for _ in 0..100 {
+                let (mut p1, mut p2) = (1.0_f64, 0.0_f64);
+                for j in 0..n {
+                    let p3 = p2;
+                    p2 = p1;
+                    let jf = j as f64;
+                    p1 = ((2.0 * jf + 1.0 - z) * p2 - jf * p3) / (jf + 1.0);
+                }
+                derivative = (nf * p1 - nf * p2) / z;
+                previous = p2;
+                let step = p1 / derivative;
+                z -= step;
+                if step.abs() <= 1e-14 * z {
+                    break;
+                }
+            }
+            let weight = -1.0 / (derivative * nf * previous);
+            nodes.push((z, weight * z.exp()));
+        }
+        nodes
+    })
+}
+
+/// Discounted Heston call price in currency units, by the Lewis (2001) formula
+/// `C = S e^{-qT} - sqrt(SK) e^{-(r+q)T/2} / π ∫₀^∞ Re[e^{iuk} φ(u - i/2)] / (u² + 1/4) du`
+/// with `k = ln(F / K)`.
+pub(crate) fn heston_call_f64(
+    spot: f64,
+    strike: f64,
+    years: f64,
+    rate: f64,
+    dividend_yield: f64,
+    parameters: &[f64; 5],
+) -> f64 {
+    let discounted_spot = spot * (-dividend_yield * years).exp();
+    if years <= 0.0 {
+        return (spot - strike).max(0.0);
+    }
+    let k = (spot / strike).ln() + (rate - dividend_yield) * years;
+    let [_, theta, _, _, v0] = *parameters;
+    // Compress the quadrature when the integrand decays quickly; never stretch it, so
+    // the peak of 1 / (u² + 1/4) near zero stays resolved
+    let decay = (40.0 / (theta.max(v0).max(1e-4) * years)).sqrt();
+    let scale = (decay / 60.0).clamp(0.02, 1.0);
+    let integral: f64 = laguerre_rule()
+        .iter()
+        .map(|&(x, weight)| {
+            let u = x * scale;
+            let phi = characteristic_function(Complex::new(u, -0.5), years, parameters);
+            let oscillation = Complex::new(0.0, u * k).exp();
+            weight * (oscillation * phi).re / (u * u + 0.25)
+        })
+        .sum::<f64>()
+        * scale;
+    discounted_spot
+        - (spot * strike).sqrt() * (-(rate + dividend_yield) * years / 2.0).exp() / PI * integral
+}
+
+/// Prices a European option under the Heston model.
+///
+/// The call price comes from the Lewis (2001) single-integral formula, integrated with
+/// 64-point Gauss-Laguerre quadrature; puts follow from put-call parity. Like
+/// [`crate::pricing::black_scholes`], the price is per contract and negative for short
+/// positions.
+///
+/// # Errors
+///
+/// Returns an error if the option is not European, its expiration cannot be read, or
+/// `rho` is outside `(-1, 1)`.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::pricing::{HestonParameters, heston_price};
+/// use optionstratlib::model::types::{OptionStyle, OptionType, Side};
+/// use optionstratlib::{pos, ExpirationDate, Options, Positive};
+/// use rust_decimal_macros::dec;
+///
+/// let option = Options::new(
+///     OptionType::European,
+///     Side::Long,
+///     "SPX".to_string(),
+///     pos!(100.0),
+///     ExpirationDate::Days(pos!(365.0)),
+///     pos!(0.2),
+///     Positive::ONE,
+///     pos!(100.0),
+///     dec!(0.0),
+///     OptionStyle::Call,
+///     Positive::ZERO,
+///     None,
+/// );
+/// let parameters = HestonParameters::new(pos!(1.5768), pos!(0.0398), pos!(0.5751), dec!(-0.5711), pos!(0.0175));
+/// let price = heston_price(&option, &parameters).unwrap();
+/// assert!((price - dec!(5.7852)).abs() < dec!(0.001));
+/// ```
+pub fn heston_price(
+    option: &Options,
+    parameters: &HestonParameters,
+) -> Result<Decimal, Box<dyn Error>> {
+    if option.option_type != OptionType::European {
+        return Err("Heston pricing is only available for European options".into());
+    }
+    if parameters.rho <= -Decimal::ONE || parameters.rho >= Decimal::ONE {
+        return Err(format!(
+            "Heston rho must be between -1 and 1, got {}",
+            parameters.rho
+        )
+        .into());
+    }
+    let years = option.expiration_date.get_years()?.to_f64();
+    let spot = option.underlying_price.to_f64();
+    let strike = option.strike_price.to_f64();
+    let rate = option.risk_free_rate.to_f64().unwrap_or(0.0);
+    let dividend_yield = option.dividend_yield.to_f64();
+    let call = heston_call_f64(
+        spot,
+        strike,
+        years,
+        rate,
+        dividend_yield,
+        &parameters.as_f64(),
+    );
+    let price = match option.option_style {
+        OptionStyle::Call => call,
+        OptionStyle::Put => {
+            call - spot * (-dividend_yield * years).exp() + strike * (-rate * years).exp()
+        }
+    }
+    .max(0.0);
+    let price = Decimal::from_f64(price).ok_or("Heston price is not a finite number")?;
+    Ok(match option.side {
+        Side::Long => price,
+        Side::Short => -price,
+    })
+}
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use crate::model::utils::create_sample_option;
+    use crate::{ExpirationDate, assert_decimal_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn option(style: OptionStyle, strike: Positive, days: Positive) -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos!(0.2),
+            Positive::ONE,
+            pos!(100.0),
+            Decimal::ZERO,
+            style,
+            Positive::ZERO,
+            None,
+        )
+    }
+
+    fn reference_parameters() -> HestonParameters {
+        HestonParameters::new(
+            pos!(1.5768),
+            pos!(0.0398),
+            pos!(0.5751),
+            dec!(-0.5711),
+            pos!(0.0175),
+        )
+    }
+
+    #[test]
+    fn test_laguerre_rule_integrates_exponentials() {
+        let integral: f64 = laguerre_rule()
+            .iter()
+            .map(|&(x, w)| w * (-2.0 * x).exp())
+            .sum();
+        assert!((integral - 0.5).abs() < 1e-10);
+        let integral: f64 = laguerre_rule()
+            .iter()
+            .map(|&(x, w)| w * x * (-x).exp())
+            .sum();
+        assert!((integral - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_reference_price() {
+        // Fang and Oosterlee (2008), COS method test case
+        let call = option(OptionStyle::Call, pos!(100.0), pos!(365.0));
+        let price = heston_price(&call, &reference_parameters()).unwrap();
+        assert_decimal_eq!(price, dec!(5.785155450), dec!(1e-5));
+    }
+
+    #[test]
+    fn test_put_call_parity_and_side() {
+        let parameters = reference_parameters();
+        let mut call = option(OptionStyle::Call, pos!(110.0), pos!(180.0));
+        call.risk_free_rate = dec!(0.03);
+        call.dividend_yield = pos!(0.01);
+        let mut put = call.clone();
+        put.option_style = OptionStyle::Put;
+        let years = call.expiration_date.get_years().unwrap().to_f64();
+        let call_price = heston_price(&call, &parameters).unwrap();
+        let put_price = heston_price(&put, &parameters).unwrap();
+        let forward_value = 100.0 * (-0.01 * years).exp() - 110.0 * (-0.03 * years).exp();
+        assert_decimal_eq!(
+            call_price - put_price,
+            Decimal::from_f64(forward_value).unwrap(),
+            dec!(1e-8)
+        );
+        call.side = Side::Short;
+        assert_eq!(heston_price(&call, &parameters).unwrap(), -call_price);
+    }
+
+    #[test]
+    fn test_constant_variance_matches_black_scholes() {
+        // No vol-of-vol and v0 = theta: Black-Scholes with sqrt(theta)
+        let parameters = HestonParameters::new(
+            pos!(2.0),
+            pos!(0.04),
+            pos!(0.001),
+            Decimal::ZERO,
+            pos!(0.04),
+        );
+        for (strike, days) in [(95.0, 7.0), (90.0, 30.0), (100.0, 30.0), (115.0, 365.0)] {
+            for side in [Side::Long, Side::Short] {
+                let mut sample = create_sample_option(
+                    OptionStyle::Put,
+                    side,
+                    pos!(100.0),
+                    Positive::ONE,
+                    pos!(strike),
+                    pos!(0.2),
+                );
+                sample.expiration_date = ExpirationDate::Days(pos!(days));
+                sample.dividend_yield = Positive::ZERO;
+                let heston = heston_price(&sample, &parameters).unwrap();
+                let black_scholes = sample.calculate_price_black_scholes().unwrap();
+                assert_decimal_eq!(heston, black_scholes, dec!(1e-4));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_inputs() {
+        let mut american = option(OptionStyle::Call, pos!(100.0), pos!(30.0));
+        american.option_type = OptionType::American;
+        assert!(heston_price(&american, &reference_parameters()).is_err());
+        let mut parameters = reference_parameters();
+        parameters.rho = dec!(1.2);
+        let call = option(OptionStyle::Call, pos!(100.0), pos!(30.0));
+        assert!(heston_price(&call, &parameters).is_err());
+        assert!(
+            reference_parameters().feller_condition() == (2.0 * 1.5768 * 0.0398 > 0.5751 * 0.5751)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::chains::chain::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::monte_carlo::{MonteCarloConfig, MonteCarloResult, monte_carlo_path_pricing};
+use crate::simulation::WalkType;
+use crate::volatility::{LocalVolatilitySurface, solve_implied_volatility};
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::{Decimal, MathematicalOps};
+use std::error::Error;
+
+/// Prices an option by Monte Carlo simulation under a local volatility surface.
+///
+/// This is [`monte_carlo_path_pricing`] with `config.process` replaced by the local
+/// volatility process of `surface`, so every option type supported there, including
+/// Asian, barrier, lookback and cliquet options, can be priced consistently with the
+/// implied volatility surface the local volatility was extracted from.
+///
+/// # Errors
+///
+/// See [`monte_carlo_path_pricing`].
+pub fn local_volatility_price(
+    option: &Options,
+    surface: &LocalVolatilitySurface,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloResult, Box<dyn Error>> {
+    let config = MonteCarloConfig {
+        process: Some(WalkType::LocalVolatility {
+            dt: Positive::ONE / DAYS_IN_A_YEAR,
+            drift: Decimal::ZERO,
+            surface: surface.clone(),
+        }),
+        ..config.clone()
+    };
+    monte_carlo_path_pricing(option, &config)
+}
+
+/// Market and local volatility model prices of one strike of a chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVolatilityRepricing {
+    /// Strike of the option.
+    pub strike: Positive,
+    /// Style of the repriced option, the out-of-the-money one at this strike.
+    pub option_style: OptionStyle,
+    /// Mid price in the chain, or its Black-Scholes price at the quoted implied
+    /// volatility when there are no bid and ask.
+    pub market_price: Decimal,
+    /// Monte Carlo price under the local volatility surface.
+    pub model_price: Decimal,
+    /// Standard error of `model_price`.
+    pub standard_error: Decimal,
+    /// Implied volatility quoted in the chain.
+    pub market_volatility: Positive,
+    /// Implied volatility of `model_price`, if it can be inverted.
+    pub model_volatility: Option<Positive>,
+}
+
+impl LocalVolatilityRepricing {
+    /// Model minus market implied volatility.
+    pub fn volatility_error(&self) -> Option<Decimal> {
+        self.model_volatility
+            .map(|model| model.to_dec() - self.market_volatility.to_dec())
+    }
+}
+
+/// Reprices the strikes of `chain` under `surface` as a consistency check of the local
+/// volatility extraction.
+///
+/// Each strike is priced with its out-of-the-money option, a call at or above the
+/// forward and a put below it. Every strike is simulated with the same seed, so the
+/// errors across the smile are smooth and reflect the surface rather than sampling
+/// noise. Strikes without quotes or implied volatility are skipped.
+///
+/// # Errors
+///
+/// Returns an error if the chain's expiration cannot be read or the simulation fails.
+pub fn reprice_chain(
+    chain: &OptionChain,
+    surface: &LocalVolatilitySurface,
+    config: &MonteCarloConfig,
+) -> Result<Vec<LocalVolatilityRepricing>, Box<dyn Error>> {
+    let days = chain
+        .get_expiration()
+        .ok_or_else(|| format!("Invalid expiration date {}", chain.get_expiration_date()))?
+        .get_days()?;
+    let rate = chain.risk_free_rate.unwrap_or(Decimal::ZERO);
+    let dividend_yield = chain.dividend_yield.unwrap_or(Positive::ZERO);
+    let forward = chain.underlying_price.to_dec()
+        * ((rate - dividend_yield.to_dec()) * (days / DAYS_IN_A_YEAR).to_dec()).exp();
+
+    let mut repricings = Vec::new();
+    for data in chain.options.iter() {
+        let (option_style, bid, ask) = if data.strike_price.to_dec() >= forward {
+            (OptionStyle::Call, data.call_bid, data.call_ask)
+        } else {
+            (OptionStyle::Put, data.put_bid, data.put_ask)
+        };
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            chain.symbol.clone(),
+            data.strike_price,
+            ExpirationDate::Days(days),
+            data.implied_volatility,
+            Positive::ONE,
+            chain.underlying_price,
+            rate,
+            option_style,
+            dividend_yield,
+            None,
+        );
+        let market_price = match (bid, ask) {
+            (Some(bid), Some(ask)) if ask >= bid && bid > Positive::ZERO => {
+                ((bid + ask) / Positive::TWO).to_dec()
+            }
+            _ if data.implied_volatility > Positive::ZERO => {
+                option.calculate_price_black_scholes()?
+            }
+            _ => continue,
+        };
+        let result = local_volatility_price(&option, surface, config)?;
+        let model_volatility = solve_implied_volatility(&option, result.price)
+            .ok()
+            .map(|implied| implied.volatility);
+        repricings.push(LocalVolatilityRepricing {
+            strike: data.strike_price,
+            option_style,
+            market_price,
+            model_price: result.price,
+            standard_error: result.standard_error,
+            market_volatility: data.implied_volatility,
+            model_volatility,
+        });
+    }
+    Ok(repricings)
+}
+
+#[cfg(test)]
+mod tests_local_volatility_pricing {
+    use super::*;
+    use crate::model::types::BarrierType;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::volatility::{SSVISlice, SSVISurface};
+    use crate::{assert_decimal_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn config() -> MonteCarloConfig {
+        MonteCarloConfig {
+            simulations: 4000,
+            steps: 60,
+            seed: Some(7),
+            ..Default::default()
+        }
+    }
+
+    fn skewed_surface() -> SSVISurface {
+        SSVISurface {
+            symbol: "TEST".to_string(),
+            underlying_price: pos!(100.0),
+            risk_free_rate: dec!(0.03),
+            dividend_yield: Positive::ZERO,
+            rho: dec!(-0.5),
+            eta: dec!(0.8),
+            gamma: dec!(0.5),
+            slices: [(30.0, 0.24), (180.0, 0.22)]
+                .iter()
+                .map(|&(days, vol)| SSVISlice {
+                    days: pos!(days),
+                    theta: pos!(vol * vol * days / 365.0),
+                })
+                .collect(),
+            strike_range: (pos!(75.0), pos!(125.0)),
+            rmse: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_reprice_chain_matches_implied_surface() {
+        let ssvi = skewed_surface();
+        let local = LocalVolatilitySurface::from_ssvi(&ssvi).unwrap();
+        let mut chain = OptionChain::new(
+            "TEST",
+            pos!(100.0),
+            get_x_days_formatted_pos(pos!(90.0)),
+            Some(dec!(0.03)),
+            Some(Positive::ZERO),
+        );
+        for strike in (85..=115).step_by(5) {
+            let strike = pos!(strike as f64);
+            chain.add_option(
+                strike,
+                None,
+                None,
+                None,
+                None,
+                ssvi.implied_volatility(strike, pos!(90.0)).unwrap(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+
+        let repricings = reprice_chain(&chain, &local, &config()).unwrap();
+        assert_eq!(repricings.len(), 7);
+        for repricing in &repricings {
+            let error = repricing.volatility_error().unwrap();
+            assert!(
+                error.abs() < dec!(0.01),
+                "strike {} error {}",
+                repricing.strike,
+                error
+            );
+        }
+        assert_eq!(repricings[0].option_style, OptionStyle::Put);
+        assert_eq!(repricings[6].option_style, OptionStyle::Call);
+    }
+
+    #[test]
+    fn test_flat_surface_matches_black_scholes_paths() {
+        let flat = LocalVolatilitySurface::new(
+            pos!(100.0),
+            dec!(0.05),
+            pos!(0.02),
+            vec![Decimal::ZERO],
+            vec![pos!(365.0)],
+            vec![vec![pos!(0.25)]],
+        )
+        .unwrap();
+        let mut barrier = Options::new(
+            OptionType::Barrier {
+                barrier_type: BarrierType::UpAndOut,
+                barrier_level: 120.0,
+            },
+            Side::Long,
+            "TEST".to_string(),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(182.5)),
+            pos!(0.25),
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            OptionStyle::Call,
+            pos!(0.02),
+            None,
+        );
+        let local = local_volatility_price(&barrier, &flat, &config()).unwrap();
+        let gbm = monte_carlo_path_pricing(&barrier, &config()).unwrap();
+        assert_decimal_eq!(local.price, gbm.price, dec!(1e-8));
+        assert_decimal_eq!(local.standard_error, gbm.standard_error, dec!(1e-8));
+
+        barrier.side = Side::Short;
+        let short = local_volatility_price(&barrier, &flat, &config()).unwrap();
+        assert_decimal_eq!(short.price, -local.price, dec!(1e-12));
+    }
+
+    #[test]
+    fn test_reprice_chain_requires_expiration() {
+        let local = LocalVolatilitySurface::from_ssvi(&skewed_surface()).unwrap();
+        let chain = OptionChain::new("TEST", pos!(100.0), "not a date".to_string(), None, None);
+        assert!(reprice_chain(&chain, &local, &config()).is_err());
+    }
+}
@@ -147,6 +147,15 @@
 //! For high-frequency calculations, consider using the Black-Scholes model
 //! when applicable, as it provides the fastest computation times.
 
+/// Closed-form approximations for American option pricing.
+///
+/// This module implements the Barone-Adesi–Whaley quadratic approximation, which
+/// prices American calls and puts on dividend-paying assets as the European value
+/// plus an analytic early-exercise premium.
+///
+/// It is used by `black_scholes` whenever the option type is `OptionType::American`.
+pub mod american;
+
 /// Binomial tree model implementation for option pricing.
 ///
 /// This module provides functionality to price options using binomial tree methods,
@@ -172,6 +181,27 @@ pub mod black_scholes_model;
 /// such as day count conventions, numerical approximation parameters, and defaults.
 pub(crate) mod constants;
 
+/// Closed-form pricing for exotic options.
+///
+/// Provides analytic prices for barrier (Reiner–Rubinstein), binary, lookback
+/// (Goldman–Sosin–Gatto and Conze–Viswanathan), Asian (Kemna–Vorst and
+/// Turnbull–Wakeman), exchange (Margrabe), simple chooser (Rubinstein) and power
+/// options under the generalized Black-Scholes-Merton model.
+pub mod exotic;
+
+/// Semi-analytic pricing under the Heston stochastic volatility model.
+///
+/// European options are priced from the characteristic function of the log price
+/// with the Lewis single-integral formula and Gauss-Laguerre quadrature.
+pub mod heston;
+
+/// Option pricing under a Dupire local volatility surface.
+///
+/// Prices vanilla and path-dependent options by simulation under
+/// `LocalVolatilitySurface` dynamics, and reprices a chain as a check that the
+/// surface reproduces the implied volatilities it was built from.
+pub mod local_volatility;
+
 /// Monte Carlo simulation methods for financial modeling.
 ///
 /// This module provides tools for pricing options and other derivatives using
@@ -179,7 +209,9 @@ pub(crate) mod constants;
 /// to estimate expected payoffs.
 ///
 /// Monte Carlo methods are particularly valuable for complex derivatives where
-/// closed-form solutions don't exist.
+/// closed-form solutions don't exist. `monte_carlo_path_pricing` simulates full
+/// paths under GBM, Heston, jump-diffusion or local volatility dynamics to price path-dependent
+/// options, with antithetic and control-variate variance reduction.
 pub mod monte_carlo;
 
 /// Payoff functions for different option types and derivatives.
@@ -207,9 +239,18 @@ pub mod telegraph;
 /// including numerical methods, date handling, and data transformation tools.
 pub(crate) mod utils;
 
+pub use american::barone_adesi_whaley;
 pub use binomial_model::{BinomialPricingParams, generate_binomial_tree, price_binomial};
 pub use black_scholes_model::{BlackScholes, black_scholes};
-pub use monte_carlo::monte_carlo_option_pricing;
+pub use exotic::{
+    asian_price, barrier_price, binary_price, chooser_price, exchange_price, lookback_price,
+    power_price,
+};
+pub use heston::{HestonParameters, heston_price};
+pub use local_volatility::{LocalVolatilityRepricing, local_volatility_price, reprice_chain};
+pub use monte_carlo::{
+    MonteCarloConfig, MonteCarloResult, monte_carlo_option_pricing, monte_carlo_path_pricing,
+};
 pub use payoff::{Payoff, PayoffInfo, Profit};
 pub use telegraph::{TelegraphProcess, telegraph};
 pub use utils::{probability_keep_under_strike, simulate_returns};
@@ -1,7 +1,13 @@
-use crate::f2d;
-use crate::pricing::utils::wiener_increment;
-use crate::{Options, Positive};
-use num_traits::{FromPrimitive, ToPrimitive};
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::model::types::{OptionType, Side};
+use crate::pricing::payoff::{Payoff, PayoffInfo};
+use crate::simulation::WalkType;
+use crate::volatility::LocalVolatilityGrid;
+use crate::{Options, Positive, d2f, f2d};
+use num_traits::FromPrimitive;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand_distr::{Distribution, Normal, Poisson};
 use rust_decimal::{Decimal, MathematicalOps};
 use std::error::Error;
 
@@ -15,48 +21,27 @@ use std::error::Error;
 ///
 /// # Returns
 ///
-/// * A floating-point number representing the estimated price of the option.
+/// * The estimated per-contract price of the option, negative for short positions.
 ///
 /// # Description
 ///
-/// The function follows the below steps:
-///
-/// 1. Calculate the time increment `dt` based on the number of steps.
-/// 2. Initialize a sum variable `payoff_sum` to accumulate the payoffs from each simulation.
-/// 3. Loop through the number of simulations:
-///     - For each simulation, initialize the stock price `st` to the underlying price.
-///     - Loop through the number of steps:
-///         - Calculate a Wiener process increment `w`.
-///         - Update the stock price `st` using the discrete approximation of the geometric Brownian motion model.
-///     - Calculate the payoff of the option for this simulation (for a call option, this is `max(st - strike_price, 0)`).
-///     - Add the payoff to the `payoff_sum`.
-/// 4. Return the average payoff discounted to its present value.
+/// This is a convenience wrapper around [`monte_carlo_path_pricing`] that simulates
+/// geometric Brownian motion paths at the option's implied volatility under the
+/// risk-neutral measure (including the dividend yield), with antithetic variates and
+/// a control variate enabled. The option style, side and type are all honoured, so
+/// path-dependent options such as Asian, barrier, lookback and cliquet options are
+/// priced on the simulated paths.
 pub fn monte_carlo_option_pricing(
     option: &Options,
     steps: usize,       // Number of time steps
     simulations: usize, // Number of Monte Carlo simulations
 ) -> Result<Decimal, Box<dyn Error>> {
-    let dt = option.expiration_date.get_years().unwrap() / steps as f64;
-    let mut payoff_sum = 0.0;
-
-    for _ in 0..simulations {
-        let mut st = option.underlying_price.to_dec();
-        for _ in 0..steps {
-            let w = wiener_increment(dt.to_dec())?;
-            st *= Decimal::ONE + option.risk_free_rate * dt + option.implied_volatility * w;
-        }
-        // Calculate the payoff for a call option
-        let payoff: f64 = (st - option.strike_price)
-            .max(Decimal::ZERO)
-            .to_f64()
-            .unwrap();
-        payoff_sum += payoff;
-    }
-    // Average value of the payoffs discounted to present value
-    let average_payoff = (payoff_sum / simulations as f64)
-        * (-option.risk_free_rate.to_f64().unwrap() * option.expiration_date.get_years().unwrap())
-            .exp();
-    Ok(f2d!(average_payoff))
+    let config = MonteCarloConfig {
+        simulations,
+        steps,
+        ..Default::default()
+    };
+    Ok(monte_carlo_path_pricing(option, &config)?.price)
 }
 
 /// Estimates the price of a financial option using the Monte Carlo simulation method.
@@ -123,6 +108,423 @@ pub fn price_option_monte_carlo(
     Ok(Positive(avg_payoff.abs()))
 }
 
+/// Configuration of the path-dependent Monte Carlo pricer.
+///
+/// See [`monte_carlo_path_pricing`] for how each field is used.
+#[derive(Debug, Clone)]
+pub struct MonteCarloConfig {
+    /// Number of independent draws. With antithetic variates every draw produces
+    /// two paths.
+    pub simulations: usize,
+    /// Number of time steps of each path. Path-dependent features (averages,
+    /// barriers, extremes and resets) are monitored on this grid.
+    pub steps: usize,
+    /// Seed of the random number generator. `None` seeds it from system entropy.
+    pub seed: Option<u64>,
+    /// Whether to pair every path with its antithetic path.
+    pub antithetic: bool,
+    /// Whether to use the discounted terminal price of the underlying as a control
+    /// variate.
+    pub control_variate: bool,
+    /// Process driving the underlying. `None` simulates geometric Brownian motion at
+    /// the option's implied volatility. `GeometricBrownian`, `Heston`, `JumpDiffusion`
+    /// and `LocalVolatility` are supported.
+    pub process: Option<WalkType>,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        MonteCarloConfig {
+            simulations: 10_000,
+            steps: 252,
+            seed: None,
+            antithetic: true,
+            control_variate: true,
+            process: None,
+        }
+    }
+}
+
+/// Result of a Monte Carlo valuation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonteCarloResult {
+    /// Estimated per-contract price, negative for short positions.
+    pub price: Decimal,
+    /// Standard error of the price estimate.
+    pub standard_error: Decimal,
+    /// Number of simulated paths.
+    pub paths: usize,
+}
+
+/// Risk-neutral dynamics of the underlying, built from a `WalkType`.
+enum PathProcess {
+    GeometricBrownian {
+        volatility: f64,
+    },
+    Heston {
+        variance: f64,
+        kappa: f64,
+        theta: f64,
+        xi: f64,
+        rho: f64,
+    },
+    JumpDiffusion {
+        volatility: f64,
+        intensity: f64,
+        jump_mean: f64,
+        jump_volatility: f64,
+    },
+    LocalVolatility {
+        grid: LocalVolatilityGrid,
+    },
+}
+
+impl PathProcess {
+    fn new(option: &Options, process: &Option<WalkType>) -> Result<Self, Box<dyn Error>> {
+        match process {
+            None => Ok(PathProcess::GeometricBrownian {
+                volatility: option.implied_volatility.to_f64(),
+            }),
+            Some(WalkType::GeometricBrownian { volatility, .. }) => {
+                Ok(PathProcess::GeometricBrownian {
+                    volatility: volatility.to_f64(),
+                })
+            }
+            Some(WalkType::Heston {
+                volatility,
+                kappa,
+                theta,
+                xi,
+                rho,
+                ..
+            }) => {
+                let rho = d2f!(*rho);
+                if !(-1.0..=1.0).contains(&rho) {
+                    return Err("Correlation rho must be between -1 and 1".into());
+                }
+                Ok(PathProcess::Heston {
+                    variance: volatility.to_f64().powi(2),
+                    kappa: kappa.to_f64(),
+                    theta: theta.to_f64(),
+                    xi: xi.to_f64(),
+                    rho,
+                })
+            }
+            Some(WalkType::JumpDiffusion {
+                volatility,
+                intensity,
+                jump_mean,
+                jump_volatility,
+                ..
+            }) => Ok(PathProcess::JumpDiffusion {
+                volatility: volatility.to_f64(),
+                intensity: intensity.to_f64(),
+                jump_mean: d2f!(*jump_mean),
+                jump_volatility: jump_volatility.to_f64(),
+            }),
+            Some(WalkType::LocalVolatility { surface, .. }) => Ok(PathProcess::LocalVolatility {
+                grid: surface.grid(),
+            }),
+            Some(other) => {
+                Err(format!("Walk type {other} is not supported by the Monte Carlo pricer").into())
+            }
+        }
+    }
+
+    /// Random draws of one path, shared between a path and its antithetic twin.
+    fn draw_shocks(
+        &self,
+        rng: &mut StdRng,
+        steps: usize,
+        dt: f64,
+    ) -> Result<Vec<[f64; 3]>, Box<dyn Error>> {
+        let normal = Normal::new(0.0, 1.0)?;
+        let jumps = match self {
+            PathProcess::JumpDiffusion { intensity, .. } if *intensity > 0.0 => {
+                Some(Poisson::new(intensity * dt)?)
+            }
+            _ => None,
+        };
+        let mut shocks = Vec::with_capacity(steps);
+        for _ in 0..steps {
+            let first = normal.sample(rng);
+            let second = normal.sample(rng);
+            let log_jump = match (self, &jumps) {
+                (
+                    PathProcess::JumpDiffusion {
+                        jump_mean,
+                        jump_volatility,
+                        ..
+                    },
+                    Some(poisson),
+                ) => {
+                    let count = poisson.sample(rng);
+                    if count > 0.0 {
+                        count * jump_mean + count.sqrt() * jump_volatility * normal.sample(rng)
+                    } else {
+                        0.0
+                    }
+                }
+                _ => 0.0,
+            };
+            shocks.push([first, second, log_jump]);
+        }
+        Ok(shocks)
+    }
+
+    /// Builds a path from its shocks. `sign` flips the diffusion shocks to produce the
+    /// antithetic path; jumps are shared.
+    fn simulate(&self, spot: f64, carry: f64, dt: f64, shocks: &[[f64; 3]], sign: f64) -> Vec<f64> {
+        let mut path = Vec::with_capacity(shocks.len() + 1);
+        let mut price = spot;
+        path.push(price);
+        match self {
+            PathProcess::GeometricBrownian { volatility } => {
+                let drift = (carry - volatility * volatility / 2.0) * dt;
+                let diffusion = volatility * dt.sqrt();
+                for shock in shocks {
+                    price *= (drift + diffusion * sign * shock[0]).exp();
+                    path.push(price);
+                }
+            }
+            PathProcess::Heston {
+                variance,
+                kappa,
+                theta,
+                xi,
+                rho,
+            } => {
+                // Full truncation Euler scheme on the log price
+                let mut variance = *variance;
+                let orthogonal = (1.0 - rho * rho).sqrt();
+                for shock in shocks {
+                    let positive_variance = variance.max(0.0);
+                    let price_shock = sign * shock[0];
+                    let variance_shock = rho * price_shock + orthogonal * sign * shock[1];
+                    price *= ((carry - positive_variance / 2.0) * dt
+                        + (positive_variance * dt).sqrt() * price_shock)
+                        .exp();
+                    variance += kappa * (theta - positive_variance) * dt
+                        + xi * (positive_variance * dt).sqrt() * variance_shock;
+                    path.push(price);
+                }
+            }
+            PathProcess::JumpDiffusion {
+                volatility,
+                intensity,
+                jump_mean,
+                jump_volatility,
+            } => {
+                // Merton dynamics: lognormal jumps with a compensated drift
+                let compensator =
+                    intensity * ((jump_mean + jump_volatility * jump_volatility / 2.0).exp() - 1.0);
+                let drift = (carry - compensator - volatility * volatility / 2.0) * dt;
+                let diffusion = volatility * dt.sqrt();
+                for shock in shocks {
+                    price *= (drift + diffusion * sign * shock[0] + shock[2]).exp();
+                    path.push(price);
+                }
+            }
+            PathProcess::LocalVolatility { grid } => {
+                for (step, shock) in shocks.iter().enumerate() {
+                    let volatility = grid.volatility(price, step as f64 * dt);
+                    price *= ((carry - volatility * volatility / 2.0) * dt
+                        + volatility * dt.sqrt() * sign * shock[0])
+                        .exp();
+                    path.push(price);
+                }
+            }
+        }
+        path
+    }
+}
+
+/// Evaluates the undiscounted long payoff of `option` on a simulated path.
+///
+/// The path extremes feed `spot_min`/`spot_max`, the observations after the start feed
+/// `spot_prices` for Asian options (preceded by any past fixings in `exotic_params`),
+/// and the prices on the reset dates feed `spot_prices` for cliquet options.
+fn path_payoff(option: &Options, path: &[f64], dt: f64) -> Result<f64, Box<dyn Error>> {
+    let params = option.exotic_params.as_ref();
+    let terminal = *path.last().ok_or("Empty Monte Carlo path")?;
+    let mut spot_min = path.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut spot_max = path.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if let Some(min) = params.and_then(|params| params.spot_min) {
+        spot_min = spot_min.min(d2f!(min));
+    }
+    if let Some(max) = params.and_then(|params| params.spot_max) {
+        spot_max = spot_max.max(d2f!(max));
+    }
+    let spot_prices = match &option.option_type {
+        OptionType::Asian { .. } => {
+            let mut fixings: Vec<f64> = params
+                .and_then(|params| params.spot_prices.as_ref())
+                .map(|prices| prices.iter().map(|price| price.to_f64()).collect())
+                .unwrap_or_default();
+            fixings.extend_from_slice(&path[1..]);
+            Some(fixings)
+        }
+        OptionType::Cliquet { reset_dates } => {
+            let last = path.len() - 1;
+            let fixings = reset_dates
+                .iter()
+                .map(|days| (days / DAYS_IN_A_YEAR.to_f64() / dt).round() as usize)
+                .filter(|&index| index > 0 && index < last)
+                .map(|index| path[index])
+                .collect();
+            Some(fixings)
+        }
+        _ => None,
+    };
+    let info = PayoffInfo {
+        spot: Positive::new(terminal)?,
+        strike: option.strike_price,
+        style: option.option_style,
+        side: Side::Long,
+        spot_prices,
+        spot_min: Some(spot_min),
+        spot_max: Some(spot_max),
+    };
+    Ok(option.option_type.payoff(&info))
+}
+
+fn mean_and_standard_error(samples: &[f64]) -> (f64, f64) {
+    let count = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / count;
+    if samples.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0);
+    (mean, (variance / count).sqrt())
+}
+
+/// Prices an option by simulating full paths of the underlying.
+///
+/// Paths are simulated under the risk-neutral measure on a grid of `config.steps`
+/// steps up to expiration, with drift `r - q` regardless of the drift and `dt` stored
+/// in `config.process`. Each path is turned into a `PayoffInfo` (terminal spot, path
+/// minimum and maximum, and the relevant fixings) and valued with the option type's
+/// `Payoff` implementation, so Asian, barrier, lookback and cliquet options are priced
+/// on their whole path. Barriers are monitored discretely on the simulation grid.
+///
+/// # Variance reduction
+///
+/// * Antithetic variates pair each path with the path built from the negated
+///   diffusion shocks and average their payoffs.
+/// * The control variate is the discounted terminal price, whose expectation
+///   `S·e^(-qT)` is known under every supported process.
+///
+/// # Arguments
+///
+/// * `option` - The option to price. Its implied volatility is used unless `config.process`
+///   provides another one.
+/// * `config` - Simulation settings; a fixed `seed` makes the result reproducible.
+///
+/// # Returns
+///
+/// The per-contract price (negative for short positions), its standard error and the
+/// number of simulated paths.
+///
+/// # Errors
+///
+/// Returns an error for options with early exercise (American and Bermuda), for option
+/// types whose payoff depends on other assets, for unsupported walk types, and when
+/// `simulations` or `steps` is zero.
+pub fn monte_carlo_path_pricing(
+    option: &Options,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloResult, Box<dyn Error>> {
+    match option.option_type {
+        OptionType::European
+        | OptionType::Asian { .. }
+        | OptionType::Barrier { .. }
+        | OptionType::Binary { .. }
+        | OptionType::Lookback { .. }
+        | OptionType::Cliquet { .. }
+        | OptionType::Power { .. } => {}
+        ref other => {
+            return Err(format!("Monte Carlo path pricing does not support: {other}").into());
+        }
+    }
+    if config.simulations == 0 || config.steps == 0 {
+        return Err("Monte Carlo pricing requires at least one simulation and one step".into());
+    }
+
+    let process = PathProcess::new(option, &config.process)?;
+    let spot = option.underlying_price.to_f64();
+    let rate = d2f!(option.risk_free_rate);
+    let dividend_yield = option.dividend_yield.to_f64();
+    let time = option.time_to_expiration()?.to_f64();
+    let sign = match option.side {
+        Side::Long => 1.0,
+        Side::Short => -1.0,
+    };
+
+    if time == 0.0 {
+        let payoff = path_payoff(option, &[spot], 1.0)?;
+        return Ok(MonteCarloResult {
+            price: f2d!(sign * payoff),
+            standard_error: Decimal::ZERO,
+            paths: 1,
+        });
+    }
+
+    let dt = time / config.steps as f64;
+    let discount = (-rate * time).exp();
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+
+    let mut payoffs = Vec::with_capacity(config.simulations);
+    let mut controls = Vec::with_capacity(config.simulations);
+    for _ in 0..config.simulations {
+        let shocks = process.draw_shocks(&mut rng, config.steps, dt)?;
+        let path = process.simulate(spot, rate - dividend_yield, dt, &shocks, 1.0);
+        let mut payoff = path_payoff(option, &path, dt)?;
+        let mut control = path[config.steps];
+        if config.antithetic {
+            let antithetic = process.simulate(spot, rate - dividend_yield, dt, &shocks, -1.0);
+            payoff = (payoff + path_payoff(option, &antithetic, dt)?) / 2.0;
+            control = (control + antithetic[config.steps]) / 2.0;
+        }
+        payoffs.push(discount * payoff);
+        controls.push(discount * control);
+    }
+
+    if config.control_variate {
+        let expected_control = spot * (-dividend_yield * time).exp();
+        let (control_mean, _) = mean_and_standard_error(&controls);
+        let (payoff_mean, _) = mean_and_standard_error(&payoffs);
+        let (covariance, variance) = payoffs.iter().zip(&controls).fold(
+            (0.0, 0.0),
+            |(covariance, variance), (payoff, control)| {
+                (
+                    covariance + (payoff - payoff_mean) * (control - control_mean),
+                    variance + (control - control_mean).powi(2),
+                )
+            },
+        );
+        if variance > 0.0 {
+            let beta = covariance / variance;
+            for (payoff, control) in payoffs.iter_mut().zip(&controls) {
+                *payoff -= beta * (control - expected_control);
+            }
+        }
+    }
+
+    let (price, standard_error) = mean_and_standard_error(&payoffs);
+    Ok(MonteCarloResult {
+        price: f2d!(sign * price),
+        standard_error: f2d!(standard_error),
+        paths: if config.antithetic {
+            2 * config.simulations
+        } else {
+            config.simulations
+        },
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +600,226 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod tests_monte_carlo_path_pricing {
+    use super::*;
+    use crate::model::option::ExoticParams;
+    use crate::model::types::{AsianAveragingType, BarrierType, LookbackType, OptionStyle};
+    use crate::pricing::black_scholes;
+    use crate::pricing::exotic::{asian_price, barrier_price, lookback_price};
+    use crate::{ExpirationDate, pos};
+    use rust_decimal_macros::dec;
+
+    fn create_option(option_type: OptionType, style: OptionStyle) -> Options {
+        Options::new(
+            option_type,
+            Side::Long,
+            "TEST".to_string(),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(182.5)),
+            pos!(0.25),
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            style,
+            pos!(0.02),
+            None,
+        )
+    }
+
+    fn config(simulations: usize) -> MonteCarloConfig {
+        MonteCarloConfig {
+            simulations,
+            steps: 100,
+            seed: Some(42),
+            ..Default::default()
+        }
+    }
+
+    fn assert_within(result: &MonteCarloResult, expected: Decimal, slack: Decimal) {
+        let tolerance = dec!(4) * result.standard_error + slack;
+        assert!(
+            (result.price - expected).abs() <= tolerance,
+            "price {} expected {} tolerance {}",
+            result.price,
+            expected,
+            tolerance
+        );
+    }
+
+    #[test]
+    fn test_european_matches_black_scholes() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            let option = create_option(OptionType::European, style);
+            let result = monte_carlo_path_pricing(&option, &config(4000)).unwrap();
+            assert_within(&result, black_scholes(&option).unwrap(), dec!(0.0));
+            assert_eq!(result.paths, 8000);
+        }
+    }
+
+    #[test]
+    fn test_short_side_and_seed_reproducibility() {
+        let mut option = create_option(OptionType::European, OptionStyle::Call);
+        let long = monte_carlo_path_pricing(&option, &config(500)).unwrap();
+        option.side = Side::Short;
+        let short = monte_carlo_path_pricing(&option, &config(500)).unwrap();
+        assert_eq!(short.price, -long.price);
+        assert_eq!(short.standard_error, long.standard_error);
+    }
+
+    #[test]
+    fn test_variance_reduction_lowers_standard_error() {
+        let option = create_option(OptionType::European, OptionStyle::Call);
+        let plain = MonteCarloConfig {
+            antithetic: false,
+            control_variate: false,
+            ..config(2000)
+        };
+        let plain = monte_carlo_path_pricing(&option, &plain).unwrap();
+        let reduced = monte_carlo_path_pricing(&option, &config(2000)).unwrap();
+        assert!(reduced.standard_error < plain.standard_error);
+    }
+
+    #[test]
+    fn test_geometric_asian_matches_closed_form() {
+        let averaging_type = AsianAveragingType::Geometric;
+        let option = create_option(
+            OptionType::Asian {
+                averaging_type: averaging_type.clone(),
+            },
+            OptionStyle::Call,
+        );
+        let result = monte_carlo_path_pricing(&option, &config(4000)).unwrap();
+        let expected = asian_price(&option, &averaging_type).unwrap();
+        assert_within(&result, expected, dec!(0.05));
+    }
+
+    #[test]
+    fn test_barrier_in_plus_out_equals_vanilla() {
+        let settings = MonteCarloConfig {
+            control_variate: false,
+            ..config(1000)
+        };
+        let price = |barrier_type: BarrierType| {
+            let option = create_option(
+                OptionType::Barrier {
+                    barrier_type,
+                    barrier_level: 90.0,
+                },
+                OptionStyle::Put,
+            );
+            monte_carlo_path_pricing(&option, &settings).unwrap().price
+        };
+        let vanilla = monte_carlo_path_pricing(
+            &create_option(OptionType::European, OptionStyle::Put),
+            &settings,
+        )
+        .unwrap()
+        .price;
+        let parity = price(BarrierType::DownAndIn) + price(BarrierType::DownAndOut);
+        assert!((parity - vanilla).abs() < dec!(0.000001));
+    }
+
+    #[test]
+    fn test_up_and_out_call_close_to_closed_form() {
+        let barrier_type = BarrierType::UpAndOut;
+        let option = create_option(
+            OptionType::Barrier {
+                barrier_type: barrier_type.clone(),
+                barrier_level: 120.0,
+            },
+            OptionStyle::Call,
+        );
+        let result = monte_carlo_path_pricing(&option, &config(4000)).unwrap();
+        let continuous = barrier_price(&option, &barrier_type, 120.0).unwrap();
+        // Discrete monitoring knocks out less often than the continuous barrier
+        assert!(result.price > continuous);
+        assert!(result.price < continuous * dec!(1.5));
+    }
+
+    #[test]
+    fn test_floating_lookback_close_to_closed_form() {
+        let mut option = create_option(
+            OptionType::Lookback {
+                lookback_type: LookbackType::FloatingStrike,
+            },
+            OptionStyle::Put,
+        );
+        option.exotic_params = Some(ExoticParams {
+            spot_max: Some(dec!(105)),
+            ..Default::default()
+        });
+        let result = monte_carlo_path_pricing(&option, &config(4000)).unwrap();
+        let continuous = lookback_price(&option, &LookbackType::FloatingStrike).unwrap();
+        // Discrete monitoring misses part of the running maximum
+        assert!(result.price < continuous);
+        assert!(result.price > continuous * dec!(0.9));
+    }
+
+    #[test]
+    fn test_cliquet_dominates_vanilla() {
+        let settings = MonteCarloConfig {
+            control_variate: false,
+            ..config(1000)
+        };
+        let cliquet = create_option(
+            OptionType::Cliquet {
+                reset_dates: vec![45.0, 90.0, 135.0],
+            },
+            OptionStyle::Call,
+        );
+        let vanilla = create_option(OptionType::European, OptionStyle::Call);
+        let cliquet = monte_carlo_path_pricing(&cliquet, &settings).unwrap();
+        let vanilla = monte_carlo_path_pricing(&vanilla, &settings).unwrap();
+        assert!(cliquet.price > vanilla.price);
+    }
+
+    #[test]
+    fn test_heston_without_vol_of_vol_matches_black_scholes() {
+        let option = create_option(OptionType::European, OptionStyle::Call);
+        let settings = MonteCarloConfig {
+            process: Some(WalkType::Heston {
+                dt: pos!(0.01),
+                drift: Decimal::ZERO,
+                volatility: pos!(0.25),
+                kappa: pos!(2.0),
+                theta: pos!(0.0625),
+                xi: Positive::ZERO,
+                rho: dec!(-0.5),
+            }),
+            ..config(4000)
+        };
+        let result = monte_carlo_path_pricing(&option, &settings).unwrap();
+        assert_within(&result, black_scholes(&option).unwrap(), dec!(0.0));
+    }
+
+    #[test]
+    fn test_jumps_increase_option_value() {
+        let option = create_option(OptionType::European, OptionStyle::Put);
+        let settings = MonteCarloConfig {
+            process: Some(WalkType::JumpDiffusion {
+                dt: pos!(0.01),
+                drift: Decimal::ZERO,
+                volatility: pos!(0.25),
+                intensity: pos!(1.0),
+                jump_mean: dec!(-0.1),
+                jump_volatility: pos!(0.2),
+            }),
+            ..config(4000)
+        };
+        let result = monte_carlo_path_pricing(&option, &settings).unwrap();
+        assert!(result.price > black_scholes(&option).unwrap() + dec!(4) * result.standard_error);
+    }
+
+    #[test]
+    fn test_unsupported_inputs() {
+        let american = create_option(OptionType::American, OptionStyle::Put);
+        assert!(monte_carlo_path_pricing(&american, &config(10)).is_err());
+        let european = create_option(OptionType::European, OptionStyle::Put);
+        assert!(monte_carlo_path_pricing(&european, &config(0)).is_err());
+    }
+}
+
 #[cfg(test)]
 mod tests_price_option_monte_carlo {
     use super::*;
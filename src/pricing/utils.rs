@@ -14,7 +14,6 @@ use crate::pricing::payoff::{Payoff, PayoffInfo};
 use crate::utils::random_decimal;
 use num_traits::FromPrimitive;
 use rand::Rng;
-use rand_distr::{Distribution, Normal};
 use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
 
@@ -293,35 +292,6 @@ pub(crate) fn calculate_discounted_payoff(
     }
 }
 
-/// Calculates a Wiener process (Brownian motion) increment over a small-time step `dt`.
-///
-/// This function uses the standard normal distribution to sample a value and scales it
-/// by the square root of `dt` to produce the Wiener increment. The Wiener increment is a
-/// random variable with a normal distribution, which is essential for simulating Brownian motion
-/// in continuous time.
-///
-/// # Arguments
-///
-/// * `dt` - A small time step over which the Wiener increment is calculated.
-///
-/// # Returns
-///
-/// * `f64` - The Wiener process increment for the given time step.
-///
-/// # Panics
-///
-/// This function will panic if the creation of the normal distribution fails, which is
-/// highly unlikely with valid inputs.
-///
-pub(crate) fn wiener_increment(dt: Decimal) -> Result<Decimal, DecimalError> {
-    let normal = Normal::new(0.0, 1.0).unwrap();
-    let mut rng = rand::rng();
-
-    let sample = Decimal::from_f64(normal.sample(&mut rng)).unwrap();
-
-    Ok(sample * dt.sqrt().unwrap())
-}
-
 /// Calculates the probability that the option will remain under the strike price.
 ///
 /// # Parameters
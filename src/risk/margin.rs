@@ -0,0 +1,859 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::error::MetricsError;
+use crate::model::UnderlyingPosition;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, Side};
+use crate::strategies::base::Positionable;
+use crate::{Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+
+/// Lowest implied volatility used when a portfolio margin scenario shifts volatility down.
+const MIN_SCENARIO_VOLATILITY: Decimal = dec!(0.0001);
+
+/// Rates of the Reg-T strategy-based margin rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegTParameters {
+    /// Fraction of the underlying value charged on a naked short option (20%).
+    pub naked_rate: Decimal,
+    /// Minimum fraction charged on a naked short option: of the underlying value for
+    /// calls and of the strike for puts (10%).
+    pub minimum_rate: Decimal,
+    /// Initial margin on a position in the underlying (50%).
+    pub underlying_rate: Decimal,
+}
+
+impl RegTParameters {
+    /// Creates a new set of Reg-T rates.
+    pub fn new(naked_rate: Decimal, minimum_rate: Decimal, underlying_rate: Decimal) -> Self {
+        RegTParameters {
+            naked_rate,
+            minimum_rate,
+            underlying_rate,
+        }
+    }
+}
+
+impl Default for RegTParameters {
+    fn default() -> Self {
+        RegTParameters::new(dec!(0.20), dec!(0.10), dec!(0.50))
+    }
+}
+
+/// Parameters of an OCC/TIMS-style portfolio margin calculation.
+///
+/// The underlying price is moved over `price_steps` equal intervals between
+/// `-price_range` and `+price_range`, each point combined with every volatility shift.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioMarginParameters {
+    /// Largest price move of the grid, as a fraction of the underlying price.
+    pub price_range: Decimal,
+    /// Number of intervals of the price grid.
+    pub price_steps: usize,
+    /// Absolute implied volatility shifts applied at every price point.
+    pub volatility_shifts: Vec<Decimal>,
+    /// Minimum requirement per unit of option quantity.
+    pub minimum_per_contract: Decimal,
+}
+
+impl PortfolioMarginParameters {
+    /// Creates a new set of portfolio margin parameters.
+    pub fn new(
+        price_range: Decimal,
+        price_steps: usize,
+        volatility_shifts: Vec<Decimal>,
+        minimum_per_contract: Decimal,
+    ) -> Self {
+        PortfolioMarginParameters {
+            price_range,
+            price_steps,
+            volatility_shifts,
+            minimum_per_contract,
+        }
+    }
+
+    fn price_moves(&self) -> Vec<Decimal> {
+        let steps = self.price_steps.max(1);
+        let step = self.price_range * dec!(2) / Decimal::from(steps);
+        (0..=steps)
+            .map(|i| -self.price_range + step * Decimal::from(i))
+            .collect()
+    }
+}
+
+impl Default for PortfolioMarginParameters {
+    /// ±15% in 3% steps, volatility shifted by ±5 points, 0.375 minimum per unit
+    /// (37.50 per 100-share contract).
+    fn default() -> Self {
+        PortfolioMarginParameters::new(
+            dec!(0.15),
+            10,
+            vec![dec!(-0.05), Decimal::ZERO, dec!(0.05)],
+            dec!(0.375),
+        )
+    }
+}
+
+/// Rules used to size the capital a set of positions ties up.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MarginMethod {
+    /// Reg-T strategy-based rules.
+    RegT(RegTParameters),
+    /// OCC/TIMS-style portfolio margin.
+    Portfolio(PortfolioMarginParameters),
+}
+
+impl Default for MarginMethod {
+    fn default() -> Self {
+        MarginMethod::RegT(RegTParameters::default())
+    }
+}
+
+/// Breakdown of a Reg-T strategy-based requirement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginRequirement {
+    /// Margin on the position held in the underlying.
+    pub underlying_requirement: Decimal,
+    /// Requirement of the hedged option legs: their maximum loss at expiration.
+    pub spread_requirement: Decimal,
+    /// Requirement of the naked short options, net of the premium they received.
+    pub naked_requirement: Decimal,
+    /// Premium of long options that cannot hedge any short and are paid in full.
+    pub long_premium: Decimal,
+    /// Premium received on short options covered by the underlying.
+    pub covered_premium: Decimal,
+    /// Total premium paid on long options.
+    pub premium_paid: Decimal,
+    /// Total premium received on short options.
+    pub premium_received: Decimal,
+    /// Buying-power requirement of the whole position.
+    pub total: Decimal,
+}
+
+/// One point of the portfolio margin grid.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioMarginScenario {
+    /// Underlying price move as a fraction of the current price.
+    pub price_move: Decimal,
+    /// Absolute implied volatility shift.
+    pub volatility_shift: Decimal,
+    /// Loss of the portfolio in the scenario; negative values are gains.
+    pub loss: Decimal,
+}
+
+/// Result of a portfolio margin calculation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioMarginRequirement {
+    /// Every scenario of the grid.
+    pub scenarios: Vec<PortfolioMarginScenario>,
+    /// Largest loss over the grid, floored at zero.
+    pub worst_loss: Decimal,
+    /// Minimum requirement based on the number of option contracts.
+    pub minimum: Decimal,
+    /// Requirement: the greater of `worst_loss` and `minimum`.
+    pub total: Decimal,
+}
+
+/// An option leg being allocated by the Reg-T rules.
+#[derive(Debug, Clone)]
+struct Leg {
+    option: Options,
+    premium: Decimal,
+    quantity: Decimal,
+    days: Decimal,
+}
+
+impl Leg {
+    fn from_position(position: &Position) -> Result<Self, MetricsError> {
+        Ok(Leg {
+            option: position.option.clone(),
+            premium: position.premium.to_dec(),
+            quantity: position.option.quantity.to_dec(),
+            days: position.option.expiration_date.get_days()?.to_dec(),
+        })
+    }
+
+    fn is_short(&self) -> bool {
+        self.option.side == Side::Short
+    }
+
+    fn strike(&self) -> Decimal {
+        self.option.strike_price.to_dec()
+    }
+
+    fn intrinsic(&self, price: Decimal) -> Decimal {
+        match self.option.option_style {
+            OptionStyle::Call => (price - self.strike()).max(Decimal::ZERO),
+            OptionStyle::Put => (self.strike() - price).max(Decimal::ZERO),
+        }
+    }
+
+    /// Profit at expiration including the premium paid or received.
+    fn payoff(&self, price: Decimal) -> Decimal {
+        let value = self.intrinsic(price) - self.premium;
+        match self.option.side {
+            Side::Long => value * self.quantity,
+            Side::Short => -value * self.quantity,
+        }
+    }
+
+    /// Naked requirement per unit, without the premium received.
+    fn naked_requirement(&self, parameters: &RegTParameters) -> Decimal {
+        let underlying = self.option.underlying_price.to_dec();
+        let (out_of_the_money, minimum) = match self.option.option_style {
+            OptionStyle::Call => (
+                (self.strike() - underlying).max(Decimal::ZERO),
+                parameters.minimum_rate * underlying,
+            ),
+            OptionStyle::Put => (
+                (underlying - self.strike()).max(Decimal::ZERO),
+                parameters.minimum_rate * self.strike(),
+            ),
+        };
+        (parameters.naked_rate * underlying - out_of_the_money).max(minimum) * self.quantity
+    }
+}
+
+/// Moves up to `available` units of the short legs selected by `matches` out of `legs`,
+/// in the order the legs are sorted, and returns them.
+fn take_shorts(legs: &mut [Leg], available: Decimal, matches: impl Fn(&Leg) -> bool) -> Vec<Leg> {
+    let mut remaining = available;
+    let mut taken = Vec::new();
+    for leg in legs.iter_mut().filter(|l| l.is_short() && matches(l)) {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let quantity = leg.quantity.min(remaining);
+        let mut part = leg.clone();
+        part.quantity = quantity;
+        leg.quantity -= quantity;
+        remaining -= quantity;
+        taken.push(part);
+    }
+    taken
+}
+
+/// Margin requirements for anything that holds option positions.
+///
+/// The trait is implemented for every [`Positionable`] type, so all strategies can
+/// report the capital they tie up, either with the Reg-T strategy-based rules or with
+/// a risk-based portfolio margin.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::risk::MarginCalculator;
+/// use optionstratlib::strategies::BullCallSpread;
+/// use optionstratlib::{pos, ExpirationDate};
+/// use rust_decimal::Decimal;
+/// use rust_decimal_macros::dec;
+///
+/// let spread = BullCallSpread::new(
+///     "SP500".to_string(),
+///     pos!(5780.0),
+///     pos!(5750.0),
+///     pos!(5820.0),
+///     ExpirationDate::Days(pos!(2.0)),
+///     pos!(0.18),
+///     dec!(0.05),
+///     Default::default(),
+///     pos!(1.0),
+///     pos!(85.04),
+///     pos!(29.85),
+///     pos!(0.78),
+///     pos!(0.78),
+///     pos!(0.73),
+///     pos!(0.73),
+/// );
+///
+/// // A debit spread ties up exactly the debit paid.
+/// let margin = spread.reg_t_margin(&Default::default()).unwrap();
+/// assert_eq!(margin.total, dec!(55.19));
+/// ```
+pub trait MarginCalculator: Positionable {
+    /// Calculates the Reg-T strategy-based requirement.
+    ///
+    /// The rules are applied in this order:
+    /// 1. Short calls are covered by long shares and short puts by short shares; the
+    ///    underlying carries `underlying_rate` and the covered premium is applied
+    ///    against it.
+    /// 2. Short options not matched by at least as many long options of the same style
+    ///    expiring no earlier are naked and charged `max(naked_rate * S - OTM,
+    ///    minimum_rate * base)`. When both calls and puts are naked only the greater
+    ///    side is charged, as for short straddles and strangles.
+    /// 3. The remaining legs (verticals, iron condors, butterflies, calendars) are
+    ///    charged their maximum loss at expiration, which is the net debit for debit
+    ///    structures and the width minus the credit for credit structures.
+    /// 4. Long options that cannot hedge any short are paid in full.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the positions cannot be read.
+    fn reg_t_margin(&self, parameters: &RegTParameters) -> Result<MarginRequirement, MetricsError> {
+        let positions = self
+            .get_positions()
+            .map_err(|e| MetricsError::RiskError(e.to_string()))?;
+        let mut legs = positions
+            .iter()
+            .map(|p| Leg::from_position(p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let premium = |side: Side| -> Decimal {
+            legs.iter()
+                .filter(|l| l.option.side == side)
+                .map(|l| l.premium * l.quantity)
+                .sum()
+        };
+        let premium_paid = premium(Side::Long);
+        let premium_received = premium(Side::Short);
+
+        // 1. Coverage by the underlying
+        let underlying = self.get_underlying_position();
+        let underlying_requirement = underlying
+            .map(|u| parameters.underlying_rate * u.price * u.quantity)
+            .unwrap_or(Decimal::ZERO);
+        let covered = match underlying {
+            Some(UnderlyingPosition {
+                side: Side::Long,
+                quantity,
+                ..
+            }) => {
+                legs.sort_by_key(|l| l.strike());
+                take_shorts(&mut legs, quantity.to_dec(), |l| {
+                    l.option.option_style == OptionStyle::Call
+                })
+            }
+            Some(UnderlyingPosition {
+                side: Side::Short,
+                quantity,
+                ..
+            }) => {
+                legs.sort_by_key(|l| std::cmp::Reverse(l.strike()));
+                take_shorts(&mut legs, quantity.to_dec(), |l| {
+                    l.option.option_style == OptionStyle::Put
+                })
+            }
+            None => Vec::new(),
+        };
+        let covered_premium: Decimal = covered.iter().map(|l| l.premium * l.quantity).sum();
+
+        // 2. Naked shorts and long options that cannot hedge
+        let mut naked_by_style = [Decimal::ZERO, Decimal::ZERO];
+        let mut long_premium = Decimal::ZERO;
+        let mut hedged: Vec<Leg> = Vec::new();
+        for (index, style) in [OptionStyle::Call, OptionStyle::Put]
+            .into_iter()
+            .enumerate()
+        {
+            let mut style_legs: Vec<Leg> = legs
+                .iter()
+                .filter(|l| l.option.option_style == style && l.quantity > Decimal::ZERO)
+                .cloned()
+                .collect();
+            let first_short_expiry = style_legs
+                .iter()
+                .filter(|l| l.is_short())
+                .map(|l| l.days)
+                .min();
+            let (orphans, eligible): (Vec<Leg>, Vec<Leg>) = style_legs
+                .drain(..)
+                .partition(|l| !l.is_short() && first_short_expiry.is_none_or(|d| l.days < d));
+            style_legs = eligible;
+            long_premium += orphans
+                .iter()
+                .map(|l| l.premium * l.quantity)
+                .sum::<Decimal>();
+
+            let quantity = |short: bool| -> Decimal {
+                style_legs
+                    .iter()
+                    .filter(|l| l.is_short() == short)
+                    .map(|l| l.quantity)
+                    .sum()
+            };
+            let excess = (quantity(true) - quantity(false)).max(Decimal::ZERO);
+            // The shorts left naked are the ones closest to (or deepest) in the money.
+            match style {
+                OptionStyle::Call => style_legs.sort_by_key(|l| l.strike()),
+                OptionStyle::Put => style_legs.sort_by_key(|l| std::cmp::Reverse(l.strike())),
+            }
+            let naked = take_shorts(&mut style_legs, excess, |_| true);
+            naked_by_style[index] = naked.iter().map(|l| l.naked_requirement(parameters)).sum();
+            hedged.extend(
+                style_legs
+                    .into_iter()
+                    .filter(|l| l.quantity > Decimal::ZERO),
+            );
+        }
+        let [naked_calls, naked_puts] = naked_by_style;
+        let naked_requirement = if naked_calls > Decimal::ZERO && naked_puts > Decimal::ZERO {
+            naked_calls.max(naked_puts)
+        } else {
+            naked_calls + naked_puts
+        };
+
+        // 3. Maximum loss of the hedged legs. Every short is matched by a long of the
+        //    same style, so the payoff cannot fall beyond its value at a strike or at zero.
+        let spread_requirement = if hedged.is_empty() {
+            Decimal::ZERO
+        } else {
+            std::iter::once(Decimal::ZERO)
+                .chain(hedged.iter().map(|l| l.strike()))
+                .map(|price| hedged.iter().map(|l| l.payoff(price)).sum::<Decimal>())
+                .fold(Decimal::ZERO, |worst, payoff| worst.max(-payoff))
+        };
+
+        let total =
+            (underlying_requirement + spread_requirement + naked_requirement + long_premium
+                - covered_premium)
+                .max(Decimal::ZERO);
+        Ok(MarginRequirement {
+            underlying_requirement,
+            spread_requirement,
+            naked_requirement,
+            long_premium,
+            covered_premium,
+            premium_paid,
+            premium_received,
+            total,
+        })
+    }
+
+    /// Calculates an OCC/TIMS-style portfolio margin.
+    ///
+    /// Every option is revalued with Black-Scholes at each point of the price grid and
+    /// for each volatility shift; the underlying position, if any, moves linearly. The
+    /// requirement is the largest loss over the grid, but never less than
+    /// `minimum_per_contract` per unit of option quantity.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the positions cannot be read or priced.
+    fn portfolio_margin(
+        &self,
+        parameters: &PortfolioMarginParameters,
+    ) -> Result<PortfolioMarginRequirement, MetricsError> {
+        let positions = self
+            .get_positions()
+            .map_err(|e| MetricsError::RiskError(e.to_string()))?;
+        let price = |option: &Options| -> Result<Decimal, MetricsError> {
+            option
+                .calculate_price_black_scholes()
+                .map_err(|e| MetricsError::RiskError(e.to_string()))
+        };
+        let current_values = positions
+            .iter()
+            .map(|p| price(&p.option))
+            .collect::<Result<Vec<_>, _>>()?;
+        let underlying = self.get_underlying_position();
+
+        let mut scenarios = Vec::new();
+        for price_move in parameters.price_moves() {
+            for &volatility_shift in &parameters.volatility_shifts {
+                let mut loss = Decimal::ZERO;
+                for (position, current) in positions.iter().zip(&current_values) {
+                    let option = &position.option;
+                    let mut shifted = option.clone();
+                    shifted.underlying_price = Positive::from(
+                        (option.underlying_price.to_dec() * (Decimal::ONE + price_move))
+                            .max(Decimal::ZERO),
+                    );
+                    shifted.implied_volatility = Positive::from(
+                        (option.implied_volatility.to_dec() + volatility_shift)
+                            .max(MIN_SCENARIO_VOLATILITY),
+                    );
+                    loss += (*current - price(&shifted)?) * option.quantity;
+                }
+                if let Some(underlying) = underlying {
+                    loss -= underlying.delta() * underlying.price * price_move;
+                }
+                scenarios.push(PortfolioMarginScenario {
+                    price_move,
+                    volatility_shift,
+                    loss,
+                });
+            }
+        }
+
+        let worst_loss = scenarios
+            .iter()
+            .fold(Decimal::ZERO, |worst, s| worst.max(s.loss));
+        let contracts: Decimal = positions.iter().map(|p| p.option.quantity.to_dec()).sum();
+        let minimum = parameters.minimum_per_contract * contracts;
+        Ok(PortfolioMarginRequirement {
+            scenarios,
+            worst_loss,
+            minimum,
+            total: worst_loss.max(minimum),
+        })
+    }
+
+    /// Total requirement under `method`.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the positions cannot be read or priced.
+    fn margin_requirement(&self, method: &MarginMethod) -> Result<Decimal, MetricsError> {
+        match method {
+            MarginMethod::RegT(parameters) => Ok(self.reg_t_margin(parameters)?.total),
+            MarginMethod::Portfolio(parameters) => Ok(self.portfolio_margin(parameters)?.total),
+        }
+    }
+
+    /// Buying-power requirement under the default Reg-T rates.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the positions cannot be read.
+    fn buying_power_requirement(&self) -> Result<Decimal, MetricsError> {
+        Ok(self.reg_t_margin(&RegTParameters::default())?.total)
+    }
+}
+
+impl<T: Positionable + ?Sized> MarginCalculator for T {}
+
+#[cfg(test)]
+mod tests_margin {
+    use super::*;
+    use crate::model::utils::create_sample_option;
+    use crate::strategies::{
+        BullCallSpread, BullPutSpread, CoveredCall, IronCondor, LongButterflySpread, LongCall,
+        ShortPut, ShortStrangle, StrategyConstructor,
+    };
+    use crate::{ExpirationDate, assert_decimal_eq, pos};
+    use chrono::Utc;
+
+    fn days() -> ExpirationDate {
+        ExpirationDate::Days(pos!(30.0))
+    }
+
+    fn single_leg(
+        style: OptionStyle,
+        side: Side,
+        strike: Positive,
+        quantity: Positive,
+        premium: Positive,
+    ) -> Vec<Position> {
+        let option = create_sample_option(style, side, pos!(100.0), quantity, strike, pos!(0.2));
+        vec![Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )]
+    }
+
+    #[test]
+    fn test_naked_short_put() {
+        let strategy = ShortPut::get_strategy(&single_leg(
+            OptionStyle::Put,
+            Side::Short,
+            pos!(95.0),
+            Positive::ONE,
+            pos!(2.0),
+        ))
+        .unwrap();
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        // max(20% * 100 - 5, 10% * 95) = 15
+        assert_eq!(margin.naked_requirement, dec!(15.0));
+        assert_eq!(margin.spread_requirement, Decimal::ZERO);
+        assert_eq!(margin.total, dec!(15.0));
+        assert_eq!(margin.premium_received, dec!(2.0));
+    }
+
+    #[test]
+    fn test_long_call_paid_in_full() {
+        let strategy = LongCall::get_strategy(&single_leg(
+            OptionStyle::Call,
+            Side::Long,
+            pos!(100.0),
+            pos!(2.0),
+            pos!(3.0),
+        ))
+        .unwrap();
+        let margin = strategy.buying_power_requirement().unwrap();
+        assert_eq!(margin, dec!(6.0));
+    }
+
+    #[test]
+    fn test_credit_vertical() {
+        let strategy = BullPutSpread::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(90.0),
+            pos!(95.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.0),
+            pos!(2.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        // width 5 minus credit 1.5
+        assert_eq!(margin.naked_requirement, Decimal::ZERO);
+        assert_eq!(margin.total, dec!(3.5));
+    }
+
+    #[test]
+    fn test_debit_vertical() {
+        let strategy = BullCallSpread::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(105.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(6.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        assert_eq!(margin.total, dec!(4.0));
+    }
+
+    #[test]
+    fn test_iron_condor_charges_one_side() {
+        let strategy = IronCondor::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(105.0),
+            pos!(95.0),
+            pos!(110.0),
+            pos!(90.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(2.0),
+            pos!(2.0),
+            pos!(0.5),
+            pos!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        // widest wing 5 minus total credit 3
+        assert_eq!(margin.total, dec!(2.0));
+    }
+
+    #[test]
+    fn test_long_butterfly_is_net_debit() {
+        let strategy = LongButterflySpread::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(90.0),
+            pos!(100.0),
+            pos!(110.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(12.0),
+            pos!(3.0),
+            pos!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        // 12 + 1 - 2 * 3
+        assert_eq!(margin.total, dec!(7.0));
+        assert_eq!(margin.naked_requirement, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_short_strangle_charges_greater_side() {
+        let strategy = ShortStrangle::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            pos!(95.0),
+            days(),
+            pos!(0.2),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        // call: max(20 - 10, 10) = 10; put: max(20 - 5, 9.5) = 15
+        assert_eq!(margin.naked_requirement, dec!(15.0));
+        assert_eq!(margin.total, dec!(15.0));
+    }
+
+    #[test]
+    fn test_covered_call() {
+        let strategy = CoveredCall::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(10.0),
+            pos!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let margin = strategy.reg_t_margin(&RegTParameters::default()).unwrap();
+        assert_eq!(margin.underlying_requirement, dec!(500.0));
+        assert_eq!(margin.naked_requirement, Decimal::ZERO);
+        assert_eq!(margin.covered_premium, dec!(15.0));
+        assert_eq!(margin.total, dec!(485.0));
+    }
+
+    #[test]
+    fn test_long_expiring_before_short_does_not_hedge() {
+        struct Calendar(Vec<Position>);
+        impl Positionable for Calendar {
+            fn get_positions(&self) -> Result<Vec<&Position>, crate::error::PositionError> {
+                Ok(self.0.iter().collect())
+            }
+        }
+        let mut short = create_sample_option(
+            OptionStyle::Call,
+            Side::Short,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(100.0),
+            pos!(0.2),
+        );
+        short.expiration_date = ExpirationDate::Days(pos!(60.0));
+        let long = create_sample_option(
+            OptionStyle::Call,
+            Side::Long,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(100.0),
+            pos!(0.2),
+        );
+        let position = |option: Options, premium: Positive| {
+            Position::new(
+                option,
+                premium,
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+                None,
+                None,
+            )
+        };
+        let calendar = Calendar(vec![position(short, pos!(4.0)), position(long, pos!(3.0))]);
+        let margin = calendar.reg_t_margin(&RegTParameters::default()).unwrap();
+        assert_eq!(margin.long_premium, dec!(3.0));
+        assert_eq!(margin.naked_requirement, dec!(20.0));
+    }
+
+    #[test]
+    fn test_portfolio_margin_grid() {
+        let strategy = ShortPut::get_strategy(&single_leg(
+            OptionStyle::Put,
+            Side::Short,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(2.0),
+        ))
+        .unwrap();
+        let parameters = PortfolioMarginParameters::default();
+        let margin = strategy.portfolio_margin(&parameters).unwrap();
+        assert_eq!(margin.scenarios.len(), 33);
+        assert_eq!(margin.scenarios[0].price_move, dec!(-0.15));
+        assert_eq!(margin.scenarios[32].price_move, dec!(0.15));
+        let worst = margin
+            .scenarios
+            .iter()
+            .max_by(|a, b| a.loss.cmp(&b.loss))
+            .unwrap();
+        assert_eq!(worst.price_move, dec!(-0.15));
+        assert_eq!(worst.volatility_shift, dec!(0.05));
+        assert!(margin.worst_loss > dec!(10.0));
+        assert_eq!(margin.total, margin.worst_loss);
+    }
+
+    #[test]
+    fn test_margin_requirement_follows_method() {
+        let strategy = ShortPut::get_strategy(&single_leg(
+            OptionStyle::Put,
+            Side::Short,
+            pos!(95.0),
+            Positive::ONE,
+            pos!(2.0),
+        ))
+        .unwrap();
+        let reg_t = RegTParameters::default();
+        let portfolio = PortfolioMarginParameters::default();
+        assert_eq!(
+            strategy
+                .margin_requirement(&MarginMethod::RegT(reg_t.clone()))
+                .unwrap(),
+            strategy.reg_t_margin(&reg_t).unwrap().total
+        );
+        assert_eq!(
+            strategy
+                .margin_requirement(&MarginMethod::Portfolio(portfolio.clone()))
+                .unwrap(),
+            strategy.portfolio_margin(&portfolio).unwrap().total
+        );
+        assert_eq!(
+            strategy
+                .margin_requirement(&MarginMethod::default())
+                .unwrap(),
+            strategy.buying_power_requirement().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_portfolio_margin_minimum_and_underlying() {
+        let strategy = CoveredCall::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(150.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(0.01),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let parameters =
+            PortfolioMarginParameters::new(dec!(0.15), 10, vec![Decimal::ZERO], dec!(20.0));
+        let margin = strategy.portfolio_margin(&parameters).unwrap();
+        // The shares lose 15 on the down move; the far call is worth almost nothing.
+        assert_decimal_eq!(margin.worst_loss, dec!(15.0), dec!(0.01));
+        assert_eq!(margin.minimum, dec!(20.0));
+        assert_eq!(margin.total, dec!(20.0));
+    }
+}
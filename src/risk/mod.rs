@@ -148,6 +148,66 @@
 //!     .collect();
 //! ```
 //!
+//! ### Portfolio SPAN
+//!
+//! `SPANPortfolio` groups options, futures and underlying holdings by combined
+//! commodity and evaluates each group over the standard 16-scenario risk array
+//! (price moves of 0, ±1/3, ±2/3 and ±3/3 of the scanning range with volatility up and
+//! down, plus two extreme moves covered at a fraction). Inter-month spread charges,
+//! inter-commodity credits and the short option minimum are then applied, and every
+//! scenario loss is reported so results can be reconciled against exchange files.
+//!
+//! ```rust
+//! use chrono::Utc;
+//! use rust_decimal_macros::dec;
+//! use optionstratlib::model::UnderlyingPosition;
+//! use optionstratlib::risk::{InterCommoditySpread, SPANPortfolio, SPANRiskParameters};
+//! use optionstratlib::{pos, Side};
+//!
+//! let mut portfolio = SPANPortfolio::new(SPANRiskParameters::default());
+//! let long_spy = UnderlyingPosition::new(
+//!     "SPY".to_string(), Side::Long, pos!(10.0), pos!(500.0), Utc::now(), pos!(0.0), pos!(0.0),
+//! );
+//! let short_qqq = UnderlyingPosition::new(
+//!     "QQQ".to_string(), Side::Short, pos!(12.0), pos!(420.0), Utc::now(), pos!(0.0), pos!(0.0),
+//! );
+//! portfolio.add_underlying(long_spy, pos!(500.0));
+//! portfolio.add_underlying(short_qqq, pos!(420.0));
+//! portfolio.add_inter_commodity_spread(InterCommoditySpread::new(
+//!     "SPY".to_string(), "QQQ".to_string(), pos!(5.0), pos!(6.0), dec!(0.6),
+//! ).unwrap());
+//!
+//! let result = portfolio.calculate_margin().unwrap();
+//! for commodity in &result.commodities {
+//!     println!("{}: scan risk {} margin {}", commodity.commodity, commodity.scan_risk, commodity.margin);
+//! }
+//! ```
+//!
+//! ### Strategy Margin
+//!
+//! `MarginCalculator` is implemented for every `Positionable` type and reports the
+//! buying-power requirement of a strategy, either with the Reg-T strategy-based rules
+//! (naked shorts, verticals, iron condors, butterflies and covered positions) or with an
+//! OCC/TIMS-style portfolio margin that revalues the positions over a ±15% price grid
+//! combined with volatility shifts. `MarginMethod` selects between the two, and the
+//! backtest engine uses it to size the margin behind `return_on_margin`.
+//!
+//! ### Value-at-Risk
+//!
+//! `VaRCalculator` is implemented for every `Strategies + Greeks` type and computes
+//! Value-at-Risk and Expected Shortfall over a configurable horizon and confidence,
+//! using historical simulation on `OhlcvCandle` closes, a parametric delta-gamma
+//! approximation, or a full-revaluation Monte Carlo driven by a `Simulator`. The
+//! resulting `VaRReport` can fill `RiskMetricsSimulation` and `AdvancedRiskMetrics`.
+//!
+//! ### Stress Testing
+//!
+//! `StressTester` revalues any `Strategies + Greeks` type under `StressScenario`s that
+//! combine spot, parallel and skew-tilted volatility, time and rate shocks. A
+//! `StressGrid` expands ranges of shocks into scenarios, and the resulting
+//! `StressReport` can be turned into a `Surface` of P&L or Greeks. Built-in
+//! scenarios replay the 1987, 2008 and 2020 sell-offs.
+//!
 //! ## Implementation Details
 //!
 //! ### Risk Array Calculation
@@ -179,8 +239,24 @@
 //! - Short option minimum is always enforced for short positions
 //! - Results are conservative estimates of potential losses
 
+mod margin;
 mod model;
 mod span;
+mod span_portfolio;
+mod stress;
+mod var;
 
+pub use margin::{
+    MarginCalculator, MarginMethod, MarginRequirement, PortfolioMarginParameters,
+    PortfolioMarginRequirement, PortfolioMarginScenario, RegTParameters,
+};
 pub use model::{RiskCategory, RiskMetricsSimulation};
 pub use span::SPANMargin;
+pub use span_portfolio::{
+    InterCommoditySpread, SPANCommodityResult, SPANInstrument, SPANPortfolio, SPANPortfolioResult,
+    SPANRiskParameters, SPANScenario, SPANScenarioLoss,
+};
+pub use stress::{
+    StressGrid, StressMetric, StressReport, StressResult, StressScenario, StressTester,
+};
+pub use var::{VaRCalculator, VaRMethod, VaRParameters, VaRReport};
@@ -106,8 +106,7 @@ impl SPANMargin {
         let short_option_minimum = self.calculate_short_option_minimum(position);
         risk_array
             .into_iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap())
-            .unwrap()
+            .fold(Decimal::ZERO, Decimal::max)
             .max(short_option_minimum)
     }
 
@@ -199,9 +198,9 @@ impl SPANMargin {
         ]
     }
 
-    /// Calculates the potential profit or loss for a position in a given price and volatility scenario.
+    /// Calculates the potential loss for a position in a given price and volatility scenario.
     ///
-    /// This function computes how the value of an option position would change under different
+    /// This function computes how much value an option position would lose under different
     /// market conditions by comparing the current option price with the theoretical price in the scenario.
     ///
     /// # Arguments
@@ -210,9 +209,9 @@ impl SPANMargin {
     /// * `scenario_volatility` - The hypothetical implied volatility level in the scenario
     ///
     /// # Returns
-    /// A `Decimal` representing the profit (positive) or loss (negative) based on the scenario.
-    /// For long positions, a higher scenario price results in positive returns.
-    /// For short positions, the sign is flipped (losses when scenario price increases).
+    /// A `Decimal` representing the loss (positive) or gain (negative) in the scenario.
+    /// The Black-Scholes price is already signed by the position side, so a short call
+    /// loses when the scenario price increases and a long call gains.
     ///
     fn calculate_scenario_loss(
         &self,
@@ -226,13 +225,7 @@ impl SPANMargin {
         scenario_option.underlying_price = scenario_price;
         scenario_option.implied_volatility = scenario_volatility;
         let scenario_price = scenario_option.calculate_price_black_scholes().unwrap();
-        (scenario_price - current_price)
-            * option.quantity
-            * if option.is_short() {
-                Decimal::NEGATIVE_ONE
-            } else {
-                Decimal::ONE
-            }
+        (current_price - scenario_price) * option.quantity
     }
 
     /// Calculates the minimum margin requirement for short option positions.
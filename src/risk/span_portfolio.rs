@@ -0,0 +1,859 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::error::MetricsError;
+use crate::greeks::delta;
+use crate::model::UnderlyingPosition;
+use crate::model::position::Position;
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Lowest implied volatility used when a scenario shifts volatility down.
+const MIN_SCENARIO_VOLATILITY: Decimal = dec!(0.0001);
+
+/// Label of the tier that holds positions without an expiration (cash underlying).
+const SPOT_TIER: &str = "SPOT";
+
+/// Risk parameters of a combined commodity, as published in an exchange risk
+/// parameter file.
+///
+/// All ranges are decimals: `price_scan_range` is a fraction of the underlying price
+/// (0.10 for a 10% move) and `volatility_scan_range` is an absolute change in implied
+/// volatility (0.04 for four volatility points).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SPANRiskParameters {
+    /// Price scanning range as a fraction of the underlying price.
+    pub price_scan_range: Decimal,
+    /// Volatility scanning range as an absolute shift of implied volatility.
+    pub volatility_scan_range: Decimal,
+    /// Minimum charge per short option, as a fraction of the underlying value.
+    pub short_option_minimum: Decimal,
+    /// Charge per unit of delta spread between two expiration months.
+    pub inter_month_spread_charge: Decimal,
+    /// Size of the extreme moves expressed in multiples of the price scanning range.
+    pub extreme_move_multiplier: Decimal,
+    /// Fraction of the extreme move loss that is included in the risk array.
+    pub extreme_move_cover: Decimal,
+}
+
+impl SPANRiskParameters {
+    /// Creates the risk parameters of a combined commodity.
+    ///
+    /// # Parameters
+    /// * `price_scan_range` - Price scanning range as a fraction of the underlying price
+    /// * `volatility_scan_range` - Absolute implied volatility shift
+    /// * `short_option_minimum` - Minimum charge per short option as a fraction of the underlying value
+    /// * `inter_month_spread_charge` - Charge per unit of delta spread across months
+    /// * `extreme_move_multiplier` - Extreme move size in multiples of the price scan range
+    /// * `extreme_move_cover` - Fraction of the extreme move loss that is charged
+    pub fn new(
+        price_scan_range: Decimal,
+        volatility_scan_range: Decimal,
+        short_option_minimum: Decimal,
+        inter_month_spread_charge: Decimal,
+        extreme_move_multiplier: Decimal,
+        extreme_move_cover: Decimal,
+    ) -> Self {
+        SPANRiskParameters {
+            price_scan_range,
+            volatility_scan_range,
+            short_option_minimum,
+            inter_month_spread_charge,
+            extreme_move_multiplier,
+            extreme_move_cover,
+        }
+    }
+
+    /// Builds the standard 16-scenario SPAN risk array definition.
+    ///
+    /// Scenarios 1 to 14 move the price by 0, ±1/3, ±2/3 and ±3/3 of the scanning range,
+    /// each combined with volatility up and down. Scenarios 15 and 16 are the extreme
+    /// moves, with volatility unchanged and only `extreme_move_cover` of the loss counted.
+    pub fn scenarios(&self) -> Vec<SPANScenario> {
+        let third = Decimal::ONE / dec!(3);
+        let mut scenarios = Vec::with_capacity(16);
+        for price_move in [
+            Decimal::ZERO,
+            third,
+            -third,
+            third * dec!(2),
+            -third * dec!(2),
+            Decimal::ONE,
+            -Decimal::ONE,
+        ] {
+            for volatility_move in [Decimal::ONE, Decimal::NEGATIVE_ONE] {
+                scenarios.push(SPANScenario {
+                    id: scenarios.len() + 1,
+                    price_move,
+                    volatility_move,
+                    cover: Decimal::ONE,
+                });
+            }
+        }
+        for price_move in [self.extreme_move_multiplier, -self.extreme_move_multiplier] {
+            scenarios.push(SPANScenario {
+                id: scenarios.len() + 1,
+                price_move,
+                volatility_move: Decimal::ZERO,
+                cover: self.extreme_move_cover,
+            });
+        }
+        scenarios
+    }
+}
+
+impl Default for SPANRiskParameters {
+    /// 15% price range, 4 volatility points, 1% short option minimum, no spread
+    /// charge and the usual 3x extreme moves covered at 35%.
+    fn default() -> Self {
+        SPANRiskParameters::new(
+            dec!(0.15),
+            dec!(0.04),
+            dec!(0.01),
+            Decimal::ZERO,
+            dec!(3.0),
+            dec!(0.35),
+        )
+    }
+}
+
+/// One market scenario of a SPAN risk array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SPANScenario {
+    /// Scenario number, from 1 to 16 for the standard risk array.
+    pub id: usize,
+    /// Price move in multiples of the price scanning range.
+    pub price_move: Decimal,
+    /// Volatility move in multiples of the volatility scanning range (-1, 0 or 1).
+    pub volatility_move: Decimal,
+    /// Fraction of the scenario loss that is counted.
+    pub cover: Decimal,
+}
+
+/// A credit granted between two combined commodities whose positions offset each other.
+///
+/// One spread is made of `first_delta` units of net delta in `first` against
+/// `second_delta` units of opposite net delta in `second`. Each commodity receives
+/// `credit_rate` of the price risk carried by the deltas consumed by the spreads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterCommoditySpread {
+    /// First combined commodity of the spread.
+    pub first: String,
+    /// Second combined commodity of the spread.
+    pub second: String,
+    /// Delta of the first commodity in one spread.
+    pub first_delta: Positive,
+    /// Delta of the second commodity in one spread.
+    pub second_delta: Positive,
+    /// Credit granted as a fraction of the price risk of the spread legs.
+    pub credit_rate: Decimal,
+}
+
+impl InterCommoditySpread {
+    /// Creates a new inter-commodity spread definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MetricsError::RangeError` if either delta is zero.
+    pub fn new(
+        first: String,
+        second: String,
+        first_delta: Positive,
+        second_delta: Positive,
+        credit_rate: Decimal,
+    ) -> Result<Self, MetricsError> {
+        if first_delta.is_zero() || second_delta.is_zero() {
+            return Err(MetricsError::RangeError(format!(
+                "Inter-commodity spread {first}/{second} needs positive deltas, \
+                 got {first_delta} and {second_delta}"
+            )));
+        }
+        Ok(InterCommoditySpread {
+            first,
+            second,
+            first_delta,
+            second_delta,
+            credit_rate,
+        })
+    }
+}
+
+/// An instrument held in a SPAN portfolio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum SPANInstrument {
+    /// An option position, revalued with Black-Scholes in every scenario.
+    Option(Box<Position>),
+    /// A futures position on the underlying, marked at `settlement_price`.
+    Future {
+        /// The futures holding.
+        position: UnderlyingPosition,
+        /// Expiration of the futures contract.
+        expiration: ExpirationDate,
+        /// Current settlement price of the contract.
+        settlement_price: Positive,
+    },
+    /// A holding in the underlying itself, marked at `price`.
+    Underlying {
+        /// The underlying holding.
+        position: UnderlyingPosition,
+        /// Current market price of the underlying.
+        price: Positive,
+    },
+}
+
+impl SPANInstrument {
+    /// Combined commodity the instrument belongs to: its underlying symbol.
+    pub fn commodity(&self) -> &str {
+        match self {
+            SPANInstrument::Option(position) => &position.option.underlying_symbol,
+            SPANInstrument::Future { position, .. }
+            | SPANInstrument::Underlying { position, .. } => &position.symbol,
+        }
+    }
+
+    /// Expiration month (`YYYY-MM`) used to group deltas into tiers.
+    fn tier(&self) -> Result<String, MetricsError> {
+        let expiration = match self {
+            SPANInstrument::Option(position) => &position.option.expiration_date,
+            SPANInstrument::Future { expiration, .. } => expiration,
+            SPANInstrument::Underlying { .. } => return Ok(SPOT_TIER.to_string()),
+        };
+        Ok(expiration.get_date()?.format("%Y-%m").to_string())
+    }
+
+    /// Signed delta of the instrument in units of the underlying.
+    pub fn delta(&self) -> Result<Decimal, MetricsError> {
+        match self {
+            SPANInstrument::Option(position) => delta(&position.option)
+                .map_err(|e| MetricsError::RiskError(format!("Delta calculation failed: {}", e))),
+            SPANInstrument::Future { position, .. }
+            | SPANInstrument::Underlying { position, .. } => Ok(position.delta()),
+        }
+    }
+
+    /// Loss of the instrument in `scenario`: current value minus scenario value,
+    /// before the scenario cover is applied.
+    fn scenario_loss(
+        &self,
+        scenario: &SPANScenario,
+        parameters: &SPANRiskParameters,
+    ) -> Result<Decimal, MetricsError> {
+        let price_factor = Decimal::ONE + scenario.price_move * parameters.price_scan_range;
+        match self {
+            SPANInstrument::Option(position) => {
+                let option = &position.option;
+                let current_value = option
+                    .calculate_price_black_scholes()
+                    .map_err(|e| MetricsError::RiskError(e.to_string()))?;
+                let mut shifted = option.clone();
+                shifted.underlying_price = Positive::from(
+                    (option.underlying_price.to_dec() * price_factor).max(Decimal::ZERO),
+                );
+                let volatility = option.implied_volatility.to_dec()
+                    + scenario.volatility_move * parameters.volatility_scan_range;
+                shifted.implied_volatility =
+                    Positive::from(volatility.max(MIN_SCENARIO_VOLATILITY));
+                let scenario_value = shifted
+                    .calculate_price_black_scholes()
+                    .map_err(|e| MetricsError::RiskError(e.to_string()))?;
+                Ok((current_value - scenario_value) * option.quantity)
+            }
+            SPANInstrument::Future {
+                position,
+                settlement_price: price,
+                ..
+            }
+            | SPANInstrument::Underlying { position, price } => {
+                let price_change = price.to_dec() * (price_factor - Decimal::ONE);
+                Ok(-position.delta() * price_change)
+            }
+        }
+    }
+
+    /// Short option minimum charge contributed by the instrument.
+    fn short_option_minimum(&self, parameters: &SPANRiskParameters) -> Decimal {
+        match self {
+            SPANInstrument::Option(position) if position.option.is_short() => {
+                parameters.short_option_minimum
+                    * position.option.underlying_price
+                    * position.option.quantity
+            }
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+/// Loss of a combined commodity in one scenario of its risk array.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SPANScenarioLoss {
+    /// The scenario evaluated.
+    pub scenario: SPANScenario,
+    /// Loss after cover; negative values are gains.
+    pub loss: Decimal,
+}
+
+/// Margin breakdown of a single combined commodity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SPANCommodityResult {
+    /// Combined commodity name.
+    pub commodity: String,
+    /// Losses of the commodity in every scenario of the risk array.
+    pub risk_array: Vec<SPANScenarioLoss>,
+    /// Largest loss of the risk array, floored at zero.
+    pub scan_risk: Decimal,
+    /// Net delta per expiration tier, ordered by tier.
+    pub tier_deltas: Vec<(String, Decimal)>,
+    /// Net delta of the combined commodity.
+    pub net_delta: Decimal,
+    /// Charge for delta spread between expiration months.
+    pub inter_month_spread_charge: Decimal,
+    /// Credit received from inter-commodity spreads.
+    pub inter_commodity_credit: Decimal,
+    /// Short option minimum charge.
+    pub short_option_minimum: Decimal,
+    /// Final requirement: `max(scan_risk + inter_month - credit, short_option_minimum)`.
+    pub margin: Decimal,
+}
+
+/// Margin requirement of a whole SPAN portfolio.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SPANPortfolioResult {
+    /// Breakdown per combined commodity, ordered by name.
+    pub commodities: Vec<SPANCommodityResult>,
+    /// Sum of the requirements of all combined commodities.
+    pub total_margin: Decimal,
+}
+
+impl SPANPortfolioResult {
+    /// Returns the breakdown of `commodity`, if it is part of the portfolio.
+    pub fn commodity(&self, commodity: &str) -> Option<&SPANCommodityResult> {
+        self.commodities.iter().find(|c| c.commodity == commodity)
+    }
+}
+
+/// Portfolio-level SPAN margin engine.
+///
+/// Positions are grouped by combined commodity (their underlying symbol). Each group
+/// is revalued over the standard 16-scenario risk array built from its
+/// [`SPANRiskParameters`], then inter-month spread charges, inter-commodity credits and
+/// the short option minimum are applied to obtain the requirement.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::Utc;
+/// use optionstratlib::model::position::Position;
+/// use optionstratlib::model::utils::create_sample_option;
+/// use optionstratlib::risk::{SPANPortfolio, SPANRiskParameters};
+/// use optionstratlib::{pos, OptionStyle, Side};
+///
+/// let option = create_sample_option(
+///     OptionStyle::Call,
+///     Side::Short,
+///     pos!(100.0),
+///     pos!(1.0),
+///     pos!(105.0),
+///     pos!(0.2),
+/// );
+/// let position = Position::new(option, pos!(2.0), Utc::now(), pos!(0.0), pos!(0.0), None, None);
+///
+/// let mut portfolio = SPANPortfolio::new(SPANRiskParameters::default());
+/// portfolio.add_position(position);
+///
+/// let result = portfolio.calculate_margin().unwrap();
+/// assert_eq!(result.commodities[0].risk_array.len(), 16);
+/// assert!(result.total_margin > rust_decimal::Decimal::ZERO);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SPANPortfolio {
+    /// Instruments held in the portfolio.
+    pub instruments: Vec<SPANInstrument>,
+    /// Parameters applied to commodities without specific parameters.
+    pub default_parameters: SPANRiskParameters,
+    /// Risk parameters per combined commodity.
+    pub parameters: BTreeMap<String, SPANRiskParameters>,
+    /// Inter-commodity spreads, applied in priority order.
+    pub inter_commodity_spreads: Vec<InterCommoditySpread>,
+}
+
+impl SPANPortfolio {
+    /// Creates an empty portfolio using `default_parameters` for every commodity.
+    pub fn new(default_parameters: SPANRiskParameters) -> Self {
+        SPANPortfolio {
+            default_parameters,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the risk parameters of a combined commodity.
+    pub fn set_parameters(&mut self, commodity: &str, parameters: SPANRiskParameters) {
+        self.parameters.insert(commodity.to_string(), parameters);
+    }
+
+    /// Adds an inter-commodity spread; spreads are applied in the order they are added.
+    pub fn add_inter_commodity_spread(&mut self, spread: InterCommoditySpread) {
+        self.inter_commodity_spreads.push(spread);
+    }
+
+    /// Adds an option position.
+    pub fn add_position(&mut self, position: Position) {
+        self.instruments
+            .push(SPANInstrument::Option(Box::new(position)));
+    }
+
+    /// Adds a futures position with its expiration and current settlement price.
+    pub fn add_future(
+        &mut self,
+        position: UnderlyingPosition,
+        expiration: ExpirationDate,
+        settlement_price: Positive,
+    ) {
+        self.instruments.push(SPANInstrument::Future {
+            position,
+            expiration,
+            settlement_price,
+        });
+    }
+
+    /// Adds a holding in the underlying asset marked at `price`.
+    pub fn add_underlying(&mut self, position: UnderlyingPosition, price: Positive) {
+        self.instruments
+            .push(SPANInstrument::Underlying { position, price });
+    }
+
+    fn parameters_for(&self, commodity: &str) -> &SPANRiskParameters {
+        self.parameters
+            .get(commodity)
+            .unwrap_or(&self.default_parameters)
+    }
+
+    /// Calculates the SPAN requirement of the portfolio.
+    ///
+    /// # Returns
+    /// The per-commodity breakdown, including the full risk array of each combined
+    /// commodity, and the total requirement. An empty portfolio requires no margin.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if an option cannot be priced or an
+    /// expiration date cannot be resolved.
+    pub fn calculate_margin(&self) -> Result<SPANPortfolioResult, MetricsError> {
+        let mut groups: BTreeMap<&str, Vec<&SPANInstrument>> = BTreeMap::new();
+        for instrument in &self.instruments {
+            groups
+                .entry(instrument.commodity())
+                .or_default()
+                .push(instrument);
+        }
+
+        let mut commodities = Vec::with_capacity(groups.len());
+        for (commodity, instruments) in groups {
+            commodities.push(self.commodity_result(commodity, &instruments)?);
+        }
+        self.apply_inter_commodity_credits(&mut commodities);
+
+        let total_margin = commodities.iter().map(|c| c.margin).sum();
+        Ok(SPANPortfolioResult {
+            commodities,
+            total_margin,
+        })
+    }
+
+    fn commodity_result(
+        &self,
+        commodity: &str,
+        instruments: &[&SPANInstrument],
+    ) -> Result<SPANCommodityResult, MetricsError> {
+        let parameters = self.parameters_for(commodity);
+
+        let mut risk_array = Vec::with_capacity(16);
+        for scenario in parameters.scenarios() {
+            let mut loss = Decimal::ZERO;
+            for instrument in instruments {
+                loss += instrument.scenario_loss(&scenario, parameters)?;
+            }
+            risk_array.push(SPANScenarioLoss {
+                loss: loss * scenario.cover,
+                scenario,
+            });
+        }
+        let scan_risk = risk_array
+            .iter()
+            .fold(Decimal::ZERO, |acc, s| acc.max(s.loss));
+
+        let mut tiers: BTreeMap<String, Decimal> = BTreeMap::new();
+        for instrument in instruments {
+            *tiers.entry(instrument.tier()?).or_default() += instrument.delta()?;
+        }
+        let net_delta: Decimal = tiers.values().sum();
+        // Spot holdings have no month, so they offset the scan risk but never form a
+        // calendar spread
+        let month_deltas = tiers
+            .iter()
+            .filter(|(tier, _)| tier.as_str() != SPOT_TIER)
+            .map(|(_, delta)| *delta);
+        let long_delta: Decimal = month_deltas.clone().filter(|d| *d > Decimal::ZERO).sum();
+        let short_delta: Decimal = -month_deltas.filter(|d| *d < Decimal::ZERO).sum::<Decimal>();
+        let inter_month_spread_charge =
+            long_delta.min(short_delta) * parameters.inter_month_spread_charge;
+
+        let short_option_minimum = instruments
+            .iter()
+            .map(|i| i.short_option_minimum(parameters))
+            .sum();
+
+        let mut result = SPANCommodityResult {
+            commodity: commodity.to_string(),
+            risk_array,
+            scan_risk,
+            net_delta,
+            tier_deltas: tiers.into_iter().collect(),
+            inter_month_spread_charge,
+            inter_commodity_credit: Decimal::ZERO,
+            short_option_minimum,
+            margin: Decimal::ZERO,
+        };
+        result.margin = Self::requirement(&result);
+        Ok(result)
+    }
+
+    fn requirement(result: &SPANCommodityResult) -> Decimal {
+        (result.scan_risk + result.inter_month_spread_charge - result.inter_commodity_credit)
+            .max(result.short_option_minimum)
+            .max(Decimal::ZERO)
+    }
+
+    /// Grants the inter-commodity credits in priority order. Each spread consumes net
+    /// delta of opposite signs from both commodities, so the same delta is never
+    /// credited twice.
+    fn apply_inter_commodity_credits(&self, commodities: &mut [SPANCommodityResult]) {
+        let mut remaining: Vec<Decimal> = commodities.iter().map(|c| c.net_delta.abs()).collect();
+        for spread in &self.inter_commodity_spreads {
+            let first = commodities.iter().position(|c| c.commodity == spread.first);
+            let second = commodities
+                .iter()
+                .position(|c| c.commodity == spread.second);
+            let (Some(i), Some(j)) = (first, second) else {
+                continue;
+            };
+            // Deserialized spreads bypass the delta check of `new`
+            if i == j
+                || spread.first_delta.is_zero()
+                || spread.second_delta.is_zero()
+                || commodities[i].net_delta * commodities[j].net_delta >= Decimal::ZERO
+            {
+                continue;
+            }
+            let spreads =
+                (remaining[i] / spread.first_delta).min(remaining[j] / spread.second_delta);
+            if spreads <= Decimal::ZERO {
+                continue;
+            }
+            for (k, legs) in [
+                (i, spreads * spread.first_delta),
+                (j, spreads * spread.second_delta),
+            ] {
+                let risk_per_delta = commodities[k].scan_risk / commodities[k].net_delta.abs();
+                commodities[k].inter_commodity_credit += legs * risk_per_delta * spread.credit_rate;
+                remaining[k] -= legs;
+            }
+        }
+        for commodity in commodities.iter_mut() {
+            commodity.margin = Self::requirement(commodity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_span_portfolio {
+    use super::*;
+    use crate::model::types::{OptionStyle, Side};
+    use crate::model::utils::create_sample_option;
+    use crate::{assert_decimal_eq, pos};
+    use chrono::Utc;
+
+    fn option_position(style: OptionStyle, side: Side, strike: Positive) -> Position {
+        let option = create_sample_option(style, side, pos!(100.0), pos!(1.0), strike, pos!(0.2));
+        Position::new(
+            option,
+            pos!(2.0),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    fn shares(symbol: &str, side: Side, quantity: Positive) -> UnderlyingPosition {
+        UnderlyingPosition::new(
+            symbol.to_string(),
+            side,
+            quantity,
+            pos!(100.0),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn flat_parameters() -> SPANRiskParameters {
+        SPANRiskParameters::new(
+            dec!(0.1),
+            dec!(0.04),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            dec!(3.0),
+            dec!(0.35),
+        )
+    }
+
+    #[test]
+    fn test_standard_scenarios() {
+        let scenarios = SPANRiskParameters::default().scenarios();
+        assert_eq!(scenarios.len(), 16);
+        assert_eq!(scenarios[0].price_move, Decimal::ZERO);
+        assert_eq!(scenarios[0].volatility_move, Decimal::ONE);
+        assert_eq!(scenarios[1].volatility_move, Decimal::NEGATIVE_ONE);
+        assert_eq!(scenarios[10].price_move, Decimal::ONE);
+        assert_eq!(scenarios[14].price_move, dec!(3.0));
+        assert_eq!(scenarios[15].price_move, dec!(-3.0));
+        assert_eq!(scenarios[15].cover, dec!(0.35));
+        assert!(scenarios.iter().enumerate().all(|(i, s)| s.id == i + 1));
+    }
+
+    #[test]
+    fn test_empty_portfolio() {
+        let portfolio = SPANPortfolio::new(SPANRiskParameters::default());
+        let result = portfolio.calculate_margin().unwrap();
+        assert!(result.commodities.is_empty());
+        assert_eq!(result.total_margin, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_long_underlying_risk_array() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        portfolio.add_underlying(shares("AAPL", Side::Long, pos!(10.0)), pos!(100.0));
+        let result = portfolio.calculate_margin().unwrap();
+        let aapl = result.commodity("AAPL").unwrap();
+
+        // Full down move: 10 units * 100 * 10%
+        assert_decimal_eq!(aapl.risk_array[13].loss, dec!(100.0), dec!(1e-10));
+        // Extreme down move: 3x range, 35% covered
+        assert_decimal_eq!(aapl.risk_array[15].loss, dec!(105.0), dec!(1e-10));
+        assert_decimal_eq!(aapl.risk_array[14].loss, dec!(-105.0), dec!(1e-10));
+        assert_decimal_eq!(aapl.scan_risk, dec!(105.0), dec!(1e-10));
+        assert_eq!(aapl.net_delta, dec!(10.0));
+        assert_decimal_eq!(result.total_margin, dec!(105.0), dec!(1e-10));
+    }
+
+    #[test]
+    fn test_short_option_minimum_floor() {
+        let mut parameters = flat_parameters();
+        parameters.short_option_minimum = dec!(0.5);
+        let mut portfolio = SPANPortfolio::new(parameters);
+        portfolio.add_position(option_position(OptionStyle::Call, Side::Short, pos!(150.0)));
+        let result = portfolio.calculate_margin().unwrap();
+        let aapl = result.commodity("AAPL").unwrap();
+
+        assert!(aapl.scan_risk < dec!(50.0));
+        assert_eq!(aapl.short_option_minimum, dec!(50.0));
+        assert_eq!(aapl.margin, dec!(50.0));
+    }
+
+    #[test]
+    fn test_short_call_loses_on_up_moves() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        portfolio.add_position(option_position(OptionStyle::Call, Side::Short, pos!(100.0)));
+        let result = portfolio.calculate_margin().unwrap();
+        let aapl = result.commodity("AAPL").unwrap();
+
+        assert!(aapl.risk_array[10].loss > Decimal::ZERO);
+        assert!(aapl.risk_array[12].loss < Decimal::ZERO);
+        assert!(aapl.risk_array[0].loss > aapl.risk_array[1].loss);
+        assert!(aapl.net_delta < Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_hedged_position_reduces_margin() {
+        let mut naked = SPANPortfolio::new(flat_parameters());
+        naked.add_position(option_position(OptionStyle::Call, Side::Short, pos!(100.0)));
+        let naked_margin = naked.calculate_margin().unwrap().total_margin;
+
+        let mut hedged = naked.clone();
+        hedged.add_underlying(shares("AAPL", Side::Long, pos!(0.5)), pos!(100.0));
+        let hedged_margin = hedged.calculate_margin().unwrap().total_margin;
+
+        assert!(hedged_margin < naked_margin);
+    }
+
+    #[test]
+    fn test_inter_month_spread_charge() {
+        let mut parameters = flat_parameters();
+        parameters.inter_month_spread_charge = dec!(2.0);
+        let mut portfolio = SPANPortfolio::new(parameters);
+        portfolio.add_future(
+            shares("ES", Side::Long, pos!(3.0)),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(100.0),
+        );
+        portfolio.add_future(
+            shares("ES", Side::Short, pos!(2.0)),
+            ExpirationDate::Days(pos!(120.0)),
+            pos!(100.0),
+        );
+        let result = portfolio.calculate_margin().unwrap();
+        let es = result.commodity("ES").unwrap();
+
+        assert_eq!(es.tier_deltas.len(), 2);
+        assert_eq!(es.net_delta, dec!(1.0));
+        assert_eq!(es.inter_month_spread_charge, dec!(4.0));
+        assert_decimal_eq!(es.margin, es.scan_risk + dec!(4.0), dec!(1e-10));
+    }
+
+    #[test]
+    fn test_inter_commodity_credit() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        portfolio.add_underlying(shares("SPY", Side::Long, pos!(10.0)), pos!(100.0));
+        portfolio.add_underlying(shares("QQQ", Side::Short, pos!(5.0)), pos!(100.0));
+        let without_credit = portfolio.calculate_margin().unwrap();
+
+        portfolio.add_inter_commodity_spread(
+            InterCommoditySpread::new(
+                "SPY".to_string(),
+                "QQQ".to_string(),
+                pos!(2.0),
+                pos!(1.0),
+                dec!(0.5),
+            )
+            .unwrap(),
+        );
+        let result = portfolio.calculate_margin().unwrap();
+        let spy = result.commodity("SPY").unwrap();
+        let qqq = result.commodity("QQQ").unwrap();
+
+        // 5 spreads consume all 10 SPY deltas and all 5 QQQ deltas
+        assert_decimal_eq!(
+            spy.inter_commodity_credit,
+            spy.scan_risk * dec!(0.5),
+            dec!(1e-10)
+        );
+        assert_decimal_eq!(
+            qqq.inter_commodity_credit,
+            qqq.scan_risk * dec!(0.5),
+            dec!(1e-10)
+        );
+        assert_decimal_eq!(
+            result.total_margin,
+            without_credit.total_margin * dec!(0.5),
+            dec!(1e-10)
+        );
+    }
+
+    #[test]
+    fn test_inter_commodity_credit_requires_opposite_deltas() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        portfolio.add_underlying(shares("SPY", Side::Long, pos!(10.0)), pos!(100.0));
+        portfolio.add_underlying(shares("QQQ", Side::Long, pos!(5.0)), pos!(100.0));
+        portfolio.add_inter_commodity_spread(
+            InterCommoditySpread::new(
+                "SPY".to_string(),
+                "QQQ".to_string(),
+                pos!(2.0),
+                pos!(1.0),
+                dec!(0.5),
+            )
+            .unwrap(),
+        );
+        let result = portfolio.calculate_margin().unwrap();
+        assert!(
+            result
+                .commodities
+                .iter()
+                .all(|c| c.inter_commodity_credit == Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_inter_commodity_spread_rejects_zero_delta() {
+        assert!(
+            InterCommoditySpread::new(
+                "SPY".to_string(),
+                "QQQ".to_string(),
+                Positive::ZERO,
+                pos!(1.0),
+                dec!(0.5),
+            )
+            .is_err()
+        );
+        assert!(
+            InterCommoditySpread::new(
+                "SPY".to_string(),
+                "QQQ".to_string(),
+                pos!(2.0),
+                Positive::ZERO,
+                dec!(0.5),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_inter_commodity_credit_skips_zero_delta_spread() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        portfolio.add_underlying(shares("SPY", Side::Long, pos!(10.0)), pos!(100.0));
+        portfolio.add_underlying(shares("QQQ", Side::Short, pos!(5.0)), pos!(100.0));
+        portfolio.add_inter_commodity_spread(InterCommoditySpread {
+            first: "SPY".to_string(),
+            second: "QQQ".to_string(),
+            first_delta: Positive::ZERO,
+            second_delta: pos!(1.0),
+            credit_rate: dec!(0.5),
+        });
+        let result = portfolio.calculate_margin().unwrap();
+        assert!(
+            result
+                .commodities
+                .iter()
+                .all(|c| c.inter_commodity_credit == Decimal::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_spot_hedge_has_no_inter_month_charge() {
+        let mut parameters = flat_parameters();
+        parameters.inter_month_spread_charge = dec!(2.0);
+        let mut portfolio = SPANPortfolio::new(parameters);
+        portfolio.add_future(
+            shares("ES", Side::Short, pos!(3.0)),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(100.0),
+        );
+        portfolio.add_underlying(shares("ES", Side::Long, pos!(3.0)), pos!(100.0));
+        let result = portfolio.calculate_margin().unwrap();
+        let es = result.commodity("ES").unwrap();
+
+        assert_eq!(es.tier_deltas.len(), 2);
+        assert_eq!(es.net_delta, Decimal::ZERO);
+        assert_eq!(es.inter_month_spread_charge, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_commodity_specific_parameters() {
+        let mut portfolio = SPANPortfolio::new(flat_parameters());
+        let mut wide = flat_parameters();
+        wide.price_scan_range = dec!(0.2);
+        portfolio.set_parameters("QQQ", wide);
+        portfolio.add_underlying(shares("SPY", Side::Long, pos!(1.0)), pos!(100.0));
+        portfolio.add_underlying(shares("QQQ", Side::Long, pos!(1.0)), pos!(100.0));
+        let result = portfolio.calculate_margin().unwrap();
+
+        let spy = result.commodity("SPY").unwrap();
+        let qqq = result.commodity("QQQ").unwrap();
+        assert_decimal_eq!(qqq.scan_risk, spy.scan_risk * dec!(2.0), dec!(1e-10));
+    }
+}
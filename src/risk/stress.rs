@@ -0,0 +1,647 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::error::{GreeksError, MetricsError};
+use crate::greeks::{Greeks, delta, gamma, side_sign, theta, vega};
+use crate::risk::var::option_value;
+use crate::strategies::Strategies;
+use crate::surfaces::{Point3D, Surface};
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Lowest implied volatility a stressed option can reach.
+const MIN_VOLATILITY: Decimal = dec!(0.01);
+
+/// A single market shock applied to a strategy.
+///
+/// Spot moves are relative (`-0.10` is a 10% drop), volatility moves are absolute
+/// (`0.08` adds 8 volatility points) and rate moves are absolute as well. The skew
+/// tilt changes each option's volatility by `iv_skew * (strike / spot - 1)`, so a
+/// negative tilt raises the volatility of lower strikes, as in a sell-off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressScenario {
+    /// Name of the scenario.
+    pub name: String,
+    /// Relative change of the underlying price.
+    pub spot_shift: Decimal,
+    /// Parallel change of implied volatility.
+    pub iv_shift: Decimal,
+    /// Change of implied volatility per unit of moneyness.
+    pub iv_skew: Decimal,
+    /// Days elapsed before the strategy is revalued.
+    pub days_passed: Positive,
+    /// Change of the risk-free rate.
+    pub rate_shift: Decimal,
+}
+
+impl StressScenario {
+    /// Creates a new stress scenario.
+    pub fn new(
+        name: &str,
+        spot_shift: Decimal,
+        iv_shift: Decimal,
+        iv_skew: Decimal,
+        days_passed: Positive,
+        rate_shift: Decimal,
+    ) -> Self {
+        StressScenario {
+            name: name.to_string(),
+            spot_shift,
+            iv_shift,
+            iv_skew,
+            days_passed,
+            rate_shift,
+        }
+    }
+
+    /// October 19, 1987: the S&P 500 lost over 20% in a single session while implied
+    /// volatility tripled and the put skew steepened.
+    pub fn black_monday_1987() -> Self {
+        StressScenario::new(
+            "Black Monday 1987",
+            dec!(-0.205),
+            dec!(0.30),
+            dec!(-0.50),
+            Positive::ONE,
+            dec!(-0.005),
+        )
+    }
+
+    /// The weeks after the Lehman Brothers bankruptcy in September 2008: a 25% decline
+    /// over about a month, implied volatility above 60% and emergency rate cuts.
+    pub fn financial_crisis_2008() -> Self {
+        StressScenario::new(
+            "Financial Crisis 2008",
+            dec!(-0.25),
+            dec!(0.40),
+            dec!(-0.30),
+            Positive::from(dec!(25.0)),
+            dec!(-0.01),
+        )
+    }
+
+    /// February to March 2020: a 30% drop in under four weeks, implied volatility
+    /// above 80% and rates cut to zero.
+    pub fn covid_crash_2020() -> Self {
+        StressScenario::new(
+            "COVID Crash 2020",
+            dec!(-0.30),
+            dec!(0.55),
+            dec!(-0.30),
+            Positive::from(dec!(20.0)),
+            dec!(-0.015),
+        )
+    }
+
+    /// Returns all the built-in historical scenarios.
+    pub fn historical() -> Vec<StressScenario> {
+        vec![
+            StressScenario::black_monday_1987(),
+            StressScenario::financial_crisis_2008(),
+            StressScenario::covid_crash_2020(),
+        ]
+    }
+
+    /// Applies the scenario to an option quoted at `spot`.
+    fn apply(&self, option: &Options, spot: Decimal) -> Result<Options, MetricsError> {
+        let mut shocked = option.clone();
+        shocked.underlying_price = Positive::from(
+            (option.underlying_price.to_dec() * (Decimal::ONE + self.spot_shift))
+                .max(Decimal::ZERO),
+        );
+        let moneyness = if spot > Decimal::ZERO {
+            option.strike_price.to_dec() / spot - Decimal::ONE
+        } else {
+            Decimal::ZERO
+        };
+        let volatility =
+            option.implied_volatility.to_dec() + self.iv_shift + self.iv_skew * moneyness;
+        shocked.implied_volatility = Positive::from(volatility.max(MIN_VOLATILITY));
+        shocked.risk_free_rate += self.rate_shift;
+        let remaining = option.expiration_date.get_days()?.to_dec() - self.days_passed.to_dec();
+        shocked.expiration_date =
+            ExpirationDate::Days(Positive::from(remaining.max(Decimal::ZERO)));
+        Ok(shocked)
+    }
+}
+
+/// Grid of shocks whose Cartesian product defines a set of stress scenarios.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressGrid {
+    /// Relative spot changes.
+    pub spot_shifts: Vec<Decimal>,
+    /// Parallel implied volatility changes.
+    pub iv_shifts: Vec<Decimal>,
+    /// Skew tilt applied in every scenario of the grid.
+    pub iv_skew: Decimal,
+    /// Elapsed days.
+    pub days_passed: Vec<Positive>,
+    /// Risk-free rate changes.
+    pub rate_shifts: Vec<Decimal>,
+}
+
+impl StressGrid {
+    /// Creates a new stress grid.
+    pub fn new(
+        spot_shifts: Vec<Decimal>,
+        iv_shifts: Vec<Decimal>,
+        iv_skew: Decimal,
+        days_passed: Vec<Positive>,
+        rate_shifts: Vec<Decimal>,
+    ) -> Self {
+        StressGrid {
+            spot_shifts,
+            iv_shifts,
+            iv_skew,
+            days_passed,
+            rate_shifts,
+        }
+    }
+
+    /// Expands the grid into individual scenarios.
+    pub fn scenarios(&self) -> Vec<StressScenario> {
+        let mut scenarios = Vec::with_capacity(
+            self.spot_shifts.len()
+                * self.iv_shifts.len()
+                * self.days_passed.len()
+                * self.rate_shifts.len(),
+        );
+        for &days in &self.days_passed {
+            for &rate in &self.rate_shifts {
+                for &spot in &self.spot_shifts {
+                    for &iv in &self.iv_shifts {
+                        let name = format!("spot {spot} iv {iv} days {days} rate {rate}");
+                        scenarios.push(StressScenario::new(
+                            &name,
+                            spot,
+                            iv,
+                            self.iv_skew,
+                            days,
+                            rate,
+                        ));
+                    }
+                }
+            }
+        }
+        scenarios
+    }
+}
+
+impl Default for StressGrid {
+    /// Spot from -20% to +20% in 5% steps and volatility from -10 to +10 points in
+    /// 5 point steps, with no skew, time decay or rate change.
+    fn default() -> Self {
+        StressGrid::new(
+            (-4..=4).map(|i| Decimal::from(i) * dec!(0.05)).collect(),
+            (-2..=2).map(|i| Decimal::from(i) * dec!(0.05)).collect(),
+            Decimal::ZERO,
+            vec![Positive::ZERO],
+            vec![Decimal::ZERO],
+        )
+    }
+}
+
+/// Quantity read from a stress report when building a surface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StressMetric {
+    /// Profit or loss of the scenario.
+    PnL,
+    /// Delta after the shock.
+    Delta,
+    /// Gamma after the shock.
+    Gamma,
+    /// Vega after the shock.
+    Vega,
+    /// Theta after the shock.
+    Theta,
+}
+
+/// Revaluation of a strategy under one scenario.
+///
+/// Greeks are position Greeks, signed by side and scaled by quantity. The underlying
+/// held by the strategy, if any, contributes to P&L and delta.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressResult {
+    /// Scenario applied.
+    pub scenario: StressScenario,
+    /// Change in the value of the strategy.
+    pub pnl: Decimal,
+    /// Delta after the shock.
+    pub delta: Decimal,
+    /// Gamma after the shock.
+    pub gamma: Decimal,
+    /// Vega after the shock.
+    pub vega: Decimal,
+    /// Theta after the shock.
+    pub theta: Decimal,
+}
+
+impl StressResult {
+    /// Returns the requested metric.
+    pub fn metric(&self, metric: StressMetric) -> Decimal {
+        match metric {
+            StressMetric::PnL => self.pnl,
+            StressMetric::Delta => self.delta,
+            StressMetric::Gamma => self.gamma,
+            StressMetric::Vega => self.vega,
+            StressMetric::Theta => self.theta,
+        }
+    }
+}
+
+/// Results of a stress test, in the order the scenarios were given.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StressReport {
+    /// One result per scenario.
+    pub results: Vec<StressResult>,
+}
+
+impl StressReport {
+    /// Scenario with the largest loss.
+    pub fn worst(&self) -> Option<&StressResult> {
+        self.results.iter().min_by_key(|result| result.pnl)
+    }
+
+    /// Scenario with the largest gain.
+    pub fn best(&self) -> Option<&StressResult> {
+        self.results.iter().max_by_key(|result| result.pnl)
+    }
+
+    /// Result of the scenario with the given name.
+    pub fn scenario(&self, name: &str) -> Option<&StressResult> {
+        self.results
+            .iter()
+            .find(|result| result.scenario.name == name)
+    }
+
+    /// Builds a surface of `metric` with the spot shift on the x axis and the
+    /// volatility shift on the y axis, for the scenarios with the given elapsed days
+    /// and rate shift.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if no scenario matches `days_passed` and
+    /// `rate_shift`.
+    pub fn surface(
+        &self,
+        metric: StressMetric,
+        days_passed: Positive,
+        rate_shift: Decimal,
+    ) -> Result<Surface, MetricsError> {
+        let points: BTreeSet<Point3D> = self
+            .results
+            .iter()
+            .filter(|result| {
+                result.scenario.days_passed == days_passed
+                    && result.scenario.rate_shift == rate_shift
+            })
+            .map(|result| {
+                Point3D::new(
+                    result.scenario.spot_shift,
+                    result.scenario.iv_shift,
+                    result.metric(metric),
+                )
+            })
+            .collect();
+        if points.is_empty() {
+            return Err(MetricsError::RiskError(format!(
+                "No stress scenario with {} days passed and rate shift {}",
+                days_passed, rate_shift
+            )));
+        }
+        Ok(Surface::new(points))
+    }
+}
+
+/// Scenario analysis for strategies.
+///
+/// Implemented for every type that is both [`Strategies`] and [`Greeks`]. Each option
+/// is fully revalued with Black-Scholes after the shock; options that expire within
+/// the scenario are settled at intrinsic value.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::risk::{StressScenario, StressTester};
+/// use optionstratlib::strategies::IronCondor;
+/// use optionstratlib::{pos, ExpirationDate, Positive};
+/// use rust_decimal_macros::dec;
+///
+/// let condor = IronCondor::new(
+///     "SPY".to_string(),
+///     pos!(100.0),
+///     pos!(105.0),
+///     pos!(95.0),
+///     pos!(110.0),
+///     pos!(90.0),
+///     ExpirationDate::Days(pos!(30.0)),
+///     pos!(0.2),
+///     dec!(0.05),
+///     Positive::ZERO,
+///     Positive::ONE,
+///     pos!(1.5),
+///     pos!(1.4),
+///     pos!(0.5),
+///     pos!(0.4),
+///     Positive::ZERO,
+///     Positive::ZERO,
+/// );
+///
+/// let shock = StressScenario::new("sell-off", dec!(-0.10), dec!(0.08), dec!(0.0), pos!(5.0), dec!(0.0));
+/// let report = condor.stress_test(&[shock]).unwrap();
+/// assert!(report.results[0].pnl < dec!(0.0));
+/// ```
+pub trait StressTester: Strategies + Greeks {
+    /// Revalues the strategy under each scenario.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if an option cannot be priced or its Greeks
+    /// cannot be computed.
+    fn stress_test(&self, scenarios: &[StressScenario]) -> Result<StressReport, MetricsError> {
+        let greek_error = |e: GreeksError| MetricsError::RiskError(e.to_string());
+        let options = self.get_options().map_err(greek_error)?;
+        let spot = self.get_underlying_price().to_dec();
+        let underlying_delta = self
+            .get_underlying_position()
+            .map(|u| u.delta())
+            .unwrap_or(Decimal::ZERO);
+        let current = options
+            .iter()
+            .map(|option| option_value(option, Decimal::ZERO))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut results = Vec::with_capacity(scenarios.len());
+        for scenario in scenarios {
+            let mut result = StressResult {
+                scenario: scenario.clone(),
+                pnl: underlying_delta * spot * scenario.spot_shift,
+                delta: underlying_delta,
+                gamma: Decimal::ZERO,
+                vega: Decimal::ZERO,
+                theta: Decimal::ZERO,
+            };
+            for (option, value) in options.iter().zip(&current) {
+                let shocked = scenario.apply(option, spot)?;
+                result.pnl += option_value(&shocked, Decimal::ZERO)? - value;
+                let sign = side_sign(option);
+                result.delta += delta(&shocked).map_err(greek_error)?;
+                result.gamma += sign * gamma(&shocked).map_err(greek_error)?;
+                result.vega += sign * vega(&shocked).map_err(greek_error)?;
+                result.theta += sign * theta(&shocked).map_err(greek_error)?;
+            }
+            results.push(result);
+        }
+        Ok(StressReport { results })
+    }
+
+    /// Revalues the strategy over every scenario of a grid.
+    ///
+    /// # Errors
+    /// See [`StressTester::stress_test`].
+    fn stress_grid(&self, grid: &StressGrid) -> Result<StressReport, MetricsError> {
+        self.stress_test(&grid.scenarios())
+    }
+
+    /// Revalues the strategy under the built-in historical scenarios.
+    ///
+    /// # Errors
+    /// See [`StressTester::stress_test`].
+    fn historical_stress_test(&self) -> Result<StressReport, MetricsError> {
+        self.stress_test(&StressScenario::historical())
+    }
+}
+
+impl<T: Strategies + Greeks + ?Sized> StressTester for T {}
+
+#[cfg(test)]
+mod tests_stress {
+    use super::*;
+    use crate::strategies::{BasicAble, CoveredCall, IronCondor, ShortStrangle};
+    use crate::{assert_decimal_eq, pos};
+
+    fn iron_condor() -> IronCondor {
+        IronCondor::new(
+            "SPY".to_string(),
+            pos!(100.0),
+            pos!(105.0),
+            pos!(95.0),
+            pos!(110.0),
+            pos!(90.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.5),
+            pos!(1.4),
+            pos!(0.5),
+            pos!(0.4),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_unchanged_scenario_has_no_pnl() {
+        let condor = iron_condor();
+        let flat = StressScenario::new(
+            "flat",
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Positive::ZERO,
+            Decimal::ZERO,
+        );
+        let report = condor.stress_test(&[flat]).unwrap();
+        let result = &report.results[0];
+        assert_decimal_eq!(result.pnl, Decimal::ZERO, dec!(1e-9));
+        assert_decimal_eq!(result.delta, condor.delta().unwrap(), dec!(1e-6));
+        // Short options dominate: negative gamma and vega, positive theta
+        assert!(result.gamma < Decimal::ZERO);
+        assert!(result.vega < Decimal::ZERO);
+        assert!(result.theta > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_sell_off_hurts_iron_condor() {
+        let condor = iron_condor();
+        let scenarios = [
+            StressScenario::new(
+                "sell-off",
+                dec!(-0.10),
+                dec!(0.08),
+                Decimal::ZERO,
+                pos!(5.0),
+                Decimal::ZERO,
+            ),
+            StressScenario::new(
+                "quiet",
+                Decimal::ZERO,
+                dec!(-0.05),
+                Decimal::ZERO,
+                pos!(5.0),
+                Decimal::ZERO,
+            ),
+        ];
+        let report = condor.stress_test(&scenarios).unwrap();
+        let sell_off = report.scenario("sell-off").unwrap();
+        let quiet = report.scenario("quiet").unwrap();
+        assert!(sell_off.pnl < Decimal::ZERO);
+        assert!(quiet.pnl > Decimal::ZERO);
+        assert_eq!(report.worst().unwrap().scenario.name, "sell-off");
+        assert_eq!(report.best().unwrap().scenario.name, "quiet");
+        // The short put is now in the money
+        assert!(sell_off.delta > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_expiration_within_scenario_settles_intrinsic() {
+        let condor = iron_condor();
+        let expiry = StressScenario::new(
+            "expiry",
+            dec!(-0.20),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            pos!(30.0),
+            Decimal::ZERO,
+        );
+        let report = condor.stress_test(&[expiry]).unwrap();
+        let result = &report.results[0];
+        // Put spread 95/90 fully in the money: settles at -5
+        let current: Decimal = condor
+            .get_options()
+            .unwrap()
+            .into_iter()
+            .map(|option| option_value(option, Decimal::ZERO).unwrap())
+            .sum();
+        assert_decimal_eq!(result.pnl, dec!(-5.0) - current, dec!(1e-9));
+        assert_eq!(result.gamma, Decimal::ZERO);
+        assert_eq!(result.vega, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_skew_tilt_raises_low_strike_volatility() {
+        let condor = iron_condor();
+        let spot = condor.get_underlying_price().to_dec();
+        let options = condor.get_options().unwrap();
+        let low_put = options
+            .iter()
+            .find(|option| option.strike_price == pos!(90.0))
+            .unwrap();
+        let high_call = options
+            .iter()
+            .find(|option| option.strike_price == pos!(110.0))
+            .unwrap();
+        let tilt = StressScenario::new(
+            "tilt",
+            Decimal::ZERO,
+            Decimal::ZERO,
+            dec!(-0.5),
+            Positive::ZERO,
+            Decimal::ZERO,
+        );
+        assert_eq!(
+            tilt.apply(low_put, spot).unwrap().implied_volatility,
+            pos!(0.25)
+        );
+        assert_eq!(
+            tilt.apply(high_call, spot).unwrap().implied_volatility,
+            pos!(0.15)
+        );
+        let floor = StressScenario::new(
+            "floor",
+            Decimal::ZERO,
+            dec!(-1.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Decimal::ZERO,
+        );
+        assert_eq!(
+            floor.apply(low_put, spot).unwrap().implied_volatility,
+            Positive::from(MIN_VOLATILITY)
+        );
+    }
+
+    #[test]
+    fn test_grid_and_surface() {
+        let condor = iron_condor();
+        let grid = StressGrid::new(
+            vec![dec!(-0.1), Decimal::ZERO, dec!(0.1)],
+            vec![dec!(-0.05), dec!(0.05)],
+            Decimal::ZERO,
+            vec![Positive::ZERO, pos!(10.0)],
+            vec![Decimal::ZERO],
+        );
+        assert_eq!(grid.scenarios().len(), 12);
+        let report = condor.stress_grid(&grid).unwrap();
+        assert_eq!(report.results.len(), 12);
+
+        let surface = report
+            .surface(StressMetric::PnL, pos!(10.0), Decimal::ZERO)
+            .unwrap();
+        assert_eq!(surface.points.len(), 6);
+        assert_eq!(surface.x_range, (dec!(-0.1), dec!(0.1)));
+        assert_eq!(surface.y_range, (dec!(-0.05), dec!(0.05)));
+        assert!(
+            report
+                .surface(StressMetric::Vega, pos!(3.0), Decimal::ZERO)
+                .is_err()
+        );
+        assert_eq!(StressGrid::default().scenarios().len(), 45);
+    }
+
+    #[test]
+    fn test_historical_scenarios() {
+        let strangle = ShortStrangle::new(
+            "SPY".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            pos!(90.0),
+            ExpirationDate::Days(pos!(45.0)),
+            pos!(0.2),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.0),
+            pos!(1.2),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let report = strangle.historical_stress_test().unwrap();
+        assert_eq!(report.results.len(), 3);
+        assert!(report.results.iter().all(|result| result.pnl < dec!(-5.0)));
+        assert!(report.scenario("COVID Crash 2020").is_some());
+    }
+
+    #[test]
+    fn test_underlying_leg_included() {
+        let covered_call = CoveredCall::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(200.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos!(10.0),
+            pos!(0.01),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let report = covered_call
+            .stress_test(&[StressScenario::black_monday_1987()])
+            .unwrap();
+        // Ten shares fall 20.5% while the far call stays worthless
+        assert_decimal_eq!(report.results[0].pnl, dec!(-205.0), dec!(0.01));
+        assert_decimal_eq!(report.results[0].delta, dec!(10.0), dec!(0.01));
+    }
+}
@@ -0,0 +1,711 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::backtesting::metrics::AdvancedRiskMetrics;
+use crate::error::MetricsError;
+use crate::greeks::{Greeks, gamma, side_sign};
+use crate::model::types::{OptionStyle, Side};
+use crate::risk::RiskMetricsSimulation;
+use crate::simulation::simulator::Simulator;
+use crate::strategies::Strategies;
+use crate::utils::OhlcvCandle;
+use crate::utils::stats::{mean, quantile};
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::{Decimal, MathematicalOps};
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{Continuous, ContinuousCDF, Normal};
+use std::fmt::Display;
+use std::ops::AddAssign;
+
+/// Days per year used to scale annualized volatility to the VaR horizon.
+const DAYS_PER_YEAR: Decimal = dec!(365.0);
+
+/// Horizon and confidence level of a Value-at-Risk calculation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaRParameters {
+    /// Holding period in days. Options lose this much time in every scenario.
+    pub horizon_days: Positive,
+    /// Confidence level, e.g. 0.95 or 0.99.
+    pub confidence: Decimal,
+}
+
+impl VaRParameters {
+    /// Creates new VaR parameters.
+    pub fn new(horizon_days: Positive, confidence: Decimal) -> Self {
+        VaRParameters {
+            horizon_days,
+            confidence,
+        }
+    }
+
+    fn validate(&self) -> Result<(), MetricsError> {
+        if self.confidence <= Decimal::ZERO || self.confidence >= Decimal::ONE {
+            return Err(MetricsError::RiskError(format!(
+                "Confidence level must be between 0 and 1, got {}",
+                self.confidence
+            )));
+        }
+        if self.horizon_days == Positive::ZERO {
+            return Err(MetricsError::RiskError(
+                "VaR horizon must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for VaRParameters {
+    /// One-day VaR at 95% confidence.
+    fn default() -> Self {
+        VaRParameters::new(Positive::ONE, dec!(0.95))
+    }
+}
+
+/// Method used to obtain a Value-at-Risk figure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VaRMethod {
+    /// Strategy revalued under past returns of the underlying.
+    Historical,
+    /// Delta-gamma approximation with a Cornish-Fisher adjustment for skew.
+    DeltaGamma,
+    /// Strategy revalued at the terminal prices of simulated random walks.
+    MonteCarlo,
+}
+
+/// Value-at-Risk and Expected Shortfall of a strategy.
+///
+/// VaR and Expected Shortfall are reported as positive amounts of money lost over the
+/// horizon. The scenario P&L behind sample-based methods is kept, sorted from the worst
+/// to the best outcome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VaRReport {
+    /// Method that produced the report.
+    pub method: VaRMethod,
+    /// Horizon of the calculation in days.
+    pub horizon_days: Positive,
+    /// Confidence level requested.
+    pub confidence: Decimal,
+    /// Value-at-Risk at the requested confidence.
+    pub var: Decimal,
+    /// Expected Shortfall (CVaR) at the requested confidence.
+    pub expected_shortfall: Decimal,
+    /// Value-at-Risk at 95%.
+    pub var_95: Decimal,
+    /// Value-at-Risk at 99%.
+    pub var_99: Decimal,
+    /// Expected Shortfall at 95%.
+    pub expected_shortfall_95: Decimal,
+    /// Ratio of the 95th percentile gain to the 5th percentile loss, when available.
+    pub tail_ratio: Option<Decimal>,
+    /// P&L of every scenario; empty for parametric methods.
+    pub scenarios: Vec<Decimal>,
+}
+
+impl VaRReport {
+    fn from_scenarios(
+        method: VaRMethod,
+        parameters: &VaRParameters,
+        mut scenarios: Vec<Decimal>,
+    ) -> Result<Self, MetricsError> {
+        if scenarios.is_empty() {
+            return Err(MetricsError::RiskError(
+                "At least one scenario is required to compute VaR".to_string(),
+            ));
+        }
+        scenarios.sort();
+        let var = |confidence: Decimal| {
+            quantile(&scenarios, Decimal::ONE - confidence)
+                .map(|q| (-q).max(Decimal::ZERO))
+                .unwrap_or(Decimal::ZERO)
+        };
+        let shortfall = |confidence: Decimal| {
+            let threshold = quantile(&scenarios, Decimal::ONE - confidence).unwrap_or_default();
+            let tail: Vec<Decimal> = scenarios
+                .iter()
+                .copied()
+                .filter(|pnl| *pnl <= threshold)
+                .collect();
+            mean(&tail)
+                .map(|loss| (-loss).max(Decimal::ZERO))
+                .unwrap_or(Decimal::ZERO)
+        };
+        let tail_ratio = quantile(&scenarios, dec!(0.95))
+            .zip(quantile(&scenarios, dec!(0.05)))
+            .filter(|(_, low)| !low.is_zero())
+            .map(|(high, low)| high.abs() / low.abs());
+
+        Ok(VaRReport {
+            method,
+            horizon_days: parameters.horizon_days,
+            confidence: parameters.confidence,
+            var: var(parameters.confidence),
+            expected_shortfall: shortfall(parameters.confidence),
+            var_95: var(dec!(0.95)),
+            var_99: var(dec!(0.99)),
+            expected_shortfall_95: shortfall(dec!(0.95)),
+            tail_ratio,
+            scenarios,
+        })
+    }
+
+    /// Copies the VaR figures into a `RiskMetricsSimulation`.
+    ///
+    /// `RiskMetricsSimulation` stores VaR as the P&L quantile, so losses are written as
+    /// negative values. The other fields are left untouched.
+    pub fn fill_risk_metrics(&self, metrics: &mut RiskMetricsSimulation) {
+        metrics.var_95 = -self.var_95;
+        metrics.var_99 = -self.var_99;
+        metrics.cvar_95 = -self.expected_shortfall_95;
+    }
+
+    /// Copies the VaR figures into `AdvancedRiskMetrics`, as positive losses.
+    ///
+    /// The drawdown and loss-streak fields are left untouched.
+    pub fn fill_advanced_risk_metrics(&self, metrics: &mut AdvancedRiskMetrics) {
+        metrics.value_at_risk_95 = Some(self.var_95);
+        metrics.value_at_risk_99 = Some(self.var_99);
+        metrics.expected_shortfall = Some(self.expected_shortfall_95);
+        metrics.tail_ratio = self.tail_ratio;
+    }
+}
+
+/// Value of an option position after `horizon_days`, signed by side. Options that
+/// expire within the horizon are worth their intrinsic value.
+pub(super) fn option_value(
+    option: &Options,
+    horizon_days: Decimal,
+) -> Result<Decimal, MetricsError> {
+    let remaining = option.expiration_date.get_days()?.to_dec() - horizon_days;
+    if remaining <= Decimal::ZERO {
+        let price = option.underlying_price.to_dec();
+        let strike = option.strike_price.to_dec();
+        let intrinsic = match option.option_style {
+            OptionStyle::Call => (price - strike).max(Decimal::ZERO),
+            OptionStyle::Put => (strike - price).max(Decimal::ZERO),
+        };
+        let signed = match option.side {
+            Side::Long => intrinsic,
+            Side::Short => -intrinsic,
+        };
+        return Ok(signed * option.quantity);
+    }
+    let mut aged = option.clone();
+    if horizon_days > Decimal::ZERO {
+        aged.expiration_date = ExpirationDate::Days(Positive::from(remaining));
+    }
+    let price = aged
+        .calculate_price_black_scholes()
+        .map_err(|e| MetricsError::RiskError(e.to_string()))?;
+    Ok(price * option.quantity)
+}
+
+/// Full revaluation of a strategy: the options with their current values, the
+/// underlying holding, and the spot price they are marked at.
+struct Revaluation {
+    options: Vec<(Options, Decimal)>,
+    underlying_delta: Decimal,
+    spot: Decimal,
+    horizon_days: Decimal,
+}
+
+impl Revaluation {
+    fn new<T: Strategies + Greeks + ?Sized>(
+        strategy: &T,
+        horizon_days: Positive,
+    ) -> Result<Self, MetricsError> {
+        let options = strategy
+            .get_options()
+            .map_err(|e| MetricsError::RiskError(e.to_string()))?
+            .into_iter()
+            .map(|option| Ok((option.clone(), option_value(option, Decimal::ZERO)?)))
+            .collect::<Result<Vec<_>, MetricsError>>()?;
+        let underlying_delta = strategy
+            .get_underlying_position()
+            .map(|u| u.delta())
+            .unwrap_or(Decimal::ZERO);
+        Ok(Revaluation {
+            options,
+            underlying_delta,
+            spot: strategy.get_underlying_price().to_dec(),
+            horizon_days: horizon_days.to_dec(),
+        })
+    }
+
+    /// P&L of the strategy if the underlying returns `relative_move` over the horizon.
+    fn pnl(&self, relative_move: Decimal) -> Result<Decimal, MetricsError> {
+        let mut pnl = self.underlying_delta * self.spot * relative_move;
+        for (option, current) in &self.options {
+            let mut shifted = option.clone();
+            shifted.underlying_price = Positive::from(
+                (option.underlying_price.to_dec() * (Decimal::ONE + relative_move))
+                    .max(Decimal::ZERO),
+            );
+            pnl += option_value(&shifted, self.horizon_days)? - current;
+        }
+        Ok(pnl)
+    }
+}
+
+/// Value-at-Risk and Expected Shortfall engines.
+///
+/// Implemented for every type that is both [`Strategies`] and [`Greeks`], so any
+/// strategy can be measured with historical simulation, the parametric delta-gamma
+/// approximation, or a full-revaluation Monte Carlo.
+///
+/// # Examples
+///
+/// ```rust
+/// use optionstratlib::risk::{VaRCalculator, VaRParameters};
+/// use optionstratlib::strategies::ShortStrangle;
+/// use optionstratlib::{pos, ExpirationDate, Positive};
+/// use rust_decimal_macros::dec;
+///
+/// let strangle = ShortStrangle::new(
+///     "AAPL".to_string(),
+///     pos!(100.0),
+///     pos!(110.0),
+///     pos!(90.0),
+///     ExpirationDate::Days(pos!(30.0)),
+///     pos!(0.25),
+///     pos!(0.25),
+///     dec!(0.05),
+///     Positive::ZERO,
+///     Positive::ONE,
+///     pos!(1.2),
+///     pos!(1.1),
+///     Positive::ZERO,
+///     Positive::ZERO,
+///     Positive::ZERO,
+///     Positive::ZERO,
+/// );
+///
+/// let parameters = VaRParameters::new(pos!(5.0), dec!(0.99));
+/// let report = strangle.delta_gamma_var(pos!(0.25), &parameters).unwrap();
+/// assert!(report.var > rust_decimal::Decimal::ZERO);
+/// ```
+pub trait VaRCalculator: Strategies + Greeks {
+    /// Historical simulation VaR.
+    ///
+    /// Every window of `horizon_days` consecutive candles provides one return of the
+    /// underlying (close to close). The strategy is revalued under each return with the
+    /// horizon elapsed, and VaR and Expected Shortfall are read from the resulting P&L
+    /// distribution. Candles are assumed to be daily and sorted by date.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if there are not enough candles for one
+    /// horizon, the parameters are invalid, or the strategy cannot be revalued.
+    fn historical_var(
+        &self,
+        candles: &[OhlcvCandle],
+        parameters: &VaRParameters,
+    ) -> Result<VaRReport, MetricsError> {
+        parameters.validate()?;
+        let step = parameters
+            .horizon_days
+            .to_dec()
+            .round()
+            .to_usize()
+            .unwrap_or(1)
+            .max(1);
+        if candles.len() <= step {
+            return Err(MetricsError::RiskError(format!(
+                "Historical VaR over {} days needs more than {} candles, got {}",
+                step,
+                step,
+                candles.len()
+            )));
+        }
+        let revaluation = Revaluation::new(self, parameters.horizon_days)?;
+        let scenarios = candles
+            .windows(step + 1)
+            .filter(|window| window[0].close > Decimal::ZERO)
+            .map(|window| revaluation.pnl(window[step].close / window[0].close - Decimal::ONE))
+            .collect::<Result<Vec<_>, _>>()?;
+        VaRReport::from_scenarios(VaRMethod::Historical, parameters, scenarios)
+    }
+
+    /// Parametric delta-gamma VaR.
+    ///
+    /// The underlying move over the horizon is taken as normal with standard deviation
+    /// `S * volatility * sqrt(horizon / 365)`. The P&L `delta * dS + gamma * dS² / 2` has
+    /// known mean, variance and skew; its quantile is obtained with a Cornish-Fisher
+    /// expansion, and Expected Shortfall with the normal approximation. Time decay is
+    /// not included.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the parameters are invalid or the Greeks
+    /// cannot be computed.
+    fn delta_gamma_var(
+        &self,
+        volatility: Positive,
+        parameters: &VaRParameters,
+    ) -> Result<VaRReport, MetricsError> {
+        parameters.validate()?;
+        let greek_error = |e: crate::error::GreeksError| MetricsError::RiskError(e.to_string());
+        let delta = self.delta().map_err(greek_error)?;
+        let mut position_gamma = Decimal::ZERO;
+        for option in self.get_options().map_err(greek_error)? {
+            position_gamma += side_sign(option) * gamma(option).map_err(greek_error)?;
+        }
+
+        let years = parameters.horizon_days.to_dec() / DAYS_PER_YEAR;
+        let sigma = self.get_underlying_price().to_dec()
+            * volatility.to_dec()
+            * years.sqrt().unwrap_or(Decimal::ZERO);
+        let variance_s = sigma * sigma;
+        let pnl_mean = position_gamma * variance_s / dec!(2);
+        let pnl_variance = delta * delta * variance_s
+            + position_gamma * position_gamma * variance_s * variance_s / dec!(2);
+        let pnl_std = pnl_variance.sqrt().unwrap_or(Decimal::ZERO);
+        let third_moment = dec!(3) * delta * delta * position_gamma * variance_s * variance_s
+            + position_gamma.powi(3) * variance_s.powi(3);
+        let skew = if pnl_std > Decimal::ZERO {
+            third_moment / pnl_std.powi(3)
+        } else {
+            Decimal::ZERO
+        };
+
+        let normal = Normal::new(0.0, 1.0).map_err(|e| MetricsError::RiskError(e.to_string()))?;
+        let tail_quantile = |confidence: Decimal| -> Result<Decimal, MetricsError> {
+            let alpha = (Decimal::ONE - confidence).to_f64().unwrap_or(0.05);
+            Decimal::from_f64(normal.inverse_cdf(alpha)).ok_or_else(|| {
+                MetricsError::RiskError(format!("Invalid confidence level {}", confidence))
+            })
+        };
+        let var = |confidence: Decimal| -> Result<Decimal, MetricsError> {
+            let z = tail_quantile(confidence)?;
+            let z_cf = z + (z * z - Decimal::ONE) * skew / dec!(6);
+            Ok((-(pnl_mean + z_cf * pnl_std)).max(Decimal::ZERO))
+        };
+        let shortfall = |confidence: Decimal| -> Result<Decimal, MetricsError> {
+            let z = tail_quantile(confidence)?.to_f64().unwrap_or_default();
+            let density = Decimal::from_f64(normal.pdf(z)).unwrap_or_default();
+            Ok((pnl_std * density / (Decimal::ONE - confidence) - pnl_mean).max(Decimal::ZERO))
+        };
+
+        Ok(VaRReport {
+            method: VaRMethod::DeltaGamma,
+            horizon_days: parameters.horizon_days,
+            confidence: parameters.confidence,
+            var: var(parameters.confidence)?,
+            expected_shortfall: shortfall(parameters.confidence)?,
+            var_95: var(dec!(0.95))?,
+            var_99: var(dec!(0.99))?,
+            expected_shortfall_95: shortfall(dec!(0.95))?,
+            tail_ratio: None,
+            scenarios: Vec::new(),
+        })
+    }
+
+    /// Full-revaluation Monte Carlo VaR.
+    ///
+    /// Each random walk of the simulator provides one scenario: the underlying moves by
+    /// the ratio between the last and first values of the walk, and the strategy is
+    /// revalued with `horizon_days` elapsed. The walks should therefore span the horizon.
+    ///
+    /// # Errors
+    /// Returns `MetricsError::RiskError` if the simulator has no walks, the parameters
+    /// are invalid, or the strategy cannot be revalued.
+    fn monte_carlo_var<X, Y>(
+        &self,
+        simulator: &Simulator<X, Y>,
+        parameters: &VaRParameters,
+    ) -> Result<VaRReport, MetricsError>
+    where
+        X: Copy + Into<Positive> + AddAssign + Display,
+        Y: Into<Positive> + Display + Clone,
+    {
+        parameters.validate()?;
+        let revaluation = Revaluation::new(self, parameters.horizon_days)?;
+        let mut scenarios = Vec::new();
+        for walk in simulator {
+            let (Some(first), Some(last)) = (walk.first(), walk.last()) else {
+                continue;
+            };
+            let start = first.get_positive_value();
+            if start == Positive::ZERO {
+                continue;
+            }
+            let relative_move = (last.get_positive_value() / start).to_dec() - Decimal::ONE;
+            scenarios.push(revaluation.pnl(relative_move)?);
+        }
+        VaRReport::from_scenarios(VaRMethod::MonteCarlo, parameters, scenarios)
+    }
+}
+
+impl<T: Strategies + Greeks + ?Sized> VaRCalculator for T {}
+
+#[cfg(test)]
+mod tests_var {
+    use super::*;
+    use crate::chains::generator_positive;
+    use crate::simulation::steps::{Step, Xstep, Ystep};
+    use crate::simulation::{WalkParams, WalkType, WalkTypeAble};
+    use crate::strategies::{BullCallSpread, CoveredCall, ShortStrangle};
+    use crate::utils::TimeFrame;
+    use crate::{assert_decimal_eq, pos};
+    use chrono::NaiveDate;
+
+    struct TestWalker;
+
+    impl WalkTypeAble<Positive, Positive> for TestWalker {}
+
+    fn candles(closes: &[Decimal]) -> Vec<OhlcvCandle> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, close)| OhlcvCandle {
+                date: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Days::new(i as u64),
+                time: "00:00:00".to_string(),
+                open: *close,
+                high: *close,
+                low: *close,
+                close: *close,
+                volume: 0,
+            })
+            .collect()
+    }
+
+    fn covered_call() -> CoveredCall {
+        CoveredCall::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(200.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos!(10.0),
+            pos!(0.01),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn short_strangle() -> ShortStrangle {
+        ShortStrangle::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            pos!(90.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.25),
+            pos!(0.25),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.2),
+            pos!(1.1),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let strategy = covered_call();
+        let history = candles(&[dec!(100.0), dec!(101.0)]);
+        let parameters = VaRParameters::new(Positive::ONE, dec!(1.5));
+        assert!(strategy.historical_var(&history, &parameters).is_err());
+        let parameters = VaRParameters::new(Positive::ONE, dec!(0.95));
+        assert!(strategy.historical_var(&history[..1], &parameters).is_err());
+    }
+
+    #[test]
+    fn test_historical_var_linear_position() {
+        // The far call is worthless, so the strategy behaves like 10 shares.
+        let strategy = covered_call();
+        let closes: Vec<Decimal> = (0..=100)
+            .map(|i| dec!(100.0) + Decimal::from(i % 11) - dec!(5.0))
+            .collect();
+        let history = candles(&closes);
+        let report = strategy
+            .historical_var(&history, &VaRParameters::default())
+            .unwrap();
+
+        assert_eq!(report.method, VaRMethod::Historical);
+        assert_eq!(report.scenarios.len(), 100);
+        assert!(report.scenarios.windows(2).all(|w| w[0] <= w[1]));
+        // Worst one-day move is a drop from 105 to 95
+        let worst = -report.scenarios[0];
+        assert_decimal_eq!(
+            worst,
+            dec!(10.0) * dec!(100.0) * dec!(10.0) / dec!(105.0),
+            dec!(0.01)
+        );
+        assert!(report.var > Decimal::ZERO);
+        assert!(report.expected_shortfall >= report.var);
+        assert!(report.var_99 >= report.var_95);
+    }
+
+    #[test]
+    fn test_historical_var_multi_day_horizon() {
+        let strategy = covered_call();
+        let closes: Vec<Decimal> = (0..30).map(|i| dec!(100.0) - Decimal::from(i)).collect();
+        let history = candles(&closes);
+        let one_day = strategy
+            .historical_var(&history, &VaRParameters::new(Positive::ONE, dec!(0.95)))
+            .unwrap();
+        let five_days = strategy
+            .historical_var(&history, &VaRParameters::new(pos!(5.0), dec!(0.95)))
+            .unwrap();
+        assert_eq!(five_days.scenarios.len(), 25);
+        assert!(five_days.var > one_day.var);
+    }
+
+    #[test]
+    fn test_delta_gamma_short_gamma_is_skewed() {
+        let strategy = short_strangle();
+        let report = strategy
+            .delta_gamma_var(pos!(0.25), &VaRParameters::new(pos!(5.0), dec!(0.99)))
+            .unwrap();
+        assert_eq!(report.method, VaRMethod::DeltaGamma);
+        assert!(report.scenarios.is_empty());
+        assert!(report.var > Decimal::ZERO);
+        assert_eq!(report.var, report.var_99);
+        assert!(report.var_99 > report.var_95);
+        assert!(report.expected_shortfall_95 > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_delta_gamma_linear_matches_normal() {
+        let strategy = covered_call();
+        let report = strategy
+            .delta_gamma_var(pos!(0.2), &VaRParameters::new(pos!(365.0), dec!(0.95)))
+            .unwrap();
+        // delta ~ 10, sigma = 100 * 0.2 = 20, VaR ~ 1.645 * 200
+        assert_decimal_eq!(report.var, dec!(329.0), dec!(1.0));
+    }
+
+    #[test]
+    fn test_monte_carlo_var() {
+        let prices = [
+            dec!(90.0),
+            dec!(95.0),
+            dec!(100.0),
+            dec!(105.0),
+            dec!(110.0),
+        ];
+        let counter = std::cell::Cell::new(0usize);
+        let generator = |params: &WalkParams<Positive, Positive>| {
+            let index = counter.get();
+            counter.set(index + 1);
+            let first = params.init_step.clone();
+            let last = first
+                .next(Positive::from(prices[index % prices.len()]))
+                .unwrap();
+            vec![first, last]
+        };
+        let init_step = Step {
+            x: Xstep::new(
+                Positive::ONE,
+                TimeFrame::Day,
+                ExpirationDate::Days(pos!(30.0)),
+            ),
+            y: Ystep::new(0, pos!(100.0)),
+        };
+        let walk_params = WalkParams {
+            size: 2,
+            init_step,
+            walk_type: WalkType::GeometricBrownian {
+                dt: pos!(1.0),
+                drift: Decimal::ZERO,
+                volatility: pos!(0.2),
+            },
+            walker: Box::new(TestWalker),
+        };
+        let simulator = Simulator::new("VaR".to_string(), 5, &walk_params, generator);
+
+        let strategy = covered_call();
+        let report = strategy
+            .monte_carlo_var(&simulator, &VaRParameters::new(Positive::ONE, dec!(0.8)))
+            .unwrap();
+        assert_eq!(report.method, VaRMethod::MonteCarlo);
+        assert_eq!(report.scenarios.len(), 5);
+        // Interpolated 20th percentile of {-100, -50, 0, 50, 100}; only -100 lies beyond it
+        assert_decimal_eq!(report.var, dec!(60.0), dec!(0.5));
+        assert_decimal_eq!(report.expected_shortfall, dec!(100.0), dec!(0.5));
+    }
+
+    #[test]
+    fn test_monte_carlo_with_random_walks() {
+        let init_step = Step {
+            x: Xstep::new(
+                Positive::ONE,
+                TimeFrame::Day,
+                ExpirationDate::Days(pos!(30.0)),
+            ),
+            y: Ystep::new(0, pos!(100.0)),
+        };
+        let walk_params = WalkParams {
+            size: 6,
+            init_step,
+            walk_type: WalkType::GeometricBrownian {
+                dt: pos!(1.0) / pos!(365.0),
+                drift: Decimal::ZERO,
+                volatility: pos!(0.25),
+            },
+            walker: Box::new(TestWalker),
+        };
+        let simulator = Simulator::new("VaR".to_string(), 200, &walk_params, generator_positive);
+        let strategy = BullCallSpread::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(105.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.25),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(7.0),
+            pos!(2.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let report = strategy
+            .monte_carlo_var(&simulator, &VaRParameters::new(pos!(5.0), dec!(0.95)))
+            .unwrap();
+        assert_eq!(report.scenarios.len(), 200);
+        // A debit spread cannot lose more than the debit paid.
+        assert!(report.expected_shortfall <= dec!(4.5));
+    }
+
+    #[test]
+    fn test_fill_metrics() {
+        let strategy = covered_call();
+        let closes: Vec<Decimal> = (0..=40)
+            .map(|i| dec!(100.0) + Decimal::from(i % 7) - dec!(3.0))
+            .collect();
+        let report = strategy
+            .historical_var(&candles(&closes), &VaRParameters::default())
+            .unwrap();
+
+        let mut simulation = RiskMetricsSimulation::default();
+        report.fill_risk_metrics(&mut simulation);
+        assert_eq!(simulation.var_95, -report.var_95);
+        assert_eq!(simulation.var_99, -report.var_99);
+        assert_eq!(simulation.cvar_95, -report.expected_shortfall_95);
+        assert_eq!(simulation.max_drawdown, pos!(0.01));
+
+        let mut advanced = AdvancedRiskMetrics::default();
+        report.fill_advanced_risk_metrics(&mut advanced);
+        assert_eq!(advanced.value_at_risk_95, Some(report.var_95));
+        assert_eq!(advanced.value_at_risk_99, Some(report.var_99));
+        assert_eq!(
+            advanced.expected_shortfall,
+            Some(report.expected_shortfall_95)
+        );
+        assert_eq!(advanced.tail_ratio, report.tail_ratio);
+    }
+}
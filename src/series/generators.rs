@@ -128,6 +128,10 @@ pub fn generator_optionseries(
             walk_params.walker.heston(walk_params).unwrap(),
             Some(*volatility),
         ),
+        WalkType::LocalVolatility { surface, .. } => (
+            walk_params.walker.local_volatility(walk_params).unwrap(),
+            Some(surface.atm_volatility()),
+        ),
         WalkType::Custom { volatility, .. } => (
             walk_params.walker.custom(walk_params).unwrap(),
             Some(*volatility),
@@ -3,11 +3,17 @@
 //! Provides tools to manage, filter, and analyze multiple option chains grouped by expiration dates.
 //! Includes utilities for constructing series data, navigating expirations, and performing
 //! cross-expiration analysis and visualization.
+//!
+//! The term-structure queries on `OptionSeries` (ATM term structure, forward variance,
+//! constant-maturity interpolation and calendar-arbitrage checks) interpolate total
+//! implied variance linearly in time between the listed expirations.
 
 mod generators;
 mod model;
 mod params;
+mod term_structure;
 
 pub use generators::generator_optionseries;
 pub use model::OptionSeries;
 pub use params::OptionSeriesBuildParams;
+pub use term_structure::CalendarArbitrage;
@@ -0,0 +1,485 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::chains::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::curves::{Curve, Point2D};
+use crate::series::OptionSeries;
+use crate::surfaces::{Point3D, Surface};
+use rust_decimal::{Decimal, MathematicalOps};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::error::Error;
+
+/// A strike at which total implied variance decreases between two expirations.
+///
+/// Total variance `σ²T` must be non-decreasing in maturity for every strike, otherwise a
+/// calendar spread bought at that strike has a negative price.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarArbitrage {
+    /// Strike at which the violation was found.
+    pub strike: Positive,
+    /// Days to the nearer expiration.
+    pub near_days: Positive,
+    /// Days to the farther expiration.
+    pub far_days: Positive,
+    /// Total implied variance of the nearer expiration at `strike`.
+    pub near_total_variance: Decimal,
+    /// Total implied variance of the farther expiration at `strike`.
+    pub far_total_variance: Decimal,
+}
+
+/// Implied volatility of a chain at `strike`, linearly interpolated between the listed
+/// strikes and held flat beyond them. Strikes without implied volatility are ignored.
+fn chain_iv_at_strike(chain: &OptionChain, strike: Decimal) -> Option<Decimal> {
+    let points: Vec<(Decimal, Decimal)> = chain
+        .options
+        .iter()
+        .filter(|option| option.implied_volatility > Positive::ZERO)
+        .map(|option| {
+            (
+                option.strike_price.to_dec(),
+                option.implied_volatility.to_dec(),
+            )
+        })
+        .collect();
+    let (first, last) = (points.first()?, points.last()?);
+    if strike <= first.0 {
+        return Some(first.1);
+    }
+    if strike >= last.0 {
+        return Some(last.1);
+    }
+    points.windows(2).find_map(|pair| {
+        let ((k0, v0), (k1, v1)) = (pair[0], pair[1]);
+        (strike >= k0 && strike <= k1).then(|| v0 + (v1 - v0) * (strike - k0) / (k1 - k0))
+    })
+}
+
+fn years(days: Positive) -> Decimal {
+    (days / DAYS_IN_A_YEAR).to_dec()
+}
+
+impl OptionSeries {
+    /// Returns the chains with their days to expiration, ordered by maturity.
+    fn chains_by_days(&self) -> Result<Vec<(Positive, &OptionChain)>, Box<dyn Error>> {
+        let chains = self
+            .chains
+            .iter()
+            .map(|(expiration, chain)| Ok((expiration.get_days()?, chain)))
+            .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+        if chains.is_empty() {
+            return Err(format!("Option series {} has no chains", self.symbol).into());
+        }
+        Ok(chains)
+    }
+
+    /// Interpolates total variance in maturity between the listed expirations.
+    ///
+    /// `iv_at` returns the implied volatility of a chain at the point of interest. Total
+    /// variance is interpolated linearly in time between the bracketing expirations, and
+    /// the volatility is held flat before the first and after the last expiration.
+    fn iv_at_days<F>(&self, days: Positive, iv_at: F) -> Result<Positive, Box<dyn Error>>
+    where
+        F: Fn(&OptionChain) -> Option<Decimal>,
+    {
+        if days == Positive::ZERO {
+            return Err("Cannot interpolate implied volatility at zero days".into());
+        }
+        let points = self
+            .chains_by_days()?
+            .into_iter()
+            .filter(|(chain_days, _)| *chain_days > Positive::ZERO)
+            .filter_map(|(chain_days, chain)| iv_at(chain).map(|iv| (chain_days, iv)))
+            .collect::<Vec<_>>();
+        let (first, last) = match (points.first(), points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err("No chain in the series has implied volatility data".into()),
+        };
+        if days <= first.0 {
+            return Ok(Positive(first.1));
+        }
+        if days >= last.0 {
+            return Ok(Positive(last.1));
+        }
+        let t = years(days);
+        for pair in points.windows(2) {
+            let ((near_days, near_iv), (far_days, far_iv)) = (pair[0], pair[1]);
+            if days >= near_days && days <= far_days {
+                let (t0, t1) = (years(near_days), years(far_days));
+                let w0 = near_iv * near_iv * t0;
+                let w1 = far_iv * far_iv * t1;
+                let w = w0 + (w1 - w0) * (t - t0) / (t1 - t0);
+                if w <= Decimal::ZERO {
+                    return Err(format!(
+                        "Negative interpolated total variance at {days} days: calendar arbitrage"
+                    )
+                    .into());
+                }
+                return Ok(Positive((w / t).sqrt().unwrap_or(Decimal::ZERO)));
+            }
+        }
+        Err(format!("Could not bracket {days} days in the series").into())
+    }
+
+    /// Builds the at-the-money implied volatility term structure.
+    ///
+    /// For each expiration the smile is interpolated at the underlying price of its chain.
+    ///
+    /// # Returns
+    ///
+    /// A `Curve` whose x values are days to expiration and whose y values are the ATM
+    /// implied volatilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the series has no chains or an expiration cannot be converted
+    /// to days.
+    pub fn atm_term_structure(&self) -> Result<Curve, Box<dyn Error>> {
+        let points: BTreeSet<Point2D> = self
+            .chains_by_days()?
+            .into_iter()
+            .filter_map(|(days, chain)| {
+                chain_iv_at_strike(chain, chain.underlying_price.to_dec())
+                    .map(|iv| Point2D::new(days.to_dec(), iv))
+            })
+            .collect();
+        Ok(Curve::new(points))
+    }
+
+    /// Calculates the forward variance between two maturities from the ATM term structure.
+    ///
+    /// The forward variance is the annualised variance implied for the period between
+    /// `near_days` and `far_days`:
+    ///
+    /// ```math
+    /// \sigma_f^2 = \frac{\sigma_2^2 T_2 - \sigma_1^2 T_1}{T_2 - T_1}
+    /// ```
+    ///
+    /// Maturities that are not listed are interpolated with [`Self::constant_maturity_iv`].
+    /// A negative result means the term structure admits calendar arbitrage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `far_days` is not after `near_days`, or if the ATM volatility
+    /// cannot be interpolated at either maturity.
+    pub fn forward_variance(
+        &self,
+        near_days: Positive,
+        far_days: Positive,
+    ) -> Result<Decimal, Box<dyn Error>> {
+        if far_days <= near_days {
+            return Err(format!(
+                "Far maturity ({far_days} days) must be after near maturity ({near_days} days)"
+            )
+            .into());
+        }
+        let near_iv = self.constant_maturity_iv(near_days)?.to_dec();
+        let far_iv = self.constant_maturity_iv(far_days)?.to_dec();
+        let (t0, t1) = (years(near_days), years(far_days));
+        Ok((far_iv * far_iv * t1 - near_iv * near_iv * t0) / (t1 - t0))
+    }
+
+    /// Builds the curve of forward variances between consecutive listed expirations.
+    ///
+    /// Each point is placed at the farther expiration of the pair, in days.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the series has no chains or the ATM volatility of an expiration
+    /// cannot be computed.
+    pub fn forward_variance_curve(&self) -> Result<Curve, Box<dyn Error>> {
+        let days: Vec<Positive> = self
+            .chains_by_days()?
+            .into_iter()
+            .map(|(days, _)| days)
+            .filter(|days| *days > Positive::ZERO)
+            .collect();
+        let mut points = BTreeSet::new();
+        for pair in days.windows(2) {
+            let variance = self.forward_variance(pair[0], pair[1])?;
+            points.insert(Point2D::new(pair[1].to_dec(), variance));
+        }
+        Ok(Curve::new(points))
+    }
+
+    /// Interpolates the implied volatility at an arbitrary strike and maturity.
+    ///
+    /// Each chain's smile is interpolated linearly in strike (flat beyond the listed
+    /// strikes), then total variance is interpolated linearly in time between the
+    /// bracketing expirations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `days` is zero, the series has no implied volatility data, or
+    /// the interpolated total variance is negative.
+    pub fn interpolated_iv(
+        &self,
+        strike: Positive,
+        days: Positive,
+    ) -> Result<Positive, Box<dyn Error>> {
+        self.iv_at_days(days, |chain| chain_iv_at_strike(chain, strike.to_dec()))
+    }
+
+    /// Interpolates the ATM implied volatility at a constant maturity, e.g. 30 days.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error under the same conditions as [`Self::interpolated_iv`].
+    pub fn constant_maturity_iv(&self, days: Positive) -> Result<Positive, Box<dyn Error>> {
+        self.iv_at_days(days, |chain| {
+            chain_iv_at_strike(chain, chain.underlying_price.to_dec())
+        })
+    }
+
+    /// Builds the implied volatility smile at a constant maturity.
+    ///
+    /// The smile is evaluated at every strike listed in any chain of the series.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the volatility cannot be interpolated at `days`.
+    pub fn constant_maturity_smile(&self, days: Positive) -> Result<Curve, Box<dyn Error>> {
+        let mut points = BTreeSet::new();
+        for strike in self.strikes() {
+            let iv = self.interpolated_iv(strike, days)?;
+            points.insert(Point2D::new(strike.to_dec(), iv.to_dec()));
+        }
+        Ok(Curve::new(points))
+    }
+
+    /// Builds the implied volatility surface of the series.
+    ///
+    /// Points are `(strike, days to expiration, implied volatility)` for every quoted
+    /// option with a non-zero implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the series has no chains or an expiration cannot be converted
+    /// to days.
+    pub fn volatility_surface(&self) -> Result<Surface, Box<dyn Error>> {
+        let mut points = BTreeSet::new();
+        for (days, chain) in self.chains_by_days()? {
+            for option in chain
+                .options
+                .iter()
+                .filter(|option| option.implied_volatility > Positive::ZERO)
+            {
+                points.insert(Point3D::new(
+                    option.strike_price.to_dec(),
+                    days.to_dec(),
+                    option.implied_volatility.to_dec(),
+                ));
+            }
+        }
+        Ok(Surface::new(points))
+    }
+
+    /// Checks that total implied variance is non-decreasing in maturity.
+    ///
+    /// For every pair of consecutive expirations, the strikes of the nearer chain are
+    /// compared against the interpolated smile of the farther one.
+    ///
+    /// # Returns
+    ///
+    /// The violations found, empty if the series is free of calendar arbitrage.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the series has no chains or an expiration cannot be converted
+    /// to days.
+    pub fn calendar_arbitrage(&self) -> Result<Vec<CalendarArbitrage>, Box<dyn Error>> {
+        let chains = self.chains_by_days()?;
+        let mut violations = Vec::new();
+        for pair in chains.windows(2) {
+            let ((near_days, near), (far_days, far)) = (pair[0], pair[1]);
+            let (t0, t1) = (years(near_days), years(far_days));
+            for option in near
+                .options
+                .iter()
+                .filter(|option| option.implied_volatility > Positive::ZERO)
+            {
+                let Some(far_iv) = chain_iv_at_strike(far, option.strike_price.to_dec()) else {
+                    continue;
+                };
+                let near_iv = option.implied_volatility.to_dec();
+                let near_total_variance = near_iv * near_iv * t0;
+                let far_total_variance = far_iv * far_iv * t1;
+                if far_total_variance < near_total_variance {
+                    violations.push(CalendarArbitrage {
+                        strike: option.strike_price,
+                        near_days,
+                        far_days,
+                        near_total_variance,
+                        far_total_variance,
+                    });
+                }
+            }
+        }
+        Ok(violations)
+    }
+
+    /// All strikes listed in any chain of the series, in ascending order.
+    fn strikes(&self) -> BTreeSet<Positive> {
+        self.chains
+            .values()
+            .flat_map(|chain| chain.options.iter().map(|option| option.strike_price))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests_term_structure {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::{assert_decimal_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn chain_with_smile(days: Positive, smile: &[(f64, f64)]) -> OptionChain {
+        let date = get_x_days_formatted_pos(days);
+        let mut chain = OptionChain::new("TEST", pos!(100.0), date, Some(dec!(0.05)), None);
+        for (strike, iv) in smile {
+            chain.add_option(
+                pos!(*strike),
+                None,
+                None,
+                None,
+                None,
+                pos!(*iv),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        chain
+    }
+
+    fn create_series() -> OptionSeries {
+        let mut series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        let smiles = [
+            (30.0, [(90.0, 0.30), (100.0, 0.25), (110.0, 0.28)]),
+            (60.0, [(90.0, 0.28), (100.0, 0.24), (110.0, 0.26)]),
+            (90.0, [(90.0, 0.27), (100.0, 0.23), (110.0, 0.25)]),
+        ];
+        for (days, smile) in smiles {
+            series.chains.insert(
+                ExpirationDate::Days(pos!(days)),
+                chain_with_smile(pos!(days), &smile),
+            );
+        }
+        series
+    }
+
+    #[test]
+    fn test_atm_term_structure() {
+        let curve = create_series().atm_term_structure().unwrap();
+        let points: Vec<&Point2D> = curve.points.iter().collect();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0].x, dec!(30));
+        assert_eq!(points[0].y, dec!(0.25));
+        assert_eq!(points[2].y, dec!(0.23));
+    }
+
+    #[test]
+    fn test_constant_maturity_iv_interpolates_total_variance() {
+        let series = create_series();
+        assert_eq!(series.constant_maturity_iv(pos!(30.0)).unwrap(), pos!(0.25));
+        // Halfway in time between 30 and 60 days
+        let iv = series.constant_maturity_iv(pos!(45.0)).unwrap();
+        let expected = ((dec!(0.0625) * dec!(30) + dec!(0.0576) * dec!(60)) / dec!(2) / dec!(45))
+            .sqrt()
+            .unwrap();
+        assert_decimal_eq!(iv.to_dec(), expected, dec!(1e-12));
+        // Flat extrapolation outside the listed maturities
+        assert_eq!(series.constant_maturity_iv(pos!(7.0)).unwrap(), pos!(0.25));
+        assert_eq!(
+            series.constant_maturity_iv(pos!(365.0)).unwrap(),
+            pos!(0.23)
+        );
+    }
+
+    #[test]
+    fn test_interpolated_iv_in_strike_and_time() {
+        let series = create_series();
+        // Listed strike and maturity
+        assert_decimal_eq!(
+            series
+                .interpolated_iv(pos!(90.0), pos!(60.0))
+                .unwrap()
+                .to_dec(),
+            dec!(0.28),
+            dec!(1e-12)
+        );
+        // Strike halfway between 90 and 100 on a listed maturity
+        assert_decimal_eq!(
+            series
+                .interpolated_iv(pos!(95.0), pos!(60.0))
+                .unwrap()
+                .to_dec(),
+            dec!(0.26),
+            dec!(1e-12)
+        );
+        // Strike beyond the listed range is held flat
+        assert_eq!(
+            series.interpolated_iv(pos!(150.0), pos!(90.0)).unwrap(),
+            pos!(0.25)
+        );
+        assert!(series.interpolated_iv(pos!(100.0), Positive::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_forward_variance() {
+        let series = create_series();
+        let forward = series.forward_variance(pos!(30.0), pos!(60.0)).unwrap();
+        let expected = (dec!(0.0576) * dec!(60) - dec!(0.0625) * dec!(30)) / dec!(30);
+        assert_decimal_eq!(forward, expected, dec!(1e-12));
+        assert!(series.forward_variance(pos!(60.0), pos!(30.0)).is_err());
+
+        let curve = series.forward_variance_curve().unwrap();
+        assert_eq!(curve.points.len(), 2);
+        assert_decimal_eq!(curve.points.iter().next().unwrap().y, expected, dec!(1e-12));
+    }
+
+    #[test]
+    fn test_calendar_arbitrage() {
+        let mut series = create_series();
+        assert!(series.calendar_arbitrage().unwrap().is_empty());
+
+        // A 60-day wing far below the 30-day one breaks monotonic total variance
+        series.chains.insert(
+            ExpirationDate::Days(pos!(60.0)),
+            chain_with_smile(pos!(60.0), &[(90.0, 0.15), (100.0, 0.24), (110.0, 0.26)]),
+        );
+        let violations = series.calendar_arbitrage().unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].strike, pos!(90.0));
+        assert_eq!(violations[0].near_days, pos!(30.0));
+        assert_eq!(violations[0].far_days, pos!(60.0));
+        assert!(violations[0].far_total_variance < violations[0].near_total_variance);
+    }
+
+    #[test]
+    fn test_surfaces_and_smiles() {
+        let series = create_series();
+        let surface = series.volatility_surface().unwrap();
+        assert_eq!(surface.points.len(), 9);
+
+        let smile = series.constant_maturity_smile(pos!(30.0)).unwrap();
+        assert_eq!(smile.points.len(), 3);
+        assert_eq!(smile.points.iter().next().unwrap().y, dec!(0.30));
+    }
+
+    #[test]
+    fn test_empty_series() {
+        let series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        assert!(series.atm_term_structure().is_err());
+        assert!(series.constant_maturity_iv(pos!(30.0)).is_err());
+    }
+}
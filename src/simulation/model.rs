@@ -1,5 +1,6 @@
 use crate::Positive;
 use crate::utils::TimeFrame;
+use crate::volatility::LocalVolatilitySurface;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
@@ -99,6 +100,16 @@ pub enum WalkType {
         rho: Decimal,
     },
 
+    /// Local volatility model (volatility as a function of price and time)
+    LocalVolatility {
+        /// Time step size (fraction of year: daily=1/365, weekly=1/52, etc.)
+        dt: Positive,
+        /// Drift parameter (expected return)
+        drift: Decimal,
+        /// Local volatility surface `σ(S, t)`, with `t` measured from the start of the walk
+        surface: LocalVolatilitySurface,
+    },
+
     /// Custom process defined by a function
     Custom {
         /// Time step size (fraction of year: daily=1/365, weekly=1/52, etc.)
@@ -221,6 +232,13 @@ impl Display for WalkType {
                 "Heston {{ dt: {}, drift: {}, volatility: {}, kappa: {}, theta: {}, xi: {}, rho: {} }}",
                 dt, drift, volatility, kappa, theta, xi, rho
             ),
+            WalkType::LocalVolatility { dt, drift, surface } => write!(
+                f,
+                "LocalVolatility {{ dt: {}, drift: {}, atm_volatility: {} }}",
+                dt,
+                drift,
+                surface.atm_volatility()
+            ),
             WalkType::Custom {
                 dt,
                 drift,
@@ -597,6 +615,30 @@ mod tests_walk_type {
         assert!(display.contains("rho: -0.7"));
     }
 
+    #[test]
+    fn test_display_local_volatility() {
+        let surface = LocalVolatilitySurface::new(
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            vec![Decimal::ZERO],
+            vec![pos!(30.0)],
+            vec![vec![pos!(0.2)]],
+        )
+        .unwrap();
+        let walk = WalkType::LocalVolatility {
+            dt: pos!(0.01),
+            drift: dec!(0.05),
+            surface,
+        };
+
+        let display = format!("{}", walk);
+        assert!(display.contains("LocalVolatility"));
+        assert!(display.contains("dt: 0.01"));
+        assert!(display.contains("drift: 0.05"));
+        assert!(display.contains("atm_volatility: 0.2"));
+    }
+
     #[test]
     fn test_display_custom() {
         let walk = WalkType::Custom {
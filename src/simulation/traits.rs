@@ -2,6 +2,7 @@ use crate::Positive;
 use crate::model::decimal::decimal_normal_sample;
 use crate::simulation::{WalkParams, WalkType};
 use crate::volatility::generate_ou_process;
+use num_traits::ToPrimitive;
 use rust_decimal::{Decimal, MathematicalOps};
 use std::error::Error;
 use std::fmt::{Debug, Display};
@@ -33,6 +34,7 @@ use std::ops::AddAssign;
 /// - Jump diffusion process
 /// - GARCH (Generalized Autoregressive Conditional Heteroskedasticity)
 /// - Heston stochastic volatility model
+/// - Local volatility model
 /// - Custom stochastic process with mean-reverting volatility
 pub trait WalkTypeAble<X, Y>
 where
@@ -434,6 +436,52 @@ where
         }
     }
 
+    /// Generates a local volatility process.
+    ///
+    /// The price follows a geometric Brownian motion whose volatility is read from a
+    /// Dupire local volatility surface at the current price and elapsed time.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - Walk parameters for the local volatility process, including:
+    ///   - `dt`: Time step
+    ///   - `drift`: Drift coefficient for the price process
+    ///   - `surface`: Local volatility surface `σ(S, t)`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Positive>, Box<dyn Error>>` - A vector of positive values representing
+    ///   the generated local volatility path, or an error if parameters are invalid.
+    ///
+    /// # Notes
+    ///
+    /// The process is described by the SDE dS_t = μS_t dt + σ(S_t, t) S_t dW_t and is
+    /// discretized with a log-Euler step.
+    fn local_volatility(&self, params: &WalkParams<X, Y>) -> Result<Vec<Positive>, Box<dyn Error>> {
+        match &params.walk_type {
+            WalkType::LocalVolatility { dt, drift, surface } => {
+                let grid = surface.grid();
+                let dt = dt.to_f64();
+                let drift = drift.to_f64().unwrap_or(0.0);
+                let mut price = params.ystep_as_positive().to_f64();
+                let mut values = Vec::with_capacity(params.size);
+                values.push(params.ystep_as_positive());
+
+                for step in 0..params.size - 1 {
+                    let volatility = grid.volatility(price, step as f64 * dt);
+                    let z = decimal_normal_sample().to_f64().unwrap_or(0.0);
+                    price *= ((drift - volatility * volatility / 2.0) * dt
+                        + volatility * dt.sqrt() * z)
+                        .exp();
+                    values.push(Positive::new(price)?);
+                }
+
+                Ok(values)
+            }
+            _ => Err("Invalid walk type for local volatility model".into()),
+        }
+    }
+
     /// Generates a custom stochastic process with mean-reverting volatility.
     ///
     /// This implements a process where the underlying value follows Brownian motion,
@@ -548,6 +596,7 @@ mod tests_walk_type_able {
     use crate::simulation::steps::Step;
     use crate::simulation::traits::WalkTypeAble;
     use crate::utils::TimeFrame;
+    use crate::volatility::LocalVolatilitySurface;
     use rust_decimal::Decimal;
     use std::error::Error;
     use std::fmt::Display;
@@ -741,6 +790,36 @@ mod tests_walk_type_able {
         Ok(())
     }
 
+    #[test]
+    fn test_local_volatility_walk() -> Result<(), Box<dyn Error>> {
+        let surface = LocalVolatilitySurface::new(
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            vec![Decimal::new(-1, 1), Decimal::new(1, 1)],
+            vec![pos!(30.0), pos!(365.0)],
+            vec![vec![pos!(0.3), pos!(0.2)], vec![pos!(0.25), pos!(0.15)]],
+        )?;
+        let params = create_test_params(
+            5,
+            10.0,
+            100.0,
+            WalkType::LocalVolatility {
+                dt: pos!(0.004),
+                drift: Decimal::ZERO,
+                surface,
+            },
+        );
+
+        let walker = TestWalker {};
+        let result = walker.local_volatility(&params)?;
+
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[0], pos!(100.0));
+        assert!(walker.heston(&params).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_custom_walk() -> Result<(), Box<dyn Error>> {
         let params = create_test_params(
@@ -7,15 +7,17 @@ use crate::{
     error::{OperationErrorKind, position::PositionError, strategies::StrategyError},
     greeks::Greeks,
     model::{
-        Trade,
+        Trade, UnderlyingPosition,
         position::Position,
         types::{Action, OptionBasicType, OptionStyle, OptionType, Side},
     },
     pnl::PnLCalculator,
     pricing::payoff::Profit,
+    series::OptionSeries,
     strategies::{
         StrategyConstructor,
         delta_neutral::DeltaNeutrality,
+        liquidity::Liquidity,
         probabilities::core::ProbabilityAnalysis,
         utils::{FindOptimalSide, OptimizationCriteria, calculate_price_range},
     },
@@ -154,6 +156,44 @@ pub enum StrategyType {
     PoorMansCoveredCall,
     /// Call Butterfly strategy.
     CallButterfly,
+    /// Call Calendar Spread strategy.
+    CallCalendarSpread,
+    /// Put Calendar Spread strategy.
+    PutCalendarSpread,
+    /// Diagonal Call Spread strategy.
+    DiagonalCallSpread,
+    /// Diagonal Put Spread strategy.
+    DiagonalPutSpread,
+    /// Call Ratio Spread strategy.
+    CallRatioSpread,
+    /// Put Ratio Spread strategy.
+    PutRatioSpread,
+    /// Call Back Spread strategy.
+    CallBackSpread,
+    /// Put Back Spread strategy.
+    PutBackSpread,
+    /// Call Broken Wing Butterfly strategy.
+    CallBrokenWingButterfly,
+    /// Put Broken Wing Butterfly strategy.
+    PutBrokenWingButterfly,
+    /// Call Condor strategy.
+    CallCondor,
+    /// Put Condor strategy.
+    PutCondor,
+    /// Jade Lizard strategy.
+    JadeLizard,
+    /// Reverse Iron Condor strategy.
+    ReverseIronCondor,
+    /// Risk Reversal strategy.
+    RiskReversal,
+    /// Strip strategy.
+    Strip,
+    /// Strap strategy.
+    Strap,
+    /// Box Spread strategy.
+    BoxSpread,
+    /// Custom strategy with an arbitrary set of legs.
+    Custom,
 }
 
 impl FromStr for StrategyType {
@@ -182,6 +222,25 @@ impl FromStr for StrategyType {
             "ShortPut" => Ok(StrategyType::ShortPut),
             "PoorMansCoveredCall" => Ok(StrategyType::PoorMansCoveredCall),
             "CallButterfly" => Ok(StrategyType::CallButterfly),
+            "CallCalendarSpread" => Ok(StrategyType::CallCalendarSpread),
+            "PutCalendarSpread" => Ok(StrategyType::PutCalendarSpread),
+            "DiagonalCallSpread" => Ok(StrategyType::DiagonalCallSpread),
+            "DiagonalPutSpread" => Ok(StrategyType::DiagonalPutSpread),
+            "CallRatioSpread" => Ok(StrategyType::CallRatioSpread),
+            "PutRatioSpread" => Ok(StrategyType::PutRatioSpread),
+            "CallBackSpread" => Ok(StrategyType::CallBackSpread),
+            "PutBackSpread" => Ok(StrategyType::PutBackSpread),
+            "CallBrokenWingButterfly" => Ok(StrategyType::CallBrokenWingButterfly),
+            "PutBrokenWingButterfly" => Ok(StrategyType::PutBrokenWingButterfly),
+            "CallCondor" => Ok(StrategyType::CallCondor),
+            "PutCondor" => Ok(StrategyType::PutCondor),
+            "JadeLizard" => Ok(StrategyType::JadeLizard),
+            "ReverseIronCondor" => Ok(StrategyType::ReverseIronCondor),
+            "RiskReversal" => Ok(StrategyType::RiskReversal),
+            "Strip" => Ok(StrategyType::Strip),
+            "Strap" => Ok(StrategyType::Strap),
+            "BoxSpread" => Ok(StrategyType::BoxSpread),
+            "Custom" => Ok(StrategyType::Custom),
             _ => Err(()),
         }
     }
@@ -1210,6 +1269,43 @@ pub trait Optimizable: Validable + Strategies {
         std::iter::empty()
     }
 
+    /// Turns a combination yielded by `filter_combinations` into the legs expected by
+    /// `create_strategy`.
+    ///
+    /// By default the options of the group are the legs, in the same order. Strategies that
+    /// use one option of the group for several legs, such as straddles, override this.
+    ///
+    /// # Returns
+    /// `None` when the group has a number of options no `StrategyLegs` variant can hold.
+    fn group_legs<'a>(&self, group: OptionDataGroup<'a>) -> Option<StrategyLegs<'a>> {
+        match group {
+            OptionDataGroup::One(first) => Some(StrategyLegs::OneLeg { first }),
+            OptionDataGroup::Two(first, second) => Some(StrategyLegs::TwoLegs { first, second }),
+            OptionDataGroup::Three(first, second, third) => Some(StrategyLegs::ThreeLegs {
+                first,
+                second,
+                third,
+            }),
+            OptionDataGroup::Four(first, second, third, fourth) => Some(StrategyLegs::FourLegs {
+                first,
+                second,
+                third,
+                fourth,
+            }),
+            OptionDataGroup::Any(options) => match *options.as_slice() {
+                [first, second, third, fourth, fifth, sixth] => Some(StrategyLegs::SixLegs {
+                    first,
+                    second,
+                    third,
+                    fourth,
+                    fifth,
+                    sixth,
+                }),
+                _ => None,
+            },
+        }
+    }
+
     /// Finds the optimal strategy based on the given criteria.
     /// The default implementation panics.  Specific strategies should override
     /// this method to provide their own optimization logic.
@@ -1227,6 +1323,60 @@ pub trait Optimizable: Validable + Strategies {
         panic!("Find optimal is not applicable for this strategy");
     }
 
+    /// Finds the optimal strategy across the expirations of an `OptionSeries`.
+    /// Only strategies whose legs expire on different dates can search a series;
+    /// the default implementation logs an error and leaves the strategy unchanged,
+    /// as when no candidate is valid.
+    ///
+    /// # Arguments
+    /// * `_option_series` - A reference to the `OptionSeries` whose chains are searched.
+    /// * `_side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `_criteria` - An `OptimizationCriteria` value indicating the optimization goal (e.g., ratio, area).
+    fn find_optimal_in_series(
+        &mut self,
+        _option_series: &OptionSeries,
+        _side: FindOptimalSide,
+        _criteria: OptimizationCriteria,
+    ) {
+        error!("Find optimal in series is not applicable for this strategy");
+    }
+
+    /// Finds the optimal strategy among the liquid strikes of `option_chain`, pricing its
+    /// legs with the fill model of `liquidity` rather than at the bid and ask.
+    ///
+    /// # Arguments
+    /// * `option_chain` - A reference to the `OptionChain` containing option data.
+    /// * `side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `criteria` - An `OptimizationCriteria` value indicating the optimization goal (e.g., ratio, area).
+    /// * `liquidity` - The liquidity requirements and fill model applied to the chain.
+    fn find_optimal_liquid(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+        liquidity: &Liquidity,
+    ) {
+        self.find_optimal(&liquidity.apply(option_chain), side, criteria);
+    }
+
+    /// Finds the optimal strategy across the expirations of an `OptionSeries`, keeping
+    /// only liquid strikes and pricing the legs with the fill model of `liquidity`.
+    ///
+    /// # Arguments
+    /// * `option_series` - A reference to the `OptionSeries` whose chains are searched.
+    /// * `side` - A `FindOptimalSide` value specifying the filtering strategy.
+    /// * `criteria` - An `OptimizationCriteria` value indicating the optimization goal (e.g., ratio, area).
+    /// * `liquidity` - The liquidity requirements and fill model applied to every chain.
+    fn find_optimal_in_series_liquid(
+        &mut self,
+        option_series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+        liquidity: &Liquidity,
+    ) {
+        self.find_optimal_in_series(&liquidity.apply_to_series(option_series), side, criteria);
+    }
+
     /// Checks if a long option is valid based on the given criteria.
     ///
     /// # Arguments
@@ -1325,6 +1475,19 @@ pub trait Positionable {
         ))
     }
 
+    /// Retrieves the position held directly in the underlying asset, if any.
+    ///
+    /// Option positions are reported by `get_positions`; strategies that also hold the
+    /// underlying (covered calls, protective puts, collars) expose that leg here so that
+    /// margin and risk calculations can take it into account.
+    ///
+    /// # Default Implementation
+    ///
+    /// Returns `None`, as most strategies are made of option legs only.
+    fn get_underlying_position(&self) -> Option<&UnderlyingPosition> {
+        None
+    }
+
     /// Retrieves a specific position based on option style, side, and strike.
     ///
     /// # Arguments
@@ -1666,6 +1829,19 @@ mod tests_strategy_type {
             StrategyType::from_str("BullCallSpread"),
             Ok(StrategyType::BullCallSpread)
         );
+        assert_eq!(
+            StrategyType::from_str("CallCalendarSpread"),
+            Ok(StrategyType::CallCalendarSpread)
+        );
+        assert_eq!(
+            StrategyType::from_str("JadeLizard"),
+            Ok(StrategyType::JadeLizard)
+        );
+        assert_eq!(
+            StrategyType::from_str("CallBrokenWingButterfly"),
+            Ok(StrategyType::CallBrokenWingButterfly)
+        );
+        assert_eq!(StrategyType::from_str("Custom"), Ok(StrategyType::Custom));
         assert_eq!(StrategyType::from_str("InvalidStrategy"), Err(()));
     }
 
@@ -2142,6 +2318,20 @@ mod tests_optimizable {
         );
         assert!(strategy.is_valid_optimal_option(&option_data, &FindOptimalSide::Lower));
     }
+
+    #[test]
+    fn test_find_optimal_in_series_not_applicable() {
+        let mut strategy = TestOptimizableStrategy;
+        let series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        // Strategies without legs on different expirations are left as they are
+        strategy.find_optimal_in_series(&series, FindOptimalSide::All, OptimizationCriteria::Ratio);
+        strategy.find_optimal_in_series_liquid(
+            &series,
+            FindOptimalSide::All,
+            OptimizationCriteria::Area,
+            &Liquidity::default(),
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,111 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const BOX_SPREAD_DESCRIPTION: &str = "A Box Spread combines a bull call spread and a bear put spread on the same strikes. \
+    It pays the strike width at expiration whatever the underlying does, so its price reflects \
+    the interest rate and any mispricing between the legs.";
+
+static BOX_SPREAD: StructureSpec = StructureSpec {
+    kind: StrategyType::BoxSpread,
+    name: "Box Spread",
+    description: BOX_SPREAD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Long, false),
+        leg(0, OptionStyle::Put, Side::Short, false),
+        leg(1, OptionStyle::Call, Side::Short, false),
+        leg(1, OptionStyle::Put, Side::Long, false),
+    ],
+    ratio: QuantityRatio::One,
+    strikes: distinct_strikes,
+};
+
+/// A long call and a short put at the lower strike with a short call and a long put at the
+/// upper strike, all on the same expiration.
+///
+/// The box is worth the distance between the strikes at expiration, so its profit is the
+/// same at every price: the difference between that width and the debit paid.
+///
+/// Legs, by ascending strike: long call and short put, then short call and long put.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct BoxSpread {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl BoxSpread {
+    /// Creates a Box Spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `lower_strike` - Strike of the long call and the short put.
+    /// * `upper_strike` - Strike of the short call and the long put.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of every leg.
+    /// * `premium_long_call` - The premium paid for the long call.
+    /// * `premium_short_put` - The premium received for the short put.
+    /// * `premium_short_call` - The premium received for the short call.
+    /// * `premium_long_put` - The premium paid for the long put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_call: Positive,
+        premium_short_put: Positive,
+        premium_short_call: Positive,
+        premium_long_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = BOX_SPREAD.build(
+            underlying_symbol,
+            underlying_price,
+            &[lower_strike, upper_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::ONE,
+            &[
+                premium_long_call,
+                premium_short_put,
+                premium_short_call,
+                premium_long_put,
+            ],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(BoxSpread { strategy })
+    }
+}
+
+impl_structure!(BoxSpread, BOX_SPREAD);
@@ -8,9 +8,13 @@ use crate::error::StrategyError;
 use crate::model::Position;
 use crate::strategies::base::StrategyType;
 use crate::strategies::{
-    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly, IronButterfly,
-    IronCondor, LongButterflySpread, LongStraddle, LongStrangle, PoorMansCoveredCall,
-    ShortButterflySpread, ShortStraddle, ShortStrangle, Strategable, StrategyConstructor,
+    BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread, CallBackSpread,
+    CallBrokenWingButterfly, CallButterfly, CallCalendarSpread, CallCondor, CallRatioSpread,
+    Collar, CoveredCall, CustomStrategy, DiagonalCallSpread, DiagonalPutSpread, IronButterfly,
+    IronCondor, JadeLizard, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
+    PoorMansCoveredCall, ProtectivePut, PutBackSpread, PutBrokenWingButterfly, PutCalendarSpread,
+    PutCondor, PutRatioSpread, ReverseIronCondor, RiskReversal, ShortButterflySpread, ShortCall,
+    ShortPut, ShortStraddle, ShortStrangle, Strap, Strategable, StrategyConstructor, Strip,
 };
 use serde::{Deserialize, Serialize};
 
@@ -111,19 +115,64 @@ impl StrategyRequest {
             StrategyType::ShortStrangle => {
                 Ok(Box::new(ShortStrangle::get_strategy(&self.positions)?))
             }
-            StrategyType::CoveredCall => Err(StrategyError::NotImplemented),
-            StrategyType::ProtectivePut => Err(StrategyError::NotImplemented),
-            StrategyType::Collar => Err(StrategyError::NotImplemented),
-            StrategyType::LongCall => Err(StrategyError::NotImplemented),
-            StrategyType::LongPut => Err(StrategyError::NotImplemented),
-            StrategyType::ShortCall => Err(StrategyError::NotImplemented),
-            StrategyType::ShortPut => Err(StrategyError::NotImplemented),
+            StrategyType::CoveredCall => Ok(Box::new(CoveredCall::get_strategy(&self.positions)?)),
+            StrategyType::ProtectivePut => {
+                Ok(Box::new(ProtectivePut::get_strategy(&self.positions)?))
+            }
+            StrategyType::Collar => Ok(Box::new(Collar::get_strategy(&self.positions)?)),
+            StrategyType::CallRatioSpread => {
+                Ok(Box::new(CallRatioSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::PutRatioSpread => {
+                Ok(Box::new(PutRatioSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::CallBackSpread => {
+                Ok(Box::new(CallBackSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::PutBackSpread => {
+                Ok(Box::new(PutBackSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::CallBrokenWingButterfly => Ok(Box::new(
+                CallBrokenWingButterfly::get_strategy(&self.positions)?,
+            )),
+            StrategyType::PutBrokenWingButterfly => Ok(Box::new(
+                PutBrokenWingButterfly::get_strategy(&self.positions)?,
+            )),
+            StrategyType::CallCondor => Ok(Box::new(CallCondor::get_strategy(&self.positions)?)),
+            StrategyType::PutCondor => Ok(Box::new(PutCondor::get_strategy(&self.positions)?)),
+            StrategyType::JadeLizard => Ok(Box::new(JadeLizard::get_strategy(&self.positions)?)),
+            StrategyType::ReverseIronCondor => {
+                Ok(Box::new(ReverseIronCondor::get_strategy(&self.positions)?))
+            }
+            StrategyType::RiskReversal => {
+                Ok(Box::new(RiskReversal::get_strategy(&self.positions)?))
+            }
+            StrategyType::Strip => Ok(Box::new(Strip::get_strategy(&self.positions)?)),
+            StrategyType::Strap => Ok(Box::new(Strap::get_strategy(&self.positions)?)),
+            StrategyType::BoxSpread => Ok(Box::new(BoxSpread::get_strategy(&self.positions)?)),
+            StrategyType::Custom => Ok(Box::new(CustomStrategy::get_strategy(&self.positions)?)),
+            StrategyType::LongCall => Ok(Box::new(LongCall::get_strategy(&self.positions)?)),
+            StrategyType::LongPut => Ok(Box::new(LongPut::get_strategy(&self.positions)?)),
+            StrategyType::ShortCall => Ok(Box::new(ShortCall::get_strategy(&self.positions)?)),
+            StrategyType::ShortPut => Ok(Box::new(ShortPut::get_strategy(&self.positions)?)),
             StrategyType::PoorMansCoveredCall => Ok(Box::new(PoorMansCoveredCall::get_strategy(
                 &self.positions,
             )?)),
             StrategyType::CallButterfly => {
                 Ok(Box::new(CallButterfly::get_strategy(&self.positions)?))
             }
+            StrategyType::CallCalendarSpread => {
+                Ok(Box::new(CallCalendarSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::PutCalendarSpread => {
+                Ok(Box::new(PutCalendarSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::DiagonalCallSpread => {
+                Ok(Box::new(DiagonalCallSpread::get_strategy(&self.positions)?))
+            }
+            StrategyType::DiagonalPutSpread => {
+                Ok(Box::new(DiagonalPutSpread::get_strategy(&self.positions)?))
+            }
         }
     }
 }
@@ -294,6 +343,7 @@ mod tests {
     use crate::model::utils::create_sample_option_with_date;
     use crate::{OptionStyle, Side, assert_decimal_eq, pos};
     use chrono::{DateTime, NaiveDateTime, Utc};
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
     use serde_json;
 
@@ -574,6 +624,31 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::CoveredCall, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let strategy_request = StrategyRequest::new(
+            StrategyType::CoveredCall,
+            vec![Position::new(
+                create_sample_option_with_date(
+                    OptionStyle::Call,
+                    Side::Short,
+                    pos!(920.0),
+                    pos!(1.0),
+                    pos!(930.0),
+                    pos!(0.35),
+                    sample_date(),
+                ),
+                pos!(4.5),
+                Utc::now(),
+                pos!(1.0),
+                pos!(1.2),
+                None,
+                None,
+            )],
+        );
+        let strategy = strategy_request.get_strategy().unwrap();
+        // The stock leg dominates the delta of the short call
+        let greeks = strategy.greeks().unwrap();
+        assert!(greeks.delta > dec!(0.0) && greeks.delta < dec!(1.0));
     }
 
     #[test]
@@ -588,6 +663,70 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::Collar, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let position = |style, side, strike, premium| {
+            Position::new(
+                create_sample_option_with_date(
+                    style,
+                    side,
+                    pos!(920.0),
+                    pos!(1.0),
+                    strike,
+                    pos!(0.35),
+                    sample_date(),
+                ),
+                premium,
+                Utc::now(),
+                pos!(1.0),
+                pos!(1.2),
+                None,
+                None,
+            )
+        };
+        let strategy_request = StrategyRequest::new(
+            StrategyType::Collar,
+            vec![
+                position(OptionStyle::Put, Side::Long, pos!(900.0), pos!(3.0)),
+                position(OptionStyle::Call, Side::Short, pos!(940.0), pos!(3.5)),
+            ],
+        );
+        let strategy = strategy_request.get_strategy().unwrap();
+        let greeks = strategy.greeks().unwrap();
+        assert!(greeks.delta > dec!(0.0) && greeks.delta < dec!(1.0));
+    }
+
+    /// Serializes a single-leg request to JSON, reads it back and builds the strategy.
+    fn round_trip_single_leg(
+        strategy_type: StrategyType,
+        style: OptionStyle,
+        side: Side,
+    ) -> Box<dyn Strategable> {
+        let strategy_request = StrategyRequest::new(
+            strategy_type.clone(),
+            vec![Position::new(
+                create_sample_option_with_date(
+                    style,
+                    side,
+                    pos!(920.0),
+                    pos!(1.0),
+                    pos!(900.0),
+                    pos!(0.35),
+                    sample_date(),
+                ),
+                pos!(25.0),
+                Utc::now(),
+                pos!(1.0),
+                pos!(1.0),
+                None,
+                None,
+            )],
+        );
+        let json = serde_json::to_string(&strategy_request).unwrap();
+        let deserialized: StrategyRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.strategy_type, strategy_type);
+        let strategy = deserialized.get_strategy().unwrap();
+        assert_eq!(strategy.type_name(), strategy_type);
+        strategy
     }
 
     #[test]
@@ -595,6 +734,14 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::LongCall, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let strategy = round_trip_single_leg(StrategyType::LongCall, OptionStyle::Call, Side::Long);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(927.0)]
+        );
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(27.0));
+        assert!(strategy.greeks().unwrap().delta > Decimal::ZERO);
     }
 
     #[test]
@@ -602,6 +749,15 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::LongPut, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let strategy = round_trip_single_leg(StrategyType::LongPut, OptionStyle::Put, Side::Long);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(873.0)]
+        );
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(27.0));
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(873.0));
+        assert!(strategy.greeks().unwrap().delta < Decimal::ZERO);
     }
 
     #[test]
@@ -609,6 +765,16 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::ShortCall, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let strategy =
+            round_trip_single_leg(StrategyType::ShortCall, OptionStyle::Call, Side::Short);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(923.0)]
+        );
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(23.0));
+        assert!(strategy.get_max_loss().is_err());
+        assert!(strategy.greeks().unwrap().delta < Decimal::ZERO);
     }
 
     #[test]
@@ -616,5 +782,46 @@ mod tests {
         let strategy_request = StrategyRequest::new(StrategyType::ShortPut, vec![]);
         let result = strategy_request.get_strategy();
         assert!(result.is_err());
+
+        let strategy = round_trip_single_leg(StrategyType::ShortPut, OptionStyle::Put, Side::Short);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(877.0)]
+        );
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(23.0));
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(877.0));
+        assert!(strategy.greeks().unwrap().delta > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_strategy_single_leg_mismatch() {
+        let position = Position::new(
+            create_sample_option_with_date(
+                OptionStyle::Put,
+                Side::Long,
+                pos!(920.0),
+                pos!(1.0),
+                pos!(900.0),
+                pos!(0.35),
+                sample_date(),
+            ),
+            pos!(25.0),
+            Utc::now(),
+            pos!(1.0),
+            pos!(1.0),
+            None,
+            None,
+        );
+        for strategy_type in [
+            StrategyType::LongCall,
+            StrategyType::ShortCall,
+            StrategyType::ShortPut,
+        ] {
+            let strategy_request = StrategyRequest::new(strategy_type, vec![position.clone()]);
+            assert!(strategy_request.get_strategy().is_err());
+        }
+        let strategy_request =
+            StrategyRequest::new(StrategyType::LongPut, vec![position.clone(), position]);
+        assert!(strategy_request.get_strategy().is_err());
     }
 }
@@ -0,0 +1,101 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const CALL_BACK_SPREAD_DESCRIPTION: &str = "A Call Back Spread sells calls at a lower strike and buys a larger number of calls at a \
+    higher strike. It keeps the net credit if the underlying falls, loses most at the long strike \
+    and gains without limit on a strong rally.";
+
+static CALL_BACK_SPREAD: StructureSpec = StructureSpec {
+    kind: StrategyType::CallBackSpread,
+    name: "Call Back Spread",
+    description: CALL_BACK_SPREAD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Short, false),
+        leg(1, OptionStyle::Call, Side::Long, true),
+    ],
+    ratio: QuantityRatio::AboveOne,
+    strikes: distinct_strikes,
+};
+
+/// A short call and a larger number of long calls at a higher strike, on the same expiration.
+///
+/// The reverse of a call ratio spread, the strategy bets on a strong rally or on higher
+/// volatility. Its loss is bounded and largest at the long strike.
+///
+/// Legs, by ascending strike: `quantity` short calls and `quantity * ratio` long calls.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CallBackSpread {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl CallBackSpread {
+    /// Creates a Call Back Spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `short_strike` - Strike of the short calls, the lower one.
+    /// * `long_strike` - Strike of the long calls, the higher one.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of short calls.
+    /// * `ratio` - Long calls bought per short call, above one.
+    /// * `premium_short_call` - The premium received for each short call.
+    /// * `premium_long_call` - The premium paid for each long call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal or the ratio is not above one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_strike: Positive,
+        long_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_short_call: Positive,
+        premium_long_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = CALL_BACK_SPREAD.build(
+            underlying_symbol,
+            underlying_price,
+            &[short_strike, long_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            ratio,
+            &[premium_short_call, premium_long_call],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(CallBackSpread { strategy })
+    }
+}
+
+impl_structure!(CallBackSpread, CALL_BACK_SPREAD);
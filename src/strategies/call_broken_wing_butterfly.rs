@@ -0,0 +1,106 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, impl_structure, leg, unequal_wings};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const CALL_BROKEN_WING_BUTTERFLY_DESCRIPTION: &str = "A Call Broken Wing Butterfly is a call butterfly whose wings are not equally wide. \
+    Skipping a strike on one side lowers the cost, often to a credit, and moves all the risk to \
+    that side.";
+
+static CALL_BROKEN_WING_BUTTERFLY: StructureSpec = StructureSpec {
+    kind: StrategyType::CallBrokenWingButterfly,
+    name: "Call Broken Wing Butterfly",
+    description: CALL_BROKEN_WING_BUTTERFLY_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Long, false),
+        leg(1, OptionStyle::Call, Side::Short, true),
+        leg(2, OptionStyle::Call, Side::Long, false),
+    ],
+    ratio: QuantityRatio::Fixed(Positive::TWO),
+    strikes: unequal_wings,
+};
+
+/// A long call, two short calls and a long call at increasing strikes, with wings of
+/// different width, all on the same expiration.
+///
+/// With the upper wing wider, as usual, the structure is entered for little or no debit,
+/// keeps that outcome below the lower strike and carries its risk above the body.
+///
+/// Legs, by ascending strike: `quantity` long calls, `2 * quantity` short calls and
+/// `quantity` long calls.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CallBrokenWingButterfly {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl CallBrokenWingButterfly {
+    /// Creates a Call Broken Wing Butterfly.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `lower_strike` - Strike of the lower long call.
+    /// * `middle_strike` - Strike of the short calls, the body.
+    /// * `upper_strike` - Strike of the upper long call.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of each wing.
+    /// * `premium_lower_call` - The premium paid for the lower long call.
+    /// * `premium_middle_call` - The premium received for each short call.
+    /// * `premium_upper_call` - The premium paid for the upper long call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are not distinct or the wings are equally wide.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        middle_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_lower_call: Positive,
+        premium_middle_call: Positive,
+        premium_upper_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = CALL_BROKEN_WING_BUTTERFLY.build(
+            underlying_symbol,
+            underlying_price,
+            &[lower_strike, middle_strike, upper_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::TWO,
+            &[premium_lower_call, premium_middle_call, premium_upper_call],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(CallBrokenWingButterfly { strategy })
+    }
+}
+
+impl_structure!(CallBrokenWingButterfly, CALL_BROKEN_WING_BUTTERFLY);
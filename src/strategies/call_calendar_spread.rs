@@ -0,0 +1,1064 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Call Calendar Spread Strategy
+//!
+//! A call calendar spread sells a near-term call and buys a longer-term call at the same
+//! strike. The position is opened for a net debit and profits from the faster time decay
+//! of the front-month option, reaching its best result when the underlying sits at the
+//! strike on the front-month expiration.
+//!
+//! Unlike single-expiry strategies, its payoff is measured when the front-month call
+//! expires: the short call is settled at intrinsic value and the long call is valued with
+//! Black-Scholes over the time it still has left.
+//!
+//! ## Key characteristics
+//! - Neutral outlook around the strike
+//! - Long vega: benefits from a rise in back-month implied volatility
+//! - Maximum loss close to the net debit paid
+//! - Two break-even points around the strike
+
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::time_spread;
+use crate::chains::OptionData;
+use crate::series::OptionSeries;
+use crate::{
+    ExpirationDate, Options, Positive,
+    chains::{StrategyLegs, chain::OptionChain},
+    constants::ZERO,
+    error::{
+        GreeksError, OperationErrorKind,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::{ProfitLossErrorKind, StrategyError},
+    },
+    greeks::Greeks,
+    model::{
+        ProfitLossRange,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+};
+use chrono::Utc;
+use num_traits::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing::debug;
+
+pub(super) const CALL_CALENDAR_SPREAD_DESCRIPTION: &str = "A Call Calendar Spread sells a near-term call \
+    and buys a longer-term call at the same strike. It is opened for a net debit and profits from the faster \
+    time decay of the front-month option, with the best result when the underlying trades at the strike \
+    when the front-month call expires.";
+
+/// # CallCalendarSpread
+///
+/// Represents a call calendar spread (also known as a horizontal or time spread): a short
+/// front-month call and a long back-month call sharing the same strike.
+///
+/// ## Fields
+/// * `name`: A descriptive name for the specific strategy instance.
+/// * `kind`: The type of strategy, which is `StrategyType::CallCalendarSpread`.
+/// * `description`: A detailed description of this specific strategy instance.
+/// * `break_even_points`: Underlying prices at the front-month expiration where the strategy neither
+///   makes nor loses money.
+/// * `short_call`: The front-month call that is sold.
+/// * `long_call`: The back-month call that is bought.
+///
+/// ## Profit and Loss
+/// Profit and loss are evaluated when the short call expires. The long call is then priced with
+/// Black-Scholes using its own implied volatility and the days remaining until its expiration, so
+/// the profit curve peaks at the strike and the maximum loss approaches the net debit when the
+/// underlying moves far away from it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CallCalendarSpread {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a CallCalendarSpread strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Price points at the front-month expiration where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The front-month call option that is sold
+    pub(super) short_call: Position,
+    /// The back-month call option that is bought
+    pub(super) long_call: Position,
+}
+
+impl CallCalendarSpread {
+    /// Creates a new Call Calendar Spread.
+    ///
+    /// ## Parameters
+    /// * `underlying_symbol`: Symbol of the underlying security
+    /// * `underlying_price`: Current market price of the underlying security
+    /// * `strike`: Strike price shared by both calls
+    /// * `short_call_expiration`: Expiration of the front-month call that is sold
+    /// * `long_call_expiration`: Expiration of the back-month call that is bought, later than
+    ///   `short_call_expiration`
+    /// * `short_call_volatility`: Implied volatility of the front-month call
+    /// * `long_call_volatility`: Implied volatility of the back-month call
+    /// * `risk_free_rate`: Risk-free interest rate
+    /// * `dividend_yield`: Dividend yield of the underlying security
+    /// * `quantity`: Number of contracts for both legs
+    /// * `premium_short_call`: Premium received for the front-month call
+    /// * `premium_long_call`: Premium paid for the back-month call
+    /// * `open_fee_short_call`: Fee for opening the short call
+    /// * `close_fee_short_call`: Fee for closing the short call
+    /// * `open_fee_long_call`: Fee for opening the long call
+    /// * `close_fee_long_call`: Fee for closing the long call
+    ///
+    /// ## Panics
+    /// Panics if the long call does not expire after the short call, since the break-even points
+    /// are measured at the front-month expiration.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        short_call_expiration: ExpirationDate,
+        long_call_expiration: ExpirationDate,
+        short_call_volatility: Positive,
+        long_call_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_call: Positive,
+        premium_long_call: Positive,
+        open_fee_short_call: Positive,
+        close_fee_short_call: Positive,
+        open_fee_long_call: Positive,
+        close_fee_long_call: Positive,
+    ) -> Self {
+        let mut strategy = CallCalendarSpread::default();
+
+        let short_call_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol.clone(),
+            strike,
+            short_call_expiration,
+            short_call_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Call,
+            dividend_yield,
+            None,
+        );
+        let short_call = Position::new(
+            short_call_option,
+            premium_short_call,
+            Utc::now(),
+            open_fee_short_call,
+            close_fee_short_call,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&short_call)
+            .expect("Invalid short call option");
+
+        let long_call_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            strike,
+            long_call_expiration,
+            long_call_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Call,
+            dividend_yield,
+            None,
+        );
+        let long_call = Position::new(
+            long_call_option,
+            premium_long_call,
+            Utc::now(),
+            open_fee_long_call,
+            close_fee_long_call,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&long_call)
+            .expect("Invalid long call option");
+
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
+        strategy
+    }
+
+    /// Builds a spread with the sizing and fees of `self` from the quotes of two chains.
+    /// Returns `None` when the short call has no bid, the long call has no ask or either
+    /// leg has no implied volatility.
+    fn spread_from_quotes(
+        &self,
+        chain: &OptionChain,
+        short: &OptionData,
+        short_expiration: ExpirationDate,
+        long: &OptionData,
+        long_expiration: ExpirationDate,
+    ) -> Option<Self> {
+        let premium_short_call = short.call_bid.filter(|bid| *bid > Positive::ZERO)?;
+        let premium_long_call = long.call_ask.filter(|ask| *ask > Positive::ZERO)?;
+        Some(CallCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            short.strike_price,
+            short_expiration,
+            long_expiration,
+            time_spread::quoted_volatility(short)?,
+            time_spread::quoted_volatility(long)?,
+            chain
+                .risk_free_rate
+                .unwrap_or(self.short_call.option.risk_free_rate),
+            chain
+                .dividend_yield
+                .unwrap_or(self.short_call.option.dividend_yield),
+            self.short_call.option.quantity,
+            premium_short_call,
+            premium_long_call,
+            self.short_call.open_fee,
+            self.short_call.close_fee,
+            self.long_call.open_fee,
+            self.long_call.close_fee,
+        ))
+    }
+
+    /// Whether `option` is an acceptable strike for the spread. `Center` keeps only the
+    /// strike closest to the money.
+    fn is_valid_strike(
+        &self,
+        option: &OptionData,
+        side: &FindOptimalSide,
+        atm_strike: Option<Positive>,
+    ) -> bool {
+        match side {
+            FindOptimalSide::Center => Some(option.strike_price) == atm_strike,
+            _ => self.is_valid_optimal_option(option, side),
+        }
+    }
+}
+
+impl StrategyConstructor for CallCalendarSpread {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 2 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Call Calendar Spread get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        }
+
+        if vec_positions
+            .iter()
+            .any(|position| position.option.option_style != OptionStyle::Call)
+        {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Call Calendar Spread get_strategy".to_string(),
+                    reason: "Options must be calls".to_string(),
+                },
+            ));
+        }
+
+        let short_call = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Short);
+        let long_call = vec_positions
+            .iter()
+            .find(|position| position.option.side == Side::Long);
+        let (short_call, long_call) = match (short_call, long_call) {
+            (Some(short_call), Some(long_call)) => (short_call.clone(), long_call.clone()),
+            _ => {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Call Calendar Spread get_strategy".to_string(),
+                        reason: "Call Calendar Spread requires a short call and a long call"
+                            .to_string(),
+                    },
+                ));
+            }
+        };
+
+        if short_call.option.strike_price != long_call.option.strike_price {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Call Calendar Spread get_strategy".to_string(),
+                    reason: "Both calls must share the same strike".to_string(),
+                },
+            ));
+        }
+
+        if time_spread::remaining_expiration(&short_call.option, &long_call.option).is_err() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Call Calendar Spread get_strategy".to_string(),
+                    reason: "The long call must expire after the short call".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = CallCalendarSpread {
+            name: "Call Calendar Spread".to_string(),
+            kind: StrategyType::CallCalendarSpread,
+            description: CALL_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_call,
+            long_call,
+        };
+
+        strategy.validate();
+        strategy.update_break_even_points()?;
+
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for CallCalendarSpread {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = time_spread::break_even_points(&self.short_call, &self.long_call)?;
+        Ok(())
+    }
+}
+
+impl Validable for CallCalendarSpread {
+    fn validate(&self) -> bool {
+        self.short_call.validate()
+            && self.long_call.validate()
+            && self.short_call.option.strike_price == self.long_call.option.strike_price
+            && time_spread::remaining_expiration(&self.short_call.option, &self.long_call.option)
+                .is_ok()
+    }
+}
+
+impl Positionable for CallCalendarSpread {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Call, Side::Short) => {
+                self.short_call = position.clone();
+                Ok(())
+            }
+            (OptionStyle::Call, Side::Long) => {
+                self.long_call = position.clone();
+                Ok(())
+            }
+            _ => Err(PositionError::invalid_position_style(
+                position.option.option_style,
+                "Position is a Put, it is not valid for CallCalendarSpread".to_string(),
+            )),
+        }
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.short_call, &self.long_call])
+    }
+
+    /// Gets mutable positions matching the specified criteria from the strategy.
+    ///
+    /// # Arguments
+    /// * `option_style` - The style of the option (Put/Call)
+    /// * `side` - The side of the position (Long/Short)
+    /// * `strike` - The strike price of the option
+    ///
+    /// # Returns
+    /// * `Ok(Vec<&mut Position>)` - A vector containing mutable references to matching positions
+    /// * `Err(PositionError)` - If there was an error retrieving positions
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        match (side, option_style, strike) {
+            (_, OptionStyle::Put, _) => Err(PositionError::invalid_position_type(
+                *side,
+                "Put is not valid for CallCalendarSpread".to_string(),
+            )),
+            (Side::Short, OptionStyle::Call, strike)
+                if *strike == self.short_call.option.strike_price =>
+            {
+                Ok(vec![&mut self.short_call])
+            }
+            (Side::Long, OptionStyle::Call, strike)
+                if *strike == self.long_call.option.strike_price =>
+            {
+                Ok(vec![&mut self.long_call])
+            }
+            _ => Err(PositionError::invalid_position_type(
+                *side,
+                "Strike not found in positions".to_string(),
+            )),
+        }
+    }
+
+    /// Modifies an existing position in the strategy.
+    ///
+    /// # Arguments
+    /// * `position` - The new position data to update
+    ///
+    /// # Returns
+    /// * `Ok(())` if position was successfully modified
+    /// * `Err(PositionError)` if position was not found or validation failed
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+
+        match (
+            &position.option.side,
+            &position.option.option_style,
+            &position.option.strike_price,
+        ) {
+            (_, OptionStyle::Put, _) => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Put is not valid for CallCalendarSpread".to_string(),
+                ));
+            }
+            (Side::Short, OptionStyle::Call, strike)
+                if *strike == self.short_call.option.strike_price =>
+            {
+                self.short_call = position.clone();
+            }
+            (Side::Long, OptionStyle::Call, strike)
+                if *strike == self.long_call.option.strike_price =>
+            {
+                self.long_call = position.clone();
+            }
+            _ => {
+                return Err(PositionError::invalid_position_type(
+                    position.option.side,
+                    "Strike not found in positions".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Strategable for CallCalendarSpread {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CallCalendarSpread {
+    fn get_title(&self) -> String {
+        let strategy_title = format!("{:?} Strategy: ", self.kind);
+        let leg_titles: Vec<String> = [self.short_call.get_title(), self.long_call.get_title()]
+            .iter()
+            .map(|leg| leg.to_string())
+            .collect();
+
+        if leg_titles.is_empty() {
+            strategy_title
+        } else {
+            format!("{}\n\t{}", strategy_title, leg_titles.join("\n\t"))
+        }
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.short_call.option, &self.long_call.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.short_call.option, &self.long_call.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.short_call.option, &self.long_call.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.short_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.short_call.one_option_mut()
+    }
+    /// Moves the front-month call to `expiration_date` and shifts the back-month call by the
+    /// same amount, so the distance between both expirations is preserved.
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        let long_call_expiration = time_spread::shifted_back_expiration(
+            &self.short_call.option,
+            &self.long_call.option,
+            &expiration_date,
+        )?;
+        self.short_call.option.expiration_date = expiration_date;
+        self.long_call.option.expiration_date = long_call_expiration;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        self.short_call.option.underlying_price = *price;
+        self.short_call.premium = Positive::from(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        self.long_call.option.underlying_price = *price;
+        self.long_call.premium =
+            Positive::from(self.long_call.option.calculate_price_black_scholes()?.abs());
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        self.short_call.option.implied_volatility = *volatility;
+        self.long_call.option.implied_volatility = *volatility;
+        self.short_call.premium = Positive(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        self.long_call.premium =
+            Positive(self.long_call.option.calculate_price_black_scholes()?.abs());
+        Ok(())
+    }
+}
+
+impl Strategies for CallCalendarSpread {
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        let (max_profit, _) = time_spread::profit_extremes(&self.short_call, &self.long_call)?;
+        if max_profit <= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Max profit is negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(max_profit.into())
+        }
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        let (_, max_loss) = time_spread::profit_extremes(&self.short_call, &self.long_call)?;
+        if max_loss >= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Max loss must be negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(max_loss.abs().into())
+        }
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        Ok(time_spread::profit_area(&self.short_call, &self.long_call)?)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        let result = match (self.get_max_profit(), self.get_max_loss()) {
+            (Ok(profit), Ok(loss)) => (profit / loss).to_f64() * 100.0,
+            _ => ZERO,
+        };
+        Ok(Decimal::from_f64(result).unwrap())
+    }
+}
+
+impl Optimizable for CallCalendarSpread {
+    type Strategy = CallCalendarSpread;
+
+    /// Searches the strikes of a single chain, taken as the front month. The back-month call
+    /// keeps the current long expiration and, since the chain does not quote it, is priced with
+    /// Black-Scholes at the chain's implied volatility. Use
+    /// [`Optimizable::find_optimal_in_series`] to price both legs from market quotes.
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let atm_strike = option_chain.atm_strike().ok().copied();
+        let mut best_value = Decimal::MIN;
+
+        for option in option_chain.options.iter() {
+            if !self.is_valid_strike(option, &side, atm_strike) {
+                debug!("Invalid option: {:#?}", option.strike_price);
+                continue;
+            }
+            if option.call_bid.unwrap_or(Positive::ZERO) == Positive::ZERO
+                || time_spread::quoted_volatility(option).is_none()
+            {
+                debug!("Missing quotes for strike {:#?}", option.strike_price);
+                continue;
+            }
+
+            let legs = StrategyLegs::TwoLegs {
+                first: option,
+                second: option,
+            };
+            let strategy = self.create_strategy(option_chain, &legs);
+            if !strategy.validate() {
+                debug!("Invalid strategy");
+                continue;
+            }
+
+            if let Some(current_value) = time_spread::optimization_value(&strategy, &criteria)
+                && current_value > best_value
+            {
+                best_value = current_value;
+                *self = strategy;
+            }
+        }
+    }
+
+    /// Searches every pair of expirations in the series, selling the call of the nearer chain
+    /// at its bid and buying the call with the same strike in the farther chain at its ask.
+    fn find_optimal_in_series(
+        &mut self,
+        option_series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+
+        for (short_expiration, short_chain, long_expiration, long_chain) in
+            time_spread::expiration_pairs(option_series)
+        {
+            let atm_strike = short_chain.atm_strike().ok().copied();
+            for short_option in short_chain.options.iter() {
+                if !self.is_valid_strike(short_option, &side, atm_strike) {
+                    continue;
+                }
+                let Some(long_option) = long_chain
+                    .options
+                    .iter()
+                    .find(|option| option.strike_price == short_option.strike_price)
+                else {
+                    continue;
+                };
+                let Some(strategy) = self.spread_from_quotes(
+                    short_chain,
+                    short_option,
+                    short_expiration,
+                    long_option,
+                    long_expiration,
+                ) else {
+                    debug!("Missing quotes for strike {:#?}", short_option.strike_price);
+                    continue;
+                };
+                if !strategy.validate() {
+                    debug!("Invalid strategy");
+                    continue;
+                }
+
+                if let Some(current_value) = time_spread::optimization_value(&strategy, &criteria)
+                    && current_value > best_value
+                {
+                    best_value = current_value;
+                    *self = strategy;
+                }
+            }
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let (short, long) = match legs {
+            StrategyLegs::TwoLegs { first, second } => (first, second),
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+
+        let mut long_call = self.long_call.option.clone();
+        long_call.strike_price = long.strike_price;
+        long_call.underlying_price = chain.underlying_price;
+        long_call.implied_volatility = long.implied_volatility;
+        let premium_long_call =
+            Positive::from(long_call.calculate_price_black_scholes().unwrap().abs());
+
+        CallCalendarSpread::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            short.strike_price,
+            self.short_call.option.expiration_date,
+            self.long_call.option.expiration_date,
+            short.implied_volatility,
+            long.implied_volatility,
+            self.short_call.option.risk_free_rate,
+            self.short_call.option.dividend_yield,
+            self.short_call.option.quantity,
+            short.call_bid.unwrap(),
+            premium_long_call,
+            self.short_call.open_fee,
+            self.short_call.close_fee,
+            self.long_call.open_fee,
+            self.long_call.close_fee,
+        )
+    }
+}
+
+impl Profit for CallCalendarSpread {
+    /// Profit when the front-month call expires, with the back-month call valued at its
+    /// Black-Scholes price for the remaining time.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        time_spread::profit_at_front_expiry(&self.short_call, &self.long_call, price)
+    }
+}
+
+impl ProbabilityAnalysis for CallCalendarSpread {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        time_spread::probability_ranges(
+            &self.short_call,
+            &self.long_call,
+            self.get_break_even_points()?,
+            true,
+        )
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        time_spread::probability_ranges(
+            &self.short_call,
+            &self.long_call,
+            self.get_break_even_points()?,
+            false,
+        )
+    }
+}
+
+impl Greeks for CallCalendarSpread {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.short_call.option, &self.long_call.option])
+    }
+}
+
+impl DeltaNeutrality for CallCalendarSpread {}
+
+impl PnLCalculator for CallCalendarSpread {
+    /// `expiration_date` applies to the front-month call; the back-month call keeps the same
+    /// number of days over it.
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        let long_call_expiration = time_spread::shifted_back_expiration(
+            &self.short_call.option,
+            &self.long_call.option,
+            &expiration_date,
+        )?;
+        Ok(self
+            .short_call
+            .calculate_pnl(market_price, expiration_date, implied_volatility)?
+            + self.long_call.calculate_pnl(
+                market_price,
+                long_call_expiration,
+                implied_volatility,
+            )?)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        time_spread::pnl_at_front_expiry(&self.short_call, &self.long_call, underlying_price)
+    }
+}
+
+#[cfg(test)]
+mod tests_call_calendar_spread {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::series::OptionSeriesBuildParams;
+    use crate::{assert_decimal_eq, pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_test_strategy() -> CallCalendarSpread {
+        CallCalendarSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(30.0)),
+            ExpirationDate::Days(pos!(90.0)),
+            pos!(0.2),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(2.5),
+            pos!(4.6),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn create_test_series() -> OptionSeries {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(pos!(100.0))),
+            Some(ExpirationDate::Days(pos!(30.0))),
+            Some(dec!(0.05)),
+            spos!(0.0),
+            Some("TEST".to_string()),
+        );
+        let chain_params = OptionChainBuildParams::new(
+            "TEST".to_string(),
+            None,
+            5,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos!(0.02),
+            2,
+            price_params,
+            pos!(0.2),
+        );
+        OptionSeries::build_series(&OptionSeriesBuildParams {
+            chain_params,
+            series: vec![pos!(30.0), pos!(60.0), pos!(90.0)],
+        })
+    }
+
+    fn position(side: Side, expiration: Positive, strike: Positive) -> Position {
+        Position::new(
+            Options::new(
+                OptionType::European,
+                side,
+                "TEST".to_string(),
+                strike,
+                ExpirationDate::Days(expiration),
+                pos!(0.2),
+                Positive::ONE,
+                pos!(100.0),
+                dec!(0.05),
+                OptionStyle::Call,
+                Positive::ZERO,
+                None,
+            ),
+            pos!(3.0),
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_new_and_validate() {
+        let strategy = create_test_strategy();
+        assert!(strategy.validate());
+        assert_eq!(strategy.kind, StrategyType::CallCalendarSpread);
+        assert_eq!(strategy.get_positions().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let positions = vec![
+            position(Side::Long, pos!(90.0), pos!(100.0)),
+            position(Side::Short, pos!(30.0), pos!(100.0)),
+        ];
+        let strategy = CallCalendarSpread::get_strategy(&positions).unwrap();
+        assert_eq!(strategy.short_call.option.side, Side::Short);
+        assert_eq!(
+            strategy
+                .long_call
+                .option
+                .expiration_date
+                .get_days()
+                .unwrap(),
+            pos!(90.0)
+        );
+
+        let inverted = vec![
+            position(Side::Long, pos!(30.0), pos!(100.0)),
+            position(Side::Short, pos!(90.0), pos!(100.0)),
+        ];
+        assert!(CallCalendarSpread::get_strategy(&inverted).is_err());
+
+        let diagonal = vec![
+            position(Side::Long, pos!(90.0), pos!(105.0)),
+            position(Side::Short, pos!(30.0), pos!(100.0)),
+        ];
+        assert!(CallCalendarSpread::get_strategy(&diagonal).is_err());
+    }
+
+    #[test]
+    fn test_profit_peaks_at_strike() {
+        let strategy = create_test_strategy();
+        let break_even_points = strategy.get_break_even_points().unwrap();
+        assert_eq!(break_even_points.len(), 2);
+        assert!(break_even_points[0] < pos!(100.0) && break_even_points[1] > pos!(100.0));
+
+        let at_strike = strategy.calculate_profit_at(&pos!(100.0)).unwrap();
+        assert!(at_strike > Decimal::ZERO);
+        assert!(strategy.calculate_profit_at(&pos!(80.0)).unwrap() < Decimal::ZERO);
+        assert!(strategy.calculate_profit_at(&pos!(120.0)).unwrap() < Decimal::ZERO);
+
+        // The loss can never exceed the net debit paid
+        let max_loss = strategy.get_max_loss().unwrap();
+        assert!(max_loss <= pos!(2.1));
+        assert!(strategy.get_max_profit().unwrap().to_dec() >= at_strike);
+        assert!(strategy.get_profit_ratio().unwrap() > Decimal::ZERO);
+        assert!(strategy.get_profit_area().unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_pnl_at_expiration_matches_profit() {
+        let strategy = create_test_strategy();
+        let price = pos!(104.0);
+        let pnl = strategy.calculate_pnl_at_expiration(&price).unwrap();
+        assert_decimal_eq!(
+            pnl.realized.unwrap(),
+            strategy.calculate_profit_at(&price).unwrap(),
+            dec!(1e-12)
+        );
+    }
+
+    #[test]
+    fn test_set_expiration_date_keeps_gap() {
+        let mut strategy = create_test_strategy();
+        strategy
+            .set_expiration_date(ExpirationDate::Days(pos!(10.0)))
+            .unwrap();
+        assert_eq!(
+            strategy
+                .short_call
+                .option
+                .expiration_date
+                .get_days()
+                .unwrap(),
+            pos!(10.0)
+        );
+        assert_eq!(
+            strategy
+                .long_call
+                .option
+                .expiration_date
+                .get_days()
+                .unwrap(),
+            pos!(70.0)
+        );
+    }
+
+    #[test]
+    fn test_probability_ranges() {
+        let strategy = create_test_strategy();
+        let profit: Positive = strategy
+            .get_profit_ranges()
+            .unwrap()
+            .iter()
+            .map(|range| range.probability)
+            .sum();
+        let loss: Positive = strategy
+            .get_loss_ranges()
+            .unwrap()
+            .iter()
+            .map(|range| range.probability)
+            .sum();
+        assert_eq!(strategy.get_loss_ranges().unwrap().len(), 2);
+        assert!(profit > Positive::ZERO);
+        assert_decimal_eq!((profit + loss).to_dec(), Decimal::ONE, dec!(1e-6));
+    }
+
+    #[test]
+    fn test_greeks() {
+        let strategy = create_test_strategy();
+        assert!(strategy.delta().unwrap().abs() < dec!(0.1));
+        let delta_info = strategy.delta_neutrality().unwrap();
+        assert_eq!(delta_info.individual_deltas.len(), 2);
+        assert_eq!(delta_info.net_delta, strategy.delta().unwrap());
+    }
+
+    #[test]
+    fn test_find_optimal_in_series() {
+        let series = create_test_series();
+        let mut strategy = create_test_strategy();
+        strategy.find_optimal_in_series(&series, FindOptimalSide::All, OptimizationCriteria::Ratio);
+        assert!(strategy.validate());
+        assert!(strategy.get_max_profit().is_ok());
+
+        let mut centered = create_test_strategy();
+        centered.find_optimal_in_series(
+            &series,
+            FindOptimalSide::Center,
+            OptimizationCriteria::Area,
+        );
+        assert!(centered.validate());
+        assert_eq!(centered.short_call.option.strike_price, pos!(100.0));
+    }
+
+    #[test]
+    fn test_find_optimal_in_chain() {
+        let series = create_test_series();
+        let chain = series.chains.values().next().unwrap();
+        let mut strategy = create_test_strategy();
+        strategy.find_optimal(chain, FindOptimalSide::All, OptimizationCriteria::Ratio);
+        assert!(strategy.validate());
+        assert_eq!(
+            strategy
+                .long_call
+                .option
+                .expiration_date
+                .get_days()
+                .unwrap(),
+            pos!(90.0)
+        );
+    }
+}
@@ -0,0 +1,102 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const CALL_RATIO_SPREAD_DESCRIPTION: &str = "A Call Ratio Spread buys calls at a lower strike and sells a larger number of calls at a \
+    higher strike. It profits most at the short strike at expiration, while the extra short calls \
+    finance the trade and leave an unlimited loss on a strong rally.";
+
+static CALL_RATIO_SPREAD: StructureSpec = StructureSpec {
+    kind: StrategyType::CallRatioSpread,
+    name: "Call Ratio Spread",
+    description: CALL_RATIO_SPREAD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Long, false),
+        leg(1, OptionStyle::Call, Side::Short, true),
+    ],
+    ratio: QuantityRatio::AboveOne,
+    strikes: distinct_strikes,
+};
+
+/// A long call and a larger number of short calls at a higher strike, on the same expiration.
+///
+/// The strategy is moderately bullish: it profits from a rise up to the short strike,
+/// usually for a small debit or even a credit, and loses without limit above the upper
+/// break-even point.
+///
+/// Legs, by ascending strike: `quantity` long calls and `quantity * ratio` short calls.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CallRatioSpread {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl CallRatioSpread {
+    /// Creates a Call Ratio Spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `long_strike` - Strike of the long calls, the lower one.
+    /// * `short_strike` - Strike of the short calls, the higher one.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of long calls.
+    /// * `ratio` - Short calls sold per long call, above one.
+    /// * `premium_long_call` - The premium paid for each long call.
+    /// * `premium_short_call` - The premium received for each short call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal or the ratio is not above one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        long_strike: Positive,
+        short_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_long_call: Positive,
+        premium_short_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = CALL_RATIO_SPREAD.build(
+            underlying_symbol,
+            underlying_price,
+            &[long_strike, short_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            ratio,
+            &[premium_long_call, premium_short_call],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(CallRatioSpread { strategy })
+    }
+}
+
+impl_structure!(CallRatioSpread, CALL_RATIO_SPREAD);
@@ -1,11 +1,909 @@
-/*
-Collar Strategy
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
 
-A collar involves holding a long position in the underlying asset, buying an out-of-the-money put option, and selling an out-of-the-money call option.
-This strategy provides downside protection at the cost of limiting upside potential.
+//! # Collar Strategy
+//!
+//! A collar holds a long position in the underlying asset, buys an out-of-the-money put
+//! and sells an out-of-the-money call. The call premium finances all or part of the put,
+//! so the holding is protected below the put strike in exchange for capping the upside
+//! at the call strike.
+//!
+//! Key characteristics:
+//! - Limited profit, reached at or above the call strike
+//! - Limited loss, reached at or below the put strike
+//! - A single break-even point between the two strikes
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::{
+    ExpirationDate, Options, Positive,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::{ProfitLossErrorKind, StrategyError},
+    },
+    greeks::{Greeks, delta},
+    model::{
+        ProfitLossRange, UnderlyingPosition,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+        utils::mean_and_std,
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment},
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing::{debug, info};
 
-Key characteristics:
-- Limited profit potential
-- Provides downside protection
-- Can often be implemented for little to no cost
-*/
+pub(super) const COLLAR_DESCRIPTION: &str = "A Collar combines a long position in the underlying asset with a long out-of-the-money put \
+    and a short out-of-the-money call. The call premium pays for some or all of the put, \
+    bounding the result of the holding between the two strikes. It is used to protect gains on a holding at low or no cost.";
+
+/// Represents a Collar strategy: long underlying, long put and short call.
+///
+/// # Fields
+/// * `name` - A descriptive name for the strategy instance.
+/// * `kind` - The type of strategy, `StrategyType::Collar`.
+/// * `description` - A detailed description of the strategy.
+/// * `break_even_points` - Price at which the combined position neither makes nor loses money.
+/// * `long_underlying` - The holding in the underlying asset.
+/// * `long_put` - The put option setting the floor, at the lower strike.
+/// * `short_call` - The call option setting the cap, at the higher strike.
+///
+/// ## Risk and Reward
+/// The maximum profit is reached at or above the call strike, the maximum loss at or below
+/// the put strike. Between them the position moves one-for-one with the underlying.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Collar {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a Collar strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Price points where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The holding in the underlying asset
+    pub(super) long_underlying: UnderlyingPosition,
+    /// The put option setting the floor
+    pub(super) long_put: Position,
+    /// The call option setting the cap
+    pub(super) short_call: Position,
+}
+
+impl Collar {
+    /// Creates a new Collar, buying the underlying at `underlying_price`, buying puts at
+    /// `long_put_strike` and selling calls at `short_call_strike`.
+    ///
+    /// # Parameters
+    /// * `underlying_symbol` - Symbol of the underlying asset
+    /// * `underlying_price` - Current price of the underlying, used as the entry price of the holding
+    /// * `long_put_strike` - Strike of the put bought (the floor)
+    /// * `short_call_strike` - Strike of the call sold (the cap)
+    /// * `expiration` - Expiration shared by both options
+    /// * `implied_volatility` - Implied volatility of the options
+    /// * `risk_free_rate` - Risk-free interest rate
+    /// * `dividend_yield` - Dividend yield of the underlying
+    /// * `quantity` - Units of the underlying held, matched by the same number of puts and calls
+    /// * `premium_long_put` - Premium paid per put
+    /// * `premium_short_call` - Premium received per call
+    /// * `open_fee_long_put` / `close_fee_long_put` - Fees per put
+    /// * `open_fee_short_call` / `close_fee_short_call` - Fees per call
+    /// * `open_fee_underlying` / `close_fee_underlying` - Fees per unit of the underlying
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        long_put_strike: Positive,
+        short_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_put: Positive,
+        premium_short_call: Positive,
+        open_fee_long_put: Positive,
+        close_fee_long_put: Positive,
+        open_fee_short_call: Positive,
+        close_fee_short_call: Positive,
+        open_fee_underlying: Positive,
+        close_fee_underlying: Positive,
+    ) -> Self {
+        let mut strategy = Collar {
+            long_underlying: UnderlyingPosition::new(
+                underlying_symbol.clone(),
+                Side::Long,
+                quantity,
+                underlying_price,
+                Utc::now(),
+                open_fee_underlying,
+                close_fee_underlying,
+            ),
+            ..Default::default()
+        };
+
+        let long_put_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol.clone(),
+            long_put_strike,
+            expiration,
+            implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Put,
+            dividend_yield,
+            None,
+        );
+        let long_put = Position::new(
+            long_put_option,
+            premium_long_put,
+            Utc::now(),
+            open_fee_long_put,
+            close_fee_long_put,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&long_put)
+            .expect("Invalid long put option");
+
+        let short_call_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol,
+            short_call_strike,
+            expiration,
+            implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Call,
+            dividend_yield,
+            None,
+        );
+        let short_call = Position::new(
+            short_call_option,
+            premium_short_call,
+            Utc::now(),
+            open_fee_short_call,
+            close_fee_short_call,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&short_call)
+            .expect("Invalid short call option");
+
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
+        strategy
+    }
+}
+
+impl StrategyConstructor for Collar {
+    /// Builds a Collar from a long put and a short call. The underlying holding is created
+    /// at the options' underlying price, in the same quantity and without fees.
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 2 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Collar get_strategy".to_string(),
+                    reason: "Must have exactly 2 options".to_string(),
+                },
+            ));
+        }
+
+        let long_put = vec_positions
+            .iter()
+            .find(|p| p.option.option_style == OptionStyle::Put && p.option.side == Side::Long);
+        let short_call = vec_positions
+            .iter()
+            .find(|p| p.option.option_style == OptionStyle::Call && p.option.side == Side::Short);
+        let (long_put, short_call) = match (long_put, short_call) {
+            (Some(long_put), Some(short_call)) => (long_put, short_call),
+            _ => {
+                return Err(StrategyError::OperationError(
+                    OperationErrorKind::InvalidParameters {
+                        operation: "Collar get_strategy".to_string(),
+                        reason: "Collar requires a long put and a short call".to_string(),
+                    },
+                ));
+            }
+        };
+        if long_put.option.strike_price >= short_call.option.strike_price {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Collar get_strategy".to_string(),
+                    reason: "Put strike must be below the call strike".to_string(),
+                },
+            ));
+        }
+
+        let option = &short_call.option;
+        let mut strategy = Collar {
+            long_underlying: UnderlyingPosition::new(
+                option.underlying_symbol.clone(),
+                Side::Long,
+                option.quantity,
+                option.underlying_price,
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+            ),
+            long_put: long_put.clone(),
+            short_call: short_call.clone(),
+            ..Default::default()
+        };
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for Collar {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = Vec::new();
+        if self.long_underlying.quantity == Positive::ZERO {
+            return Ok(());
+        }
+        // Only the holding moves between the two strikes, so the break-even is the net
+        // cost per unit when it lies in that band.
+        let break_even = self.get_net_cost()? / self.long_underlying.quantity;
+        if break_even > self.long_put.option.strike_price.to_dec()
+            && break_even < self.short_call.option.strike_price.to_dec()
+        {
+            self.break_even_points
+                .push(Positive::from(break_even).round_to(2));
+        }
+        Ok(())
+    }
+}
+
+impl Validable for Collar {
+    fn validate(&self) -> bool {
+        if self.long_put.option.strike_price >= self.short_call.option.strike_price {
+            debug!("Put strike must be below the call strike");
+            return false;
+        }
+        if self.long_underlying.quantity != self.long_put.option.quantity
+            || self.long_underlying.quantity != self.short_call.option.quantity
+        {
+            debug!("Underlying quantity must match the number of puts and calls");
+            return false;
+        }
+        self.long_underlying.validate() && self.long_put.validate() && self.short_call.validate()
+    }
+}
+
+impl Positionable for Collar {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Long) => {
+                self.long_put = position.clone();
+                Ok(())
+            }
+            (OptionStyle::Call, Side::Short) => {
+                self.short_call = position.clone();
+                Ok(())
+            }
+            _ => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Collar only accepts a long put and a short call".to_string(),
+            )),
+        }
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.long_put, &self.short_call])
+    }
+
+    fn get_underlying_position(&self) -> Option<&UnderlyingPosition> {
+        Some(&self.long_underlying)
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        match (side, option_style, strike) {
+            (Side::Long, OptionStyle::Put, strike)
+                if *strike == self.long_put.option.strike_price =>
+            {
+                Ok(vec![&mut self.long_put])
+            }
+            (Side::Short, OptionStyle::Call, strike)
+                if *strike == self.short_call.option.strike_price =>
+            {
+                Ok(vec![&mut self.short_call])
+            }
+            _ => Err(PositionError::invalid_position_type(
+                *side,
+                "Position not found in Collar".to_string(),
+            )),
+        }
+    }
+
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        match (&position.option.side, &position.option.option_style) {
+            (Side::Long, OptionStyle::Put)
+                if position.option.strike_price == self.long_put.option.strike_price =>
+            {
+                self.long_put = position.clone();
+                Ok(())
+            }
+            (Side::Short, OptionStyle::Call)
+                if position.option.strike_price == self.short_call.option.strike_price =>
+            {
+                self.short_call = position.clone();
+                Ok(())
+            }
+            _ => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Position not found in Collar".to_string(),
+            )),
+        }
+    }
+}
+
+impl Strategable for Collar {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for Collar {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\tUnderlying: {:?} {} {} @ {}\n\t{}\n\t{}",
+            self.kind,
+            self.long_underlying.side,
+            self.long_underlying.quantity,
+            self.long_underlying.symbol,
+            self.long_underlying.price,
+            self.long_put.get_title(),
+            self.short_call.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        [&self.long_put.option, &self.short_call.option]
+            .into_iter()
+            .map(|option| OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            })
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.long_put.option, &self.short_call.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        [&self.long_put.option, &self.short_call.option]
+            .into_iter()
+            .map(|option| {
+                (
+                    OptionBasicType {
+                        option_style: &option.option_style,
+                        side: &option.side,
+                        strike_price: &option.strike_price,
+                        expiration_date: &option.expiration_date,
+                    },
+                    &option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        self.short_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.short_call.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.long_put.option.expiration_date = expiration_date;
+        self.short_call.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        // Both options are repriced as if traded now, so the holding is re-marked as well.
+        self.long_underlying.price = *price;
+        self.long_put.option.underlying_price = *price;
+        self.long_put.premium =
+            Positive::from(self.long_put.option.calculate_price_black_scholes()?.abs());
+        self.short_call.option.underlying_price = *price;
+        self.short_call.premium = Positive::from(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        self.long_put.option.implied_volatility = *volatility;
+        self.short_call.option.implied_volatility = *volatility;
+        self.long_put.premium =
+            Positive(self.long_put.option.calculate_price_black_scholes()?.abs());
+        self.short_call.premium = Positive(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        Ok(())
+    }
+}
+
+impl Strategies for Collar {
+    fn get_volume(&mut self) -> Result<Positive, StrategyError> {
+        Ok(self.long_put.option.quantity + self.short_call.option.quantity)
+    }
+
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        let profit = self.calculate_profit_at(&self.short_call.option.strike_price)?;
+        if profit <= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Max profit is negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(profit.into())
+        }
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        let loss = self.calculate_profit_at(&self.long_put.option.strike_price)?;
+        if loss >= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Max loss must be negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(loss.abs().into())
+        }
+    }
+
+    fn get_total_cost(&self) -> Result<Positive, PositionError> {
+        Ok(self.long_put.total_cost()?
+            + self.short_call.total_cost()?
+            + self.long_underlying.total_cost())
+    }
+
+    fn get_net_cost(&self) -> Result<Decimal, PositionError> {
+        Ok(self.long_put.net_cost()?
+            + self.short_call.net_cost()?
+            + self.long_underlying.net_cost())
+    }
+
+    fn get_fees(&self) -> Result<Positive, StrategyError> {
+        Ok(self.long_put.fees()? + self.short_call.fees()? + self.long_underlying.fees())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        let break_even = match self.break_even_points.first() {
+            Some(break_even) => *break_even,
+            None => return Ok(Decimal::ZERO),
+        };
+        let high = self.get_max_profit().unwrap_or(Positive::ZERO);
+        let base = self.short_call.option.strike_price - break_even;
+        Ok((high * base / 200.0).into())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        let max_profit = self.get_max_profit().unwrap_or(Positive::ZERO);
+        let max_loss = self.get_max_loss().unwrap_or(Positive::ZERO);
+        match (max_profit, max_loss) {
+            (value, _) if value == Positive::ZERO => Ok(Decimal::ZERO),
+            (_, value) if value == Positive::ZERO => Ok(Decimal::MAX),
+            _ => Ok((max_profit / max_loss * 100.0).into()),
+        }
+    }
+}
+
+impl Optimizable for Collar {
+    type Strategy = Collar;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_double_iter()
+            // Filter out invalid combinations based on FindOptimalSide
+            .filter(move |&(put, call)| {
+                if side == FindOptimalSide::Center {
+                    put.is_valid_optimal_side(underlying_price, &FindOptimalSide::Lower)
+                        && call.is_valid_optimal_side(underlying_price, &FindOptimalSide::Upper)
+                } else {
+                    put.is_valid_optimal_side(underlying_price, &side)
+                        && call.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            // Filter out options with invalid bid/ask prices
+            .filter(|(put, call)| {
+                put.put_ask.unwrap_or(Positive::ZERO) > Positive::ZERO
+                    && call.call_bid.unwrap_or(Positive::ZERO) > Positive::ZERO
+            })
+            // Filter out options that don't meet strategy constraints
+            .filter(move |(put, call)| {
+                let legs = StrategyLegs::TwoLegs {
+                    first: put,
+                    second: call,
+                };
+                let strategy = strategy.create_strategy(option_chain, &legs);
+                strategy.validate()
+                    && strategy.get_max_profit().is_ok()
+                    && strategy.get_max_loss().is_ok()
+            })
+            .map(move |(put, call)| OptionDataGroup::Two(put, call))
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let (put, call) = match option_data_group {
+                OptionDataGroup::Two(first, second) => (first, second),
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::TwoLegs {
+                first: put,
+                second: call,
+            };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio().unwrap(),
+                OptimizationCriteria::Area => strategy.get_profit_area().unwrap(),
+            };
+
+            if current_value > best_value {
+                info!("Found better value: {}", current_value);
+                best_value = current_value;
+                *self = strategy.clone();
+            }
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let (put, call) = match legs {
+            StrategyLegs::TwoLegs { first, second } => (first, second),
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        Collar::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            put.strike_price,
+            call.strike_price,
+            self.short_call.option.expiration_date,
+            call.implied_volatility,
+            self.short_call.option.risk_free_rate,
+            self.short_call.option.dividend_yield,
+            self.short_call.option.quantity,
+            put.put_ask.unwrap(),
+            call.call_bid.unwrap(),
+            self.long_put.open_fee,
+            self.long_put.close_fee,
+            self.short_call.open_fee,
+            self.short_call.close_fee,
+            self.long_underlying.open_fee,
+            self.long_underlying.close_fee,
+        )
+    }
+}
+
+impl Profit for Collar {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        let price_ref = Some(price);
+        Ok(self.long_put.pnl_at_expiration(&price_ref)?
+            + self.short_call.pnl_at_expiration(&price_ref)?
+            + self.long_underlying.pnl_at(price))
+    }
+}
+
+impl ProbabilityAnalysis for Collar {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Collar has no break-even point".to_string())?;
+        let option = &self.short_call.option;
+        let (mean_volatility, std_dev) = mean_and_std(vec![
+            self.long_put.option.implied_volatility,
+            self.short_call.option.implied_volatility,
+        ]);
+
+        let mut profit_range = ProfitLossRange::new(Some(break_even_point), None, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: mean_volatility,
+                std_dev_adjustment: std_dev,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Collar has no break-even point".to_string())?;
+        let option = &self.short_call.option;
+        let (mean_volatility, std_dev) = mean_and_std(vec![
+            self.long_put.option.implied_volatility,
+            self.short_call.option.implied_volatility,
+        ]);
+
+        let mut loss_range = ProfitLossRange::new(None, Some(break_even_point), Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: mean_volatility,
+                std_dev_adjustment: std_dev,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for Collar {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.long_put.option, &self.short_call.option])
+    }
+
+    /// Delta of both options plus the delta of the holding (one per unit held).
+    fn delta(&self) -> Result<Decimal, GreeksError> {
+        Ok(delta(&self.long_put.option)?
+            + delta(&self.short_call.option)?
+            + self.long_underlying.delta())
+    }
+}
+
+impl DeltaNeutrality for Collar {}
+
+impl PnLCalculator for Collar {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .long_put
+            .calculate_pnl(market_price, expiration_date, implied_volatility)?
+            + self
+                .short_call
+                .calculate_pnl(market_price, expiration_date, implied_volatility)?
+            + self.long_underlying.calculate_pnl(
+                market_price,
+                expiration_date,
+                implied_volatility,
+            )?)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .long_put
+            .calculate_pnl_at_expiration(underlying_price)?
+            + self
+                .short_call
+                .calculate_pnl_at_expiration(underlying_price)?
+            + self
+                .long_underlying
+                .calculate_pnl_at_expiration(underlying_price)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_collar {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::{assert_decimal_eq, pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> Collar {
+        Collar::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(2.0),
+            pos!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.kind, StrategyType::Collar);
+        assert_eq!(strategy.long_underlying.price, pos!(100.0));
+        assert_eq!(strategy.get_positions().unwrap().len(), 2);
+        assert!(strategy.validate());
+    }
+
+    #[test]
+    fn test_break_even_and_costs() {
+        let strategy = create_strategy();
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(100.5)]
+        );
+        assert_eq!(strategy.get_net_cost().unwrap(), dec!(100.5));
+    }
+
+    #[test]
+    fn test_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(9.5));
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(5.5));
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(150.0)).unwrap(),
+            dec!(9.5)
+        );
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(50.0)).unwrap(),
+            dec!(-5.5)
+        );
+    }
+
+    #[test]
+    fn test_delta_includes_underlying() {
+        let strategy = create_strategy();
+        let options_delta =
+            delta(&strategy.long_put.option).unwrap() + delta(&strategy.short_call.option).unwrap();
+        assert_eq!(strategy.delta().unwrap(), options_delta + dec!(1.0));
+        let neutrality = strategy.delta_neutrality().unwrap();
+        assert_eq!(neutrality.net_delta, options_delta + dec!(1.0));
+        assert_eq!(neutrality.individual_deltas.len(), 2);
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let strategy = create_strategy();
+        let rebuilt =
+            Collar::get_strategy(&[strategy.short_call.clone(), strategy.long_put.clone()])
+                .unwrap();
+        assert_eq!(rebuilt.long_put.option.strike_price, pos!(95.0));
+        assert_eq!(rebuilt.short_call.option.strike_price, pos!(110.0));
+        assert_eq!(rebuilt.get_break_even_points().unwrap(), &vec![pos!(100.5)]);
+
+        let mut inverted_put = strategy.long_put.clone();
+        inverted_put.option.strike_price = pos!(120.0);
+        assert!(Collar::get_strategy(&[inverted_put, strategy.short_call.clone()]).is_err());
+        assert!(Collar::get_strategy(std::slice::from_ref(&strategy.long_put)).is_err());
+    }
+
+    #[test]
+    fn test_pnl_at_expiration() {
+        let strategy = create_strategy();
+        let pnl = strategy.calculate_pnl_at_expiration(&pos!(105.0)).unwrap();
+        assert_eq!(pnl.realized, Some(dec!(4.5)));
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let strategy = create_strategy();
+        let profit = strategy.probability_of_profit(None, None).unwrap();
+        let loss = strategy.probability_of_loss(None, None).unwrap();
+        assert_decimal_eq!((profit + loss).to_dec(), dec!(1.0), dec!(0.001));
+    }
+
+    #[test]
+    fn test_find_optimal() {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(pos!(100.0))),
+            Some(ExpirationDate::Days(pos!(30.0))),
+            Some(dec!(0.05)),
+            spos!(0.0),
+            Some("AAPL".to_string()),
+        );
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "AAPL".to_string(),
+            None,
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos!(0.02),
+            2,
+            price_params,
+            pos!(0.2),
+        ));
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Area);
+        assert!(strategy.validate());
+        assert!(strategy.long_put.option.strike_price <= pos!(100.0));
+        assert!(strategy.short_call.option.strike_price >= pos!(100.0));
+        assert!(strategy.get_max_profit().is_ok());
+        assert!(strategy.get_max_loss().is_ok());
+    }
+}
@@ -1,11 +1,778 @@
-/*
-Covered Call Strategy
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
 
-A covered call involves holding a long position in the underlying asset and selling a call option on that same asset.
-This strategy provides limited upside potential but offers some downside protection in the form of the premium received for selling the call option.
+//! # Covered Call Strategy
+//!
+//! A covered call holds a long position in the underlying asset and sells a call option
+//! on that same asset. The premium received lowers the cost basis of the holding and
+//! offers a small cushion against declines, in exchange for giving up any upside above
+//! the strike of the short call.
+//!
+//! Key characteristics:
+//! - Limited profit potential, reached when the underlying closes at or above the call strike
+//! - Downside exposure of the underlying, reduced by the premium received
+//! - A single break-even point at the entry price minus the net premium
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::{
+    ExpirationDate, Options, Positive,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::{ProfitLossErrorKind, StrategyError},
+    },
+    greeks::{Greeks, delta},
+    model::{
+        ProfitLossRange, UnderlyingPosition,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment},
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing::{debug, info};
 
-Key characteristics:
-- Limited profit potential
-- Provides some downside protection
-- Reduces the cost basis of the underlying asset
-*/
+pub(super) const COVERED_CALL_DESCRIPTION: &str = "A Covered Call combines a long position in the underlying asset with a short call option on the same asset. \
+    The premium collected reduces the cost basis of the holding and provides limited downside cushion, \
+    while the short call caps the upside at its strike price. It suits neutral to moderately bullish outlooks.";
+
+/// Represents a Covered Call strategy: long underlying plus a short call.
+///
+/// # Fields
+/// * `name` - A descriptive name for the strategy instance.
+/// * `kind` - The type of strategy, `StrategyType::CoveredCall`.
+/// * `description` - A detailed description of the strategy.
+/// * `break_even_points` - Price at which the combined position neither makes nor loses money.
+/// * `long_underlying` - The holding in the underlying asset.
+/// * `short_call` - The call option sold against the holding.
+///
+/// ## Risk and Reward
+/// The maximum profit is reached at or above the call strike and equals the gain on the
+/// holding up to the strike plus the premium received. The maximum loss happens if the
+/// underlying goes to zero and equals the entry cost minus the premium received.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CoveredCall {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a CoveredCall strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Price points where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The holding in the underlying asset
+    pub(super) long_underlying: UnderlyingPosition,
+    /// The call option sold against the holding
+    pub(super) short_call: Position,
+}
+
+impl CoveredCall {
+    /// Creates a new Covered Call, buying the underlying at `underlying_price` and selling
+    /// `quantity` calls against it.
+    ///
+    /// # Parameters
+    /// * `underlying_symbol` - Symbol of the underlying asset
+    /// * `underlying_price` - Current price of the underlying, used as the entry price of the holding
+    /// * `short_call_strike` - Strike of the call sold
+    /// * `expiration` - Expiration of the call sold
+    /// * `implied_volatility` - Implied volatility of the call
+    /// * `risk_free_rate` - Risk-free interest rate
+    /// * `dividend_yield` - Dividend yield of the underlying
+    /// * `quantity` - Units of the underlying held, matched by the same number of calls
+    /// * `premium_short_call` - Premium received per call
+    /// * `open_fee_short_call` / `close_fee_short_call` - Fees per call
+    /// * `open_fee_underlying` / `close_fee_underlying` - Fees per unit of the underlying
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_call: Positive,
+        open_fee_short_call: Positive,
+        close_fee_short_call: Positive,
+        open_fee_underlying: Positive,
+        close_fee_underlying: Positive,
+    ) -> Self {
+        let mut strategy = CoveredCall {
+            long_underlying: UnderlyingPosition::new(
+                underlying_symbol.clone(),
+                Side::Long,
+                quantity,
+                underlying_price,
+                Utc::now(),
+                open_fee_underlying,
+                close_fee_underlying,
+            ),
+            ..Default::default()
+        };
+
+        let short_call_option = Options::new(
+            OptionType::European,
+            Side::Short,
+            underlying_symbol,
+            short_call_strike,
+            expiration,
+            implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Call,
+            dividend_yield,
+            None,
+        );
+        let short_call = Position::new(
+            short_call_option,
+            premium_short_call,
+            Utc::now(),
+            open_fee_short_call,
+            close_fee_short_call,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&short_call)
+            .expect("Invalid short call option");
+
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
+        strategy
+    }
+}
+
+impl StrategyConstructor for CoveredCall {
+    /// Builds a Covered Call from its option leg. The underlying holding is not an option
+    /// position, so it is created at the option's underlying price, in the same quantity
+    /// and without fees.
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 1 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Covered Call get_strategy".to_string(),
+                    reason: "Must have exactly 1 option".to_string(),
+                },
+            ));
+        }
+        let position = &vec_positions[0];
+        if position.option.option_style != OptionStyle::Call || position.option.side != Side::Short
+        {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Covered Call get_strategy".to_string(),
+                    reason: "Covered Call requires a short call".to_string(),
+                },
+            ));
+        }
+
+        let option = &position.option;
+        let mut strategy = CoveredCall {
+            long_underlying: UnderlyingPosition::new(
+                option.underlying_symbol.clone(),
+                Side::Long,
+                option.quantity,
+                option.underlying_price,
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+            ),
+            short_call: position.clone(),
+            ..Default::default()
+        };
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for CoveredCall {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = Vec::new();
+        if self.long_underlying.quantity == Positive::ZERO {
+            return Ok(());
+        }
+        // Below the strike the payoff moves one-for-one with the underlying, so the
+        // break-even is the net cost per unit as long as it falls in that region.
+        let break_even = self.get_net_cost()? / self.long_underlying.quantity;
+        if break_even > Decimal::ZERO && break_even < self.short_call.option.strike_price.to_dec() {
+            self.break_even_points
+                .push(Positive::from(break_even).round_to(2));
+        }
+        Ok(())
+    }
+}
+
+impl Validable for CoveredCall {
+    fn validate(&self) -> bool {
+        if self.long_underlying.quantity != self.short_call.option.quantity {
+            debug!("Underlying quantity must match the number of calls sold");
+            return false;
+        }
+        self.long_underlying.validate() && self.short_call.validate()
+    }
+}
+
+impl Positionable for CoveredCall {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Call, Side::Short) => {
+                self.short_call = position.clone();
+                Ok(())
+            }
+            (OptionStyle::Call, Side::Long) => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Long call is not valid for CoveredCall".to_string(),
+            )),
+            _ => Err(PositionError::invalid_position_style(
+                position.option.option_style,
+                "Position is a Put, it is not valid for CoveredCall".to_string(),
+            )),
+        }
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.short_call])
+    }
+
+    fn get_underlying_position(&self) -> Option<&UnderlyingPosition> {
+        Some(&self.long_underlying)
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        match (side, option_style, strike) {
+            (Side::Short, OptionStyle::Call, strike)
+                if *strike == self.short_call.option.strike_price =>
+            {
+                Ok(vec![&mut self.short_call])
+            }
+            _ => Err(PositionError::invalid_position_type(
+                *side,
+                "Position not found in CoveredCall".to_string(),
+            )),
+        }
+    }
+
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        match (&position.option.side, &position.option.option_style) {
+            (Side::Short, OptionStyle::Call)
+                if position.option.strike_price == self.short_call.option.strike_price =>
+            {
+                self.short_call = position.clone();
+                Ok(())
+            }
+            _ => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Position not found in CoveredCall".to_string(),
+            )),
+        }
+    }
+}
+
+impl Strategable for CoveredCall {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CoveredCall {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\tUnderlying: {:?} {} {} @ {}\n\t{}",
+            self.kind,
+            self.long_underlying.side,
+            self.long_underlying.quantity,
+            self.long_underlying.symbol,
+            self.long_underlying.price,
+            self.short_call.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        let option = &self.short_call.option;
+        HashSet::from([OptionBasicType {
+            option_style: &option.option_style,
+            side: &option.side,
+            strike_price: &option.strike_price,
+            expiration_date: &option.expiration_date,
+        }])
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        let option = &self.short_call.option;
+        HashMap::from([(
+            OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            },
+            &option.implied_volatility,
+        )])
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        let option = &self.short_call.option;
+        HashMap::from([(
+            OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            },
+            &option.quantity,
+        )])
+    }
+    fn one_option(&self) -> &Options {
+        self.short_call.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.short_call.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.short_call.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        // The call is repriced as if sold now, so the holding is re-marked at the same price.
+        self.long_underlying.price = *price;
+        self.short_call.option.underlying_price = *price;
+        self.short_call.premium = Positive::from(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        self.short_call.option.implied_volatility = *volatility;
+        self.short_call.premium = Positive(
+            self.short_call
+                .option
+                .calculate_price_black_scholes()?
+                .abs(),
+        );
+        Ok(())
+    }
+}
+
+impl Strategies for CoveredCall {
+    fn get_volume(&mut self) -> Result<Positive, StrategyError> {
+        Ok(self.short_call.option.quantity)
+    }
+
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        let profit = self.calculate_profit_at(&self.short_call.option.strike_price)?;
+        if profit <= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Max profit is negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(profit.into())
+        }
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        let loss = self.calculate_profit_at(&Positive::ZERO)?;
+        if loss >= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Max loss must be negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(loss.abs().into())
+        }
+    }
+
+    fn get_total_cost(&self) -> Result<Positive, PositionError> {
+        Ok(self.short_call.total_cost()? + self.long_underlying.total_cost())
+    }
+
+    fn get_net_cost(&self) -> Result<Decimal, PositionError> {
+        Ok(self.short_call.net_cost()? + self.long_underlying.net_cost())
+    }
+
+    fn get_fees(&self) -> Result<Positive, StrategyError> {
+        Ok(self.short_call.fees()? + self.long_underlying.fees())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        let break_even = match self.break_even_points.first() {
+            Some(break_even) => *break_even,
+            None => return Ok(Decimal::ZERO),
+        };
+        let high = self.get_max_profit().unwrap_or(Positive::ZERO);
+        let base = self.short_call.option.strike_price - break_even;
+        Ok((high * base / 200.0).into())
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        let max_profit = self.get_max_profit().unwrap_or(Positive::ZERO);
+        let max_loss = self.get_max_loss().unwrap_or(Positive::ZERO);
+        match (max_profit, max_loss) {
+            (value, _) if value == Positive::ZERO => Ok(Decimal::ZERO),
+            (_, value) if value == Positive::ZERO => Ok(Decimal::MAX),
+            _ => Ok((max_profit / max_loss * 100.0).into()),
+        }
+    }
+}
+
+impl Optimizable for CoveredCall {
+    type Strategy = CoveredCall;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_single_iter()
+            // Calls are written out of the money by default
+            .filter(move |call| {
+                if side == FindOptimalSide::Center {
+                    call.is_valid_optimal_side(underlying_price, &FindOptimalSide::Upper)
+                } else {
+                    call.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            .filter(|call| call.call_bid.unwrap_or(Positive::ZERO) > Positive::ZERO)
+            .filter(move |call| {
+                let legs = StrategyLegs::OneLeg { first: call };
+                let strategy = strategy.create_strategy(option_chain, &legs);
+                strategy.validate()
+                    && strategy.get_max_profit().is_ok()
+                    && strategy.get_max_loss().is_ok()
+            })
+            .map(OptionDataGroup::One)
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let call = match option_data_group {
+                OptionDataGroup::One(first) => first,
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::OneLeg { first: call };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio().unwrap(),
+                OptimizationCriteria::Area => strategy.get_profit_area().unwrap(),
+            };
+
+            if current_value > best_value {
+                info!("Found better value: {}", current_value);
+                best_value = current_value;
+                *self = strategy.clone();
+            }
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let call = match legs {
+            StrategyLegs::OneLeg { first } => first,
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        CoveredCall::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            call.strike_price,
+            self.short_call.option.expiration_date,
+            call.implied_volatility,
+            self.short_call.option.risk_free_rate,
+            self.short_call.option.dividend_yield,
+            self.short_call.option.quantity,
+            call.call_bid.unwrap(),
+            self.short_call.open_fee,
+            self.short_call.close_fee,
+            self.long_underlying.open_fee,
+            self.long_underlying.close_fee,
+        )
+    }
+}
+
+impl Profit for CoveredCall {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        Ok(self.short_call.pnl_at_expiration(&Some(price))? + self.long_underlying.pnl_at(price))
+    }
+}
+
+impl ProbabilityAnalysis for CoveredCall {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Covered Call has no break-even point".to_string())?;
+        let option = &self.short_call.option;
+
+        let mut profit_range = ProfitLossRange::new(Some(break_even_point), None, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Covered Call has no break-even point".to_string())?;
+        let option = &self.short_call.option;
+
+        let mut loss_range = ProfitLossRange::new(None, Some(break_even_point), Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for CoveredCall {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.short_call.option])
+    }
+
+    /// Delta of the short call plus the delta of the holding (one per unit held).
+    fn delta(&self) -> Result<Decimal, GreeksError> {
+        Ok(delta(&self.short_call.option)? + self.long_underlying.delta())
+    }
+}
+
+impl DeltaNeutrality for CoveredCall {}
+
+impl PnLCalculator for CoveredCall {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .short_call
+            .calculate_pnl(market_price, expiration_date, implied_volatility)?
+            + self.long_underlying.calculate_pnl(
+                market_price,
+                expiration_date,
+                implied_volatility,
+            )?)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .short_call
+            .calculate_pnl_at_expiration(underlying_price)?
+            + self
+                .long_underlying
+                .calculate_pnl_at_expiration(underlying_price)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_covered_call {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::{assert_decimal_eq, pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> CoveredCall {
+        CoveredCall::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.kind, StrategyType::CoveredCall);
+        assert_eq!(strategy.long_underlying.price, pos!(100.0));
+        assert_eq!(strategy.long_underlying.quantity, pos!(1.0));
+        assert_eq!(strategy.short_call.option.side, Side::Short);
+        assert!(strategy.validate());
+    }
+
+    #[test]
+    fn test_break_even_and_costs() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_break_even_points().unwrap(), &vec![pos!(98.0)]);
+        assert_eq!(strategy.get_net_cost().unwrap(), dec!(98.0));
+        assert_eq!(strategy.get_total_cost().unwrap(), pos!(100.0));
+    }
+
+    #[test]
+    fn test_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(12.0));
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(98.0));
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(130.0)).unwrap(),
+            dec!(12.0)
+        );
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(90.0)).unwrap(),
+            dec!(-8.0)
+        );
+    }
+
+    #[test]
+    fn test_delta_includes_underlying() {
+        let strategy = create_strategy();
+        let call_delta = delta(&strategy.short_call.option).unwrap();
+        assert!(call_delta < Decimal::ZERO);
+        assert_eq!(strategy.delta().unwrap(), call_delta + dec!(1.0));
+        assert_eq!(strategy.greeks().unwrap().delta, call_delta + dec!(1.0));
+        let neutrality = strategy.delta_neutrality().unwrap();
+        assert_eq!(neutrality.net_delta, call_delta + dec!(1.0));
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let strategy = create_strategy();
+        let rebuilt =
+            CoveredCall::get_strategy(std::slice::from_ref(&strategy.short_call)).unwrap();
+        assert_eq!(rebuilt.long_underlying.price, pos!(100.0));
+        assert_eq!(rebuilt.get_break_even_points().unwrap(), &vec![pos!(98.0)]);
+
+        let mut long_call = strategy.short_call.clone();
+        long_call.option.side = Side::Long;
+        assert!(CoveredCall::get_strategy(&[long_call]).is_err());
+        assert!(CoveredCall::get_strategy(&[]).is_err());
+    }
+
+    #[test]
+    fn test_add_position_rejects_put() {
+        let mut strategy = create_strategy();
+        let mut put = strategy.short_call.clone();
+        put.option.option_style = OptionStyle::Put;
+        assert!(strategy.add_position(&put).is_err());
+    }
+
+    #[test]
+    fn test_pnl_at_expiration() {
+        let strategy = create_strategy();
+        let pnl = strategy.calculate_pnl_at_expiration(&pos!(105.0)).unwrap();
+        assert_eq!(pnl.realized, Some(dec!(7.0)));
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let strategy = create_strategy();
+        let profit = strategy.probability_of_profit(None, None).unwrap();
+        let loss = strategy.probability_of_loss(None, None).unwrap();
+        assert_decimal_eq!((profit + loss).to_dec(), dec!(1.0), dec!(0.001));
+    }
+
+    #[test]
+    fn test_find_optimal() {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(pos!(100.0))),
+            Some(ExpirationDate::Days(pos!(30.0))),
+            Some(dec!(0.05)),
+            spos!(0.0),
+            Some("AAPL".to_string()),
+        );
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "AAPL".to_string(),
+            None,
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos!(0.02),
+            2,
+            price_params,
+            pos!(0.2),
+        ));
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Ratio);
+        assert!(strategy.validate());
+        assert!(strategy.short_call.option.strike_price >= pos!(100.0));
+        assert_eq!(strategy.long_underlying.price, pos!(100.0));
+    }
+}
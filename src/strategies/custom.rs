@@ -0,0 +1,1071 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Custom Strategy
+//!
+//! A custom strategy holds any number of option legs, optionally together with a
+//! position in the underlying asset, so structures without a dedicated type (ratio
+//! spreads, broken-wing butterflies, jade lizards, ...) can be analysed with the same
+//! tools as the named strategies.
+//!
+//! Since the legs are arbitrary, nothing is derived from the shape of the payoff.
+//! The strategy is valued at its first expiration: legs expiring then are settled at
+//! their intrinsic value and later legs are priced with Black-Scholes on the time they
+//! have left. Break-even points are located by scanning that profit over a price grid
+//! and refining each sign change by bisection, and maximum profit and loss are the
+//! extremes of the scan, or unlimited when the payoff keeps sloping above the highest
+//! strike.
+//!
+use super::base::{
+    BreakEvenable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use super::payoff_scan;
+use crate::{
+    ExpirationDate, Options, Positive,
+    error::{
+        GreeksError, OperationErrorKind,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::{ProfitLossErrorKind, StrategyError},
+    },
+    greeks::{Greeks, delta},
+    model::{
+        ProfitLossRange, UnderlyingPosition,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor, delta_neutral::DeltaNeutrality,
+        probabilities::core::ProbabilityAnalysis,
+    },
+};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing::debug;
+
+pub(super) const CUSTOM_STRATEGY_DESCRIPTION: &str = "A Custom Strategy combines any number of option legs, optionally with a position in the \
+    underlying asset. Its break-even points and profit extremes are found numerically from the payoff at the first expiration.";
+
+/// Slope of the payoff above the highest strike beyond which profit or loss is
+/// considered unlimited.
+const SLOPE_TOLERANCE: Decimal = dec!(0.001);
+
+/// A strategy made of an arbitrary set of option legs and an optional holding in the
+/// underlying asset.
+///
+/// # Fields
+/// * `name` - A descriptive name for the strategy instance.
+/// * `kind` - The type of strategy, `StrategyType::Custom`.
+/// * `description` - A detailed description of the strategy.
+/// * `break_even_points` - Prices at which the strategy neither makes nor loses money at
+///   its first expiration.
+/// * `positions` - The option legs.
+/// * `underlying` - The holding in the underlying asset, if any.
+///
+/// All legs must be written on the same underlying. They may expire on different dates,
+/// in which case profit is measured when the first of them expires.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CustomStrategy {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a custom strategy
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Price points where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The option legs
+    pub(super) positions: Vec<Position>,
+    /// The holding in the underlying asset, if any
+    pub(super) underlying: Option<UnderlyingPosition>,
+    /// Option handed out by `one_option` while there are no legs, as in a default strategy
+    #[serde(skip)]
+    pub(super) placeholder: Options,
+}
+
+impl CustomStrategy {
+    /// Creates a custom strategy from its option legs and an optional holding in the
+    /// underlying, and computes its break-even points.
+    ///
+    /// # Parameters
+    /// * `name` - Name of the strategy instance
+    /// * `positions` - Option legs, at least one
+    /// * `underlying` - Holding in the underlying asset, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if there are no legs, a leg is invalid or
+    /// the legs and the holding are not on the same underlying symbol.
+    pub fn new(
+        name: String,
+        positions: Vec<Position>,
+        underlying: Option<UnderlyingPosition>,
+    ) -> Result<Self, StrategyError> {
+        let mut strategy = CustomStrategy {
+            name,
+            positions,
+            underlying,
+            ..Default::default()
+        };
+        if !strategy.validate() {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "CustomStrategy new".to_string(),
+                    reason: "Legs must be valid, on the same underlying and at least one"
+                        .to_string(),
+                },
+            ));
+        }
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+
+    /// Days to expiration of every leg, in the order of `positions`.
+    fn leg_days(&self) -> Result<Vec<Positive>, Box<dyn Error>> {
+        self.positions
+            .iter()
+            .map(|position| position.option.expiration_date.get_days())
+            .collect()
+    }
+
+    /// Index of the first leg to expire and its days to expiration.
+    fn first_expiry(&self) -> Result<(usize, Positive), Box<dyn Error>> {
+        self.leg_days()?
+            .into_iter()
+            .enumerate()
+            .min_by(|a, b| a.1.cmp(&b.1))
+            .ok_or_else(|| "Custom strategy has no option legs".into())
+    }
+
+    /// Profit of one leg when the first leg expires, `horizon` days from now, with the
+    /// underlying at `price`. Legs expiring later are closed at their Black-Scholes value.
+    fn leg_profit(
+        position: &Position,
+        days: Positive,
+        horizon: Positive,
+        price: &Positive,
+    ) -> Result<Decimal, Box<dyn Error>> {
+        if days <= horizon {
+            return position.pnl_at_expiration(&Some(price));
+        }
+        let mut option = position.option.clone();
+        option.expiration_date = ExpirationDate::Days(days - horizon);
+        option.underlying_price = *price;
+        Ok(option.calculate_price_black_scholes()? * option.quantity
+            - position.total_cost()?.to_dec()
+            + position.premium_received()?.to_dec())
+    }
+
+    /// Lowest and highest of the strikes and the underlying price.
+    fn price_bounds(&self) -> (Positive, Positive) {
+        let underlying_price = self
+            .positions
+            .first()
+            .map(|position| position.option.underlying_price)
+            .unwrap_or(Positive::ONE);
+        self.positions.iter().fold(
+            (underlying_price, underlying_price),
+            |(lowest, highest), position| {
+                let strike = position.option.strike_price;
+                (lowest.min(strike), highest.max(strike))
+            },
+        )
+    }
+
+    /// Prices from 1% of the lowest to twice the highest of the strikes and the
+    /// underlying price. The strikes are always part of the grid, since a payoff made of
+    /// expiring legs only bends there.
+    fn price_grid(&self) -> Vec<Positive> {
+        let (lowest, highest) = self.price_bounds();
+        payoff_scan::price_grid(
+            lowest.to_dec() * dec!(0.01),
+            highest.to_dec() * Decimal::TWO,
+            self.positions.iter().map(|p| p.option.strike_price),
+        )
+    }
+
+    /// Slope of the profit above twice the highest strike, where it is linear in the
+    /// underlying price.
+    fn upper_slope(&self) -> Result<Decimal, Box<dyn Error>> {
+        let (_, highest) = self.price_bounds();
+        let (near, far) = (highest * Positive::TWO, highest * pos_four());
+        Ok(
+            (self.calculate_profit_at(&far)? - self.calculate_profit_at(&near)?)
+                / (far - near).to_dec(),
+        )
+    }
+
+    /// Profit with the underlying at zero, extrapolated linearly from the bottom of the
+    /// grid, below every strike.
+    fn profit_at_zero(&self) -> Result<Decimal, Box<dyn Error>> {
+        let (lowest, _) = self.price_bounds();
+        let bottom = lowest * dec!(0.01);
+        Ok(self.calculate_profit_at(&bottom)? * Decimal::TWO
+            - self.calculate_profit_at(&(bottom * Positive::TWO))?)
+    }
+
+    /// Highest and lowest profit over the price grid and at zero.
+    fn profit_extremes(&self) -> Result<(Decimal, Decimal), Box<dyn Error>> {
+        let at_zero = self.profit_at_zero()?;
+        let (max, min) = payoff_scan::profit_extremes(&self.price_grid(), &|price| {
+            self.calculate_profit_at(price)
+        })?;
+        Ok((max.max(at_zero), min.min(at_zero)))
+    }
+
+    /// Prices at which the profit crosses zero, in ascending order and rounded to two
+    /// decimals, including a crossing above the grid when the payoff slopes towards zero.
+    fn scan_break_even_points(&self) -> Result<Vec<Positive>, Box<dyn Error>> {
+        let grid = self.price_grid();
+        let profit = |price: &Positive| self.calculate_profit_at(price);
+        let mut break_even_points = payoff_scan::break_even_points(&grid, &profit)?;
+
+        let top = grid[grid.len() - 1];
+        let top_profit = profit(&top)?;
+        let slope = self.upper_slope()?;
+        if top_profit != Decimal::ZERO
+            && slope.abs() > SLOPE_TOLERANCE
+            && top_profit.is_sign_positive() != slope.is_sign_positive()
+        {
+            let crossing = top.to_dec() - top_profit / slope;
+            let beyond = Positive::from(crossing * Decimal::TWO - top.to_dec());
+            break_even_points.push(payoff_scan::bisect(&profit, top, beyond)?);
+        }
+        break_even_points.dedup();
+        Ok(break_even_points)
+    }
+
+    /// Price intervals delimited by the break-even points in which the strategy is
+    /// profitable (`profitable == true`) or losing, with the probability of the
+    /// underlying finishing inside each of them at the first expiration.
+    fn probability_ranges(
+        &self,
+        profitable: bool,
+    ) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let (index, _) = self.first_expiry()?;
+        payoff_scan::probability_ranges(
+            &self.positions[index].option,
+            self.positions
+                .iter()
+                .map(|position| position.option.implied_volatility)
+                .collect(),
+            &self.break_even_points,
+            &|price| self.calculate_profit_at(price),
+            profitable,
+        )
+    }
+
+    fn basic_type(option: &Options) -> OptionBasicType<'_> {
+        OptionBasicType {
+            option_style: &option.option_style,
+            side: &option.side,
+            strike_price: &option.strike_price,
+            expiration_date: &option.expiration_date,
+        }
+    }
+}
+
+fn pos_four() -> Positive {
+    Positive::TWO * Positive::TWO
+}
+
+impl StrategyConstructor for CustomStrategy {
+    /// Builds a custom strategy from any set of option legs, without a holding in the
+    /// underlying.
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        CustomStrategy::new("Custom Strategy".to_string(), vec_positions.to_vec(), None)
+    }
+}
+
+impl BreakEvenable for CustomStrategy {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = self.scan_break_even_points()?;
+        Ok(())
+    }
+}
+
+impl Validable for CustomStrategy {
+    fn validate(&self) -> bool {
+        let Some(first) = self.positions.first() else {
+            debug!("Custom strategy needs at least one option leg");
+            return false;
+        };
+        let symbol = &first.option.underlying_symbol;
+        if self
+            .positions
+            .iter()
+            .any(|position| &position.option.underlying_symbol != symbol)
+        {
+            debug!("All legs must be on the same underlying");
+            return false;
+        }
+        if let Some(underlying) = &self.underlying {
+            if &underlying.symbol != symbol {
+                debug!("Underlying holding must be on the same symbol as the legs");
+                return false;
+            }
+            if !underlying.validate() {
+                return false;
+            }
+        }
+        self.positions.iter().all(|position| position.validate())
+    }
+}
+
+impl Positionable for CustomStrategy {
+    /// Appends a leg. Break-even points are not recomputed; call
+    /// [`BreakEvenable::update_break_even_points`] once all legs are added.
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if let Some(first) = self.positions.first()
+            && first.option.underlying_symbol != position.option.underlying_symbol
+        {
+            return Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Custom strategy legs must share the same underlying".to_string(),
+            ));
+        }
+        self.positions.push(position.clone());
+        Ok(())
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(self.positions.iter().collect())
+    }
+
+    fn get_underlying_position(&self) -> Option<&UnderlyingPosition> {
+        self.underlying.as_ref()
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        let positions: Vec<&mut Position> = self
+            .positions
+            .iter_mut()
+            .filter(|position| {
+                position.option.option_style == *option_style
+                    && position.option.side == *side
+                    && position.option.strike_price == *strike
+            })
+            .collect();
+        if positions.is_empty() {
+            return Err(PositionError::invalid_position_type(
+                *side,
+                "Position not found in custom strategy".to_string(),
+            ));
+        }
+        Ok(positions)
+    }
+
+    /// Replaces the leg with the same style, side, strike and expiration.
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        let leg = self.positions.iter_mut().find(|leg| {
+            leg.option.option_style == position.option.option_style
+                && leg.option.side == position.option.side
+                && leg.option.strike_price == position.option.strike_price
+                && leg.option.expiration_date == position.option.expiration_date
+        });
+        match leg {
+            Some(leg) => {
+                *leg = position.clone();
+                Ok(())
+            }
+            None => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Position not found in custom strategy".to_string(),
+            )),
+        }
+    }
+}
+
+impl Strategable for CustomStrategy {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for CustomStrategy {
+    fn get_title(&self) -> String {
+        let mut title = format!("{:?} Strategy: {}", self.kind, self.name);
+        if let Some(underlying) = &self.underlying {
+            title.push_str(&format!(
+                "\n\tUnderlying: {:?} {} {} @ {}",
+                underlying.side, underlying.quantity, underlying.symbol, underlying.price
+            ));
+        }
+        for position in &self.positions {
+            title.push_str(&format!("\n\t{}", position.get_title()));
+        }
+        title
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        self.positions
+            .iter()
+            .map(|position| CustomStrategy::basic_type(&position.option))
+            .collect()
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        self.positions
+            .iter()
+            .map(|position| {
+                (
+                    CustomStrategy::basic_type(&position.option),
+                    &position.option.implied_volatility,
+                )
+            })
+            .collect()
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        self.positions
+            .iter()
+            .map(|position| {
+                (
+                    CustomStrategy::basic_type(&position.option),
+                    &position.option.quantity,
+                )
+            })
+            .collect()
+    }
+    fn one_option(&self) -> &Options {
+        match self.positions.first() {
+            Some(position) => position.one_option(),
+            None => &self.placeholder,
+        }
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        match self.positions.first_mut() {
+            Some(position) => position.one_option_mut(),
+            None => &mut self.placeholder,
+        }
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        for position in self.positions.iter_mut() {
+            position.option.expiration_date = expiration_date;
+        }
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        // Every leg is repriced as if traded now, so the holding is re-marked as well.
+        if let Some(underlying) = self.underlying.as_mut() {
+            underlying.price = *price;
+        }
+        for position in self.positions.iter_mut() {
+            position.option.underlying_price = *price;
+            position.premium =
+                Positive::from(position.option.calculate_price_black_scholes()?.abs());
+        }
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        for position in self.positions.iter_mut() {
+            position.option.implied_volatility = *volatility;
+            position.premium =
+                Positive::from(position.option.calculate_price_black_scholes()?.abs());
+        }
+        Ok(())
+    }
+}
+
+impl Strategies for CustomStrategy {
+    fn get_volume(&mut self) -> Result<Positive, StrategyError> {
+        Ok(self
+            .positions
+            .iter()
+            .map(|position| position.option.quantity)
+            .sum())
+    }
+
+    /// Highest profit over the price grid, or an error when it is unlimited or not
+    /// positive.
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        if self.upper_slope()? > SLOPE_TOLERANCE {
+            return Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Maximum profit is unlimited".to_string(),
+                },
+            ));
+        }
+        let (max_profit, _) = self.profit_extremes()?;
+        if max_profit <= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxProfitError {
+                    reason: "Max profit is negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(max_profit.into())
+        }
+    }
+
+    /// Deepest loss over the price grid, or an error when it is unlimited or the
+    /// strategy cannot lose.
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        if self.upper_slope()? < -SLOPE_TOLERANCE {
+            return Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Maximum loss is unlimited".to_string(),
+                },
+            ));
+        }
+        let (_, min_profit) = self.profit_extremes()?;
+        if min_profit >= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Max loss must be negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(min_profit.abs().into())
+        }
+    }
+
+    fn get_total_cost(&self) -> Result<Positive, PositionError> {
+        let options = self
+            .positions
+            .iter()
+            .map(|position| position.total_cost())
+            .sum::<Result<Positive, PositionError>>()?;
+        Ok(options
+            + self
+                .underlying
+                .as_ref()
+                .map_or(Positive::ZERO, |underlying| underlying.total_cost()))
+    }
+
+    fn get_net_cost(&self) -> Result<Decimal, PositionError> {
+        let options = self
+            .positions
+            .iter()
+            .map(|position| position.net_cost())
+            .sum::<Result<Decimal, PositionError>>()?;
+        Ok(options
+            + self
+                .underlying
+                .as_ref()
+                .map_or(Decimal::ZERO, |underlying| underlying.net_cost()))
+    }
+
+    fn get_fees(&self) -> Result<Positive, StrategyError> {
+        let options = self
+            .positions
+            .iter()
+            .map(|position| position.fees())
+            .sum::<Result<Positive, PositionError>>()?;
+        Ok(options
+            + self
+                .underlying
+                .as_ref()
+                .map_or(Positive::ZERO, |underlying| underlying.fees()))
+    }
+
+    /// Area under the positive part of the profit curve over the price grid, scaled
+    /// down by 100.
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        Ok(payoff_scan::profit_area(&self.price_grid(), &|price| {
+            self.calculate_profit_at(price)
+        })?)
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        let max_profit = self.get_max_profit().unwrap_or(Positive::ZERO);
+        let max_loss = self.get_max_loss().unwrap_or(Positive::ZERO);
+        match (max_profit, max_loss) {
+            (value, _) if value == Positive::ZERO => Ok(Decimal::ZERO),
+            (_, value) if value == Positive::ZERO => Ok(Decimal::MAX),
+            _ => Ok((max_profit / max_loss * 100.0).into()),
+        }
+    }
+}
+
+impl Profit for CustomStrategy {
+    /// Profit when the first leg expires with the underlying at `price`.
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        let days = self.leg_days()?;
+        let horizon = days
+            .iter()
+            .copied()
+            .min()
+            .ok_or("Custom strategy has no option legs")?;
+        let options = self
+            .positions
+            .iter()
+            .zip(days)
+            .map(|(position, days)| CustomStrategy::leg_profit(position, days, horizon, price))
+            .sum::<Result<Decimal, Box<dyn Error>>>()?;
+        Ok(options
+            + self
+                .underlying
+                .as_ref()
+                .map_or(Decimal::ZERO, |underlying| underlying.pnl_at(price)))
+    }
+}
+
+impl ProbabilityAnalysis for CustomStrategy {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        self.probability_ranges(true)
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        self.probability_ranges(false)
+    }
+}
+
+impl Greeks for CustomStrategy {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(self
+            .positions
+            .iter()
+            .map(|position| &position.option)
+            .collect())
+    }
+
+    /// Delta of every leg plus the delta of the holding (one per unit held).
+    fn delta(&self) -> Result<Decimal, GreeksError> {
+        let options = self
+            .positions
+            .iter()
+            .map(|position| delta(&position.option))
+            .sum::<Result<Decimal, GreeksError>>()?;
+        Ok(options
+            + self
+                .underlying
+                .as_ref()
+                .map_or(Decimal::ZERO, |underlying| underlying.delta()))
+    }
+}
+
+impl DeltaNeutrality for CustomStrategy {}
+
+impl PnLCalculator for CustomStrategy {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        let options = self
+            .positions
+            .iter()
+            .map(|position| {
+                position.calculate_pnl(market_price, expiration_date, implied_volatility)
+            })
+            .collect::<Result<Vec<PnL>, _>>()?;
+        let mut pnl: PnL = options.into_iter().sum();
+        if let Some(underlying) = &self.underlying {
+            pnl = pnl
+                + underlying.calculate_pnl(market_price, expiration_date, implied_volatility)?;
+        }
+        Ok(pnl)
+    }
+
+    /// Result when the first leg expires. Legs expiring later are closed at their
+    /// Black-Scholes value and reported as realized on that date.
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        let days = self.leg_days()?;
+        let (index, horizon) = self.first_expiry()?;
+        let date = self.positions[index].option.expiration_date.get_date()?;
+        let mut pnl = PnL::default();
+        for (position, days) in self.positions.iter().zip(days) {
+            pnl = pnl
+                + if days <= horizon {
+                    position.calculate_pnl_at_expiration(underlying_price)?
+                } else {
+                    PnL::new(
+                        Some(CustomStrategy::leg_profit(
+                            position,
+                            days,
+                            horizon,
+                            underlying_price,
+                        )?),
+                        Some(Decimal::ZERO),
+                        position.total_cost()?,
+                        position.premium_received()?,
+                        date,
+                    )
+                };
+        }
+        if let Some(underlying) = &self.underlying {
+            pnl = pnl + underlying.calculate_pnl_at_expiration(underlying_price)?;
+        }
+        Ok(pnl)
+    }
+}
+
+#[cfg(test)]
+mod tests_custom_strategy {
+    use super::*;
+    use crate::model::types::OptionType;
+    use crate::strategies::base::Validable;
+    use crate::strategies::{Collar, StrategyRequest};
+    use crate::{assert_decimal_eq, pos};
+    use chrono::Utc;
+
+    fn leg(
+        style: OptionStyle,
+        side: Side,
+        strike: Positive,
+        quantity: Positive,
+        premium: Positive,
+        days: Positive,
+    ) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos!(0.2),
+            quantity,
+            pos!(100.0),
+            dec!(0.05),
+            style,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    fn call(side: Side, strike: f64, quantity: f64, premium: f64) -> Position {
+        leg(
+            OptionStyle::Call,
+            side,
+            pos!(strike),
+            pos!(quantity),
+            pos!(premium),
+            pos!(30.0),
+        )
+    }
+
+    fn put(side: Side, strike: f64, quantity: f64, premium: f64) -> Position {
+        leg(
+            OptionStyle::Put,
+            side,
+            pos!(strike),
+            pos!(quantity),
+            pos!(premium),
+            pos!(30.0),
+        )
+    }
+
+    #[test]
+    fn test_ratio_spread() {
+        // Long one 100 call for 3, short two 110 calls for 1 each
+        let strategy = CustomStrategy::new(
+            "Ratio".to_string(),
+            vec![
+                call(Side::Long, 100.0, 1.0, 3.0),
+                call(Side::Short, 110.0, 2.0, 1.0),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(strategy.kind, StrategyType::Custom);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(101.0), pos!(119.0)]
+        );
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(9.0));
+        assert!(strategy.get_max_loss().is_err());
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(50.0)).unwrap(),
+            dec!(-1.0)
+        );
+        assert_eq!(strategy.clone().get_volume().unwrap(), pos!(3.0));
+    }
+
+    #[test]
+    fn test_broken_wing_butterfly() {
+        // Long 95 call, short two 100 calls, long 110 call
+        let strategy = CustomStrategy::new(
+            "Broken wing".to_string(),
+            vec![
+                call(Side::Long, 95.0, 1.0, 6.5),
+                call(Side::Short, 100.0, 2.0, 3.5),
+                call(Side::Long, 110.0, 1.0, 0.5),
+            ],
+            None,
+        )
+        .unwrap();
+        // Entered for no net cost: flat at zero below the lower wing, 5 at the body and
+        // -5 above the upper wing
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(5.0));
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(5.0));
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(105.0)]
+        );
+        assert!(strategy.get_profit_ratio().unwrap() > Decimal::ZERO);
+        assert!(strategy.get_profit_area().unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_jade_lizard_has_no_upside_risk() {
+        // Short 95 put, short 105 call, long 110 call for a total credit of 5.5
+        let strategy = CustomStrategy::new(
+            "Jade lizard".to_string(),
+            vec![
+                put(Side::Short, 95.0, 1.0, 2.5),
+                call(Side::Short, 105.0, 1.0, 4.0),
+                call(Side::Long, 110.0, 1.0, 1.0),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(strategy.get_break_even_points().unwrap(), &vec![pos!(89.5)]);
+        assert_eq!(strategy.get_max_profit().unwrap(), pos!(5.5));
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(200.0)).unwrap(),
+            dec!(0.5)
+        );
+        // The naked put loses down to a zero underlying price
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(89.5));
+    }
+
+    #[test]
+    fn test_matches_collar_with_underlying() {
+        let collar = Collar::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(2.0),
+            pos!(1.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let strategy = CustomStrategy::new(
+            "Collar".to_string(),
+            collar
+                .get_positions()
+                .unwrap()
+                .into_iter()
+                .cloned()
+                .collect(),
+            collar.get_underlying_position().cloned(),
+        )
+        .unwrap();
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            collar.get_break_even_points().unwrap()
+        );
+        assert_eq!(
+            strategy.get_max_profit().unwrap(),
+            collar.get_max_profit().unwrap()
+        );
+        assert_eq!(
+            strategy.get_max_loss().unwrap(),
+            collar.get_max_loss().unwrap()
+        );
+        assert_eq!(
+            strategy.get_net_cost().unwrap(),
+            collar.get_net_cost().unwrap()
+        );
+        assert_eq!(strategy.delta().unwrap(), collar.delta().unwrap());
+        assert_eq!(
+            strategy
+                .calculate_pnl_at_expiration(&pos!(105.0))
+                .unwrap()
+                .realized,
+            collar
+                .calculate_pnl_at_expiration(&pos!(105.0))
+                .unwrap()
+                .realized
+        );
+        assert_eq!(
+            strategy.get_underlying_position().unwrap().quantity,
+            pos!(1.0)
+        );
+    }
+
+    #[test]
+    fn test_calendar_values_back_leg_at_front_expiry() {
+        let front = leg(
+            OptionStyle::Call,
+            Side::Short,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(2.5),
+            pos!(30.0),
+        );
+        let back = leg(
+            OptionStyle::Call,
+            Side::Long,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(4.6),
+            pos!(90.0),
+        );
+        let strategy =
+            CustomStrategy::new("Calendar".to_string(), vec![back.clone(), front], None).unwrap();
+
+        let mut remaining = back.option.clone();
+        remaining.expiration_date = ExpirationDate::Days(pos!(60.0));
+        let expected = remaining.calculate_price_black_scholes().unwrap() + dec!(2.5) - dec!(4.6);
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(100.0)).unwrap(),
+            expected
+        );
+        assert_eq!(
+            strategy
+                .calculate_pnl_at_expiration(&pos!(100.0))
+                .unwrap()
+                .realized
+                .unwrap(),
+            expected
+        );
+
+        let break_even_points = strategy.get_break_even_points().unwrap();
+        assert_eq!(break_even_points.len(), 2);
+        for point in break_even_points {
+            let profit = strategy.calculate_profit_at(point).unwrap();
+            assert!(profit.abs() < dec!(0.01));
+        }
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let strategy = CustomStrategy::new(
+            "Ratio".to_string(),
+            vec![
+                call(Side::Long, 100.0, 1.0, 3.0),
+                call(Side::Short, 110.0, 2.0, 1.0),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(strategy.get_profit_ranges().unwrap().len(), 1);
+        assert_eq!(strategy.get_loss_ranges().unwrap().len(), 2);
+        let profit = strategy.probability_of_profit(None, None).unwrap();
+        let loss = strategy.probability_of_loss(None, None).unwrap();
+        assert_decimal_eq!((profit + loss).to_dec(), dec!(1.0), dec!(0.001));
+    }
+
+    #[test]
+    fn test_validation_and_positions() {
+        assert!(CustomStrategy::new("Empty".to_string(), vec![], None).is_err());
+
+        let mut other = call(Side::Long, 100.0, 1.0, 3.0);
+        other.option.underlying_symbol = "OTHER".to_string();
+        assert!(
+            CustomStrategy::new(
+                "Mixed".to_string(),
+                vec![call(Side::Long, 100.0, 1.0, 3.0), other.clone()],
+                None
+            )
+            .is_err()
+        );
+
+        let mut strategy =
+            CustomStrategy::get_strategy(&[call(Side::Long, 100.0, 1.0, 3.0)]).unwrap();
+        assert!(strategy.add_position(&other).is_err());
+        strategy
+            .add_position(&call(Side::Short, 110.0, 1.0, 1.0))
+            .unwrap();
+        strategy.update_break_even_points().unwrap();
+        assert!(strategy.validate());
+        assert_eq!(strategy.get_positions().unwrap().len(), 2);
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(102.0)]
+        );
+
+        let modified = call(Side::Short, 110.0, 1.0, 1.5);
+        strategy.modify_position(&modified).unwrap();
+        assert_eq!(
+            strategy
+                .get_position(&OptionStyle::Call, &Side::Short, &pos!(110.0))
+                .unwrap()[0]
+                .premium,
+            pos!(1.5)
+        );
+        assert!(
+            strategy
+                .get_position(&OptionStyle::Put, &Side::Short, &pos!(110.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_default_strategy_without_legs() {
+        let mut strategy = CustomStrategy::default();
+        let placeholder = Options::default();
+        assert_eq!(
+            strategy.get_underlying_price(),
+            &placeholder.underlying_price
+        );
+        strategy.one_option_mut().underlying_price = pos!(100.0);
+        assert_eq!(strategy.get_underlying_price(), &pos!(100.0));
+        assert!(strategy.get_positions().unwrap().is_empty());
+
+        // Structures start from an empty custom strategy
+        let jade_lizard = crate::strategies::JadeLizard::default();
+        assert_eq!(
+            jade_lizard.get_underlying_price(),
+            &placeholder.underlying_price
+        );
+    }
+
+    #[test]
+    fn test_strategy_request() {
+        let request = StrategyRequest::new(
+            StrategyType::Custom,
+            vec![
+                put(Side::Long, 95.0, 1.0, 1.0),
+                call(Side::Long, 105.0, 1.0, 1.0),
+            ],
+        );
+        let strategy = request.get_strategy().unwrap();
+        assert_eq!(strategy.type_name(), StrategyType::Custom);
+        assert_eq!(strategy.get_break_even_points().unwrap().len(), 2);
+        assert!(strategy.delta_neutrality().is_ok());
+    }
+}
@@ -1,14 +1,26 @@
+use crate::Options;
 use crate::model::Position;
+use crate::model::UnderlyingPosition;
 use crate::strategies::base::StrategyType;
+use crate::strategies::call_calendar_spread::CALL_CALENDAR_SPREAD_DESCRIPTION;
+use crate::strategies::collar::COLLAR_DESCRIPTION;
+use crate::strategies::covered_call::COVERED_CALL_DESCRIPTION;
+use crate::strategies::custom::CUSTOM_STRATEGY_DESCRIPTION;
+use crate::strategies::diagonal_call_spread::DIAGONAL_CALL_SPREAD_DESCRIPTION;
+use crate::strategies::diagonal_put_spread::DIAGONAL_PUT_SPREAD_DESCRIPTION;
 use crate::strategies::long_call::LONG_CALL_DESCRIPTION;
 use crate::strategies::long_put::LONG_PUT_DESCRIPTION;
 use crate::strategies::poor_mans_covered_call::PMCC_DESCRIPTION;
+use crate::strategies::protective_put::PROTECTIVE_PUT_DESCRIPTION;
+use crate::strategies::put_calendar_spread::PUT_CALENDAR_SPREAD_DESCRIPTION;
 use crate::strategies::short_call::SHORT_CALL_DESCRIPTION;
 use crate::strategies::short_put::SHORT_PUT_DESCRIPTION;
 use crate::strategies::{
-    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly, IronButterfly,
-    IronCondor, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
-    PoorMansCoveredCall, ShortButterflySpread, ShortCall, ShortPut, ShortStraddle, ShortStrangle,
+    BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly,
+    CallCalendarSpread, Collar, CoveredCall, CustomStrategy, DiagonalCallSpread, DiagonalPutSpread,
+    IronButterfly, IronCondor, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
+    PoorMansCoveredCall, ProtectivePut, PutCalendarSpread, ShortButterflySpread, ShortCall,
+    ShortPut, ShortStraddle, ShortStrangle,
 };
 
 impl Default for BullCallSpread {
@@ -97,6 +109,54 @@ impl Default for PoorMansCoveredCall {
         }
     }
 }
+impl Default for CallCalendarSpread {
+    fn default() -> Self {
+        CallCalendarSpread {
+            name: "Call Calendar Spread".to_string(),
+            kind: StrategyType::CallCalendarSpread,
+            description: CALL_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_call: Position::default(),
+            long_call: Position::default(),
+        }
+    }
+}
+impl Default for PutCalendarSpread {
+    fn default() -> Self {
+        PutCalendarSpread {
+            name: "Put Calendar Spread".to_string(),
+            kind: StrategyType::PutCalendarSpread,
+            description: PUT_CALENDAR_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_put: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for DiagonalCallSpread {
+    fn default() -> Self {
+        DiagonalCallSpread {
+            name: "Diagonal Call Spread".to_string(),
+            kind: StrategyType::DiagonalCallSpread,
+            description: DIAGONAL_CALL_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_call: Position::default(),
+            long_call: Position::default(),
+        }
+    }
+}
+impl Default for DiagonalPutSpread {
+    fn default() -> Self {
+        DiagonalPutSpread {
+            name: "Diagonal Put Spread".to_string(),
+            kind: StrategyType::DiagonalPutSpread,
+            description: DIAGONAL_PUT_SPREAD_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            short_put: Position::default(),
+            long_put: Position::default(),
+        }
+    }
+}
 impl Default for CallButterfly {
     fn default() -> Self {
         CallButterfly::new(
@@ -123,9 +183,56 @@ impl Default for CallButterfly {
     }
 }
 
-// impl JsonDisplay for CoveredCall {}
-// impl JsonDisplay for ProtectivePut {}
-// impl JsonDisplay for Collar {}
+impl Default for CoveredCall {
+    fn default() -> Self {
+        CoveredCall {
+            name: "Covered Call".to_string(),
+            kind: StrategyType::CoveredCall,
+            description: COVERED_CALL_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_underlying: UnderlyingPosition::default(),
+            short_call: Position::default(),
+        }
+    }
+}
+impl Default for ProtectivePut {
+    fn default() -> Self {
+        ProtectivePut {
+            name: "Protective Put".to_string(),
+            kind: StrategyType::ProtectivePut,
+            description: PROTECTIVE_PUT_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_underlying: UnderlyingPosition::default(),
+            long_put: Position::default(),
+        }
+    }
+}
+impl Default for Collar {
+    fn default() -> Self {
+        Collar {
+            name: "Collar".to_string(),
+            kind: StrategyType::Collar,
+            description: COLLAR_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            long_underlying: UnderlyingPosition::default(),
+            long_put: Position::default(),
+            short_call: Position::default(),
+        }
+    }
+}
+impl Default for CustomStrategy {
+    fn default() -> Self {
+        CustomStrategy {
+            name: "Custom Strategy".to_string(),
+            kind: StrategyType::Custom,
+            description: CUSTOM_STRATEGY_DESCRIPTION.to_string(),
+            break_even_points: Vec::new(),
+            positions: Vec::new(),
+            underlying: None,
+            placeholder: Options::default(),
+        }
+    }
+}
 impl Default for LongCall {
     fn default() -> Self {
         LongCall {
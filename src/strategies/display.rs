@@ -20,7 +20,29 @@ impl_json_display_pretty!(
     ShortCall,
     ShortPut,
     PoorMansCoveredCall,
-    CallButterfly
+    CallButterfly,
+    CallCalendarSpread,
+    PutCalendarSpread,
+    DiagonalCallSpread,
+    DiagonalPutSpread,
+    CoveredCall,
+    ProtectivePut,
+    Collar,
+    CustomStrategy,
+    CallRatioSpread,
+    PutRatioSpread,
+    CallBackSpread,
+    PutBackSpread,
+    CallBrokenWingButterfly,
+    PutBrokenWingButterfly,
+    CallCondor,
+    PutCondor,
+    JadeLizard,
+    ReverseIronCondor,
+    RiskReversal,
+    Strip,
+    Strap,
+    BoxSpread
 );
 
 #[cfg(test)]
@@ -2,10 +2,14 @@ use crate::Positive;
 use crate::pricing::Profit;
 use crate::strategies::base::BreakEvenable;
 use crate::strategies::{
-    BasicAble, BearCallSpread, BearPutSpread, BullCallSpread, BullPutSpread, CallButterfly,
-    IronButterfly, IronCondor, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
-    PoorMansCoveredCall, ShortButterflySpread, ShortCall, ShortPut, ShortStraddle, ShortStrangle,
-    Strategies,
+    BasicAble, BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread,
+    CallBackSpread, CallBrokenWingButterfly, CallButterfly, CallCalendarSpread, CallCondor,
+    CallRatioSpread, Collar, CoveredCall, CustomStrategy, DiagonalCallSpread, DiagonalPutSpread,
+    IronButterfly, IronCondor, JadeLizard, LongButterflySpread, LongCall, LongPut, LongStraddle,
+    LongStrangle, PoorMansCoveredCall, ProtectivePut, PutBackSpread, PutBrokenWingButterfly,
+    PutCalendarSpread, PutCondor, PutRatioSpread, ReverseIronCondor, RiskReversal,
+    ShortButterflySpread, ShortCall, ShortPut, ShortStraddle, ShortStrangle, Strap, Strategies,
+    Strip,
 };
 use crate::visualization::{
     ColorScheme, Graph, GraphConfig, GraphData, Label2D, LineStyle, Point2D, Series2D, TraceMode,
@@ -375,5 +379,27 @@ impl_graph_for_payoff_strategy!(
     ShortCall,
     ShortPut,
     PoorMansCoveredCall,
-    CallButterfly
+    CallButterfly,
+    CallCalendarSpread,
+    PutCalendarSpread,
+    DiagonalCallSpread,
+    DiagonalPutSpread,
+    CoveredCall,
+    ProtectivePut,
+    Collar,
+    CustomStrategy,
+    CallRatioSpread,
+    PutRatioSpread,
+    CallBackSpread,
+    PutBackSpread,
+    CallBrokenWingButterfly,
+    PutBrokenWingButterfly,
+    CallCondor,
+    PutCondor,
+    JadeLizard,
+    ReverseIronCondor,
+    RiskReversal,
+    Strip,
+    Strap,
+    BoxSpread
 );
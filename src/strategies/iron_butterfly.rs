@@ -914,6 +914,19 @@ impl Optimizable for IronButterfly {
         }
     }
 
+    fn group_legs<'a>(&self, group: OptionDataGroup<'a>) -> Option<StrategyLegs<'a>> {
+        // The short put and the short call share the middle strike
+        match group {
+            OptionDataGroup::Three(low, mid, high) => Some(StrategyLegs::FourLegs {
+                first: low,
+                second: mid,
+                third: mid,
+                fourth: high,
+            }),
+            _ => None,
+        }
+    }
+
     fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
         match legs {
             StrategyLegs::FourLegs {
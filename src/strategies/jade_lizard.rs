@@ -0,0 +1,104 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const JADE_LIZARD_DESCRIPTION: &str = "A Jade Lizard sells an out-of-the-money put and an \
+    out-of-the-money call spread. When the total credit is at least the width of the call \
+    spread there is no risk to the upside, leaving only the downside risk of the short put.";
+
+static JADE_LIZARD: StructureSpec = StructureSpec {
+    kind: StrategyType::JadeLizard,
+    name: "Jade Lizard",
+    description: JADE_LIZARD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Short, false),
+        leg(1, OptionStyle::Call, Side::Short, false),
+        leg(2, OptionStyle::Call, Side::Long, false),
+    ],
+    ratio: QuantityRatio::One,
+    strikes: distinct_strikes,
+};
+
+/// A short put below a short call spread, all on the same expiration.
+///
+/// The strategy is neutral to bullish. It collects premium on both sides and, unlike a
+/// short strangle, caps the loss above the long call.
+///
+/// Legs, by ascending strike: short put, short call and long call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JadeLizard {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl JadeLizard {
+    /// Creates a Jade Lizard.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `short_put_strike` - Strike of the short put, the lowest.
+    /// * `short_call_strike` - Strike of the short call.
+    /// * `long_call_strike` - Strike of the long call, the highest.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of every leg.
+    /// * `premium_short_put` - The premium received for the short put.
+    /// * `premium_short_call` - The premium received for the short call.
+    /// * `premium_long_call` - The premium paid for the long call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are not distinct.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_put_strike: Positive,
+        short_call_strike: Positive,
+        long_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_put: Positive,
+        premium_short_call: Positive,
+        premium_long_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = JADE_LIZARD.build(
+            underlying_symbol,
+            underlying_price,
+            &[short_put_strike, short_call_strike, long_call_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::ONE,
+            &[premium_short_put, premium_short_call, premium_long_call],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(JadeLizard { strategy })
+    }
+}
+
+impl_structure!(JadeLizard, JADE_LIZARD);
@@ -0,0 +1,352 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Liquidity
+//!
+//! The optimizers price every leg from the quotes of the chain: long legs at the ask and
+//! short legs at the bid. [`Liquidity`] prepares a chain before the search, removing the
+//! strikes that trade too little and the quotes that are too wide, and moving the
+//! remaining quotes to the prices of a [`FillModel`]. The strategies built from the
+//! prepared chain carry premiums at those fill prices.
+//!
+//! ```rust
+//! use optionstratlib::chains::chain::OptionChain;
+//! use optionstratlib::strategies::{FillModel, Liquidity};
+//! use optionstratlib::{pos, spos};
+//!
+//! let mut chain = OptionChain::new("SPY", pos!(100.0), "30".to_string(), None, None);
+//! for (strike, bid, ask, open_interest) in [(95.0, 6.0, 6.2, 500), (100.0, 2.6, 3.4, 800)] {
+//!     chain.add_option(
+//!         pos!(strike),
+//!         spos!(bid),
+//!         spos!(ask),
+//!         None,
+//!         None,
+//!         pos!(0.2),
+//!         None,
+//!         None,
+//!         None,
+//!         spos!(100.0),
+//!         Some(open_interest),
+//!         None,
+//!     );
+//! }
+//!
+//! // Calls quoted wider than 10% of the mid are dropped, the rest fill at the mid
+//! let liquidity = Liquidity::new(Some(100), None, spos!(0.1), FillModel::Mid);
+//! let liquid = liquidity.apply(&chain);
+//! assert_eq!(liquid.options.len(), 1);
+//! let option = liquid.options.first().unwrap();
+//! assert_eq!(option.call_bid, spos!(6.1));
+//! assert_eq!(option.call_ask, spos!(6.1));
+//! ```
+
+use crate::Positive;
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::series::OptionSeries;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Price at which the legs of a strategy are assumed to fill.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FillModel {
+    /// Buy at the ask and sell at the bid.
+    #[default]
+    Touch,
+    /// Buy and sell at the mid price.
+    Mid,
+    /// Pay this fraction of the half spread on top of the mid price: 0 fills at the mid,
+    /// 1 at the touch and larger values model fills through the quote. Sale prices never
+    /// drop below zero.
+    Slippage(Positive),
+}
+
+impl FillModel {
+    /// Bid and ask at which a quote fills. The quote is returned unchanged under `Touch`.
+    fn fill(&self, bid: Positive, ask: Positive) -> (Positive, Positive) {
+        let fraction = match self {
+            FillModel::Touch => return (bid, ask),
+            FillModel::Mid => Decimal::ZERO,
+            FillModel::Slippage(fraction) => fraction.to_dec(),
+        };
+        let mid = (bid.to_dec() + ask.to_dec()) / Decimal::TWO;
+        let slippage = fraction * (ask.to_dec() - bid.to_dec()) / Decimal::TWO;
+        (
+            Positive::new_decimal((mid - slippage).max(Decimal::ZERO)).unwrap_or(Positive::ZERO),
+            Positive::new_decimal(mid + slippage).unwrap_or(ask),
+        )
+    }
+}
+
+/// Liquidity requirements and fill model applied to a chain before optimizing.
+///
+/// Open interest and volume are quoted per strike, so a strike failing either minimum is
+/// removed. The spread limit applies to calls and puts separately: a style quoted wider
+/// than the limit, or quoted on one side only, loses its bid and ask, and a strike that
+/// loses all its quotes is removed. Fill models other than `Touch` also need both sides of a
+/// quote and drop one-sided quotes in the same way.
+///
+/// The default requires nothing and fills at the touch, leaving chains unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Liquidity {
+    /// Smallest open interest of a strike, `None` for no minimum.
+    pub min_open_interest: Option<u64>,
+    /// Smallest volume of a strike, `None` for no minimum.
+    pub min_volume: Option<Positive>,
+    /// Widest bid-ask spread as a fraction of the mid price, so 0.1 allows a spread of
+    /// 10% of the mid. `None` for no limit.
+    pub max_spread_ratio: Option<Positive>,
+    /// Price at which the legs fill.
+    pub fill: FillModel,
+}
+
+impl Liquidity {
+    /// Creates liquidity requirements.
+    pub fn new(
+        min_open_interest: Option<u64>,
+        min_volume: Option<Positive>,
+        max_spread_ratio: Option<Positive>,
+        fill: FillModel,
+    ) -> Self {
+        Liquidity {
+            min_open_interest,
+            min_volume,
+            max_spread_ratio,
+            fill,
+        }
+    }
+
+    /// Whether the strike meets the open interest and volume minimums. A strike without
+    /// the data fails any minimum set on it.
+    pub fn admits(&self, option: &OptionData) -> bool {
+        self.min_open_interest
+            .is_none_or(|min| option.open_interest.is_some_and(|oi| oi >= min))
+            && self
+                .min_volume
+                .is_none_or(|min| option.volume.is_some_and(|volume| volume >= min))
+    }
+
+    /// Quote of one style once the spread limit and fill model are applied, `None` if the
+    /// style cannot be traded.
+    fn quote(
+        &self,
+        bid: Option<Positive>,
+        ask: Option<Positive>,
+    ) -> (Option<Positive>, Option<Positive>) {
+        let two_sided = self.max_spread_ratio.is_some() || self.fill != FillModel::Touch;
+        let (bid, ask) = match (bid, ask) {
+            (Some(bid), Some(ask)) if ask >= bid => (bid, ask),
+            _ if two_sided => return (None, None),
+            _ => return (bid, ask),
+        };
+        if let Some(max) = self.max_spread_ratio {
+            let mid = (bid.to_dec() + ask.to_dec()) / Decimal::TWO;
+            if mid.is_zero() || (ask.to_dec() - bid.to_dec()) / mid > max.to_dec() {
+                return (None, None);
+            }
+        }
+        let (bid, ask) = self.fill.fill(bid, ask);
+        (Some(bid), Some(ask))
+    }
+
+    /// Copy of `option_chain` holding only the liquid strikes, quoted at the fill prices.
+    pub fn apply(&self, option_chain: &OptionChain) -> OptionChain {
+        let mut chain = option_chain.clone();
+        chain.options = option_chain
+            .options
+            .iter()
+            .filter(|option| self.admits(option))
+            .filter_map(|option| {
+                let quoted = |o: &OptionData| {
+                    [o.call_bid, o.call_ask, o.put_bid, o.put_ask]
+                        .iter()
+                        .any(Option::is_some)
+                };
+                let mut liquid = option.clone();
+                (liquid.call_bid, liquid.call_ask) = self.quote(option.call_bid, option.call_ask);
+                (liquid.put_bid, liquid.put_ask) = self.quote(option.put_bid, option.put_ask);
+                (quoted(&liquid) || !quoted(option)).then_some(liquid)
+            })
+            .collect();
+        chain
+    }
+
+    /// Copy of `option_series` with [`Liquidity::apply`] applied to every chain.
+    pub fn apply_to_series(&self, option_series: &OptionSeries) -> OptionSeries {
+        let mut series = option_series.clone();
+        for chain in series.chains.values_mut() {
+            *chain = self.apply(chain);
+        }
+        series
+    }
+}
+
+#[cfg(test)]
+mod tests_liquidity {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::strategies::BullCallSpread;
+    use crate::strategies::base::{Optimizable, Positionable};
+    use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+    use crate::{pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn chain() -> OptionChain {
+        let mut chain = OptionChain::new("TEST", pos!(100.0), "30".to_string(), None, None);
+        // Strike, call bid, call ask, put bid, put ask, volume, open interest
+        let quotes = [
+            (
+                90.0,
+                spos!(10.0),
+                spos!(10.4),
+                spos!(0.5),
+                spos!(0.7),
+                50.0,
+                300,
+            ),
+            (
+                100.0,
+                spos!(3.0),
+                spos!(3.2),
+                spos!(2.0),
+                spos!(2.8),
+                80.0,
+                900,
+            ),
+            (110.0, None, spos!(0.4), spos!(9.0), spos!(9.2), 5.0, 20),
+        ];
+        for (strike, call_bid, call_ask, put_bid, put_ask, volume, oi) in quotes {
+            chain.add_option(
+                pos!(strike),
+                call_bid,
+                call_ask,
+                put_bid,
+                put_ask,
+                pos!(0.2),
+                None,
+                None,
+                None,
+                spos!(volume),
+                Some(oi),
+                None,
+            );
+        }
+        chain
+    }
+
+    fn strike(chain: &OptionChain, strike: f64) -> &OptionData {
+        chain
+            .options
+            .iter()
+            .find(|o| o.strike_price == pos!(strike))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_default_keeps_chain() {
+        let chain = chain();
+        let applied = Liquidity::default().apply(&chain);
+        assert_eq!(
+            format!("{:?}", applied.options),
+            format!("{:?}", chain.options)
+        );
+    }
+
+    #[test]
+    fn test_minimums_remove_strikes() {
+        let chain = chain();
+        let applied = Liquidity::new(Some(100), None, None, FillModel::Touch).apply(&chain);
+        assert_eq!(applied.options.len(), 2);
+        let applied = Liquidity::new(None, spos!(60.0), None, FillModel::Touch).apply(&chain);
+        assert_eq!(applied.options.len(), 1);
+        assert_eq!(applied.options.first().unwrap().strike_price, pos!(100.0));
+    }
+
+    #[test]
+    fn test_spread_limit_per_style() {
+        let chain = chain();
+        let applied = Liquidity::new(None, None, spos!(0.1), FillModel::Touch).apply(&chain);
+        // Both puts are quoted 33% wide of their mid, the calls are tight
+        let atm = strike(&applied, 100.0);
+        assert_eq!((atm.call_bid, atm.call_ask), (spos!(3.0), spos!(3.2)));
+        assert_eq!((atm.put_bid, atm.put_ask), (None, None));
+        assert_eq!(strike(&applied, 90.0).put_ask, None);
+        // The one-sided 110 call is dropped, its put kept
+        let otm = strike(&applied, 110.0);
+        assert_eq!((otm.call_bid, otm.call_ask), (None, None));
+        assert_eq!(otm.put_bid, spos!(9.0));
+    }
+
+    #[test]
+    fn test_fill_models() {
+        let chain = chain();
+        let mid = Liquidity::new(None, None, None, FillModel::Mid).apply(&chain);
+        let atm = strike(&mid, 100.0);
+        assert_eq!((atm.call_bid, atm.call_ask), (spos!(3.1), spos!(3.1)));
+        assert_eq!((atm.put_bid, atm.put_ask), (spos!(2.4), spos!(2.4)));
+        assert_eq!(strike(&mid, 110.0).call_ask, None);
+
+        let slipped =
+            Liquidity::new(None, None, None, FillModel::Slippage(pos!(0.5))).apply(&chain);
+        let atm = strike(&slipped, 100.0);
+        assert_eq!((atm.put_bid, atm.put_ask), (spos!(2.2), spos!(2.6)));
+
+        // Slippage through the quote never prices a sale below zero
+        let (bid, ask) = FillModel::Slippage(pos!(3.0)).fill(pos!(0.1), pos!(0.3));
+        assert_eq!((bid, ask), (Positive::ZERO, pos!(0.5)));
+    }
+
+    #[test]
+    fn test_find_optimal_liquid_fills_at_mid() {
+        let mut spread = BullCallSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(90.0),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(10.4),
+            pos!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let liquidity = Liquidity::new(None, None, None, FillModel::Mid);
+        spread.find_optimal_liquid(
+            &chain(),
+            FindOptimalSide::All,
+            OptimizationCriteria::Ratio,
+            &liquidity,
+        );
+        // The one-sided 110 call cannot be filled at the mid, leaving a single spread
+        let premiums: Vec<_> = spread
+            .get_positions()
+            .unwrap()
+            .iter()
+            .map(|p| (p.option.strike_price, p.premium))
+            .collect();
+        assert_eq!(
+            premiums,
+            vec![(pos!(90.0), pos!(10.2)), (pos!(100.0), pos!(3.1))]
+        );
+    }
+
+    #[test]
+    fn test_apply_to_series() {
+        let mut series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        series
+            .chains
+            .insert(ExpirationDate::Days(pos!(30.0)), chain());
+        let applied =
+            Liquidity::new(Some(100), None, None, FillModel::Touch).apply_to_series(&series);
+        assert_eq!(applied.chains.values().next().unwrap().options.len(), 2);
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
     error::{
         GreeksError, OperationErrorKind,
         position::{PositionError, PositionValidationErrorKind},
-        probability::ProbabilityError,
+        probability::{ProbabilityError, ProfitLossRangeErrorKind},
         strategies::{ProfitLossErrorKind, StrategyError},
     },
     greeks::Greeks,
@@ -918,9 +918,28 @@ impl Profit for LongButterflySpread {
     }
 }
 
+impl LongButterflySpread {
+    /// Break-even points of the strategy, which only has both when each wing ends in a
+    /// loss.
+    fn two_break_even_points(&self) -> Result<&Vec<Positive>, ProbabilityError> {
+        let break_even_points = self.get_break_even_points()?;
+        if break_even_points.len() != 2 {
+            return Err(ProbabilityError::RangeError(
+                ProfitLossRangeErrorKind::InvalidBreakEvenPoints {
+                    reason: format!(
+                        "Long Butterfly Spread has {} break-even points instead of 2",
+                        break_even_points.len()
+                    ),
+                },
+            ));
+        }
+        Ok(break_even_points)
+    }
+}
+
 impl ProbabilityAnalysis for LongButterflySpread {
     fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
-        let break_even_points = self.get_break_even_points()?;
+        let break_even_points = self.two_break_even_points()?;
         let option = &self.short_call.option;
         let expiration_date = &option.expiration_date;
         let risk_free_rate = option.risk_free_rate;
@@ -953,7 +972,7 @@ impl ProbabilityAnalysis for LongButterflySpread {
 
     fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
         let mut ranges = Vec::new();
-        let break_even_points = self.get_break_even_points()?;
+        let break_even_points = self.two_break_even_points()?;
         let option = &self.short_call.option;
         let expiration_date = &option.expiration_date;
         let risk_free_rate = option.risk_free_rate;
@@ -3013,6 +3032,35 @@ mod tests_butterfly_probability {
         }
     }
 
+    #[test]
+    fn test_ranges_without_two_break_even_points() {
+        // Middle premiums this large leave a net credit the wings never recover
+        let butterfly = LongButterflySpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(90.0),
+            pos!(100.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            Positive::ONE,
+            pos!(5.0),
+            Positive::ONE,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        assert!(butterfly.get_break_even_points().unwrap().len() < 2);
+        assert!(butterfly.get_profit_ranges().is_err());
+        assert!(butterfly.get_loss_ranges().is_err());
+    }
+
     #[test]
     fn test_volatility_calculations() {
         let long_butterfly = create_test_long();
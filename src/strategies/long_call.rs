@@ -1,9 +1,19 @@
-use super::base::{BreakEvenable, Positionable, StrategyType};
-use crate::error::StrategyError;
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::chains::{StrategyLegs, chain::OptionChain};
 use crate::error::strategies::ProfitLossErrorKind;
+use crate::error::{GreeksError, OperationErrorKind, ProbabilityError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::ProfitLossRange;
 use crate::model::types::OptionBasicType;
+use crate::pnl::{PnLCalculator, utils::PnL};
 use crate::pricing::Profit;
-use crate::strategies::{BasicAble, Strategies, Validable};
+use crate::strategies::delta_neutral::DeltaNeutrality;
+use crate::strategies::probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment};
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::strategies::{BasicAble, Strategies, StrategyConstructor, Validable};
 use crate::{
     ExpirationDate, Options, Positive,
     error::position::{PositionError, PositionValidationErrorKind},
@@ -87,8 +97,8 @@ impl LongCall {
     /// - The function relies on creating a default `LongCall` instance and then populating it with positions.
     /// - Uses the `Options` and `Position` structures to model and manage the long call position.
     /// - Assumes the current time (_via `Utc::now()`) when opening the long call position for tracking purposes.
-    #[allow(clippy::too_many_arguments, dead_code)]
-    fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
         underlying_symbol: String,
         long_call_strike: Positive,
         long_call_expiration: ExpirationDate,
@@ -129,6 +139,9 @@ impl LongCall {
         strategy
             .add_position(&long_call.clone())
             .expect("Invalid long call option");
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
 
         strategy
     }
@@ -282,7 +295,7 @@ impl Strategies for LongCall {
     }
     fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
         let high = self.get_max_profit().unwrap_or(Positive::ZERO);
-        let base = self.long_call.option.strike_price - self.break_even_points[0];
+        let base = self.break_even_points[0] - self.long_call.option.strike_price;
         Ok((high * base / 200.0).into())
     }
     fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
@@ -296,6 +309,96 @@ impl Strategies for LongCall {
     }
 }
 
+impl Optimizable for LongCall {
+    type Strategy = LongCall;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_single_iter()
+            // Calls are bought out of the money by default
+            .filter(move |option| {
+                if side == FindOptimalSide::Center {
+                    option.is_valid_optimal_side(underlying_price, &FindOptimalSide::Upper)
+                } else {
+                    option.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            .filter(|option| option.call_ask.unwrap_or(Positive::ZERO) > Positive::ZERO)
+            .filter(move |option| {
+                let legs = StrategyLegs::OneLeg { first: option };
+                strategy.create_strategy(option_chain, &legs).validate()
+            })
+            .map(OptionDataGroup::One)
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let option = match option_data_group {
+                OptionDataGroup::One(first) => first,
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::OneLeg { first: option };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio(),
+                OptimizationCriteria::Area => strategy.get_profit_area(),
+            };
+
+            if let Ok(current_value) = current_value
+                && current_value > best_value
+            {
+                best_value = current_value;
+                *self = strategy;
+            }
+        }
+    }
+
+    fn are_valid_legs(&self, legs: &StrategyLegs) -> bool {
+        match legs {
+            StrategyLegs::OneLeg { first } => {
+                first.call_ask.unwrap_or(Positive::ZERO) > Positive::ZERO
+            }
+            _ => false,
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let option = match legs {
+            StrategyLegs::OneLeg { first } => first,
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        LongCall::new(
+            chain.symbol.clone(),
+            option.strike_price,
+            self.long_call.option.expiration_date,
+            option.implied_volatility,
+            self.long_call.option.quantity,
+            chain.underlying_price,
+            self.long_call.option.risk_free_rate,
+            self.long_call.option.dividend_yield,
+            option.call_ask.unwrap(),
+            self.long_call.open_fee,
+            self.long_call.close_fee,
+        )
+    }
+}
+
 impl Profit for LongCall {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
         let price = Some(price);
@@ -389,4 +492,108 @@ impl Positionable for LongCall {
     }
 }
 
+impl StrategyConstructor for LongCall {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 1 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Call get_strategy".to_string(),
+                    reason: "Must have exactly 1 option".to_string(),
+                },
+            ));
+        }
+        let position = &vec_positions[0];
+        if position.option.option_style != OptionStyle::Call || position.option.side != Side::Long {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Call get_strategy".to_string(),
+                    reason: "Long Call requires a long call option".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = LongCall::default();
+        strategy.add_position(position)?;
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl Strategable for LongCall {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl ProbabilityAnalysis for LongCall {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.long_call.option;
+
+        let mut profit_range = ProfitLossRange::new(Some(break_even), None, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.long_call.option;
+
+        let mut loss_range = ProfitLossRange::new(None, Some(break_even), Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for LongCall {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.long_call.option])
+    }
+}
+
+impl DeltaNeutrality for LongCall {}
+
+impl PnLCalculator for LongCall {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.long_call
+            .calculate_pnl(market_price, expiration_date, implied_volatility)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.long_call.calculate_pnl_at_expiration(underlying_price)
+    }
+}
+
 // test_strategy_traits!(LongCall, test_long_call_implementations);
@@ -1,9 +1,19 @@
-use super::base::{BreakEvenable, Positionable, StrategyType};
-use crate::error::StrategyError;
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::chains::{StrategyLegs, chain::OptionChain};
 use crate::error::strategies::ProfitLossErrorKind;
+use crate::error::{GreeksError, OperationErrorKind, ProbabilityError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::ProfitLossRange;
 use crate::model::types::OptionBasicType;
+use crate::pnl::{PnLCalculator, utils::PnL};
 use crate::pricing::Profit;
-use crate::strategies::{BasicAble, Strategies, Validable};
+use crate::strategies::delta_neutral::DeltaNeutrality;
+use crate::strategies::probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment};
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::strategies::{BasicAble, Strategies, StrategyConstructor, Validable};
 use crate::{
     ExpirationDate, Options, Positive,
     error::position::{PositionError, PositionValidationErrorKind},
@@ -82,8 +92,8 @@ impl LongPut {
     /// This function will panic if:
     /// * The `add_position` method fails, which could happen due to invalid configurations of the long put position.
     ///
-    #[allow(clippy::too_many_arguments, dead_code)]
-    fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
         underlying_symbol: String,
         long_put_strike: Positive,
         long_put_expiration: ExpirationDate,
@@ -124,6 +134,9 @@ impl LongPut {
         strategy
             .add_position(&long_put.clone())
             .expect("Invalid long put option");
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
 
         strategy
     }
@@ -242,7 +255,7 @@ impl BreakEvenable for LongPut {
 
         self.break_even_points.push(
             (self.long_put.option.strike_price
-                + self.get_net_cost()? / self.long_put.option.quantity)
+                - self.get_net_cost()? / self.long_put.option.quantity)
                 .round_to(2),
         );
 
@@ -252,7 +265,8 @@ impl BreakEvenable for LongPut {
 
 impl Strategies for LongPut {
     fn get_max_profit(&self) -> Result<Positive, StrategyError> {
-        let profit = self.calculate_profit_at(&self.long_put.option.strike_price)?;
+        // The put pays the most when the underlying goes to zero
+        let profit = self.calculate_profit_at(&Positive::ZERO)?;
         if profit >= Decimal::ZERO {
             Ok(profit.into())
         } else {
@@ -291,6 +305,96 @@ impl Strategies for LongPut {
     }
 }
 
+impl Optimizable for LongPut {
+    type Strategy = LongPut;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_single_iter()
+            // Puts are bought out of the money by default
+            .filter(move |option| {
+                if side == FindOptimalSide::Center {
+                    option.is_valid_optimal_side(underlying_price, &FindOptimalSide::Lower)
+                } else {
+                    option.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            .filter(|option| option.put_ask.unwrap_or(Positive::ZERO) > Positive::ZERO)
+            .filter(move |option| {
+                let legs = StrategyLegs::OneLeg { first: option };
+                strategy.create_strategy(option_chain, &legs).validate()
+            })
+            .map(OptionDataGroup::One)
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let option = match option_data_group {
+                OptionDataGroup::One(first) => first,
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::OneLeg { first: option };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio(),
+                OptimizationCriteria::Area => strategy.get_profit_area(),
+            };
+
+            if let Ok(current_value) = current_value
+                && current_value > best_value
+            {
+                best_value = current_value;
+                *self = strategy;
+            }
+        }
+    }
+
+    fn are_valid_legs(&self, legs: &StrategyLegs) -> bool {
+        match legs {
+            StrategyLegs::OneLeg { first } => {
+                first.put_ask.unwrap_or(Positive::ZERO) > Positive::ZERO
+            }
+            _ => false,
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let option = match legs {
+            StrategyLegs::OneLeg { first } => first,
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        LongPut::new(
+            chain.symbol.clone(),
+            option.strike_price,
+            self.long_put.option.expiration_date,
+            option.implied_volatility,
+            self.long_put.option.quantity,
+            chain.underlying_price,
+            self.long_put.option.risk_free_rate,
+            self.long_put.option.dividend_yield,
+            option.put_ask.unwrap(),
+            self.long_put.open_fee,
+            self.long_put.close_fee,
+        )
+    }
+}
+
 impl Profit for LongPut {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
         let price = Some(price);
@@ -384,4 +488,108 @@ impl Positionable for LongPut {
     }
 }
 
+impl StrategyConstructor for LongPut {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 1 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Put get_strategy".to_string(),
+                    reason: "Must have exactly 1 option".to_string(),
+                },
+            ));
+        }
+        let position = &vec_positions[0];
+        if position.option.option_style != OptionStyle::Put || position.option.side != Side::Long {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Long Put get_strategy".to_string(),
+                    reason: "Long Put requires a long put option".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = LongPut::default();
+        strategy.add_position(position)?;
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl Strategable for LongPut {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl ProbabilityAnalysis for LongPut {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.long_put.option;
+
+        let mut profit_range = ProfitLossRange::new(None, Some(break_even), Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.long_put.option;
+
+        let mut loss_range = ProfitLossRange::new(Some(break_even), None, Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for LongPut {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.long_put.option])
+    }
+}
+
+impl DeltaNeutrality for LongPut {}
+
+impl PnLCalculator for LongPut {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.long_put
+            .calculate_pnl(market_price, expiration_date, implied_volatility)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.long_put.calculate_pnl_at_expiration(underlying_price)
+    }
+}
+
 // test_strategy_traits!(LongPut, test_long_put_implementations);
@@ -711,6 +711,17 @@ impl Optimizable for LongStraddle {
         }
     }
 
+    fn group_legs<'a>(&self, group: OptionDataGroup<'a>) -> Option<StrategyLegs<'a>> {
+        // Both legs share the strike of the only option in the group
+        match group {
+            OptionDataGroup::One(both) => Some(StrategyLegs::TwoLegs {
+                first: both,
+                second: both,
+            }),
+            _ => None,
+        }
+    }
+
     fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
         let (call, put) = match legs {
             StrategyLegs::TwoLegs { first, second } => (first, second),
@@ -16,20 +16,38 @@
 //! - `base`: Provides the base traits and structures for the strategies.
 //! - `bear_call_spread`: Implements the Bear Call Spread strategy.
 //! - `bear_put_spread`: Implements the Bear Put Spread strategy.
+//! - `box_spread`: Implements the Box Spread strategy.
 //! - `bull_call_spread`: Implements the Bull Call Spread strategy.
 //! - `bull_put_spread`: Implements the Bull Put Spread strategy.
 //! - `butterfly_spread`: Implements the Butterfly Spread strategy.
+//! - `call_back_spread`: Implements the Call Back Spread strategy.
+//! - `call_broken_wing_butterfly`: Implements the Call Broken Wing Butterfly strategy.
 //! - `call_butterfly`: Implements the Call Butterfly strategy.
+//! - `call_calendar_spread`: Implements the Call Calendar Spread strategy.
+//! - `call_condor`: Implements the Call Condor strategy.
+//! - `call_ratio_spread`: Implements the Call Ratio Spread strategy.
 //! - `collar`: Implements the Collar strategy.
 //! - `covered_call`: Implements the Covered Call strategy.
 //! - `custom`: Provides utilities for creating custom strategies.
+//! - `diagonal_call_spread`: Implements the Diagonal Call Spread strategy.
+//! - `diagonal_put_spread`: Implements the Diagonal Put Spread strategy.
 //! - `iron_butterfly`: Implements the Iron Butterfly strategy.
 //! - `iron_condor`: Implements the Iron Condor strategy.
+//! - `jade_lizard`: Implements the Jade Lizard strategy.
 //! - `poor_mans_covered_call`: Implements the Poor Man's Covered Call strategy.
 //! - `probabilities`: Provides probability calculations for the strategies.
 //! - `protective_put`: Implements the Protective Put strategy.
+//! - `put_back_spread`: Implements the Put Back Spread strategy.
+//! - `put_broken_wing_butterfly`: Implements the Put Broken Wing Butterfly strategy.
+//! - `put_calendar_spread`: Implements the Put Calendar Spread strategy.
+//! - `put_condor`: Implements the Put Condor strategy.
+//! - `put_ratio_spread`: Implements the Put Ratio Spread strategy.
+//! - `reverse_iron_condor`: Implements the Reverse Iron Condor strategy.
+//! - `risk_reversal`: Implements the Risk Reversal strategy.
 //! - `straddle`: Implements the Straddle strategy.
 //! - `strangle`: Implements the Strangle strategy.
+//! - `strap`: Implements the Strap strategy.
+//! - `strip`: Implements the Strip strategy.
 //! - `utils`: Provides utility functions for the strategies.
 //!
 //! ## Usage
@@ -200,22 +218,40 @@ pub mod base;
 pub mod bear_call_spread;
 /// Bear Put Spread strategy implementation  
 pub mod bear_put_spread;
+/// Box Spread strategy implementation
+pub mod box_spread;
 /// Internal module for strategy building utilities
 mod build;
 /// Bull Call Spread strategy implementation
 pub mod bull_call_spread;
 /// Bull Put Spread strategy implementation
 pub mod bull_put_spread;
+/// Call Back Spread strategy implementation
+pub mod call_back_spread;
+/// Call Broken Wing Butterfly strategy implementation
+pub mod call_broken_wing_butterfly;
 /// Call Butterfly strategy implementation  
 pub mod call_butterfly;
+/// Call Calendar Spread strategy implementation
+pub mod call_calendar_spread;
+/// Call Condor strategy implementation
+pub mod call_condor;
+/// Call Ratio Spread strategy implementation
+pub mod call_ratio_spread;
 /// Collar strategy implementation
 pub mod collar;
 /// Covered Call strategy implementation
 pub mod covered_call;
+/// Custom strategy with an arbitrary set of legs
+pub mod custom;
 /// Default implementation for strategies
 pub mod default;
 /// Delta-neutral strategy implementation and utilities
 pub mod delta_neutral;
+/// Diagonal Call Spread strategy implementation
+pub mod diagonal_call_spread;
+/// Diagonal Put Spread strategy implementation
+pub mod diagonal_put_spread;
 /// Display implementation for strategies
 pub mod display;
 
@@ -242,6 +278,10 @@ pub mod graph;
 pub mod iron_butterfly;
 /// Iron Condor strategy implementation
 pub mod iron_condor;
+/// Jade Lizard strategy implementation
+pub mod jade_lizard;
+/// Liquidity filters and fill models applied to chains before optimizing
+pub mod liquidity;
 /// Butterfly Spread strategy implementation
 pub mod long_butterfly_spread;
 /// Long Call strategy implementation
@@ -254,12 +294,32 @@ pub mod long_straddle;
 pub mod long_strangle;
 /// Macros for options strategies
 pub mod macros;
+/// Multi-objective optimizer ranking the combinations of an option chain
+pub mod optimizer;
+/// Numerical break-even, extreme and probability analysis of a profit function
+mod payoff_scan;
 /// Poor Man's Covered Call strategy implementation
 pub mod poor_mans_covered_call;
 /// Probability calculations for options strategies
 pub mod probabilities;
 /// Protective Put strategy implementation
 pub mod protective_put;
+/// Put Back Spread strategy implementation
+pub mod put_back_spread;
+/// Put Broken Wing Butterfly strategy implementation
+pub mod put_broken_wing_butterfly;
+/// Put Calendar Spread strategy implementation
+pub mod put_calendar_spread;
+/// Put Condor strategy implementation
+pub mod put_condor;
+/// Put Ratio Spread strategy implementation
+pub mod put_ratio_spread;
+/// Reverse Iron Condor strategy implementation
+pub mod reverse_iron_condor;
+/// Risk Reversal strategy implementation
+pub mod risk_reversal;
+/// Screener ranking the strategies that fit a market view
+pub mod screener;
 /// Short Call strategy implementation
 pub mod short_butterfly_spread;
 /// Short Call strategy implementation
@@ -270,29 +330,60 @@ pub mod short_put;
 pub mod short_straddle;
 /// Short Strangle strategy implementation
 pub mod short_strangle;
+/// Strap strategy implementation
+pub mod strap;
+/// Strip strategy implementation
+pub mod strip;
+/// Construction and chain search shared by the structures built on the custom strategy
+mod structures;
+/// Valuation shared by strategies whose legs expire on different dates
+mod time_spread;
 /// Utility functions for options calculations and analysis
 pub mod utils;
 
 pub use base::{BasicAble, Strategable, Strategies, StrategyBasics, Validable};
 pub use bear_call_spread::BearCallSpread;
 pub use bear_put_spread::BearPutSpread;
+pub use box_spread::BoxSpread;
 pub use build::model::StrategyRequest;
 pub use build::traits::StrategyConstructor;
 pub use bull_call_spread::BullCallSpread;
 pub use bull_put_spread::BullPutSpread;
+pub use call_back_spread::CallBackSpread;
+pub use call_broken_wing_butterfly::CallBrokenWingButterfly;
 pub use call_butterfly::CallButterfly;
+pub use call_calendar_spread::CallCalendarSpread;
+pub use call_condor::CallCondor;
+pub use call_ratio_spread::CallRatioSpread;
+pub use collar::Collar;
+pub use covered_call::CoveredCall;
+pub use custom::CustomStrategy;
 pub use delta_neutral::{DELTA_THRESHOLD, DeltaAdjustment, DeltaInfo, DeltaNeutrality};
+pub use diagonal_call_spread::DiagonalCallSpread;
+pub use diagonal_put_spread::DiagonalPutSpread;
 pub use iron_butterfly::IronButterfly;
 pub use iron_condor::IronCondor;
+pub use jade_lizard::JadeLizard;
+pub use liquidity::{FillModel, Liquidity};
 pub use long_butterfly_spread::LongButterflySpread;
 pub use long_call::LongCall;
 pub use long_put::LongPut;
 pub use long_straddle::LongStraddle;
 pub use long_strangle::LongStrangle;
 pub use poor_mans_covered_call::PoorMansCoveredCall;
+pub use protective_put::ProtectivePut;
+pub use put_back_spread::PutBackSpread;
+pub use put_broken_wing_butterfly::PutBrokenWingButterfly;
+pub use put_calendar_spread::PutCalendarSpread;
+pub use put_condor::PutCondor;
+pub use put_ratio_spread::PutRatioSpread;
+pub use reverse_iron_condor::ReverseIronCondor;
+pub use risk_reversal::RiskReversal;
 pub use short_butterfly_spread::ShortButterflySpread;
 pub use short_call::ShortCall;
 pub use short_put::ShortPut;
 pub use short_straddle::ShortStraddle;
 pub use short_strangle::ShortStrangle;
+pub use strap::Strap;
+pub use strip::Strip;
 pub use utils::FindOptimalSide;
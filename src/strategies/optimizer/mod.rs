@@ -0,0 +1,82 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Multi-Objective Optimizer
+//!
+//! `Optimizable::find_optimal` keeps the one strategy that maximises the profit ratio or
+//! the profit area. This module ranks every combination of a chain instead, on several
+//! weighted objectives at once and subject to hard constraints:
+//!
+//! - [`Objective`]: probability of profit, expected value, maximum loss, theta and vega
+//!   targets and return on margin, under Reg-T or portfolio margin.
+//! - [`Constraint`]: maximum loss, minimum credit, delta band and per-leg open interest,
+//!   volume and bid-ask width.
+//! - [`ParetoOptimizable::pareto_optimal`]: returns the candidates that meet every
+//!   constraint, ordered by Pareto front and then by weighted score, without modifying the
+//!   strategy used as a template.
+//!
+//! The weighted score averages the percentile of the candidate on each objective, so the
+//! weights do not depend on the scale of the objectives. The `liquidity` field of
+//! [`OptimizerConfig`] drops illiquid strikes before the search and sets the fill model
+//! the candidates are priced with.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::chains::chain::OptionChain;
+//! use optionstratlib::strategies::BullCallSpread;
+//! use optionstratlib::strategies::optimizer::{
+//!     Constraint, Objective, OptimizerConfig, ParetoOptimizable, WeightedObjective,
+//! };
+//! use optionstratlib::strategies::FindOptimalSide;
+//! use optionstratlib::{ExpirationDate, Positive, pos};
+//! use rust_decimal::Decimal;
+//! use rust_decimal_macros::dec;
+//!
+//! let chain =
+//!     OptionChain::load_from_json("./examples/Chains/SP500-18-oct-2024-5781.88.json").unwrap();
+//! let template = BullCallSpread::new(
+//!     "SP500".to_string(),
+//!     chain.underlying_price,
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     ExpirationDate::Days(pos!(2.0)),
+//!     Positive::ZERO,
+//!     Decimal::ZERO,
+//!     Positive::ZERO,
+//!     pos!(1.0),
+//!     Positive::ZERO,
+//!     Positive::ZERO,
+//!     pos!(0.5),
+//!     pos!(0.5),
+//!     pos!(0.5),
+//!     pos!(0.5),
+//! );
+//! let config = OptimizerConfig::new(
+//!     FindOptimalSide::Range(pos!(5750.0), pos!(5850.0)),
+//!     vec![
+//!         WeightedObjective::new(Objective::ProbabilityOfProfit, pos!(1.0)),
+//!         WeightedObjective::new(Objective::ReturnOnMargin, pos!(2.0)),
+//!     ],
+//!     vec![Constraint::MaxLoss(pos!(30.0)), Constraint::DeltaBand(dec!(0.0), dec!(0.5))],
+//!     5,
+//! );
+//!
+//! let candidates = template.pareto_optimal(&chain, &config).unwrap();
+//! assert!(candidates.len() <= 5);
+//! for candidate in &candidates {
+//!     assert!(candidate.metrics.max_loss.unwrap() <= pos!(30.0));
+//! }
+//! ```
+
+mod model;
+mod search;
+
+pub use model::{
+    CandidateMetrics, Constraint, Objective, OptimizerConfig, RankedCandidate, WeightedObjective,
+};
+pub use search::ParetoOptimizable;
+pub(crate) use search::{admit, rank};
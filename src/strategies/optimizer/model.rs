@@ -0,0 +1,314 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::error::strategies::StrategyError;
+use crate::risk::MarginMethod;
+use crate::strategies::liquidity::Liquidity;
+use crate::strategies::utils::FindOptimalSide;
+use num_traits::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// A goal of the multi-objective optimizer.
+///
+/// Every objective is turned into a value where higher is better before candidates are
+/// compared, so minimising objectives such as `MaxLoss` are negated and targets are scored
+/// by their distance to the target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Objective {
+    /// Maximise the probability of expiring with a profit.
+    ProbabilityOfProfit,
+    /// Maximise the expected profit at expiration.
+    ExpectedValue,
+    /// Minimise the maximum loss. Unbounded losses rank last.
+    MaxLoss,
+    /// Keep the theta of the strategy as close as possible to the target.
+    ThetaTarget(Decimal),
+    /// Keep the vega of the strategy as close as possible to the target.
+    VegaTarget(Decimal),
+    /// Maximise the maximum profit per unit of margin, sized with the configured margin
+    /// method. Unbounded returns rank first.
+    ReturnOnMargin,
+}
+
+impl Objective {
+    /// Value of the objective for a candidate, oriented so that higher is better.
+    pub(super) fn value(&self, metrics: &CandidateMetrics) -> f64 {
+        let to_f64 = |value: Decimal| value.to_f64().unwrap_or(0.0);
+        match self {
+            Objective::ProbabilityOfProfit => metrics.probability_of_profit.to_f64(),
+            Objective::ExpectedValue => metrics.expected_value.to_f64(),
+            Objective::MaxLoss => metrics
+                .max_loss
+                .map_or(f64::NEG_INFINITY, |loss| -loss.to_f64()),
+            Objective::ThetaTarget(target) => -to_f64((metrics.theta - target).abs()),
+            Objective::VegaTarget(target) => -to_f64((metrics.vega - target).abs()),
+            Objective::ReturnOnMargin => metrics.return_on_margin.map_or(f64::INFINITY, to_f64),
+        }
+    }
+}
+
+/// An objective and its weight in the combined score of a candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WeightedObjective {
+    /// The objective.
+    pub objective: Objective,
+    /// Relative weight of the objective. Only the ratios between weights matter.
+    pub weight: Positive,
+}
+
+impl WeightedObjective {
+    /// Creates a weighted objective.
+    pub fn new(objective: Objective, weight: Positive) -> Self {
+        WeightedObjective { objective, weight }
+    }
+}
+
+/// A hard requirement every candidate must meet to be ranked at all.
+///
+/// The liquidity constraints apply to each leg on its own, reading the quotes of the
+/// option chain for the style of the leg.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Constraint {
+    /// The maximum loss is bounded and does not exceed the value.
+    MaxLoss(Positive),
+    /// The strategy is opened for a net credit, after fees, of at least the value.
+    MinCredit(Positive),
+    /// The delta of the strategy lies between the bounds, inclusive.
+    DeltaBand(Decimal, Decimal),
+    /// Every leg has at least this open interest.
+    MinOpenInterest(u64),
+    /// Every leg has traded at least this volume.
+    MinVolume(Positive),
+    /// The bid-ask spread of every leg is no wider than the value.
+    MaxBidAskWidth(Positive),
+}
+
+impl Constraint {
+    /// Checks the constraints that depend on the chain quotes of the legs. Constraints on
+    /// the strategy metrics always pass here.
+    pub(super) fn admits_legs(&self, legs: &[LegQuote]) -> bool {
+        match self {
+            Constraint::MinOpenInterest(min) => legs
+                .iter()
+                .all(|leg| leg.open_interest.is_some_and(|oi| oi >= *min)),
+            Constraint::MinVolume(min) => legs
+                .iter()
+                .all(|leg| leg.volume.is_some_and(|volume| volume >= *min)),
+            Constraint::MaxBidAskWidth(max) => legs
+                .iter()
+                .all(|leg| leg.width.is_some_and(|width| width <= max.to_dec())),
+            _ => true,
+        }
+    }
+
+    /// Checks the constraints on the strategy metrics. Constraints on the legs always
+    /// pass here.
+    pub(super) fn admits_metrics(&self, metrics: &CandidateMetrics) -> bool {
+        match self {
+            Constraint::MaxLoss(max) => metrics.max_loss.is_some_and(|loss| loss <= *max),
+            Constraint::MinCredit(min) => metrics.net_premium >= min.to_dec(),
+            Constraint::DeltaBand(low, high) => metrics.delta >= *low && metrics.delta <= *high,
+            _ => true,
+        }
+    }
+}
+
+/// Chain data of one leg, used by the liquidity constraints.
+#[derive(Debug, Clone, Copy, Default)]
+pub(super) struct LegQuote {
+    pub open_interest: Option<u64>,
+    pub volume: Option<Positive>,
+    /// Ask minus bid for the style of the leg, `None` without a two-sided quote.
+    pub width: Option<Decimal>,
+}
+
+/// Settings of a multi-objective search.
+#[derive(Debug, Clone)]
+pub struct OptimizerConfig {
+    /// Strikes the combinations are taken from, as in `Optimizable::find_optimal`.
+    pub side: FindOptimalSide,
+    /// Objectives combined into the score of each candidate.
+    pub objectives: Vec<WeightedObjective>,
+    /// Requirements a candidate must meet to be ranked.
+    pub constraints: Vec<Constraint>,
+    /// Largest number of candidates returned.
+    pub max_candidates: usize,
+    /// Margin method used by `Objective::ReturnOnMargin`.
+    pub margin: MarginMethod,
+    /// Liquidity requirements and fill model applied to the chain before the search.
+    /// Constraints on the legs still read the quotes of the original chain.
+    pub liquidity: Liquidity,
+}
+
+impl OptimizerConfig {
+    /// Creates a configuration with the default Reg-T margin, filling every leg at the
+    /// touch without liquidity requirements.
+    pub fn new(
+        side: FindOptimalSide,
+        objectives: Vec<WeightedObjective>,
+        constraints: Vec<Constraint>,
+        max_candidates: usize,
+    ) -> Self {
+        OptimizerConfig {
+            side,
+            objectives,
+            constraints,
+            max_candidates,
+            margin: MarginMethod::default(),
+            liquidity: Liquidity::default(),
+        }
+    }
+
+    /// Checks that the configuration can rank candidates.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when there are no objectives, every weight
+    /// is zero or `max_candidates` is zero.
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        let reason = if self.objectives.is_empty() {
+            "at least one objective is required"
+        } else if self.objectives.iter().all(|o| o.weight == Positive::ZERO) {
+            "at least one objective must have a positive weight"
+        } else if self.max_candidates == 0 {
+            "max_candidates must be positive"
+        } else {
+            return Ok(());
+        };
+        Err(StrategyError::invalid_parameters("optimize", reason))
+    }
+}
+
+/// Risk and reward figures of a candidate strategy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateMetrics {
+    /// Maximum profit at expiration, `None` when unbounded.
+    pub max_profit: Option<Positive>,
+    /// Maximum loss at expiration, `None` when unbounded.
+    pub max_loss: Option<Positive>,
+    /// Premium received minus premium paid and fees: positive for a credit, negative for
+    /// a debit.
+    pub net_premium: Decimal,
+    /// Probability of expiring with a profit.
+    pub probability_of_profit: Positive,
+    /// Expected profit at expiration.
+    pub expected_value: Positive,
+    /// Delta of the strategy.
+    pub delta: Decimal,
+    /// Theta of the strategy.
+    pub theta: Decimal,
+    /// Vega of the strategy.
+    pub vega: Decimal,
+    /// Buying-power requirement under the configured margin method.
+    pub margin: Decimal,
+    /// Maximum profit divided by the margin, `None` when the profit is unbounded or no
+    /// margin is required.
+    pub return_on_margin: Option<Decimal>,
+}
+
+/// A strategy that met every constraint, with its place in the ranking.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedCandidate<S> {
+    /// The candidate strategy.
+    pub strategy: S,
+    /// Its risk and reward figures.
+    pub metrics: CandidateMetrics,
+    /// Percentile of the candidate on each objective, in the order of the configuration,
+    /// from 0 for the worst candidate to 1 for the best.
+    pub objective_scores: Vec<Decimal>,
+    /// Weighted average of `objective_scores`.
+    pub score: Decimal,
+    /// Pareto front of the candidate. Front 0 holds the candidates no other candidate
+    /// beats on every objective, front 1 those left undominated once front 0 is removed,
+    /// and so on.
+    pub pareto_rank: usize,
+}
+
+#[cfg(test)]
+mod tests_optimizer_model {
+    use super::*;
+    use crate::pos;
+    use rust_decimal_macros::dec;
+
+    fn metrics() -> CandidateMetrics {
+        CandidateMetrics {
+            max_profit: Some(pos!(3.0)),
+            max_loss: Some(pos!(2.0)),
+            net_premium: dec!(-2.0),
+            probability_of_profit: pos!(0.4),
+            expected_value: pos!(0.5),
+            delta: dec!(0.3),
+            theta: dec!(-0.02),
+            vega: dec!(0.1),
+            margin: dec!(2.0),
+            return_on_margin: Some(dec!(1.5)),
+        }
+    }
+
+    #[test]
+    fn test_objective_orientation() {
+        let mut metrics = metrics();
+        assert_eq!(Objective::MaxLoss.value(&metrics), -2.0);
+        assert_eq!(Objective::ThetaTarget(dec!(0.03)).value(&metrics), -0.05);
+        assert_eq!(Objective::ReturnOnMargin.value(&metrics), 1.5);
+
+        metrics.max_loss = None;
+        metrics.return_on_margin = None;
+        assert_eq!(Objective::MaxLoss.value(&metrics), f64::NEG_INFINITY);
+        assert_eq!(Objective::ReturnOnMargin.value(&metrics), f64::INFINITY);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let metrics = metrics();
+        assert!(Constraint::MaxLoss(pos!(2.0)).admits_metrics(&metrics));
+        assert!(!Constraint::MaxLoss(pos!(1.0)).admits_metrics(&metrics));
+        assert!(!Constraint::MinCredit(pos!(0.5)).admits_metrics(&metrics));
+        assert!(Constraint::DeltaBand(dec!(0.2), dec!(0.4)).admits_metrics(&metrics));
+        assert!(Constraint::MinVolume(pos!(1.0)).admits_metrics(&metrics));
+
+        let legs = [
+            LegQuote {
+                open_interest: Some(100),
+                volume: Some(pos!(10.0)),
+                width: Some(dec!(0.2)),
+            },
+            LegQuote::default(),
+        ];
+        assert!(Constraint::MaxLoss(pos!(1.0)).admits_legs(&legs));
+        assert!(Constraint::MaxBidAskWidth(pos!(0.2)).admits_legs(&legs[..1]));
+        assert!(!Constraint::MaxBidAskWidth(pos!(0.1)).admits_legs(&legs[..1]));
+        // Legs without data never pass a liquidity constraint
+        assert!(!Constraint::MinOpenInterest(1).admits_legs(&legs));
+    }
+
+    #[test]
+    fn test_config_validate() {
+        let objective = WeightedObjective::new(Objective::ExpectedValue, pos!(1.0));
+        assert!(
+            OptimizerConfig::new(FindOptimalSide::All, vec![objective], vec![], 5)
+                .validate()
+                .is_ok()
+        );
+        assert!(
+            OptimizerConfig::new(FindOptimalSide::All, vec![], vec![], 5)
+                .validate()
+                .is_err()
+        );
+        let zero = WeightedObjective::new(Objective::ExpectedValue, Positive::ZERO);
+        assert!(
+            OptimizerConfig::new(FindOptimalSide::All, vec![zero], vec![], 5)
+                .validate()
+                .is_err()
+        );
+        assert!(
+            OptimizerConfig::new(FindOptimalSide::All, vec![objective], vec![], 0)
+                .validate()
+                .is_err()
+        );
+    }
+}
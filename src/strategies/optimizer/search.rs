@@ -0,0 +1,530 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::model::{CandidateMetrics, LegQuote, OptimizerConfig, RankedCandidate};
+use crate::Positive;
+use crate::chains::OptionData;
+use crate::chains::chain::OptionChain;
+use crate::error::strategies::StrategyError;
+use crate::greeks::Greeks;
+use crate::model::Position;
+use crate::model::types::OptionStyle;
+use crate::risk::{MarginCalculator, MarginMethod};
+use crate::strategies::base::{Optimizable, Positionable, Validable};
+use crate::strategies::probabilities::ProbabilityAnalysis;
+use num_traits::FromPrimitive;
+use rayon::prelude::*;
+use rust_decimal::Decimal;
+use std::error::Error;
+use tracing::debug;
+
+/// Multi-objective search over the combinations of an `OptionChain`.
+///
+/// Unlike `Optimizable::find_optimal`, which keeps the single best strategy for one
+/// criterion and replaces `self` with it, this search scores every combination on several
+/// weighted objectives, drops those breaking a hard constraint and returns the survivors
+/// ranked by Pareto front and then by weighted score. `self` is only used as a template.
+///
+/// The trait is implemented for every `Optimizable` whose strategies can be analysed for
+/// probabilities and Greeks. Combinations are evaluated in parallel.
+pub trait ParetoOptimizable: Optimizable {
+    /// Ranks the strategies that can be built from the chain.
+    ///
+    /// Candidates are taken from `filter_combinations`, turned into legs with `group_legs`
+    /// and built with `create_strategy`, skipping strategies that fail validation or whose
+    /// metrics cannot be computed. Quotes are checked by `filter_combinations`, as in
+    /// `find_optimal`, on the chain as prepared by `config.liquidity`, so premiums follow
+    /// its fill model.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` when the configuration is invalid.
+    fn pareto_optimal(
+        &self,
+        option_chain: &OptionChain,
+        config: &OptimizerConfig,
+    ) -> Result<Vec<RankedCandidate<Self::Strategy>>, StrategyError>;
+}
+
+impl<T> ParetoOptimizable for T
+where
+    T: Optimizable + Sync,
+    T::Strategy: ProbabilityAnalysis + Greeks + Send,
+{
+    fn pareto_optimal(
+        &self,
+        option_chain: &OptionChain,
+        config: &OptimizerConfig,
+    ) -> Result<Vec<RankedCandidate<Self::Strategy>>, StrategyError> {
+        config.validate()?;
+        let liquid = config.liquidity.apply(option_chain);
+        let groups: Vec<_> = self.filter_combinations(&liquid, config.side).collect();
+        let candidates: Vec<_> = groups
+            .into_par_iter()
+            .filter_map(|group| {
+                let legs = self.group_legs(group)?;
+                let strategy = self.create_strategy(&liquid, &legs);
+                if !strategy.validate() {
+                    return None;
+                }
+                // Leg constraints read the quotes as listed, not at the fill prices
+                let metrics = admit(&strategy, config, |position| {
+                    option_chain
+                        .options
+                        .iter()
+                        .find(|o| o.strike_price == position.option.strike_price)
+                })?;
+                Some((strategy, metrics))
+            })
+            .collect();
+        Ok(rank(candidates, config))
+    }
+}
+
+/// Checks `strategy` against the constraints of `config` and returns its metrics when it
+/// meets all of them. `find` looks up the chain data of each position, for the liquidity
+/// constraints.
+pub(crate) fn admit<'a, S>(
+    strategy: &S,
+    config: &OptimizerConfig,
+    find: impl Fn(&Position) -> Option<&'a OptionData>,
+) -> Option<CandidateMetrics>
+where
+    S: ProbabilityAnalysis + Greeks,
+{
+    let quotes = leg_quotes(strategy, find);
+    if !config.constraints.iter().all(|c| c.admits_legs(&quotes)) {
+        return None;
+    }
+    let metrics = match evaluate(strategy, &config.margin) {
+        Ok(metrics) => metrics,
+        Err(e) => {
+            debug!("Skipping candidate without metrics: {}", e);
+            return None;
+        }
+    };
+    config
+        .constraints
+        .iter()
+        .all(|c| c.admits_metrics(&metrics))
+        .then_some(metrics)
+}
+
+/// Chain data of every position of the strategy.
+fn leg_quotes<'a, S: Positionable>(
+    strategy: &S,
+    find: impl Fn(&Position) -> Option<&'a OptionData>,
+) -> Vec<LegQuote> {
+    let Ok(positions) = strategy.get_positions() else {
+        return Vec::new();
+    };
+    positions
+        .iter()
+        .map(|position| {
+            let Some(option) = find(position) else {
+                return LegQuote::default();
+            };
+            let (bid, ask) = match position.option.option_style {
+                OptionStyle::Call => (option.call_bid, option.call_ask),
+                OptionStyle::Put => (option.put_bid, option.put_ask),
+            };
+            LegQuote {
+                open_interest: option.open_interest,
+                volume: option.volume,
+                width: bid.zip(ask).map(|(bid, ask)| ask.to_dec() - bid.to_dec()),
+            }
+        })
+        .collect()
+}
+
+/// Computes the metrics used by the objectives and constraints.
+fn evaluate<S>(strategy: &S, margin: &MarginMethod) -> Result<CandidateMetrics, Box<dyn Error>>
+where
+    S: ProbabilityAnalysis + Greeks,
+{
+    let bounded = |value: Result<Positive, StrategyError>| {
+        value.ok().filter(|value| *value != Positive::INFINITY)
+    };
+    let max_profit = bounded(strategy.get_max_profit());
+    let margin = strategy.margin_requirement(margin)?;
+    let return_on_margin = match max_profit {
+        Some(profit) if margin > Decimal::ZERO => Some(profit.to_dec() / margin),
+        _ => None,
+    };
+    Ok(CandidateMetrics {
+        max_profit,
+        max_loss: bounded(strategy.get_max_loss()),
+        net_premium: -strategy.get_net_cost()?,
+        probability_of_profit: strategy.probability_of_profit(None, None)?,
+        expected_value: strategy.expected_value(None, None)?,
+        delta: strategy.delta()?,
+        theta: strategy.theta()?,
+        vega: strategy.vega()?,
+        margin,
+        return_on_margin,
+    })
+}
+
+/// Scores the candidates and orders them by Pareto front and then by weighted score,
+/// keeping at most `config.max_candidates`.
+pub(crate) fn rank<S>(
+    candidates: Vec<(S, CandidateMetrics)>,
+    config: &OptimizerConfig,
+) -> Vec<RankedCandidate<S>> {
+    let values: Vec<Vec<f64>> = candidates
+        .iter()
+        .map(|(_, metrics)| {
+            config
+                .objectives
+                .iter()
+                .map(|o| o.objective.value(metrics))
+                .collect()
+        })
+        .collect();
+
+    // Percentiles per objective, so that objectives of different scales can be weighted
+    let mut percentiles = vec![Vec::with_capacity(config.objectives.len()); values.len()];
+    for objective in 0..config.objectives.len() {
+        let column: Vec<f64> = values.iter().map(|v| v[objective]).collect();
+        for (candidate, percentile) in percentile_ranks(&column).into_iter().enumerate() {
+            percentiles[candidate].push(percentile);
+        }
+    }
+    let total_weight: f64 = config.objectives.iter().map(|o| o.weight.to_f64()).sum();
+    let scores: Vec<f64> = percentiles
+        .iter()
+        .map(|p| {
+            p.iter()
+                .zip(&config.objectives)
+                .map(|(p, o)| p * o.weight.to_f64())
+                .sum::<f64>()
+                / total_weight
+        })
+        .collect();
+
+    // A candidate that dominates another sorts before it: its score is never lower and
+    // the unweighted sum of its percentiles is strictly higher.
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| {
+        scores[b].total_cmp(&scores[a]).then_with(|| {
+            let sum = |i: usize| percentiles[i].iter().sum::<f64>();
+            sum(b).total_cmp(&sum(a))
+        })
+    });
+
+    // Peel off Pareto fronts until enough candidates are ranked. Scanning in score order
+    // means a candidate only needs comparing with the members of the front found so far.
+    let mut fronts = vec![usize::MAX; values.len()];
+    let mut ranked = Vec::new();
+    let mut remaining = order;
+    let mut front = 0;
+    while ranked.len() < config.max_candidates && !remaining.is_empty() {
+        let mut members: Vec<usize> = Vec::new();
+        let mut rest = Vec::new();
+        for i in remaining {
+            if members.iter().any(|&j| dominates(&values[j], &values[i])) {
+                rest.push(i);
+            } else {
+                members.push(i);
+            }
+        }
+        for &i in &members {
+            fronts[i] = front;
+        }
+        ranked.extend(members);
+        remaining = rest;
+        front += 1;
+    }
+    ranked.truncate(config.max_candidates);
+
+    let mut candidates: Vec<Option<(S, CandidateMetrics)>> =
+        candidates.into_iter().map(Some).collect();
+    let to_decimal = |value: f64| Decimal::from_f64(value).unwrap_or_default();
+    ranked
+        .into_iter()
+        .filter_map(|i| {
+            let (strategy, metrics) = candidates[i].take()?;
+            Some(RankedCandidate {
+                strategy,
+                metrics,
+                objective_scores: percentiles[i].iter().map(|p| to_decimal(*p)).collect(),
+                score: to_decimal(scores[i]),
+                pareto_rank: fronts[i],
+            })
+        })
+        .collect()
+}
+
+/// Whether `a` is at least as good as `b` on every objective and better on one.
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Position of each value among all of them, from 0 for the lowest to 1 for the highest.
+/// Tied values share the average of their positions.
+fn percentile_ranks(values: &[f64]) -> Vec<f64> {
+    let n = values.len();
+    if n == 1 {
+        return vec![1.0];
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+    let mut ranks = vec![0.0; n];
+    let mut start = 0;
+    while start < n {
+        let mut end = start;
+        while end + 1 < n && values[order[end + 1]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end) as f64 / 2.0 / (n - 1) as f64;
+        for &i in &order[start..=end] {
+            ranks[i] = rank;
+        }
+        start = end + 1;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests_optimizer_search {
+    use super::*;
+    use crate::ExpirationDate;
+    use crate::risk::PortfolioMarginParameters;
+    use crate::strategies::optimizer::{Constraint, Objective, WeightedObjective};
+    use crate::strategies::utils::FindOptimalSide;
+    use crate::strategies::{BullCallSpread, FillModel, Liquidity};
+    use crate::{pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_test_chain() -> OptionChain {
+        let mut chain = OptionChain::new("TEST", pos!(100.0), "2024-12-31".to_string(), None, None);
+        let quotes = [
+            (85.0, 16.0, 16.2, 50),
+            (90.0, 11.5, 11.7, 75),
+            (95.0, 7.0, 7.2, 100),
+            (100.0, 3.5, 3.7, 125),
+            (105.0, 1.0, 1.5, 10),
+            (110.0, 0.4, 0.5, 150),
+        ];
+        for (strike, bid, ask, open_interest) in quotes {
+            chain.add_option(
+                pos!(strike),
+                spos!(bid),
+                spos!(ask),
+                None,
+                None,
+                pos!(0.2),
+                Some(dec!(0.5)),
+                Some(dec!(-0.5)),
+                Some(dec!(0.02)),
+                spos!(100.0),
+                Some(open_interest),
+                None,
+            );
+        }
+        chain
+    }
+
+    fn create_base_spread() -> BullCallSpread {
+        BullCallSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(7.2),
+            pos!(3.5),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn strikes(spread: &BullCallSpread) -> Vec<Positive> {
+        spread
+            .get_positions()
+            .unwrap()
+            .iter()
+            .map(|p| p.option.strike_price)
+            .collect()
+    }
+
+    fn config(constraints: Vec<Constraint>, max_candidates: usize) -> OptimizerConfig {
+        OptimizerConfig::new(
+            FindOptimalSide::All,
+            vec![
+                WeightedObjective::new(Objective::ProbabilityOfProfit, pos!(1.0)),
+                WeightedObjective::new(Objective::ReturnOnMargin, pos!(2.0)),
+                WeightedObjective::new(Objective::MaxLoss, pos!(1.0)),
+            ],
+            constraints,
+            max_candidates,
+        )
+    }
+
+    #[test]
+    fn test_percentile_ranks() {
+        assert_eq!(percentile_ranks(&[3.0]), vec![1.0]);
+        assert_eq!(
+            percentile_ranks(&[2.0, f64::NEG_INFINITY, 2.0, 5.0]),
+            vec![0.5, 0.0, 0.5, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_dominates() {
+        assert!(dominates(&[1.0, 2.0], &[1.0, 1.0]));
+        assert!(!dominates(&[1.0, 1.0], &[1.0, 1.0]));
+        assert!(!dominates(&[2.0, 0.0], &[1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_pareto_optimal_ranks_all_candidates() {
+        let spread = create_base_spread();
+        let chain = create_test_chain();
+        let candidates = spread.pareto_optimal(&chain, &config(vec![], 100)).unwrap();
+
+        assert!(!candidates.is_empty());
+        // The template is left untouched
+        assert_eq!(strikes(&spread), vec![pos!(95.0), pos!(100.0)]);
+        for pair in candidates.windows(2) {
+            assert!((pair[0].pareto_rank, -pair[0].score) <= (pair[1].pareto_rank, -pair[1].score));
+        }
+
+        // No candidate of the first front is dominated by any other candidate
+        let objectives = config(vec![], 100).objectives;
+        let values = |c: &RankedCandidate<BullCallSpread>| -> Vec<f64> {
+            objectives
+                .iter()
+                .map(|o| o.objective.value(&c.metrics))
+                .collect()
+        };
+        for front in candidates.iter().filter(|c| c.pareto_rank == 0) {
+            assert!(
+                candidates
+                    .iter()
+                    .all(|other| !dominates(&values(other), &values(front)))
+            );
+        }
+        for candidate in &candidates {
+            assert_eq!(candidate.objective_scores.len(), 3);
+            assert!(candidate.score >= Decimal::ZERO && candidate.score <= Decimal::ONE);
+            assert!(candidate.metrics.net_premium < Decimal::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_pareto_optimal_applies_constraints() {
+        let spread = create_base_spread();
+        let chain = create_test_chain();
+
+        let candidates = spread
+            .pareto_optimal(
+                &chain,
+                &config(
+                    vec![
+                        Constraint::MinOpenInterest(50),
+                        Constraint::MaxBidAskWidth(pos!(0.3)),
+                        Constraint::MaxLoss(pos!(4.0)),
+                    ],
+                    100,
+                ),
+            )
+            .unwrap();
+        assert!(!candidates.is_empty());
+        for candidate in &candidates {
+            // The 105 strike is both illiquid and wide
+            assert!(!strikes(&candidate.strategy).contains(&pos!(105.0)));
+            assert!(candidate.metrics.max_loss.unwrap() <= pos!(4.0));
+        }
+
+        let limited = spread.pareto_optimal(&chain, &config(vec![], 2)).unwrap();
+        assert_eq!(limited.len(), 2);
+
+        // A debit spread never meets a credit requirement
+        let credit = spread
+            .pareto_optimal(&chain, &config(vec![Constraint::MinCredit(pos!(0.1))], 10))
+            .unwrap();
+        assert!(credit.is_empty());
+    }
+
+    #[test]
+    fn test_pareto_optimal_uses_configured_margin_method() {
+        let spread = create_base_spread();
+        let chain = create_test_chain();
+        let method = MarginMethod::Portfolio(PortfolioMarginParameters::default());
+        let mut portfolio = config(vec![], 100);
+        portfolio.margin = method.clone();
+
+        let reg_t = spread.pareto_optimal(&chain, &config(vec![], 100)).unwrap();
+        let candidates = spread.pareto_optimal(&chain, &portfolio).unwrap();
+        assert_eq!(candidates.len(), reg_t.len());
+        for candidate in &candidates {
+            assert_eq!(
+                candidate.metrics.margin,
+                candidate.strategy.margin_requirement(&method).unwrap()
+            );
+        }
+        assert!(candidates.iter().any(|candidate| {
+            candidate.metrics.margin
+                != candidate
+                    .strategy
+                    .margin_requirement(&MarginMethod::default())
+                    .unwrap()
+        }));
+    }
+
+    #[test]
+    fn test_pareto_optimal_applies_liquidity() {
+        let spread = create_base_spread();
+        let chain = create_test_chain();
+        let net_premium = |candidates: &[RankedCandidate<BullCallSpread>]| {
+            candidates
+                .iter()
+                .find(|c| strikes(&c.strategy) == vec![pos!(95.0), pos!(100.0)])
+                .map(|c| c.metrics.net_premium)
+        };
+
+        let touch = spread.pareto_optimal(&chain, &config(vec![], 100)).unwrap();
+        assert_eq!(net_premium(&touch), Some(dec!(-3.7)));
+
+        let mut mid = config(vec![Constraint::MaxBidAskWidth(pos!(0.3))], 100);
+        mid.liquidity = Liquidity::new(None, None, None, FillModel::Mid);
+        let candidates = spread.pareto_optimal(&chain, &mid).unwrap();
+        assert_eq!(net_premium(&candidates), Some(dec!(-3.5)));
+        // Leg constraints still see the 0.5 wide quote of the 105 strike
+        assert!(
+            candidates
+                .iter()
+                .all(|c| !strikes(&c.strategy).contains(&pos!(105.0)))
+        );
+
+        // Both out-of-the-money strikes are quoted wider than 20% of their mid
+        let mut tight = config(vec![], 100);
+        tight.liquidity = Liquidity::new(None, None, spos!(0.2), FillModel::Touch);
+        let candidates = spread.pareto_optimal(&chain, &tight).unwrap();
+        assert!(!candidates.is_empty());
+        for candidate in &candidates {
+            assert!(
+                strikes(&candidate.strategy)
+                    .iter()
+                    .all(|k| *k <= pos!(100.0))
+            );
+        }
+    }
+
+    #[test]
+    fn test_pareto_optimal_rejects_invalid_config() {
+        let spread = create_base_spread();
+        let chain = create_test_chain();
+        let config = OptimizerConfig::new(FindOptimalSide::All, vec![], vec![], 10);
+        assert!(spread.pareto_optimal(&chain, &config).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! Numerical analysis of a profit function, shared by the strategies whose payoff is
+//! not piecewise linear (calendar and diagonal spreads) or not known in advance
+//! (custom strategies).
+//!
+//! The profit is sampled over a price grid that always contains the strikes; sign
+//! changes between grid points are refined by bisection into break-even points, and
+//! the same grid gives the profit extremes and the profit area.
+
+use crate::error::probability::ProbabilityError;
+use crate::model::ProfitLossRange;
+use crate::model::utils::mean_and_std;
+use crate::strategies::probabilities::utils::VolatilityAdjustment;
+use crate::{Options, Positive};
+use rust_decimal::Decimal;
+use std::error::Error;
+
+/// Number of intervals in the price grid used to locate break-even points and extremes.
+const PRICE_GRID_STEPS: usize = 100;
+
+/// Maximum number of bisection steps used to refine a break-even point.
+const BISECTION_ITERATIONS: usize = 40;
+
+/// Profit of a strategy at its valuation date with the underlying at the given price.
+pub(super) type ProfitFn<'a> = dyn Fn(&Positive) -> Result<Decimal, Box<dyn Error>> + 'a;
+
+/// Prices from `low` to `high` in equal steps, together with the `strikes`, sorted and
+/// without duplicates. The strikes are always part of the grid, since a payoff bends
+/// around them.
+pub(super) fn price_grid(
+    low: Decimal,
+    high: Decimal,
+    strikes: impl IntoIterator<Item = Positive>,
+) -> Vec<Positive> {
+    let step = (high - low) / Decimal::from(PRICE_GRID_STEPS);
+    let mut grid: Vec<Positive> = (0..=PRICE_GRID_STEPS)
+        .map(|i| Positive::from(low + step * Decimal::from(i)))
+        .chain(strikes)
+        .collect();
+    grid.sort();
+    grid.dedup();
+    grid
+}
+
+/// Refines a sign change of `profit` between `low` and `high` by bisection, rounded to
+/// two decimals.
+pub(super) fn bisect(
+    profit: &ProfitFn<'_>,
+    low: Positive,
+    high: Positive,
+) -> Result<Positive, Box<dyn Error>> {
+    let (mut low, mut high) = (low.to_dec(), high.to_dec());
+    let low_is_positive = profit(&Positive::from(low))?.is_sign_positive();
+    for _ in 0..BISECTION_ITERATIONS {
+        let middle = (low + high) / Decimal::TWO;
+        if profit(&Positive::from(middle))?.is_sign_positive() == low_is_positive {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    Ok(Positive::from((low + high) / Decimal::TWO).round_to(2))
+}
+
+/// Prices of `grid` at which `profit` crosses zero, in ascending order and rounded to
+/// two decimals.
+///
+/// A run of exact zeros is a break-even only when the profit changes sign across it, so
+/// a payoff that is flat at zero adds no points.
+pub(super) fn break_even_points(
+    grid: &[Positive],
+    profit: &ProfitFn<'_>,
+) -> Result<Vec<Positive>, Box<dyn Error>> {
+    let mut break_even_points = Vec::new();
+    let mut last_nonzero: Option<(Positive, Decimal)> = None;
+    let mut first_zero: Option<Positive> = None;
+    for price in grid {
+        let value = profit(price)?;
+        if value == Decimal::ZERO {
+            first_zero.get_or_insert(*price);
+            continue;
+        }
+        if let Some((previous_price, previous_value)) = last_nonzero
+            && previous_value.is_sign_positive() != value.is_sign_positive()
+        {
+            break_even_points.push(match first_zero {
+                Some(zero) => zero.round_to(2),
+                None => bisect(profit, previous_price, *price)?,
+            });
+        }
+        last_nonzero = Some((*price, value));
+        first_zero = None;
+    }
+    break_even_points.dedup();
+    Ok(break_even_points)
+}
+
+/// Highest and lowest profit over `grid`.
+pub(super) fn profit_extremes(
+    grid: &[Positive],
+    profit: &ProfitFn<'_>,
+) -> Result<(Decimal, Decimal), Box<dyn Error>> {
+    grid.iter()
+        .try_fold((Decimal::MIN, Decimal::MAX), |(max, min), price| {
+            let value = profit(price)?;
+            Ok((max.max(value), min.min(value)))
+        })
+}
+
+/// Area under the positive part of the profit curve, integrated over `grid` with the
+/// trapezoidal rule and scaled down by 100.
+pub(super) fn profit_area(
+    grid: &[Positive],
+    profit: &ProfitFn<'_>,
+) -> Result<Decimal, Box<dyn Error>> {
+    let profits = grid
+        .iter()
+        .map(|price| Ok(profit(price)?.max(Decimal::ZERO)))
+        .collect::<Result<Vec<_>, Box<dyn Error>>>()?;
+    let area: Decimal = (1..grid.len())
+        .map(|i| (grid[i] - grid[i - 1]).to_dec() * (profits[i] + profits[i - 1]) / Decimal::TWO)
+        .sum();
+    Ok(area / Decimal::ONE_HUNDRED)
+}
+
+/// Price intervals delimited by `break_even_points` in which `profit` is positive
+/// (`profitable == true`) or not, with the probability of the underlying finishing
+/// inside each of them at the expiration of `option`. The volatility is the mean of
+/// `volatilities`, adjusted by their standard deviation.
+pub(super) fn probability_ranges(
+    option: &Options,
+    volatilities: Vec<Positive>,
+    break_even_points: &[Positive],
+    profit: &ProfitFn<'_>,
+    profitable: bool,
+) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+    let (mean_volatility, std_dev) = mean_and_std(volatilities);
+
+    let mut bounds: Vec<Option<Positive>> = vec![None];
+    bounds.extend(break_even_points.iter().copied().map(Some));
+    bounds.push(None);
+
+    let mut ranges = Vec::new();
+    for window in bounds.windows(2) {
+        let (lower, upper) = (window[0], window[1]);
+        let sample = match (lower, upper) {
+            (Some(lower), Some(upper)) => (lower + upper) / Positive::TWO,
+            (None, Some(upper)) => upper / Positive::TWO,
+            (Some(lower), None) => lower * Positive::TWO,
+            (None, None) => option.underlying_price,
+        };
+        if (profit(&sample)? > Decimal::ZERO) != profitable {
+            continue;
+        }
+
+        let mut range = ProfitLossRange::new(lower, upper, Positive::ZERO)?;
+        range.calculate_probability(
+            &option.underlying_price,
+            Some(VolatilityAdjustment {
+                base_volatility: mean_volatility,
+                std_dev_adjustment: std_dev,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+        ranges.push(range);
+    }
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests_payoff_scan {
+    use super::*;
+    use crate::pos;
+    use rust_decimal_macros::dec;
+
+    /// Long call struck at 100 bought for 5: breaks even at 105.
+    fn long_call(price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        Ok((price.to_dec() - dec!(100)).max(Decimal::ZERO) - dec!(5))
+    }
+
+    #[test]
+    fn test_price_grid_contains_strikes() {
+        let grid = price_grid(dec!(50), dec!(150), [pos!(101.5), pos!(100.0)]);
+        assert_eq!(grid.first(), Some(&pos!(50.0)));
+        assert_eq!(grid.last(), Some(&pos!(150.0)));
+        assert!(grid.contains(&pos!(101.5)));
+        assert_eq!(grid.len(), PRICE_GRID_STEPS + 2);
+        assert!(grid.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_break_even_and_extremes() {
+        let grid = price_grid(dec!(50), dec!(150), [pos!(100.0)]);
+        assert_eq!(
+            break_even_points(&grid, &long_call).unwrap(),
+            vec![pos!(105.0)]
+        );
+        assert_eq!(
+            bisect(&long_call, pos!(101.0), pos!(120.0)).unwrap(),
+            pos!(105.0)
+        );
+        assert_eq!(
+            profit_extremes(&grid, &long_call).unwrap(),
+            (dec!(45), dec!(-5))
+        );
+        // Triangle from 105 to 150 with height 45, scaled down by 100
+        assert_eq!(profit_area(&grid, &long_call).unwrap(), dec!(10.125));
+    }
+
+    #[test]
+    fn test_flat_zero_payoff_has_no_break_even() {
+        let flat = |_: &Positive| -> Result<Decimal, Box<dyn Error>> { Ok(Decimal::ZERO) };
+        let grid = price_grid(dec!(50), dec!(150), []);
+        assert!(break_even_points(&grid, &flat).unwrap().is_empty());
+    }
+}
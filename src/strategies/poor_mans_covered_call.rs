@@ -27,7 +27,9 @@
 use super::base::{
     BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
 };
+use super::time_spread;
 use crate::chains::OptionData;
+use crate::series::OptionSeries;
 use crate::{
     ExpirationDate, Options, Positive,
     chains::{StrategyLegs, chain::OptionChain},
@@ -253,6 +255,33 @@ impl PoorMansCoveredCall {
             .expect("Unable to update break even points");
         strategy
     }
+
+    /// Whether the pair of strikes is acceptable for the strategy: the long call is struck
+    /// below the short call. `Center` sells above and buys below the underlying price.
+    fn are_valid_strikes(
+        &self,
+        long: &OptionData,
+        short: &OptionData,
+        side: &FindOptimalSide,
+    ) -> bool {
+        if long.strike_price >= short.strike_price {
+            debug!(
+                "Invalid strike prices long call option: {:#?} short call option: {:#?} ",
+                long.strike_price, short.strike_price
+            );
+            return false;
+        }
+        match side {
+            FindOptimalSide::Center => {
+                self.is_valid_optimal_option(short, &FindOptimalSide::Upper)
+                    && self.is_valid_optimal_option(long, &FindOptimalSide::Lower)
+            }
+            _ => {
+                self.is_valid_optimal_option(short, side)
+                    && self.is_valid_optimal_option(long, side)
+            }
+        }
+    }
 }
 
 impl StrategyConstructor for PoorMansCoveredCall {
@@ -671,24 +700,7 @@ impl Optimizable for PoorMansCoveredCall {
                     "Long: {:#?} Short: {:#?}",
                     long_call_option.strike_price, short_call_option.strike_price
                 );
-                if long_call_option.strike_price >= short_call_option.strike_price {
-                    debug!(
-                        "Invalid strike prices long call option: {:#?} short call option: {:#?} ",
-                        long_call_option.strike_price, short_call_option.strike_price
-                    );
-                    continue;
-                }
-
-                if side == FindOptimalSide::Center {
-                    if !self.is_valid_optimal_option(short_call_option, &FindOptimalSide::Upper)
-                        || !self.is_valid_optimal_option(long_call_option, &FindOptimalSide::Lower)
-                    {
-                        debug!("Invalid option");
-                        continue;
-                    }
-                } else if !self.is_valid_optimal_option(short_call_option, &side)
-                    || !self.is_valid_optimal_option(long_call_option, &side)
-                {
+                if !self.are_valid_strikes(long_call_option, short_call_option, &side) {
                     debug!("Invalid option");
                     continue;
                 }
@@ -717,6 +729,76 @@ impl Optimizable for PoorMansCoveredCall {
         }
     }
 
+    /// Searches every pair of expirations in the series, selling a call of the nearer chain
+    /// at its bid and buying a lower strike call in the farther chain at its ask.
+    fn find_optimal_in_series(
+        &mut self,
+        option_series: &OptionSeries,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+
+        for (short_expiration, short_chain, long_expiration, long_chain) in
+            time_spread::expiration_pairs(option_series)
+        {
+            for short_option in short_chain.options.iter() {
+                let Some(premium_short_call) =
+                    short_option.call_bid.filter(|bid| *bid > Positive::ZERO)
+                else {
+                    continue;
+                };
+                let Some(implied_volatility) = time_spread::quoted_volatility(short_option) else {
+                    continue;
+                };
+                for long_option in long_chain.options.iter() {
+                    if !self.are_valid_strikes(long_option, short_option, &side) {
+                        continue;
+                    }
+                    let Some(premium_long_call) =
+                        long_option.call_ask.filter(|ask| *ask > Positive::ZERO)
+                    else {
+                        continue;
+                    };
+                    let strategy = PoorMansCoveredCall::new(
+                        short_chain.symbol.clone(),
+                        short_chain.underlying_price,
+                        long_option.strike_price,
+                        short_option.strike_price,
+                        long_expiration,
+                        short_expiration,
+                        implied_volatility,
+                        short_chain
+                            .risk_free_rate
+                            .unwrap_or(self.short_call.option.risk_free_rate),
+                        short_chain
+                            .dividend_yield
+                            .unwrap_or(self.short_call.option.dividend_yield),
+                        self.short_call.option.quantity,
+                        premium_long_call,
+                        premium_short_call,
+                        self.long_call.open_fee,
+                        self.long_call.close_fee,
+                        self.short_call.open_fee,
+                        self.short_call.close_fee,
+                    );
+                    if !strategy.validate() {
+                        debug!("Invalid strategy");
+                        continue;
+                    }
+
+                    if let Some(current_value) =
+                        time_spread::optimization_value(&strategy, &criteria)
+                        && current_value > best_value
+                    {
+                        best_value = current_value;
+                        *self = strategy;
+                    }
+                }
+            }
+        }
+    }
+
     fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
         let (long, short) = match legs {
             StrategyLegs::TwoLegs { first, second } => (first, second),
@@ -4,6 +4,7 @@
    Date: 30/11/24
 ******************************************************************************/
 use crate::Positive;
+use serde::{Deserialize, Serialize};
 
 /// # StrategyProbabilityAnalysis
 ///
@@ -37,7 +38,7 @@ use crate::Positive;
 /// that incorporate factors such as implied volatility, time to expiration, and price movement
 /// probabilities.
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StrategyProbabilityAnalysis {
     /// The probability of profit (POP)
     pub probability_of_profit: Positive,
@@ -1,11 +1,760 @@
-/*
-Protective Put Strategy
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
 
-A protective put involves holding a long position in the underlying asset and buying a put option on that same asset.
-This strategy provides unlimited upside potential while limiting downside risk.
+//! # Protective Put Strategy
+//!
+//! A protective put holds a long position in the underlying asset and buys a put option
+//! on that same asset. The put acts as insurance: below its strike any further decline of
+//! the holding is offset by the put, while the upside of the holding remains open.
+//!
+//! Key characteristics:
+//! - Unlimited profit potential, reduced by the premium paid for the put
+//! - Maximum loss limited to the distance from the entry price to the put strike plus the premium
+//! - A single break-even point at the entry price plus the premium paid
+//!
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType, Validable,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::{
+    ExpirationDate, Options, Positive,
+    chains::{StrategyLegs, chain::OptionChain},
+    error::{
+        GreeksError, OperationErrorKind,
+        position::{PositionError, PositionValidationErrorKind},
+        probability::ProbabilityError,
+        strategies::{ProfitLossErrorKind, StrategyError},
+    },
+    greeks::{Greeks, delta},
+    model::{
+        ProfitLossRange, UnderlyingPosition,
+        position::Position,
+        types::{OptionBasicType, OptionStyle, OptionType, Side},
+    },
+    pnl::{PnLCalculator, utils::PnL},
+    pricing::payoff::Profit,
+    strategies::{
+        BasicAble, Strategies, StrategyConstructor,
+        delta_neutral::DeltaNeutrality,
+        probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment},
+        utils::{FindOptimalSide, OptimizationCriteria},
+    },
+};
+use chrono::Utc;
+use num_traits::FromPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use tracing::{debug, info};
 
-Key characteristics:
-- Unlimited profit potential
-- Limits downside risk
-- Acts as a form of insurance for the underlying asset
-*/
+pub(super) const PROTECTIVE_PUT_DESCRIPTION: &str = "A Protective Put combines a long position in the underlying asset with a long put option on the same asset. \
+    The put limits the loss on the holding below its strike price while keeping the upside of the underlying, \
+    at the cost of the premium paid. It is used to insure an existing holding against a sharp decline.";
+
+/// Represents a Protective Put strategy: long underlying plus a long put.
+///
+/// # Fields
+/// * `name` - A descriptive name for the strategy instance.
+/// * `kind` - The type of strategy, `StrategyType::ProtectivePut`.
+/// * `description` - A detailed description of the strategy.
+/// * `break_even_points` - Price at which the combined position neither makes nor loses money.
+/// * `long_underlying` - The holding in the underlying asset.
+/// * `long_put` - The put option protecting the holding.
+///
+/// ## Risk and Reward
+/// The profit is unlimited above the break-even point. The maximum loss is reached at or
+/// below the put strike and equals the entry price minus the strike, plus the premium and fees.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProtectivePut {
+    /// Name identifier for this specific strategy instance
+    pub name: String,
+    /// Identifies this as a ProtectivePut strategy type
+    pub kind: StrategyType,
+    /// Detailed description of this strategy instance
+    pub description: String,
+    /// Price points where the strategy neither makes nor loses money
+    pub break_even_points: Vec<Positive>,
+    /// The holding in the underlying asset
+    pub(super) long_underlying: UnderlyingPosition,
+    /// The put option protecting the holding
+    pub(super) long_put: Position,
+}
+
+impl ProtectivePut {
+    /// Creates a new Protective Put, buying the underlying at `underlying_price` and
+    /// `quantity` puts to protect it.
+    ///
+    /// # Parameters
+    /// * `underlying_symbol` - Symbol of the underlying asset
+    /// * `underlying_price` - Current price of the underlying, used as the entry price of the holding
+    /// * `long_put_strike` - Strike of the put bought
+    /// * `expiration` - Expiration of the put bought
+    /// * `implied_volatility` - Implied volatility of the put
+    /// * `risk_free_rate` - Risk-free interest rate
+    /// * `dividend_yield` - Dividend yield of the underlying
+    /// * `quantity` - Units of the underlying held, matched by the same number of puts
+    /// * `premium_long_put` - Premium paid per put
+    /// * `open_fee_long_put` / `close_fee_long_put` - Fees per put
+    /// * `open_fee_underlying` / `close_fee_underlying` - Fees per unit of the underlying
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        long_put_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_put: Positive,
+        open_fee_long_put: Positive,
+        close_fee_long_put: Positive,
+        open_fee_underlying: Positive,
+        close_fee_underlying: Positive,
+    ) -> Self {
+        let mut strategy = ProtectivePut {
+            long_underlying: UnderlyingPosition::new(
+                underlying_symbol.clone(),
+                Side::Long,
+                quantity,
+                underlying_price,
+                Utc::now(),
+                open_fee_underlying,
+                close_fee_underlying,
+            ),
+            ..Default::default()
+        };
+
+        let long_put_option = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            long_put_strike,
+            expiration,
+            implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Put,
+            dividend_yield,
+            None,
+        );
+        let long_put = Position::new(
+            long_put_option,
+            premium_long_put,
+            Utc::now(),
+            open_fee_long_put,
+            close_fee_long_put,
+            None,
+            None,
+        );
+        strategy
+            .add_position(&long_put)
+            .expect("Invalid long put option");
+
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
+        strategy
+    }
+}
+
+impl StrategyConstructor for ProtectivePut {
+    /// Builds a Protective Put from its option leg. The underlying holding is created at
+    /// the option's underlying price, in the same quantity and without fees.
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 1 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Protective Put get_strategy".to_string(),
+                    reason: "Must have exactly 1 option".to_string(),
+                },
+            ));
+        }
+        let position = &vec_positions[0];
+        if position.option.option_style != OptionStyle::Put || position.option.side != Side::Long {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Protective Put get_strategy".to_string(),
+                    reason: "Protective Put requires a long put".to_string(),
+                },
+            ));
+        }
+
+        let option = &position.option;
+        let mut strategy = ProtectivePut {
+            long_underlying: UnderlyingPosition::new(
+                option.underlying_symbol.clone(),
+                Side::Long,
+                option.quantity,
+                option.underlying_price,
+                Utc::now(),
+                Positive::ZERO,
+                Positive::ZERO,
+            ),
+            long_put: position.clone(),
+            ..Default::default()
+        };
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl BreakEvenable for ProtectivePut {
+    fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+        Ok(&self.break_even_points)
+    }
+
+    fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+        self.break_even_points = Vec::new();
+        if self.long_underlying.quantity == Positive::ZERO {
+            return Ok(());
+        }
+        // Above the strike only the holding moves, so the break-even is the net cost per
+        // unit. A put deep enough in the money can lock in a profit, leaving no break-even.
+        let break_even = self.get_net_cost()? / self.long_underlying.quantity;
+        if break_even > self.long_put.option.strike_price.to_dec() {
+            self.break_even_points
+                .push(Positive::from(break_even).round_to(2));
+        }
+        Ok(())
+    }
+}
+
+impl Validable for ProtectivePut {
+    fn validate(&self) -> bool {
+        if self.long_underlying.quantity != self.long_put.option.quantity {
+            debug!("Underlying quantity must match the number of puts bought");
+            return false;
+        }
+        self.long_underlying.validate() && self.long_put.validate()
+    }
+}
+
+impl Positionable for ProtectivePut {
+    fn add_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        match (position.option.option_style, position.option.side) {
+            (OptionStyle::Put, Side::Long) => {
+                self.long_put = position.clone();
+                Ok(())
+            }
+            (OptionStyle::Put, Side::Short) => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Short put is not valid for ProtectivePut".to_string(),
+            )),
+            _ => Err(PositionError::invalid_position_style(
+                position.option.option_style,
+                "Position is a Call, it is not valid for ProtectivePut".to_string(),
+            )),
+        }
+    }
+
+    fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+        Ok(vec![&self.long_put])
+    }
+
+    fn get_underlying_position(&self) -> Option<&UnderlyingPosition> {
+        Some(&self.long_underlying)
+    }
+
+    fn get_position(
+        &mut self,
+        option_style: &OptionStyle,
+        side: &Side,
+        strike: &Positive,
+    ) -> Result<Vec<&mut Position>, PositionError> {
+        match (side, option_style, strike) {
+            (Side::Long, OptionStyle::Put, strike)
+                if *strike == self.long_put.option.strike_price =>
+            {
+                Ok(vec![&mut self.long_put])
+            }
+            _ => Err(PositionError::invalid_position_type(
+                *side,
+                "Position not found in ProtectivePut".to_string(),
+            )),
+        }
+    }
+
+    fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+        if !position.validate() {
+            return Err(PositionError::ValidationError(
+                PositionValidationErrorKind::InvalidPosition {
+                    reason: "Invalid position data".to_string(),
+                },
+            ));
+        }
+        match (&position.option.side, &position.option.option_style) {
+            (Side::Long, OptionStyle::Put)
+                if position.option.strike_price == self.long_put.option.strike_price =>
+            {
+                self.long_put = position.clone();
+                Ok(())
+            }
+            _ => Err(PositionError::invalid_position_type(
+                position.option.side,
+                "Position not found in ProtectivePut".to_string(),
+            )),
+        }
+    }
+}
+
+impl Strategable for ProtectivePut {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl BasicAble for ProtectivePut {
+    fn get_title(&self) -> String {
+        format!(
+            "{:?} Strategy: \n\tUnderlying: {:?} {} {} @ {}\n\t{}",
+            self.kind,
+            self.long_underlying.side,
+            self.long_underlying.quantity,
+            self.long_underlying.symbol,
+            self.long_underlying.price,
+            self.long_put.get_title()
+        )
+    }
+    fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+        let option = &self.long_put.option;
+        HashSet::from([OptionBasicType {
+            option_style: &option.option_style,
+            side: &option.side,
+            strike_price: &option.strike_price,
+            expiration_date: &option.expiration_date,
+        }])
+    }
+    fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        let option = &self.long_put.option;
+        HashMap::from([(
+            OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            },
+            &option.implied_volatility,
+        )])
+    }
+    fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+        let option = &self.long_put.option;
+        HashMap::from([(
+            OptionBasicType {
+                option_style: &option.option_style,
+                side: &option.side,
+                strike_price: &option.strike_price,
+                expiration_date: &option.expiration_date,
+            },
+            &option.quantity,
+        )])
+    }
+    fn one_option(&self) -> &Options {
+        self.long_put.one_option()
+    }
+    fn one_option_mut(&mut self) -> &mut Options {
+        self.long_put.one_option_mut()
+    }
+    fn set_expiration_date(
+        &mut self,
+        expiration_date: ExpirationDate,
+    ) -> Result<(), StrategyError> {
+        self.long_put.option.expiration_date = expiration_date;
+        Ok(())
+    }
+    fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+        // The put is repriced as if bought now, so the holding is re-marked at the same price.
+        self.long_underlying.price = *price;
+        self.long_put.option.underlying_price = *price;
+        self.long_put.premium =
+            Positive::from(self.long_put.option.calculate_price_black_scholes()?.abs());
+        Ok(())
+    }
+    fn set_implied_volatility(&mut self, volatility: &Positive) -> Result<(), StrategyError> {
+        self.long_put.option.implied_volatility = *volatility;
+        self.long_put.premium =
+            Positive(self.long_put.option.calculate_price_black_scholes()?.abs());
+        Ok(())
+    }
+}
+
+impl Strategies for ProtectivePut {
+    fn get_volume(&mut self) -> Result<Positive, StrategyError> {
+        Ok(self.long_put.option.quantity)
+    }
+
+    fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+        Ok(Positive::INFINITY) // The holding keeps its upside
+    }
+
+    fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+        let loss = self.calculate_profit_at(&self.long_put.option.strike_price)?;
+        if loss >= Decimal::ZERO {
+            Err(StrategyError::ProfitLossError(
+                ProfitLossErrorKind::MaxLossError {
+                    reason: "Max loss must be negative".to_string(),
+                },
+            ))
+        } else {
+            Ok(loss.abs().into())
+        }
+    }
+
+    fn get_total_cost(&self) -> Result<Positive, PositionError> {
+        Ok(self.long_put.total_cost()? + self.long_underlying.total_cost())
+    }
+
+    fn get_net_cost(&self) -> Result<Decimal, PositionError> {
+        Ok(self.long_put.net_cost()? + self.long_underlying.net_cost())
+    }
+
+    fn get_fees(&self) -> Result<Positive, StrategyError> {
+        Ok(self.long_put.fees()? + self.long_underlying.fees())
+    }
+
+    fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+        let max_loss = self.get_max_loss().unwrap_or(Positive::ZERO);
+        let break_even = match self.break_even_points.first() {
+            Some(break_even) => *break_even,
+            None => return Ok(Decimal::MAX),
+        };
+        if max_loss == Positive::ZERO {
+            return Ok(Decimal::MAX);
+        }
+        // With unlimited upside, rank by the size of the loss triangle between the strike
+        // and the break-even: the smaller it is, the larger the returned area.
+        let loss_area = ((break_even - self.long_put.option.strike_price) * max_loss
+            / 2.0
+            / self.long_underlying.price)
+            .to_f64();
+        Ok(Decimal::from_f64(1.0 / loss_area).unwrap_or(Decimal::MAX))
+    }
+
+    fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+        let max_loss = self.get_max_loss().unwrap_or(Positive::ZERO);
+        if max_loss == Positive::ZERO {
+            return Ok(Decimal::MAX);
+        }
+        // Protected notional per unit of worst-case loss
+        let protected = self.long_put.option.strike_price * self.long_put.option.quantity;
+        Ok((protected / max_loss * 100.0).into())
+    }
+}
+
+impl Optimizable for ProtectivePut {
+    type Strategy = ProtectivePut;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_single_iter()
+            // Protection is bought out of the money by default
+            .filter(move |put| {
+                if side == FindOptimalSide::Center {
+                    put.is_valid_optimal_side(underlying_price, &FindOptimalSide::Lower)
+                } else {
+                    put.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            .filter(|put| put.put_ask.unwrap_or(Positive::ZERO) > Positive::ZERO)
+            .filter(move |put| {
+                let legs = StrategyLegs::OneLeg { first: put };
+                let strategy = strategy.create_strategy(option_chain, &legs);
+                strategy.validate() && strategy.get_max_loss().is_ok()
+            })
+            .map(OptionDataGroup::One)
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let put = match option_data_group {
+                OptionDataGroup::One(first) => first,
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::OneLeg { first: put };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio().unwrap(),
+                OptimizationCriteria::Area => strategy.get_profit_area().unwrap(),
+            };
+
+            if current_value > best_value {
+                info!("Found better value: {}", current_value);
+                best_value = current_value;
+                *self = strategy.clone();
+            }
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let put = match legs {
+            StrategyLegs::OneLeg { first } => first,
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        ProtectivePut::new(
+            chain.symbol.clone(),
+            chain.underlying_price,
+            put.strike_price,
+            self.long_put.option.expiration_date,
+            put.implied_volatility,
+            self.long_put.option.risk_free_rate,
+            self.long_put.option.dividend_yield,
+            self.long_put.option.quantity,
+            put.put_ask.unwrap(),
+            self.long_put.open_fee,
+            self.long_put.close_fee,
+            self.long_underlying.open_fee,
+            self.long_underlying.close_fee,
+        )
+    }
+}
+
+impl Profit for ProtectivePut {
+    fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+        Ok(self.long_put.pnl_at_expiration(&Some(price))? + self.long_underlying.pnl_at(price))
+    }
+}
+
+impl ProbabilityAnalysis for ProtectivePut {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Protective Put has no break-even point".to_string())?;
+        let option = &self.long_put.option;
+
+        let mut profit_range = ProfitLossRange::new(Some(break_even_point), None, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even_point = *self
+            .get_break_even_points()?
+            .first()
+            .ok_or("Protective Put has no break-even point".to_string())?;
+        let option = &self.long_put.option;
+
+        let mut loss_range = ProfitLossRange::new(None, Some(break_even_point), Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for ProtectivePut {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.long_put.option])
+    }
+
+    /// Delta of the long put plus the delta of the holding (one per unit held).
+    fn delta(&self) -> Result<Decimal, GreeksError> {
+        Ok(delta(&self.long_put.option)? + self.long_underlying.delta())
+    }
+}
+
+impl DeltaNeutrality for ProtectivePut {}
+
+impl PnLCalculator for ProtectivePut {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .long_put
+            .calculate_pnl(market_price, expiration_date, implied_volatility)?
+            + self.long_underlying.calculate_pnl(
+                market_price,
+                expiration_date,
+                implied_volatility,
+            )?)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        Ok(self
+            .long_put
+            .calculate_pnl_at_expiration(underlying_price)?
+            + self
+                .long_underlying
+                .calculate_pnl_at_expiration(underlying_price)?)
+    }
+}
+
+#[cfg(test)]
+mod tests_protective_put {
+    use super::*;
+    use crate::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+    use crate::{assert_decimal_eq, pos, spos};
+    use rust_decimal_macros::dec;
+
+    fn create_strategy() -> ProtectivePut {
+        ProtectivePut::new(
+            "AAPL".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.kind, StrategyType::ProtectivePut);
+        assert_eq!(strategy.long_underlying.price, pos!(100.0));
+        assert_eq!(strategy.long_put.option.option_style, OptionStyle::Put);
+        assert!(strategy.validate());
+    }
+
+    #[test]
+    fn test_break_even_and_costs() {
+        let strategy = create_strategy();
+        assert_eq!(
+            strategy.get_break_even_points().unwrap(),
+            &vec![pos!(102.0)]
+        );
+        assert_eq!(strategy.get_net_cost().unwrap(), dec!(102.0));
+        assert_eq!(strategy.get_total_cost().unwrap(), pos!(102.0));
+    }
+
+    #[test]
+    fn test_max_profit_and_loss() {
+        let strategy = create_strategy();
+        assert_eq!(strategy.get_max_profit().unwrap(), Positive::INFINITY);
+        assert_eq!(strategy.get_max_loss().unwrap(), pos!(7.0));
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(50.0)).unwrap(),
+            dec!(-7.0)
+        );
+        assert_eq!(
+            strategy.calculate_profit_at(&pos!(120.0)).unwrap(),
+            dec!(18.0)
+        );
+    }
+
+    #[test]
+    fn test_delta_includes_underlying() {
+        let strategy = create_strategy();
+        let put_delta = delta(&strategy.long_put.option).unwrap();
+        assert!(put_delta < Decimal::ZERO);
+        assert_eq!(strategy.delta().unwrap(), put_delta + dec!(1.0));
+        assert!(strategy.delta().unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_get_strategy() {
+        let strategy = create_strategy();
+        let rebuilt =
+            ProtectivePut::get_strategy(std::slice::from_ref(&strategy.long_put)).unwrap();
+        assert_eq!(rebuilt.long_underlying.quantity, pos!(1.0));
+        assert_eq!(rebuilt.get_break_even_points().unwrap(), &vec![pos!(102.0)]);
+
+        let mut call = strategy.long_put.clone();
+        call.option.option_style = OptionStyle::Call;
+        assert!(ProtectivePut::get_strategy(&[call]).is_err());
+    }
+
+    #[test]
+    fn test_deep_itm_put_has_no_break_even() {
+        let mut strategy = create_strategy();
+        let mut put = strategy.long_put.clone();
+        put.option.strike_price = pos!(110.0);
+        put.premium = pos!(9.0);
+        strategy.add_position(&put).unwrap();
+        strategy.update_break_even_points().unwrap();
+        assert!(strategy.get_break_even_points().unwrap().is_empty());
+        assert!(strategy.get_max_loss().is_err());
+    }
+
+    #[test]
+    fn test_probabilities() {
+        let strategy = create_strategy();
+        let profit = strategy.probability_of_profit(None, None).unwrap();
+        let loss = strategy.probability_of_loss(None, None).unwrap();
+        assert_decimal_eq!((profit + loss).to_dec(), dec!(1.0), dec!(0.001));
+    }
+
+    #[test]
+    fn test_find_optimal() {
+        let price_params = OptionDataPriceParams::new(
+            Some(Box::new(pos!(100.0))),
+            Some(ExpirationDate::Days(pos!(30.0))),
+            Some(dec!(0.05)),
+            spos!(0.0),
+            Some("AAPL".to_string()),
+        );
+        let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+            "AAPL".to_string(),
+            None,
+            10,
+            spos!(5.0),
+            dec!(-0.2),
+            dec!(0.1),
+            pos!(0.02),
+            2,
+            price_params,
+            pos!(0.2),
+        ));
+        let mut strategy = create_strategy();
+        strategy.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Ratio);
+        assert!(strategy.validate());
+        assert!(strategy.long_put.option.strike_price <= pos!(100.0));
+        assert!(strategy.get_max_loss().is_ok());
+    }
+}
@@ -0,0 +1,101 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const PUT_BACK_SPREAD_DESCRIPTION: &str = "A Put Back Spread sells puts at a higher strike and buys a larger number of puts at a \
+    lower strike. It keeps the net credit if the underlying rises, loses most at the long strike \
+    and gains on a sharp fall.";
+
+static PUT_BACK_SPREAD: StructureSpec = StructureSpec {
+    kind: StrategyType::PutBackSpread,
+    name: "Put Back Spread",
+    description: PUT_BACK_SPREAD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Long, true),
+        leg(1, OptionStyle::Put, Side::Short, false),
+    ],
+    ratio: QuantityRatio::AboveOne,
+    strikes: distinct_strikes,
+};
+
+/// A short put and a larger number of long puts at a lower strike, on the same expiration.
+///
+/// The reverse of a put ratio spread, the strategy bets on a sharp fall or on higher
+/// volatility. Its loss is bounded and largest at the long strike.
+///
+/// Legs, by ascending strike: `quantity * ratio` long puts and `quantity` short puts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PutBackSpread {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl PutBackSpread {
+    /// Creates a Put Back Spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `short_strike` - Strike of the short puts, the higher one.
+    /// * `long_strike` - Strike of the long puts, the lower one.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of short puts.
+    /// * `ratio` - Long puts bought per short put, above one.
+    /// * `premium_short_put` - The premium received for each short put.
+    /// * `premium_long_put` - The premium paid for each long put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal or the ratio is not above one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_strike: Positive,
+        long_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_short_put: Positive,
+        premium_long_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = PUT_BACK_SPREAD.build(
+            underlying_symbol,
+            underlying_price,
+            &[long_strike, short_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            ratio,
+            &[premium_long_put, premium_short_put],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(PutBackSpread { strategy })
+    }
+}
+
+impl_structure!(PutBackSpread, PUT_BACK_SPREAD);
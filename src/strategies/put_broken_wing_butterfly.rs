@@ -0,0 +1,106 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, impl_structure, leg, unequal_wings};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const PUT_BROKEN_WING_BUTTERFLY_DESCRIPTION: &str = "A Put Broken Wing Butterfly is a put butterfly whose wings are not equally wide. \
+    Skipping a strike on one side lowers the cost, often to a credit, and moves all the risk to \
+    that side.";
+
+static PUT_BROKEN_WING_BUTTERFLY: StructureSpec = StructureSpec {
+    kind: StrategyType::PutBrokenWingButterfly,
+    name: "Put Broken Wing Butterfly",
+    description: PUT_BROKEN_WING_BUTTERFLY_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Long, false),
+        leg(1, OptionStyle::Put, Side::Short, true),
+        leg(2, OptionStyle::Put, Side::Long, false),
+    ],
+    ratio: QuantityRatio::Fixed(Positive::TWO),
+    strikes: unequal_wings,
+};
+
+/// A long put, two short puts and a long put at increasing strikes, with wings of
+/// different width, all on the same expiration.
+///
+/// With the lower wing wider, as usual, the structure is entered for little or no debit,
+/// keeps that outcome above the upper strike and carries its risk below the body.
+///
+/// Legs, by ascending strike: `quantity` long puts, `2 * quantity` short puts and
+/// `quantity` long puts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PutBrokenWingButterfly {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl PutBrokenWingButterfly {
+    /// Creates a Put Broken Wing Butterfly.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `lower_strike` - Strike of the lower long put.
+    /// * `middle_strike` - Strike of the short puts, the body.
+    /// * `upper_strike` - Strike of the upper long put.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of each wing.
+    /// * `premium_lower_put` - The premium paid for the lower long put.
+    /// * `premium_middle_put` - The premium received for each short put.
+    /// * `premium_upper_put` - The premium paid for the upper long put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are not distinct or the wings are equally wide.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        lower_strike: Positive,
+        middle_strike: Positive,
+        upper_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_lower_put: Positive,
+        premium_middle_put: Positive,
+        premium_upper_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = PUT_BROKEN_WING_BUTTERFLY.build(
+            underlying_symbol,
+            underlying_price,
+            &[lower_strike, middle_strike, upper_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::TWO,
+            &[premium_lower_put, premium_middle_put, premium_upper_put],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(PutBrokenWingButterfly { strategy })
+    }
+}
+
+impl_structure!(PutBrokenWingButterfly, PUT_BROKEN_WING_BUTTERFLY);
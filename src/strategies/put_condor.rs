@@ -0,0 +1,120 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const PUT_CONDOR_DESCRIPTION: &str = "A Put Condor buys puts at the outer strikes and sells puts at the two inner \
+    strikes. It profits when the underlying ends between the inner strikes, with both profit \
+    and loss bounded.";
+
+static PUT_CONDOR: StructureSpec = StructureSpec {
+    kind: StrategyType::PutCondor,
+    name: "Put Condor",
+    description: PUT_CONDOR_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Long, false),
+        leg(1, OptionStyle::Put, Side::Short, false),
+        leg(2, OptionStyle::Put, Side::Short, false),
+        leg(3, OptionStyle::Put, Side::Long, false),
+    ],
+    ratio: QuantityRatio::One,
+    strikes: distinct_strikes,
+};
+
+/// Long puts at the outer strikes and short puts at the inner strikes, all on the same
+/// expiration.
+///
+/// A neutral strategy that profits when the underlying stays between the inner strikes,
+/// like an iron condor built with puts only.
+///
+/// Legs, by ascending strike: long, short, short and long put.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PutCondor {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl PutCondor {
+    /// Creates a Put Condor.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `long_low_strike` - Strike of the lower long put, the lowest.
+    /// * `short_low_strike` - Strike of the lower short put.
+    /// * `short_high_strike` - Strike of the upper short put.
+    /// * `long_high_strike` - Strike of the upper long put, the highest.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of every leg.
+    /// * `premium_long_low_put` - The premium paid for the lower long put.
+    /// * `premium_short_low_put` - The premium received for the lower short put.
+    /// * `premium_short_high_put` - The premium received for the upper short put.
+    /// * `premium_long_high_put` - The premium paid for the upper long put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are not distinct.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        long_low_strike: Positive,
+        short_low_strike: Positive,
+        short_high_strike: Positive,
+        long_high_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_long_low_put: Positive,
+        premium_short_low_put: Positive,
+        premium_short_high_put: Positive,
+        premium_long_high_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = PUT_CONDOR.build(
+            underlying_symbol,
+            underlying_price,
+            &[
+                long_low_strike,
+                short_low_strike,
+                short_high_strike,
+                long_high_strike,
+            ],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::ONE,
+            &[
+                premium_long_low_put,
+                premium_short_low_put,
+                premium_short_high_put,
+                premium_long_high_put,
+            ],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(PutCondor { strategy })
+    }
+}
+
+impl_structure!(PutCondor, PUT_CONDOR);
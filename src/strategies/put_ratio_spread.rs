@@ -0,0 +1,102 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const PUT_RATIO_SPREAD_DESCRIPTION: &str = "A Put Ratio Spread buys puts at a higher strike and sells a larger number of puts at a \
+    lower strike. It profits most at the short strike at expiration and loses below the lower \
+    break-even point, down to a zero underlying price.";
+
+static PUT_RATIO_SPREAD: StructureSpec = StructureSpec {
+    kind: StrategyType::PutRatioSpread,
+    name: "Put Ratio Spread",
+    description: PUT_RATIO_SPREAD_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Short, true),
+        leg(1, OptionStyle::Put, Side::Long, false),
+    ],
+    ratio: QuantityRatio::AboveOne,
+    strikes: distinct_strikes,
+};
+
+/// A long put and a larger number of short puts at a lower strike, on the same expiration.
+///
+/// The strategy is moderately bearish: it profits from a fall down to the short strike and
+/// loses below the lower break-even point, a loss bounded only by the underlying reaching
+/// zero.
+///
+/// Legs, by ascending strike: `quantity * ratio` short puts and `quantity` long puts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PutRatioSpread {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl PutRatioSpread {
+    /// Creates a Put Ratio Spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `long_strike` - Strike of the long puts, the higher one.
+    /// * `short_strike` - Strike of the short puts, the lower one.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of long puts.
+    /// * `ratio` - Short puts sold per long put, above one.
+    /// * `premium_long_put` - The premium paid for each long put.
+    /// * `premium_short_put` - The premium received for each short put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal or the ratio is not above one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        long_strike: Positive,
+        short_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premium_long_put: Positive,
+        premium_short_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = PUT_RATIO_SPREAD.build(
+            underlying_symbol,
+            underlying_price,
+            &[short_strike, long_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            ratio,
+            &[premium_short_put, premium_long_put],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(PutRatioSpread { strategy })
+    }
+}
+
+impl_structure!(PutRatioSpread, PUT_RATIO_SPREAD);
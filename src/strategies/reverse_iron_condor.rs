@@ -0,0 +1,119 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const REVERSE_IRON_CONDOR_DESCRIPTION: &str = "A Reverse Iron Condor buys a put spread and a call spread around the underlying price. \
+    It pays a debit and profits from a large move in either direction, with both profit and loss \
+    bounded.";
+
+static REVERSE_IRON_CONDOR: StructureSpec = StructureSpec {
+    kind: StrategyType::ReverseIronCondor,
+    name: "Reverse Iron Condor",
+    description: REVERSE_IRON_CONDOR_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Short, false),
+        leg(1, OptionStyle::Put, Side::Long, false),
+        leg(2, OptionStyle::Call, Side::Long, false),
+        leg(3, OptionStyle::Call, Side::Short, false),
+    ],
+    ratio: QuantityRatio::One,
+    strikes: distinct_strikes,
+};
+
+/// A long put spread below a long call spread, all on the same expiration.
+///
+/// The opposite of an iron condor: the strategy expects a breakout and loses the debit if
+/// the underlying stays between the long strikes.
+///
+/// Legs, by ascending strike: short put, long put, long call and short call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReverseIronCondor {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl ReverseIronCondor {
+    /// Creates a Reverse Iron Condor.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `short_put_strike` - Strike of the short put, the lowest.
+    /// * `long_put_strike` - Strike of the long put.
+    /// * `long_call_strike` - Strike of the long call.
+    /// * `short_call_strike` - Strike of the short call, the highest.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of every leg.
+    /// * `premium_short_put` - The premium received for the short put.
+    /// * `premium_long_put` - The premium paid for the long put.
+    /// * `premium_long_call` - The premium paid for the long call.
+    /// * `premium_short_call` - The premium received for the short call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are not distinct.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_put_strike: Positive,
+        long_put_strike: Positive,
+        long_call_strike: Positive,
+        short_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_put: Positive,
+        premium_long_put: Positive,
+        premium_long_call: Positive,
+        premium_short_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = REVERSE_IRON_CONDOR.build(
+            underlying_symbol,
+            underlying_price,
+            &[
+                short_put_strike,
+                long_put_strike,
+                long_call_strike,
+                short_call_strike,
+            ],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::ONE,
+            &[
+                premium_short_put,
+                premium_long_put,
+                premium_long_call,
+                premium_short_call,
+            ],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(ReverseIronCondor { strategy })
+    }
+}
+
+impl_structure!(ReverseIronCondor, REVERSE_IRON_CONDOR);
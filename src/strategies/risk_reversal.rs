@@ -0,0 +1,99 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const RISK_REVERSAL_DESCRIPTION: &str = "A Risk Reversal sells an out-of-the-money put to buy an out-of-the-money call. It gives \
+    synthetic long exposure outside the strikes for little or no premium.";
+
+static RISK_REVERSAL: StructureSpec = StructureSpec {
+    kind: StrategyType::RiskReversal,
+    name: "Risk Reversal",
+    description: RISK_REVERSAL_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Put, Side::Short, false),
+        leg(1, OptionStyle::Call, Side::Long, false),
+    ],
+    ratio: QuantityRatio::One,
+    strikes: distinct_strikes,
+};
+
+/// A short put and a long call at a higher strike, on the same expiration.
+///
+/// A bullish position that behaves like the underlying above the call strike and below the
+/// put strike, usually financed by the put. For the bearish version, build the opposite
+/// legs with a [`CustomStrategy`].
+///
+/// Legs, by ascending strike: short put and long call.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RiskReversal {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl RiskReversal {
+    /// Creates a Risk Reversal.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `short_put_strike` - Strike of the short put, the lower one.
+    /// * `long_call_strike` - Strike of the long call, the higher one.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of contracts of every leg.
+    /// * `premium_short_put` - The premium received for the short put.
+    /// * `premium_long_call` - The premium paid for the long call.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the strikes are equal.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        short_put_strike: Positive,
+        long_call_strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_short_put: Positive,
+        premium_long_call: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = RISK_REVERSAL.build(
+            underlying_symbol,
+            underlying_price,
+            &[short_put_strike, long_call_strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::ONE,
+            &[premium_short_put, premium_long_call],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(RiskReversal { strategy })
+    }
+}
+
+impl_structure!(RiskReversal, RISK_REVERSAL);
@@ -0,0 +1,1250 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::model::{MarketView, Outlook, ScreenedStrategy, ScreenerConfig, VolatilityOutlook};
+use crate::chains::chain::OptionChain;
+use crate::error::strategies::StrategyError;
+use crate::greeks::Greeks;
+use crate::model::Position;
+use crate::series::OptionSeries;
+use crate::strategies::base::{Optimizable, StrategyType};
+use crate::strategies::optimizer::{CandidateMetrics, OptimizerConfig, ParetoOptimizable, admit};
+use crate::strategies::probabilities::ProbabilityAnalysis;
+use crate::strategies::utils::OptimizationCriteria;
+use crate::strategies::{
+    BearCallSpread, BearPutSpread, BoxSpread, BullCallSpread, BullPutSpread, CallBackSpread,
+    CallBrokenWingButterfly, CallButterfly, CallCalendarSpread, CallCondor, CallRatioSpread,
+    Collar, CoveredCall, DiagonalCallSpread, DiagonalPutSpread, IronButterfly, IronCondor,
+    JadeLizard, LongButterflySpread, LongCall, LongPut, LongStraddle, LongStrangle,
+    PoorMansCoveredCall, ProtectivePut, PutBackSpread, PutBrokenWingButterfly, PutCalendarSpread,
+    PutCondor, PutRatioSpread, ReverseIronCondor, RiskReversal, ShortButterflySpread, ShortCall,
+    ShortPut, ShortStraddle, ShortStrangle, Strap, Strip,
+};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use tracing::debug;
+
+/// Vega exposure of a strategy type.
+#[derive(Clone, Copy)]
+enum Vega {
+    Long,
+    Short,
+    Flat,
+}
+
+/// The market views a strategy type is suited to.
+pub(super) struct Profile {
+    pub kind: StrategyType,
+    outlooks: &'static [Outlook],
+    vega: Vega,
+}
+
+impl Profile {
+    /// Whether the strategy type suits `view`. Types with no net vega fit any volatility
+    /// outlook.
+    pub(super) fn fits(&self, view: &MarketView) -> bool {
+        self.outlooks.contains(&view.outlook)
+            && matches!(
+                (view.volatility, self.vega),
+                (None, _)
+                    | (_, Vega::Flat)
+                    | (Some(VolatilityOutlook::Rising), Vega::Long)
+                    | (Some(VolatilityOutlook::Falling), Vega::Short)
+            )
+    }
+}
+
+const fn profile(kind: StrategyType, outlooks: &'static [Outlook], vega: Vega) -> Profile {
+    Profile {
+        kind,
+        outlooks,
+        vega,
+    }
+}
+
+const BULLISH: &[Outlook] = &[Outlook::Bullish];
+const BEARISH: &[Outlook] = &[Outlook::Bearish];
+const NEUTRAL: &[Outlook] = &[Outlook::Neutral];
+const NEUTRAL_BULLISH: &[Outlook] = &[Outlook::Neutral, Outlook::Bullish];
+const NEUTRAL_BEARISH: &[Outlook] = &[Outlook::Neutral, Outlook::Bearish];
+
+/// Strategy types searched on a single expiration. Box spreads lock in a rate rather than
+/// express a view, so no view selects them.
+pub(super) static CHAIN_PROFILES: &[Profile] = &[
+    profile(StrategyType::LongCall, BULLISH, Vega::Long),
+    profile(StrategyType::LongPut, BEARISH, Vega::Long),
+    profile(StrategyType::ShortPut, NEUTRAL_BULLISH, Vega::Short),
+    profile(StrategyType::ShortCall, NEUTRAL_BEARISH, Vega::Short),
+    profile(StrategyType::BullCallSpread, BULLISH, Vega::Flat),
+    profile(StrategyType::BullPutSpread, BULLISH, Vega::Flat),
+    profile(StrategyType::BearCallSpread, BEARISH, Vega::Flat),
+    profile(StrategyType::BearPutSpread, BEARISH, Vega::Flat),
+    profile(StrategyType::RiskReversal, BULLISH, Vega::Flat),
+    profile(StrategyType::Collar, BULLISH, Vega::Flat),
+    profile(StrategyType::ProtectivePut, BULLISH, Vega::Long),
+    profile(StrategyType::CallBackSpread, BULLISH, Vega::Long),
+    profile(StrategyType::PutBackSpread, BEARISH, Vega::Long),
+    profile(StrategyType::Strap, NEUTRAL_BULLISH, Vega::Long),
+    profile(StrategyType::Strip, NEUTRAL_BEARISH, Vega::Long),
+    profile(StrategyType::CoveredCall, NEUTRAL_BULLISH, Vega::Short),
+    profile(StrategyType::JadeLizard, NEUTRAL_BULLISH, Vega::Short),
+    profile(StrategyType::CallRatioSpread, NEUTRAL_BULLISH, Vega::Short),
+    profile(StrategyType::PutRatioSpread, NEUTRAL_BEARISH, Vega::Short),
+    profile(StrategyType::CallButterfly, NEUTRAL_BULLISH, Vega::Short),
+    profile(StrategyType::LongStraddle, NEUTRAL, Vega::Long),
+    profile(StrategyType::LongStrangle, NEUTRAL, Vega::Long),
+    profile(StrategyType::ShortButterflySpread, NEUTRAL, Vega::Long),
+    profile(StrategyType::ReverseIronCondor, NEUTRAL, Vega::Long),
+    profile(StrategyType::ShortStraddle, NEUTRAL, Vega::Short),
+    profile(StrategyType::ShortStrangle, NEUTRAL, Vega::Short),
+    profile(StrategyType::IronCondor, NEUTRAL, Vega::Short),
+    profile(StrategyType::IronButterfly, NEUTRAL, Vega::Short),
+    profile(StrategyType::LongButterflySpread, NEUTRAL, Vega::Short),
+    profile(StrategyType::CallCondor, NEUTRAL, Vega::Short),
+    profile(StrategyType::PutCondor, NEUTRAL, Vega::Short),
+    profile(StrategyType::CallBrokenWingButterfly, NEUTRAL, Vega::Short),
+    profile(StrategyType::PutBrokenWingButterfly, NEUTRAL, Vega::Short),
+    profile(StrategyType::BoxSpread, &[], Vega::Flat),
+];
+
+/// Strategy types whose legs expire on different dates, searched on a series only. Custom
+/// strategies have no fixed legs to search for and are never screened.
+pub(super) static SERIES_PROFILES: &[Profile] = &[
+    profile(StrategyType::CallCalendarSpread, NEUTRAL, Vega::Long),
+    profile(StrategyType::PutCalendarSpread, NEUTRAL, Vega::Long),
+    profile(
+        StrategyType::DiagonalCallSpread,
+        NEUTRAL_BULLISH,
+        Vega::Long,
+    ),
+    profile(StrategyType::DiagonalPutSpread, NEUTRAL_BEARISH, Vega::Long),
+    profile(
+        StrategyType::PoorMansCoveredCall,
+        NEUTRAL_BULLISH,
+        Vega::Long,
+    ),
+];
+
+/// Terms shared by every template. Strikes and premiums are placeholders the optimizers
+/// replace with those of the chain.
+pub(super) struct Terms {
+    symbol: String,
+    price: Positive,
+    expiration: ExpirationDate,
+    risk_free_rate: Decimal,
+    dividend_yield: Positive,
+    /// At-the-money implied volatility, which time spreads need to value their back leg.
+    volatility: Option<Positive>,
+    quantity: Positive,
+    ratio: Positive,
+    open_fee: Positive,
+    close_fee: Positive,
+}
+
+impl Terms {
+    /// Terms of `chain`, falling back to `risk_free_rate` and `dividend_yield` when the
+    /// chain carries none.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the expiration of the chain cannot be
+    /// parsed.
+    pub(super) fn new(
+        chain: &OptionChain,
+        risk_free_rate: Option<Decimal>,
+        dividend_yield: Option<Positive>,
+        config: &ScreenerConfig,
+    ) -> Result<Self, StrategyError> {
+        let expiration = chain.get_expiration().ok_or_else(|| {
+            StrategyError::invalid_parameters(
+                "screen",
+                &format!("invalid chain expiration {}", chain.get_expiration_date()),
+            )
+        })?;
+        Ok(Terms {
+            symbol: chain.symbol.clone(),
+            price: chain.underlying_price,
+            expiration,
+            risk_free_rate: chain
+                .risk_free_rate
+                .or(risk_free_rate)
+                .unwrap_or(Decimal::ZERO),
+            dividend_yield: chain
+                .dividend_yield
+                .or(dividend_yield)
+                .unwrap_or(Positive::ZERO),
+            volatility: chain.get_atm_implied_volatility().ok().copied(),
+            quantity: config.quantity,
+            ratio: config.ratio,
+            open_fee: config.open_fee,
+            close_fee: config.close_fee,
+        })
+    }
+
+    /// Distinct strikes with unequal gaps, from the underlying price up. Templates are
+    /// built on these rather than zero strikes, which break the break-even computation of
+    /// several strategies.
+    fn strikes(&self) -> [Positive; 4] {
+        let price = self.price;
+        [price, price + 1.0, price + 3.0, price + 6.0]
+    }
+}
+
+/// Runs the multi-objective optimizer of `kind` on `chain`.
+///
+/// # Errors
+///
+/// Returns `StrategyError` if the template of `kind` cannot be built or the optimizer
+/// configuration is invalid.
+pub(super) fn screen_chain(
+    kind: &StrategyType,
+    chain: &OptionChain,
+    terms: &Terms,
+    config: &OptimizerConfig,
+) -> Result<Vec<(ScreenedStrategy, CandidateMetrics)>, StrategyError> {
+    let zero_volatility = Positive::ZERO;
+    // Short legs without a premium fail to build
+    let premium = Positive::ONE;
+    let strikes = terms.strikes();
+    let symbol = terms.symbol.clone();
+    let (price, expiration) = (terms.price, terms.expiration);
+    let (risk_free_rate, dividend_yield) = (terms.risk_free_rate, terms.dividend_yield);
+    let (quantity, ratio) = (terms.quantity, terms.ratio);
+    let (open_fee, close_fee) = (terms.open_fee, terms.close_fee);
+    match kind {
+        StrategyType::LongCall => ranked(
+            kind,
+            LongCall::new(
+                symbol,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                quantity,
+                price,
+                risk_free_rate,
+                dividend_yield,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::LongPut => ranked(
+            kind,
+            LongPut::new(
+                symbol,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                quantity,
+                price,
+                risk_free_rate,
+                dividend_yield,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ShortCall => ranked(
+            kind,
+            ShortCall::new(
+                symbol,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                quantity,
+                price,
+                risk_free_rate,
+                dividend_yield,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ShortPut => ranked(
+            kind,
+            ShortPut::new(
+                symbol,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                quantity,
+                price,
+                risk_free_rate,
+                dividend_yield,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::BullCallSpread => ranked(
+            kind,
+            BullCallSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::BullPutSpread => ranked(
+            kind,
+            BullPutSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::BearCallSpread => ranked(
+            kind,
+            BearCallSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::BearPutSpread => ranked(
+            kind,
+            BearPutSpread::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::Collar => ranked(
+            kind,
+            Collar::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ProtectivePut => ranked(
+            kind,
+            ProtectivePut::new(
+                symbol,
+                price,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::CoveredCall => ranked(
+            kind,
+            CoveredCall::new(
+                symbol,
+                price,
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::CallButterfly => ranked(
+            kind,
+            CallButterfly::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::LongStraddle => ranked(
+            kind,
+            LongStraddle::new(
+                symbol,
+                price,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ShortStraddle => ranked(
+            kind,
+            ShortStraddle::new(
+                symbol,
+                price,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::LongStrangle => ranked(
+            kind,
+            LongStrangle::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ShortStrangle => ranked(
+            kind,
+            ShortStrangle::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::LongButterflySpread => ranked(
+            kind,
+            LongButterflySpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::ShortButterflySpread => ranked(
+            kind,
+            ShortButterflySpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::IronCondor => ranked(
+            kind,
+            IronCondor::new(
+                symbol,
+                price,
+                strikes[2],
+                strikes[1],
+                strikes[3],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::IronButterfly => ranked(
+            kind,
+            IronButterfly::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[2],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            ),
+            chain,
+            config,
+        ),
+        StrategyType::RiskReversal => ranked(
+            kind,
+            RiskReversal::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::Strap => ranked(
+            kind,
+            Strap::new(
+                symbol,
+                price,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::Strip => ranked(
+            kind,
+            Strip::new(
+                symbol,
+                price,
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::CallBackSpread => ranked(
+            kind,
+            CallBackSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                ratio,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::PutBackSpread => ranked(
+            kind,
+            PutBackSpread::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                ratio,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::CallRatioSpread => ranked(
+            kind,
+            CallRatioSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                ratio,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::PutRatioSpread => ranked(
+            kind,
+            PutRatioSpread::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                ratio,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::JadeLizard => ranked(
+            kind,
+            JadeLizard::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::CallBrokenWingButterfly => ranked(
+            kind,
+            CallBrokenWingButterfly::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::PutBrokenWingButterfly => ranked(
+            kind,
+            PutBrokenWingButterfly::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::CallCondor => ranked(
+            kind,
+            CallCondor::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                strikes[3],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::PutCondor => ranked(
+            kind,
+            PutCondor::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                strikes[3],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::ReverseIronCondor => ranked(
+            kind,
+            ReverseIronCondor::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                strikes[2],
+                strikes[3],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        StrategyType::BoxSpread => ranked(
+            kind,
+            BoxSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                expiration,
+                zero_volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+            )?,
+            chain,
+            config,
+        ),
+        // Legs on different expirations are searched by `screen_series`
+        StrategyType::CallCalendarSpread
+        | StrategyType::PutCalendarSpread
+        | StrategyType::DiagonalCallSpread
+        | StrategyType::DiagonalPutSpread
+        | StrategyType::PoorMansCoveredCall => Ok(Vec::new()),
+        // A custom strategy has no fixed legs to search for
+        StrategyType::Custom => Ok(Vec::new()),
+    }
+}
+
+/// Runs the series optimizer of `kind`, whose legs expire on different dates, on `series`.
+/// These optimizers keep the strategy with the best profit ratio, so at most one candidate
+/// is returned.
+///
+/// # Errors
+///
+/// Returns `StrategyError::OperationError` if the series has fewer than two expirations.
+pub(super) fn screen_series(
+    kind: &StrategyType,
+    series: &OptionSeries,
+    terms: &Terms,
+    config: &OptimizerConfig,
+) -> Result<Vec<(ScreenedStrategy, CandidateMetrics)>, StrategyError> {
+    let (Some(front), Some(back)) = (series.chains.keys().next(), series.chains.keys().last())
+    else {
+        return Ok(Vec::new());
+    };
+    if front == back {
+        return Err(StrategyError::invalid_parameters(
+            "screen",
+            "time spreads need at least two expirations",
+        ));
+    }
+    let Some(volatility) = terms.volatility else {
+        debug!("Skipping {:?}: no at-the-money implied volatility", kind);
+        return Ok(Vec::new());
+    };
+    let premium = Positive::ONE;
+    let strikes = terms.strikes();
+    let (symbol, price) = (terms.symbol.clone(), terms.price);
+    let (front, back) = (*front, *back);
+    let (risk_free_rate, dividend_yield) = (terms.risk_free_rate, terms.dividend_yield);
+    let quantity = terms.quantity;
+    let (open_fee, close_fee) = (terms.open_fee, terms.close_fee);
+    let candidate = match kind {
+        StrategyType::CallCalendarSpread => best_in_series(
+            kind,
+            CallCalendarSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                front,
+                back,
+                volatility,
+                volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            series,
+            config,
+        ),
+        StrategyType::PutCalendarSpread => best_in_series(
+            kind,
+            PutCalendarSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                front,
+                back,
+                volatility,
+                volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            series,
+            config,
+        ),
+        StrategyType::DiagonalCallSpread => best_in_series(
+            kind,
+            DiagonalCallSpread::new(
+                symbol,
+                price,
+                strikes[1],
+                strikes[0],
+                front,
+                back,
+                volatility,
+                volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            series,
+            config,
+        ),
+        StrategyType::DiagonalPutSpread => best_in_series(
+            kind,
+            DiagonalPutSpread::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                front,
+                back,
+                volatility,
+                volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            series,
+            config,
+        ),
+        StrategyType::PoorMansCoveredCall => best_in_series(
+            kind,
+            PoorMansCoveredCall::new(
+                symbol,
+                price,
+                strikes[0],
+                strikes[1],
+                back,
+                front,
+                volatility,
+                risk_free_rate,
+                dividend_yield,
+                quantity,
+                premium,
+                premium,
+                open_fee,
+                close_fee,
+                open_fee,
+                close_fee,
+            ),
+            series,
+            config,
+        ),
+        // Legs on a single expiration are searched by `screen_chain`
+        StrategyType::BullCallSpread
+        | StrategyType::BearCallSpread
+        | StrategyType::BullPutSpread
+        | StrategyType::BearPutSpread
+        | StrategyType::LongButterflySpread
+        | StrategyType::ShortButterflySpread
+        | StrategyType::IronCondor
+        | StrategyType::IronButterfly
+        | StrategyType::LongStraddle
+        | StrategyType::ShortStraddle
+        | StrategyType::LongStrangle
+        | StrategyType::ShortStrangle
+        | StrategyType::CoveredCall
+        | StrategyType::ProtectivePut
+        | StrategyType::Collar
+        | StrategyType::LongCall
+        | StrategyType::LongPut
+        | StrategyType::ShortCall
+        | StrategyType::ShortPut
+        | StrategyType::CallButterfly
+        | StrategyType::CallRatioSpread
+        | StrategyType::PutRatioSpread
+        | StrategyType::CallBackSpread
+        | StrategyType::PutBackSpread
+        | StrategyType::CallBrokenWingButterfly
+        | StrategyType::PutBrokenWingButterfly
+        | StrategyType::CallCondor
+        | StrategyType::PutCondor
+        | StrategyType::JadeLizard
+        | StrategyType::ReverseIronCondor
+        | StrategyType::RiskReversal
+        | StrategyType::Strip
+        | StrategyType::Strap
+        | StrategyType::BoxSpread
+        | StrategyType::Custom => None,
+    };
+    Ok(candidate.into_iter().collect())
+}
+
+/// Candidates of the multi-objective optimizer, with their analysis.
+fn ranked<T>(
+    kind: &StrategyType,
+    template: T,
+    chain: &OptionChain,
+    config: &OptimizerConfig,
+) -> Result<Vec<(ScreenedStrategy, CandidateMetrics)>, StrategyError>
+where
+    T: ParetoOptimizable,
+    T::Strategy: ProbabilityAnalysis + Greeks,
+{
+    Ok(template
+        .pareto_optimal(chain, config)?
+        .into_iter()
+        .filter_map(|candidate| Some((screened(kind, &candidate.strategy)?, candidate.metrics)))
+        .collect())
+}
+
+/// The best spread found by the series optimizer, if it meets the constraints.
+fn best_in_series<T>(
+    kind: &StrategyType,
+    mut template: T,
+    series: &OptionSeries,
+    config: &OptimizerConfig,
+) -> Option<(ScreenedStrategy, CandidateMetrics)>
+where
+    T: Optimizable + ProbabilityAnalysis + Greeks,
+{
+    let placeholder: Vec<Position> = template
+        .get_positions()
+        .ok()?
+        .into_iter()
+        .cloned()
+        .collect();
+    template.find_optimal_in_series_liquid(
+        series,
+        config.side,
+        OptimizationCriteria::Ratio,
+        &config.liquidity,
+    );
+    // The optimizer leaves the template untouched when no spread is valid
+    if template
+        .get_positions()
+        .ok()?
+        .into_iter()
+        .eq(placeholder.iter())
+    {
+        return None;
+    }
+    let metrics = admit(&template, config, |position| {
+        series
+            .chains
+            .get(&position.option.expiration_date)?
+            .options
+            .iter()
+            .find(|option| option.strike_price == position.option.strike_price)
+    })?;
+    Some((screened(kind, &template)?, metrics))
+}
+
+/// Greeks and probability analysis of `strategy`, or `None` if either fails.
+fn screened<S>(kind: &StrategyType, strategy: &S) -> Option<ScreenedStrategy>
+where
+    S: ProbabilityAnalysis + Greeks,
+{
+    let analysis = strategy
+        .greeks()
+        .map_err(|e| e.to_string())
+        .and_then(|greeks| {
+            let probabilities = strategy
+                .analyze_probabilities(None, None)
+                .map_err(|e| e.to_string())?;
+            Ok((greeks, probabilities))
+        });
+    let (greeks, probabilities) = match analysis {
+        Ok(analysis) => analysis,
+        Err(e) => {
+            debug!("Skipping {:?} without analysis: {}", kind, e);
+            return None;
+        }
+    };
+    Some(ScreenedStrategy {
+        strategy_type: kind.clone(),
+        positions: strategy
+            .get_positions()
+            .ok()?
+            .into_iter()
+            .cloned()
+            .collect(),
+        greeks,
+        probabilities,
+    })
+}
@@ -0,0 +1,91 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! # Strategy Screener
+//!
+//! Finding a trade with `Optimizable` means picking a strategy type first and then
+//! searching its strikes. The [`Screener`] works from a [`MarketView`] instead: it selects
+//! every strategy type suited to the view, runs the multi-objective optimizer of each one
+//! over an `OptionChain` or every expiration of an `OptionSeries`, and ranks all the
+//! candidates together.
+//!
+//! | View | Strategy types |
+//! |------|----------------|
+//! | Bullish | Long call, bull spreads, risk reversal, collar, protective put, call back spread, strap |
+//! | Bearish | Long put, bear spreads, put back spread, strip |
+//! | Neutral | Straddles, strangles, butterflies, condors, iron structures, calendars |
+//!
+//! Neutral to bullish types such as the short put, covered call, poor man's covered call,
+//! jade lizard or call ratio spread are screened for both views, as are neutral to bearish
+//! ones such as the short call. Calendars, diagonals and the poor man's covered call are
+//! only screened on a series with several expirations. Box spreads fit no view and custom
+//! strategies have no fixed legs to search for, so neither is ever screened. A volatility outlook further keeps only the types that are
+//! long vega when volatility should rise and short vega when it should fall.
+//!
+//! The [`ScreenerReport`] serializes to JSON and holds, for each candidate, its positions,
+//! P&L metrics, Greeks and probability analysis.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use optionstratlib::chains::chain::OptionChain;
+//! use optionstratlib::chains::utils::{OptionChainBuildParams, OptionDataPriceParams};
+//! use optionstratlib::strategies::FindOptimalSide;
+//! use optionstratlib::strategies::optimizer::{Constraint, Objective, WeightedObjective};
+//! use optionstratlib::strategies::screener::{
+//!     MarketView, Outlook, Screener, ScreenerConfig, VolatilityOutlook,
+//! };
+//! use optionstratlib::{ExpirationDate, pos, spos};
+//! use rust_decimal_macros::dec;
+//!
+//! let price_params = OptionDataPriceParams::new(
+//!     Some(Box::new(pos!(100.0))),
+//!     Some(ExpirationDate::Days(pos!(30.0))),
+//!     Some(dec!(0.05)),
+//!     spos!(0.0),
+//!     Some("SPY".to_string()),
+//! );
+//! let chain = OptionChain::build_chain(&OptionChainBuildParams::new(
+//!     "SPY".to_string(),
+//!     spos!(1000.0),
+//!     8,
+//!     spos!(5.0),
+//!     dec!(-0.2),
+//!     dec!(0.1),
+//!     pos!(0.02),
+//!     2,
+//!     price_params,
+//!     pos!(0.2),
+//! ));
+//!
+//! let config = ScreenerConfig::new(
+//!     FindOptimalSide::Range(pos!(85.0), pos!(115.0)),
+//!     vec![
+//!         WeightedObjective::new(Objective::ProbabilityOfProfit, pos!(1.0)),
+//!         WeightedObjective::new(Objective::ExpectedValue, pos!(1.0)),
+//!     ],
+//!     vec![Constraint::MaxLoss(pos!(10.0))],
+//!     10,
+//! );
+//! let screener = Screener::new(
+//!     MarketView::new(Outlook::Bearish, Some(VolatilityOutlook::Rising)),
+//!     config,
+//! );
+//!
+//! let report = screener.scan_chain(&chain).unwrap();
+//! assert!(!report.entries.is_empty() && report.entries.len() <= 10);
+//! let json = serde_json::to_string(&report).unwrap();
+//! assert!(json.contains("probability_of_profit"));
+//! ```
+
+mod catalog;
+mod model;
+mod scan;
+
+pub use model::{
+    MarketView, Outlook, ScreenedStrategy, ScreenerConfig, ScreenerReport, VolatilityOutlook,
+};
+pub use scan::Screener;
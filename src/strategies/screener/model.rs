@@ -0,0 +1,155 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::greeks::Greek;
+use crate::model::Position;
+use crate::risk::MarginMethod;
+use crate::strategies::base::StrategyType;
+use crate::strategies::liquidity::Liquidity;
+use crate::strategies::optimizer::{
+    Constraint, OptimizerConfig, RankedCandidate, WeightedObjective,
+};
+use crate::strategies::probabilities::StrategyProbabilityAnalysis;
+use crate::strategies::utils::FindOptimalSide;
+use serde::{Deserialize, Serialize};
+
+/// Expected direction of the underlying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outlook {
+    /// The underlying is expected to rise.
+    Bullish,
+    /// The underlying is expected to fall.
+    Bearish,
+    /// The underlying is expected to stay in a range.
+    Neutral,
+}
+
+/// Expected move of implied volatility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VolatilityOutlook {
+    /// Implied volatility is expected to rise, favouring long vega.
+    Rising,
+    /// Implied volatility is expected to fall, favouring short vega.
+    Falling,
+}
+
+/// The market view that decides which strategy types are screened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketView {
+    /// Direction of the underlying.
+    pub outlook: Outlook,
+    /// Move of implied volatility, `None` to accept any vega exposure.
+    pub volatility: Option<VolatilityOutlook>,
+}
+
+impl MarketView {
+    /// Creates a market view.
+    pub fn new(outlook: Outlook, volatility: Option<VolatilityOutlook>) -> Self {
+        MarketView {
+            outlook,
+            volatility,
+        }
+    }
+}
+
+/// Settings of a screen.
+///
+/// The objectives and constraints are those of the multi-objective optimizer. Each
+/// strategy type contributes at most `candidates_per_strategy` candidates, which are then
+/// ranked together and cut to `max_entries`.
+#[derive(Debug, Clone)]
+pub struct ScreenerConfig {
+    /// Strikes the combinations are taken from.
+    pub side: FindOptimalSide,
+    /// Objectives combined into the score of each candidate.
+    pub objectives: Vec<WeightedObjective>,
+    /// Requirements a candidate must meet to be reported.
+    pub constraints: Vec<Constraint>,
+    /// Candidates kept from each strategy type before the global ranking.
+    pub candidates_per_strategy: usize,
+    /// Largest number of entries in the report.
+    pub max_entries: usize,
+    /// Number of contracts of the base legs.
+    pub quantity: Positive,
+    /// Ratio of the ratio and back spreads.
+    pub ratio: Positive,
+    /// Fee paid per contract when opening each leg.
+    pub open_fee: Positive,
+    /// Fee paid per contract when closing each leg.
+    pub close_fee: Positive,
+    /// Margin method used by `Objective::ReturnOnMargin`.
+    ///
+    /// [`Objective::ReturnOnMargin`]: crate::strategies::optimizer::Objective::ReturnOnMargin
+    pub margin: MarginMethod,
+    /// Liquidity requirements and fill model applied to every chain before the search.
+    pub liquidity: Liquidity,
+}
+
+impl ScreenerConfig {
+    /// Creates a configuration keeping five candidates per strategy type, trading one
+    /// contract per leg without fees, using 1x2 ratio spreads and filling at the touch.
+    pub fn new(
+        side: FindOptimalSide,
+        objectives: Vec<WeightedObjective>,
+        constraints: Vec<Constraint>,
+        max_entries: usize,
+    ) -> Self {
+        ScreenerConfig {
+            side,
+            objectives,
+            constraints,
+            candidates_per_strategy: 5,
+            max_entries,
+            quantity: Positive::ONE,
+            ratio: Positive::TWO,
+            open_fee: Positive::ZERO,
+            close_fee: Positive::ZERO,
+            margin: MarginMethod::default(),
+            liquidity: Liquidity::default(),
+        }
+    }
+
+    /// Optimizer settings keeping `max_candidates` candidates.
+    pub(super) fn optimizer(&self, max_candidates: usize) -> OptimizerConfig {
+        OptimizerConfig {
+            side: self.side,
+            objectives: self.objectives.clone(),
+            constraints: self.constraints.clone(),
+            max_candidates,
+            margin: self.margin.clone(),
+            liquidity: self.liquidity,
+        }
+    }
+}
+
+/// A screened strategy, with the analysis reported for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenedStrategy {
+    /// Type of the strategy.
+    pub strategy_type: StrategyType,
+    /// Option positions of the strategy.
+    pub positions: Vec<Position>,
+    /// Greeks of the whole strategy.
+    pub greeks: Greek,
+    /// Probabilities, expected value and break-even points at expiration.
+    pub probabilities: StrategyProbabilityAnalysis,
+}
+
+/// Result of a screen, best candidates first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenerReport {
+    /// Symbol of the underlying.
+    pub symbol: String,
+    /// Price of the underlying when screened.
+    pub underlying_price: Positive,
+    /// The market view screened for.
+    pub view: MarketView,
+    /// Strategy types that fit the view and were searched.
+    pub screened: Vec<StrategyType>,
+    /// The ranked candidates, with their P&L metrics in `metrics` and their Greeks and
+    /// probabilities in `strategy`.
+    pub entries: Vec<RankedCandidate<ScreenedStrategy>>,
+}
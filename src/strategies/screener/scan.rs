@@ -0,0 +1,380 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::catalog::{CHAIN_PROFILES, SERIES_PROFILES, Terms, screen_chain, screen_series};
+use super::model::{MarketView, ScreenerConfig, ScreenerReport};
+use crate::chains::chain::OptionChain;
+use crate::error::strategies::StrategyError;
+use crate::series::OptionSeries;
+use crate::strategies::base::StrategyType;
+use crate::strategies::optimizer::rank;
+use tracing::info;
+
+/// Screens option chains for the strategies that best fit a market view.
+///
+/// Every strategy type suited to the view is instantiated from the terms of the chain and
+/// searched with the multi-objective optimizer. The candidates of all types are then
+/// ranked together, so the scores and Pareto fronts of the report compare strategies of
+/// different types.
+#[derive(Debug, Clone)]
+pub struct Screener {
+    /// The market view that selects the strategy types.
+    pub view: MarketView,
+    /// Objectives, constraints and trading terms of the screen.
+    pub config: ScreenerConfig,
+}
+
+impl Screener {
+    /// Creates a screener.
+    pub fn new(view: MarketView, config: ScreenerConfig) -> Self {
+        Screener { view, config }
+    }
+
+    /// Strategy types screened on a single expiration.
+    pub fn chain_strategies(&self) -> Vec<StrategyType> {
+        CHAIN_PROFILES
+            .iter()
+            .filter(|profile| profile.fits(&self.view))
+            .map(|profile| profile.kind.clone())
+            .collect()
+    }
+
+    /// Strategy types screened on a series: those of [`Screener::chain_strategies`],
+    /// searched on every expiration, followed by the suited strategies whose legs expire on
+    /// different dates, such as time spreads.
+    pub fn series_strategies(&self) -> Vec<StrategyType> {
+        let mut strategies = self.chain_strategies();
+        strategies.extend(
+            SERIES_PROFILES
+                .iter()
+                .filter(|profile| profile.fits(&self.view))
+                .map(|profile| profile.kind.clone()),
+        );
+        strategies
+    }
+
+    /// Screens a single expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the configuration is invalid or the
+    /// expiration of the chain cannot be parsed.
+    pub fn scan_chain(&self, option_chain: &OptionChain) -> Result<ScreenerReport, StrategyError> {
+        self.validate()?;
+        let terms = Terms::new(option_chain, None, None, &self.config)?;
+        let optimizer = self.config.optimizer(self.config.candidates_per_strategy);
+        let screened = self.chain_strategies();
+        let mut candidates = Vec::new();
+        for kind in &screened {
+            let found = screen_chain(kind, option_chain, &terms, &optimizer)?;
+            info!("{:?}: {} candidates", kind, found.len());
+            candidates.extend(found);
+        }
+        Ok(ScreenerReport {
+            symbol: option_chain.symbol.clone(),
+            underlying_price: option_chain.underlying_price,
+            view: self.view,
+            screened,
+            entries: rank(candidates, &self.config.optimizer(self.config.max_entries)),
+        })
+    }
+
+    /// Screens every expiration of a series, adding the strategies with legs on different
+    /// expirations that suit the view when the series has more than one expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the configuration is invalid or the
+    /// expiration of a chain cannot be parsed.
+    pub fn scan_series(
+        &self,
+        option_series: &OptionSeries,
+    ) -> Result<ScreenerReport, StrategyError> {
+        self.validate()?;
+        let optimizer = self.config.optimizer(self.config.candidates_per_strategy);
+        let rate = option_series.risk_free_rate;
+        let dividend = option_series.dividend_yield;
+        let chain_strategies = self.chain_strategies();
+        let mut screened = chain_strategies.clone();
+        let mut candidates = Vec::new();
+        let mut front_terms = None;
+        for chain in option_series.chains.values() {
+            let terms = Terms::new(chain, rate, dividend, &self.config)?;
+            for kind in &chain_strategies {
+                let found = screen_chain(kind, chain, &terms, &optimizer)?;
+                info!(
+                    "{:?} {}: {} candidates",
+                    kind,
+                    chain.get_expiration_date(),
+                    found.len()
+                );
+                candidates.extend(found);
+            }
+            front_terms.get_or_insert(terms);
+        }
+        if let Some(terms) = front_terms.filter(|_| option_series.chains.len() > 1) {
+            for profile in SERIES_PROFILES.iter().filter(|p| p.fits(&self.view)) {
+                candidates.extend(screen_series(
+                    &profile.kind,
+                    option_series,
+                    &terms,
+                    &optimizer,
+                )?);
+                screened.push(profile.kind.clone());
+            }
+        }
+        Ok(ScreenerReport {
+            symbol: option_series.symbol.clone(),
+            underlying_price: option_series.underlying_price,
+            view: self.view,
+            screened,
+            entries: rank(candidates, &self.config.optimizer(self.config.max_entries)),
+        })
+    }
+
+    fn validate(&self) -> Result<(), StrategyError> {
+        self.config.optimizer(self.config.max_entries).validate()?;
+        if self.config.candidates_per_strategy == 0 {
+            return Err(StrategyError::invalid_parameters(
+                "screen",
+                "candidates_per_strategy must be positive",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests_screener {
+    use super::*;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::strategies::FindOptimalSide;
+    use crate::strategies::optimizer::{Constraint, Objective, WeightedObjective};
+    use crate::strategies::screener::{Outlook, ScreenedStrategy, VolatilityOutlook};
+    use crate::{ExpirationDate, Options, Positive, pos, spos};
+    use rust_decimal_macros::dec;
+
+    /// Chain quoted 2% around the Black-Scholes price.
+    fn chain(days: f64) -> OptionChain {
+        let expiration = format!("{}", days);
+        let mut chain = OptionChain::new("TEST", pos!(100.0), expiration, None, None);
+        for strike in (85..=115).step_by(5) {
+            let strike = pos!(strike as f64);
+            let price = |style| {
+                let option = Options::new(
+                    OptionType::European,
+                    Side::Long,
+                    "TEST".to_string(),
+                    strike,
+                    ExpirationDate::Days(pos!(days)),
+                    pos!(0.2),
+                    Positive::ONE,
+                    pos!(100.0),
+                    dec!(0.05),
+                    style,
+                    Positive::ZERO,
+                    None,
+                );
+                Positive::from(option.calculate_price_black_scholes().unwrap())
+            };
+            let (call, put) = (price(OptionStyle::Call), price(OptionStyle::Put));
+            chain.add_option(
+                strike,
+                Some(call * 0.98),
+                Some(call * 1.02),
+                Some(put * 0.98),
+                Some(put * 1.02),
+                pos!(0.2),
+                None,
+                None,
+                None,
+                spos!(100.0),
+                Some(50),
+                None,
+            );
+        }
+        chain
+    }
+
+    fn screener(view: MarketView, max_entries: usize) -> Screener {
+        let config = ScreenerConfig::new(
+            FindOptimalSide::All,
+            vec![
+                WeightedObjective::new(Objective::ProbabilityOfProfit, pos!(1.0)),
+                WeightedObjective::new(Objective::ReturnOnMargin, pos!(1.0)),
+            ],
+            vec![Constraint::MaxLoss(pos!(20.0))],
+            max_entries,
+        );
+        Screener::new(view, config)
+    }
+
+    #[test]
+    fn test_strategies_fit_view() {
+        let bullish = screener(MarketView::new(Outlook::Bullish, None), 10);
+        let types = bullish.chain_strategies();
+        assert!(types.contains(&StrategyType::BullCallSpread));
+        assert!(types.contains(&StrategyType::JadeLizard));
+        assert!(types.contains(&StrategyType::LongCall));
+        assert!(types.contains(&StrategyType::ShortPut));
+        assert!(!types.contains(&StrategyType::LongPut));
+        assert!(!types.contains(&StrategyType::BearPutSpread));
+        assert!(!types.contains(&StrategyType::BoxSpread));
+
+        let long_vol = screener(
+            MarketView::new(Outlook::Neutral, Some(VolatilityOutlook::Rising)),
+            10,
+        );
+        let types = long_vol.series_strategies();
+        assert!(types.contains(&StrategyType::LongStraddle));
+        assert!(types.contains(&StrategyType::CallCalendarSpread));
+        assert!(!types.contains(&StrategyType::IronCondor));
+
+        let bearish = screener(MarketView::new(Outlook::Bearish, None), 10);
+        let types = bearish.series_strategies();
+        assert!(types.contains(&StrategyType::LongPut));
+        assert!(types.contains(&StrategyType::ShortCall));
+        assert!(!types.contains(&StrategyType::PoorMansCoveredCall));
+    }
+
+    #[test]
+    fn test_scan_chain() {
+        let screener = screener(MarketView::new(Outlook::Bearish, None), 8);
+        let report = screener.scan_chain(&chain(30.0)).unwrap();
+
+        assert_eq!(report.screened, screener.chain_strategies());
+        assert!(!report.entries.is_empty() && report.entries.len() <= 8);
+        for pair in report.entries.windows(2) {
+            assert!((pair[0].pareto_rank, -pair[0].score) <= (pair[1].pareto_rank, -pair[1].score));
+        }
+        for entry in &report.entries {
+            assert!(report.screened.contains(&entry.strategy.strategy_type));
+            assert!(entry.metrics.max_loss.unwrap() <= pos!(20.0));
+            assert_eq!(
+                entry.strategy.probabilities.probability_of_profit,
+                entry.metrics.probability_of_profit
+            );
+        }
+
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: ScreenerReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.entries.len(), report.entries.len());
+        let first: &ScreenedStrategy = &parsed.entries[0].strategy;
+        assert_eq!(first.positions, report.entries[0].strategy.positions);
+    }
+
+    fn series() -> OptionSeries {
+        let mut series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        for days in [30.0, 60.0] {
+            series
+                .chains
+                .insert(ExpirationDate::Days(pos!(days)), chain(days));
+        }
+        series
+    }
+
+    #[test]
+    fn test_scan_series_adds_time_spreads() {
+        let series = series();
+        let screener = screener(
+            MarketView::new(Outlook::Neutral, Some(VolatilityOutlook::Rising)),
+            100,
+        );
+        let report = screener.scan_series(&series).unwrap();
+
+        assert_eq!(report.screened, screener.series_strategies());
+        // Time spreads score poorly on these objectives, so keep every candidate
+        for kind in [
+            StrategyType::CallCalendarSpread,
+            StrategyType::PutCalendarSpread,
+            StrategyType::DiagonalCallSpread,
+            StrategyType::DiagonalPutSpread,
+        ] {
+            assert!(
+                report
+                    .entries
+                    .iter()
+                    .any(|e| e.strategy.strategy_type == kind),
+                "{:?}",
+                kind
+            );
+        }
+        // Both expirations are screened
+        let expirations: Vec<_> = report
+            .entries
+            .iter()
+            .map(|e| e.strategy.positions[0].option.expiration_date)
+            .collect();
+        assert!(expirations.contains(&ExpirationDate::Days(pos!(60.0))));
+    }
+
+    #[test]
+    fn test_scan_single_legs_and_poor_mans_covered_call() {
+        let series = series();
+        let mut screener = screener(MarketView::new(Outlook::Bullish, None), 200);
+        // Short legs have no bounded loss
+        screener.config.constraints.clear();
+        let report = screener.scan_series(&series).unwrap();
+
+        for kind in [
+            StrategyType::LongCall,
+            StrategyType::ShortPut,
+            StrategyType::PoorMansCoveredCall,
+        ] {
+            assert!(report.screened.contains(&kind), "{:?}", kind);
+            assert!(
+                report
+                    .entries
+                    .iter()
+                    .any(|e| e.strategy.strategy_type == kind),
+                "{:?}",
+                kind
+            );
+        }
+        let pmcc = report
+            .entries
+            .iter()
+            .find(|e| e.strategy.strategy_type == StrategyType::PoorMansCoveredCall)
+            .unwrap();
+        let [short, long] = &pmcc.strategy.positions[..] else {
+            panic!("Poor man's covered call has two legs");
+        };
+        assert!(long.option.strike_price < short.option.strike_price);
+        assert!(long.option.expiration_date > short.option.expiration_date);
+
+        screener.view = MarketView::new(Outlook::Bearish, None);
+        let report = screener.scan_chain(&chain(30.0)).unwrap();
+        for kind in [StrategyType::LongPut, StrategyType::ShortCall] {
+            assert!(
+                report
+                    .entries
+                    .iter()
+                    .any(|e| e.strategy.strategy_type == kind),
+                "{:?}",
+                kind
+            );
+        }
+    }
+
+    #[test]
+    fn test_scan_every_view() {
+        let series = series();
+        for outlook in [Outlook::Bullish, Outlook::Bearish, Outlook::Neutral] {
+            let screener = screener(MarketView::new(outlook, None), 20);
+            let report = screener.scan_series(&series).unwrap();
+            assert!(!report.entries.is_empty(), "{:?}", outlook);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let mut screener = screener(MarketView::new(Outlook::Bullish, None), 10);
+        screener.config.candidates_per_strategy = 0;
+        assert!(screener.scan_chain(&chain(30.0)).is_err());
+        screener.config.candidates_per_strategy = 5;
+        screener.config.objectives.clear();
+        assert!(screener.scan_chain(&chain(30.0)).is_err());
+    }
+}
@@ -1,9 +1,19 @@
-use super::base::{BreakEvenable, Positionable, StrategyType};
-use crate::error::StrategyError;
+use super::base::{
+    BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, StrategyType,
+};
+use crate::chains::utils::OptionDataGroup;
+use crate::chains::{StrategyLegs, chain::OptionChain};
 use crate::error::strategies::ProfitLossErrorKind;
+use crate::error::{GreeksError, OperationErrorKind, ProbabilityError, StrategyError};
+use crate::greeks::Greeks;
+use crate::model::ProfitLossRange;
 use crate::model::types::OptionBasicType;
+use crate::pnl::{PnLCalculator, utils::PnL};
 use crate::pricing::Profit;
-use crate::strategies::{BasicAble, Strategies, Validable};
+use crate::strategies::delta_neutral::DeltaNeutrality;
+use crate::strategies::probabilities::{core::ProbabilityAnalysis, utils::VolatilityAdjustment};
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::strategies::{BasicAble, Strategies, StrategyConstructor, Validable};
 use crate::{
     ExpirationDate, Options, Positive,
     error::position::{PositionError, PositionValidationErrorKind},
@@ -87,8 +97,8 @@ impl ShortPut {
     /// This function will panic if adding the short put position to the strategy fails,
     /// which may happen if the position is deemed invalid.
     ///
-    #[allow(clippy::too_many_arguments, dead_code)]
-    fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
         underlying_symbol: String,
         short_put_strike: Positive,
         short_put_expiration: ExpirationDate,
@@ -129,6 +139,9 @@ impl ShortPut {
         strategy
             .add_position(&short_put.clone())
             .expect("Invalid short put option");
+        strategy
+            .update_break_even_points()
+            .expect("Unable to update break even points");
 
         strategy
     }
@@ -269,7 +282,8 @@ impl Strategies for ShortPut {
         }
     }
     fn get_max_loss(&self) -> Result<Positive, StrategyError> {
-        let loss = self.calculate_profit_at(&self.short_put.option.strike_price)?;
+        // The put costs the most when the underlying goes to zero
+        let loss = self.calculate_profit_at(&Positive::ZERO)?;
         if loss <= Decimal::ZERO {
             Ok(loss.abs().into())
         } else {
@@ -296,6 +310,96 @@ impl Strategies for ShortPut {
     }
 }
 
+impl Optimizable for ShortPut {
+    type Strategy = ShortPut;
+
+    fn filter_combinations<'a>(
+        &'a self,
+        option_chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+        let underlying_price = self.get_underlying_price();
+        let strategy = self.clone();
+        option_chain
+            .get_single_iter()
+            // Puts are sold out of the money by default
+            .filter(move |option| {
+                if side == FindOptimalSide::Center {
+                    option.is_valid_optimal_side(underlying_price, &FindOptimalSide::Lower)
+                } else {
+                    option.is_valid_optimal_side(underlying_price, &side)
+                }
+            })
+            .filter(|option| option.put_bid.unwrap_or(Positive::ZERO) > Positive::ZERO)
+            .filter(move |option| {
+                let legs = StrategyLegs::OneLeg { first: option };
+                strategy.create_strategy(option_chain, &legs).validate()
+            })
+            .map(OptionDataGroup::One)
+    }
+
+    fn find_optimal(
+        &mut self,
+        option_chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: OptimizationCriteria,
+    ) {
+        let mut best_value = Decimal::MIN;
+        let strategy_clone = self.clone();
+        let options_iter = strategy_clone.filter_combinations(option_chain, side);
+
+        for option_data_group in options_iter {
+            let option = match option_data_group {
+                OptionDataGroup::One(first) => first,
+                _ => panic!("Invalid OptionDataGroup"),
+            };
+
+            let legs = StrategyLegs::OneLeg { first: option };
+            let strategy = self.create_strategy(option_chain, &legs);
+            let current_value = match criteria {
+                OptimizationCriteria::Ratio => strategy.get_profit_ratio(),
+                OptimizationCriteria::Area => strategy.get_profit_area(),
+            };
+
+            if let Ok(current_value) = current_value
+                && current_value > best_value
+            {
+                best_value = current_value;
+                *self = strategy;
+            }
+        }
+    }
+
+    fn are_valid_legs(&self, legs: &StrategyLegs) -> bool {
+        match legs {
+            StrategyLegs::OneLeg { first } => {
+                first.put_bid.unwrap_or(Positive::ZERO) > Positive::ZERO
+            }
+            _ => false,
+        }
+    }
+
+    fn create_strategy(&self, chain: &OptionChain, legs: &StrategyLegs) -> Self::Strategy {
+        let option = match legs {
+            StrategyLegs::OneLeg { first } => first,
+            _ => panic!("Invalid number of legs for this strategy"),
+        };
+        ShortPut::new(
+            chain.symbol.clone(),
+            option.strike_price,
+            self.short_put.option.expiration_date,
+            option.implied_volatility,
+            self.short_put.option.quantity,
+            chain.underlying_price,
+            self.short_put.option.risk_free_rate,
+            self.short_put.option.dividend_yield,
+            option.put_bid.unwrap(),
+            self.short_put.open_fee,
+            self.short_put.close_fee,
+        )
+    }
+}
+
 impl Profit for ShortPut {
     fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
         let price = Some(price);
@@ -389,4 +493,108 @@ impl Positionable for ShortPut {
     }
 }
 
+impl StrategyConstructor for ShortPut {
+    fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+        if vec_positions.len() != 1 {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Short Put get_strategy".to_string(),
+                    reason: "Must have exactly 1 option".to_string(),
+                },
+            ));
+        }
+        let position = &vec_positions[0];
+        if position.option.option_style != OptionStyle::Put || position.option.side != Side::Short {
+            return Err(StrategyError::OperationError(
+                OperationErrorKind::InvalidParameters {
+                    operation: "Short Put get_strategy".to_string(),
+                    reason: "Short Put requires a short put option".to_string(),
+                },
+            ));
+        }
+
+        let mut strategy = ShortPut::default();
+        strategy.add_position(position)?;
+        strategy.update_break_even_points()?;
+        Ok(strategy)
+    }
+}
+
+impl Strategable for ShortPut {
+    fn info(&self) -> Result<StrategyBasics, StrategyError> {
+        Ok(StrategyBasics {
+            name: self.name.clone(),
+            kind: self.kind.clone(),
+            description: self.description.clone(),
+        })
+    }
+}
+
+impl ProbabilityAnalysis for ShortPut {
+    fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.short_put.option;
+
+        let mut profit_range = ProfitLossRange::new(Some(break_even), None, Positive::ZERO)?;
+        profit_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![profit_range])
+    }
+
+    fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+        let break_even = self.get_break_even_points()?[0];
+        let option = &self.short_put.option;
+
+        let mut loss_range = ProfitLossRange::new(None, Some(break_even), Positive::ZERO)?;
+        loss_range.calculate_probability(
+            self.get_underlying_price(),
+            Some(VolatilityAdjustment {
+                base_volatility: option.implied_volatility,
+                std_dev_adjustment: Positive::ZERO,
+            }),
+            None,
+            &option.expiration_date,
+            Some(option.risk_free_rate),
+        )?;
+
+        Ok(vec![loss_range])
+    }
+}
+
+impl Greeks for ShortPut {
+    fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+        Ok(vec![&self.short_put.option])
+    }
+}
+
+impl DeltaNeutrality for ShortPut {}
+
+impl PnLCalculator for ShortPut {
+    fn calculate_pnl(
+        &self,
+        market_price: &Positive,
+        expiration_date: ExpirationDate,
+        implied_volatility: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.short_put
+            .calculate_pnl(market_price, expiration_date, implied_volatility)
+    }
+
+    fn calculate_pnl_at_expiration(
+        &self,
+        underlying_price: &Positive,
+    ) -> Result<PnL, Box<dyn Error>> {
+        self.short_put.calculate_pnl_at_expiration(underlying_price)
+    }
+}
+
 // test_strategy_traits!(ShortPut, test_short_put_implementations);
@@ -0,0 +1,95 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const STRAP_DESCRIPTION: &str = "A Strap is a long straddle with two calls for every put. It profits from a large move in \
+    either direction and more from a rise, and loses at most the premium paid.";
+
+static STRAP: StructureSpec = StructureSpec {
+    kind: StrategyType::Strap,
+    name: "Strap",
+    description: STRAP_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Long, true),
+        leg(0, OptionStyle::Put, Side::Long, false),
+    ],
+    ratio: QuantityRatio::Fixed(Positive::TWO),
+    strikes: distinct_strikes,
+};
+
+/// Long calls and long puts at the same strike and expiration, with two calls for every put.
+///
+/// A bullish variant of the long straddle. The loss is bounded by the premium paid.
+///
+/// Legs: `quantity` long puts and `2 * quantity` long calls.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Strap {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl Strap {
+    /// Creates a Strap.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `strike` - Strike of every leg.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of long puts.
+    /// * `premium_call` - The premium paid for each call.
+    /// * `premium_put` - The premium paid for each put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the legs cannot be built.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_call: Positive,
+        premium_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = STRAP.build(
+            underlying_symbol,
+            underlying_price,
+            &[strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::TWO,
+            &[premium_call, premium_put],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(Strap { strategy })
+    }
+}
+
+impl_structure!(Strap, STRAP);
@@ -0,0 +1,95 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use super::structures::{QuantityRatio, StructureSpec, distinct_strikes, impl_structure, leg};
+use crate::error::strategies::StrategyError;
+use crate::model::types::{OptionStyle, Side};
+use crate::{ExpirationDate, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+const STRIP_DESCRIPTION: &str = "A Strip is a long straddle with two puts for every call. It profits from a large move in \
+    either direction and more from a fall, and loses at most the premium paid.";
+
+static STRIP: StructureSpec = StructureSpec {
+    kind: StrategyType::Strip,
+    name: "Strip",
+    description: STRIP_DESCRIPTION,
+    legs: &[
+        leg(0, OptionStyle::Call, Side::Long, false),
+        leg(0, OptionStyle::Put, Side::Long, true),
+    ],
+    ratio: QuantityRatio::Fixed(Positive::TWO),
+    strikes: distinct_strikes,
+};
+
+/// Long calls and long puts at the same strike and expiration, with two puts for every call.
+///
+/// A bearish variant of the long straddle. The loss is bounded by the premium paid.
+///
+/// Legs: `quantity` long calls and `2 * quantity` long puts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Strip {
+    pub(super) strategy: CustomStrategy,
+}
+
+impl Strip {
+    /// Creates a Strip.
+    ///
+    /// # Arguments
+    ///
+    /// * `underlying_symbol` - The ticker symbol of the underlying asset.
+    /// * `underlying_price` - The current market price of the underlying asset.
+    /// * `strike` - Strike of every leg.
+    /// * `expiration` - The expiration date of all legs.
+    /// * `implied_volatility` - The implied volatility used for pricing.
+    /// * `risk_free_rate` - The risk-free interest rate.
+    /// * `dividend_yield` - The dividend yield of the underlying asset.
+    /// * `quantity` - The number of long calls.
+    /// * `premium_call` - The premium paid for each call.
+    /// * `premium_put` - The premium paid for each put.
+    /// * `open_fee` - The fee paid per contract when opening each leg.
+    /// * `close_fee` - The fee paid per contract when closing each leg.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the legs cannot be built.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strike: Positive,
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        premium_call: Positive,
+        premium_put: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<Self, StrategyError> {
+        let strategy = STRIP.build(
+            underlying_symbol,
+            underlying_price,
+            &[strike],
+            expiration,
+            implied_volatility,
+            risk_free_rate,
+            dividend_yield,
+            quantity,
+            Positive::TWO,
+            &[premium_call, premium_put],
+            open_fee,
+            close_fee,
+        )?;
+        Ok(Strip { strategy })
+    }
+}
+
+impl_structure!(Strip, STRIP);
@@ -0,0 +1,1484 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! Construction, validation and chain search shared by the named structures built on
+//! [`CustomStrategy`]: ratio and back spreads, broken-wing butterflies, condors, the
+//! jade lizard, the reverse iron condor, the risk reversal, strips, straps and boxes.
+//!
+//! Each structure is described by a [`StructureSpec`] listing its legs and the rules
+//! its strikes and quantities follow. The spec turns strikes and premiums, a set of
+//! positions or a combination of chain quotes into the legs of a custom strategy,
+//! whose numeric payoff analysis the structure then delegates to through
+//! [`impl_structure`].
+
+use super::base::StrategyType;
+use super::custom::CustomStrategy;
+use crate::chains::chain::OptionChain;
+use crate::chains::utils::OptionDataGroup;
+use crate::chains::{OptionData, StrategyLegs};
+use crate::error::OperationErrorKind;
+use crate::error::strategies::StrategyError;
+use crate::model::position::Position;
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::strategies::Strategies;
+use crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+use crate::{ExpirationDate, Options, Positive};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use tracing::info;
+
+/// One leg of a named structure.
+pub(super) struct LegSpec {
+    /// Position of the leg's strike among the distinct strikes of the structure, in
+    /// ascending order.
+    pub strike: usize,
+    pub style: OptionStyle,
+    pub side: Side,
+    /// Whether the leg trades the base quantity times the structure's ratio.
+    pub scaled: bool,
+}
+
+/// Shorthand for the leg tables of the structures.
+pub(super) const fn leg(strike: usize, style: OptionStyle, side: Side, scaled: bool) -> LegSpec {
+    LegSpec {
+        strike,
+        style,
+        side,
+        scaled,
+    }
+}
+
+impl LegSpec {
+    fn quantity(&self, quantity: Positive, ratio: Positive) -> Positive {
+        if self.scaled {
+            quantity * ratio
+        } else {
+            quantity
+        }
+    }
+
+    /// Quote at which the leg trades: the ask when buying and the bid when selling.
+    fn quote(&self, data: &OptionData) -> Option<Positive> {
+        match (self.style, self.side) {
+            (OptionStyle::Call, Side::Long) => data.call_ask,
+            (OptionStyle::Call, Side::Short) => data.call_bid,
+            (OptionStyle::Put, Side::Long) => data.put_ask,
+            (OptionStyle::Put, Side::Short) => data.put_bid,
+        }
+        .filter(|quote| *quote > Positive::ZERO)
+    }
+
+    /// Position for this leg on the terms of `template`.
+    fn position(
+        &self,
+        template: &Options,
+        strike: Positive,
+        quantity: Positive,
+        premium: Positive,
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Position {
+        let mut option = template.clone();
+        option.strike_price = strike;
+        option.option_style = self.style;
+        option.side = self.side;
+        option.quantity = quantity;
+        Position::new(option, premium, Utc::now(), open_fee, close_fee, None, None)
+    }
+}
+
+/// How the quantity of the scaled legs relates to the base quantity.
+pub(super) enum QuantityRatio {
+    /// No leg is scaled.
+    One,
+    /// Scaled legs trade exactly this multiple of the base quantity.
+    Fixed(Positive),
+    /// Scaled legs trade any multiple above one.
+    AboveOne,
+}
+
+/// Legs and rules of a named structure.
+pub(super) struct StructureSpec {
+    pub kind: StrategyType,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub legs: &'static [LegSpec],
+    pub ratio: QuantityRatio,
+    /// Extra condition on the distinct strikes, in ascending order.
+    pub strikes: fn(&[Positive]) -> bool,
+}
+
+/// Strike rule of the structures whose strikes only need to be distinct.
+pub(super) fn distinct_strikes(_strikes: &[Positive]) -> bool {
+    true
+}
+
+/// Strike rule of the broken-wing butterflies: the wings must differ in width.
+pub(super) fn unequal_wings(strikes: &[Positive]) -> bool {
+    strikes[1] - strikes[0] != strikes[2] - strikes[1]
+}
+
+impl StructureSpec {
+    fn error(&self, reason: impl Into<String>) -> StrategyError {
+        StrategyError::OperationError(OperationErrorKind::InvalidParameters {
+            operation: format!("{} get_strategy", self.name),
+            reason: reason.into(),
+        })
+    }
+
+    fn strike_count(&self) -> usize {
+        self.legs
+            .iter()
+            .map(|leg| leg.strike + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// An empty structure, without legs.
+    pub(super) fn empty(&self) -> CustomStrategy {
+        CustomStrategy {
+            name: self.name.to_string(),
+            kind: self.kind.clone(),
+            description: self.description.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Matches `positions` to the legs of the structure and returns them in leg order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if the number of positions, their strikes,
+    /// styles, sides, expirations or quantities do not follow the structure.
+    pub(super) fn arrange(&self, positions: &[Position]) -> Result<Vec<Position>, StrategyError> {
+        if positions.len() != self.legs.len() {
+            return Err(self.error(format!("Must have exactly {} options", self.legs.len())));
+        }
+        let mut strikes: Vec<Positive> = positions.iter().map(|p| p.option.strike_price).collect();
+        strikes.sort();
+        strikes.dedup();
+        if strikes.len() != self.strike_count() || !(self.strikes)(&strikes) {
+            return Err(self.error("Strikes do not follow the structure"));
+        }
+
+        let mut remaining: Vec<&Position> = positions.iter().collect();
+        let mut arranged = Vec::with_capacity(self.legs.len());
+        for leg in self.legs {
+            let strike = strikes[leg.strike];
+            let index = remaining
+                .iter()
+                .position(|position| {
+                    position.option.strike_price == strike
+                        && position.option.option_style == leg.style
+                        && position.option.side == leg.side
+                })
+                .ok_or_else(|| {
+                    self.error(format!(
+                        "Missing {:?} {:?} leg at strike {}",
+                        leg.side, leg.style, strike
+                    ))
+                })?;
+            arranged.push(remaining.swap_remove(index).clone());
+        }
+
+        let expiration = arranged[0].option.expiration_date;
+        if arranged
+            .iter()
+            .any(|position| position.option.expiration_date != expiration)
+        {
+            return Err(self.error("All legs must expire on the same date"));
+        }
+        self.quantities(&arranged)?;
+        Ok(arranged)
+    }
+
+    /// Base quantity and ratio of legs in leg order.
+    fn quantities(&self, arranged: &[Position]) -> Result<(Positive, Positive), StrategyError> {
+        let quantities = |scaled: bool| -> Vec<Positive> {
+            self.legs
+                .iter()
+                .zip(arranged)
+                .filter(|(leg, _)| leg.scaled == scaled)
+                .map(|(_, position)| position.option.quantity)
+                .collect()
+        };
+        let (base, scaled) = (quantities(false), quantities(true));
+        if base.windows(2).any(|pair| pair[0] != pair[1])
+            || scaled.windows(2).any(|pair| pair[0] != pair[1])
+        {
+            return Err(self.error("Legs of the same kind must trade the same quantity"));
+        }
+        let quantity = *base
+            .first()
+            .ok_or_else(|| self.error("The structure has no legs"))?;
+        let ratio = scaled
+            .first()
+            .map_or(Positive::ONE, |scaled| *scaled / quantity);
+        let valid = match self.ratio {
+            QuantityRatio::One => true,
+            QuantityRatio::Fixed(expected) => ratio == expected,
+            QuantityRatio::AboveOne => ratio > Positive::ONE,
+        };
+        if !valid {
+            return Err(self.error("Quantities do not follow the ratio of the structure"));
+        }
+        Ok((quantity, ratio))
+    }
+
+    /// Builds the structure from positions in any order.
+    ///
+    /// # Errors
+    ///
+    /// See [`StructureSpec::arrange`] and [`CustomStrategy::new`].
+    pub(super) fn assemble(&self, positions: &[Position]) -> Result<CustomStrategy, StrategyError> {
+        let arranged = self.arrange(positions)?;
+        let mut strategy = CustomStrategy::new(self.name.to_string(), arranged, None)?;
+        strategy.kind = self.kind.clone();
+        strategy.description = self.description.to_string();
+        Ok(strategy)
+    }
+
+    /// Builds the structure from its distinct strikes in ascending order and the premium
+    /// of every leg in leg order. All legs share the expiration, volatility, rates and
+    /// fees given; scaled legs trade `quantity * ratio` contracts.
+    ///
+    /// # Errors
+    ///
+    /// See [`StructureSpec::assemble`].
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn build(
+        &self,
+        underlying_symbol: String,
+        underlying_price: Positive,
+        strikes: &[Positive],
+        expiration: ExpirationDate,
+        implied_volatility: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        quantity: Positive,
+        ratio: Positive,
+        premiums: &[Positive],
+        open_fee: Positive,
+        close_fee: Positive,
+    ) -> Result<CustomStrategy, StrategyError> {
+        let template = Options::new(
+            OptionType::European,
+            Side::Long,
+            underlying_symbol,
+            underlying_price,
+            expiration,
+            implied_volatility,
+            quantity,
+            underlying_price,
+            risk_free_rate,
+            OptionStyle::Call,
+            dividend_yield,
+            None,
+        );
+        let positions: Vec<Position> = self
+            .legs
+            .iter()
+            .zip(premiums)
+            .map(|(leg, premium)| {
+                leg.position(
+                    &template,
+                    strikes[leg.strike],
+                    leg.quantity(quantity, ratio),
+                    *premium,
+                    open_fee,
+                    close_fee,
+                )
+            })
+            .collect();
+        self.assemble(&positions)
+    }
+
+    /// Builds the structure at the strikes of `options`, in ascending order, from the
+    /// quotes of `chain`. Long legs are bought at the ask and short legs sold at the bid,
+    /// each at the implied volatility of its strike, while the expiration, quantities,
+    /// rates and fees are those of `template`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StrategyError::OperationError` if `template` has no legs or a leg has
+    /// no quote to trade at, besides the errors of [`StructureSpec::assemble`].
+    pub(super) fn quoted_in(
+        &self,
+        template: &CustomStrategy,
+        chain: &OptionChain,
+        options: &[&OptionData],
+    ) -> Result<CustomStrategy, StrategyError> {
+        let first = template
+            .positions
+            .first()
+            .ok_or_else(|| self.error("The structure has no legs to take its terms from"))?;
+        if options.len() != self.strike_count() {
+            return Err(self.error(format!("Must have exactly {} strikes", self.strike_count())));
+        }
+        let (quantity, ratio) = self.quantities(&template.positions)?;
+        let mut terms = first.option.clone();
+        terms.underlying_symbol = chain.symbol.clone();
+        terms.underlying_price = chain.underlying_price;
+
+        let positions = self
+            .legs
+            .iter()
+            .map(|leg| {
+                let data = options[leg.strike];
+                let premium = leg.quote(data).ok_or_else(|| {
+                    self.error(format!(
+                        "No quote for the {:?} {:?} leg at strike {}",
+                        leg.side, leg.style, data.strike_price
+                    ))
+                })?;
+                terms.implied_volatility = data.implied_volatility;
+                Ok(leg.position(
+                    &terms,
+                    data.strike_price,
+                    leg.quantity(quantity, ratio),
+                    premium,
+                    first.open_fee,
+                    first.close_fee,
+                ))
+            })
+            .collect::<Result<Vec<_>, StrategyError>>()?;
+        self.assemble(&positions)
+    }
+
+    /// Whether every leg has a quote to trade at in `options`.
+    pub(super) fn has_quotes(&self, options: &[&OptionData]) -> bool {
+        options.len() == self.strike_count()
+            && self
+                .legs
+                .iter()
+                .all(|leg| leg.quote(options[leg.strike]).is_some())
+    }
+
+    /// Combinations of distinct strikes of `chain`, as many as the structure has, in
+    /// ascending order. With `FindOptimalSide::Center` the strikes must surround the
+    /// underlying price; any other side applies to every strike.
+    pub(super) fn combinations<'a>(
+        &self,
+        chain: &'a OptionChain,
+        side: FindOptimalSide,
+    ) -> impl Iterator<Item = Vec<&'a OptionData>> + 'a {
+        let underlying_price = chain.underlying_price;
+        let groups: Box<dyn Iterator<Item = Vec<&'a OptionData>> + 'a> = match self.strike_count() {
+            1 => Box::new(chain.get_single_iter().map(|a| vec![a])),
+            2 => Box::new(chain.get_double_iter().map(|(a, b)| vec![a, b])),
+            3 => Box::new(chain.get_triple_iter().map(|(a, b, c)| vec![a, b, c])),
+            _ => Box::new(chain.get_quad_iter().map(|(a, b, c, d)| vec![a, b, c, d])),
+        };
+        groups
+            .map(|mut group| {
+                group.sort_by_key(|option| option.strike_price);
+                group
+            })
+            .filter(move |group| match side {
+                FindOptimalSide::Center => {
+                    group[0].strike_price <= underlying_price
+                        && group[group.len() - 1].strike_price >= underlying_price
+                }
+                _ => group
+                    .iter()
+                    .all(|option| option.is_valid_optimal_side(&underlying_price, &side)),
+            })
+    }
+
+    /// Value of `strategy` under `criteria`. The profit ratio is only defined when both
+    /// profit and loss are bounded.
+    fn score(strategy: &CustomStrategy, criteria: &OptimizationCriteria) -> Option<Decimal> {
+        match criteria {
+            OptimizationCriteria::Ratio => {
+                if strategy.get_max_profit().is_err() || strategy.get_max_loss().is_err() {
+                    return None;
+                }
+                strategy.get_profit_ratio().ok()
+            }
+            OptimizationCriteria::Area => strategy.get_profit_area().ok(),
+        }
+    }
+
+    /// Best structure in `chain` under `criteria`, on the terms of `template`, or `None`
+    /// if no combination of strikes can be traded. Structures with unlimited profit or
+    /// loss have no profit ratio, so only `OptimizationCriteria::Area` selects them.
+    pub(super) fn find_optimal(
+        &self,
+        template: &CustomStrategy,
+        chain: &OptionChain,
+        side: FindOptimalSide,
+        criteria: &OptimizationCriteria,
+    ) -> Option<CustomStrategy> {
+        let mut best: Option<(Decimal, CustomStrategy)> = None;
+        for options in self.combinations(chain, side) {
+            let Ok(candidate) = self.quoted_in(template, chain, &options) else {
+                continue;
+            };
+            let Some(value) = Self::score(&candidate, criteria) else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best_value, _)| value > *best_value)
+            {
+                info!("Found better value: {}", value);
+                best = Some((value, candidate));
+            }
+        }
+        best.map(|(_, strategy)| strategy)
+    }
+}
+
+/// Group of options, in order.
+pub(super) fn options_group<'a>(options: &[&'a OptionData]) -> OptionDataGroup<'a> {
+    match *options {
+        [a] => OptionDataGroup::One(a),
+        [a, b] => OptionDataGroup::Two(a, b),
+        [a, b, c] => OptionDataGroup::Three(a, b, c),
+        [a, b, c, d] => OptionDataGroup::Four(a, b, c, d),
+        _ => OptionDataGroup::Any(options.to_vec()),
+    }
+}
+
+/// Options of the legs, in order.
+pub(super) fn leg_options<'a>(legs: &StrategyLegs<'a>) -> Vec<&'a OptionData> {
+    match *legs {
+        StrategyLegs::OneLeg { first } => vec![first],
+        StrategyLegs::TwoLegs { first, second } => vec![first, second],
+        StrategyLegs::ThreeLegs {
+            first,
+            second,
+            third,
+        } => vec![first, second, third],
+        StrategyLegs::FourLegs {
+            first,
+            second,
+            third,
+            fourth,
+        } => vec![first, second, third, fourth],
+        StrategyLegs::SixLegs {
+            first,
+            second,
+            third,
+            fourth,
+            fifth,
+            sixth,
+        } => vec![first, second, third, fourth, fifth, sixth],
+    }
+}
+
+/// Implements the strategy traits of a named structure, a struct wrapping a
+/// `CustomStrategy` in its `strategy` field, by delegating to the wrapped strategy.
+/// `Default`, `StrategyConstructor`, `Validable` and `Optimizable` go through the
+/// structure's [`StructureSpec`] instead, so the legs always keep their shape.
+macro_rules! impl_structure {
+    ($structure:ident, $spec:expr) => {
+        const _: () = {
+            use rust_decimal::Decimal;
+            use std::collections::{HashMap, HashSet};
+            use std::error::Error;
+            use $crate::chains::StrategyLegs;
+            use $crate::chains::chain::OptionChain;
+            use $crate::chains::utils::OptionDataGroup;
+            use $crate::error::GreeksError;
+            use $crate::error::position::PositionError;
+            use $crate::error::probability::ProbabilityError;
+            use $crate::error::strategies::StrategyError;
+            use $crate::greeks::Greeks;
+            use $crate::model::ProfitLossRange;
+            use $crate::model::position::Position;
+            use $crate::model::types::{OptionBasicType, OptionStyle, Side};
+            use $crate::pnl::PnLCalculator;
+            use $crate::pnl::utils::PnL;
+            use $crate::pricing::payoff::Profit;
+            use $crate::strategies::base::{
+                BreakEvenable, Optimizable, Positionable, Strategable, StrategyBasics, Validable,
+            };
+            use $crate::strategies::delta_neutral::DeltaNeutrality;
+            use $crate::strategies::probabilities::core::ProbabilityAnalysis;
+            use $crate::strategies::structures::{leg_options, options_group};
+            use $crate::strategies::utils::{FindOptimalSide, OptimizationCriteria};
+            use $crate::strategies::{BasicAble, Strategies, StrategyConstructor};
+            use $crate::{ExpirationDate, Options, Positive};
+
+            impl Default for $structure {
+                fn default() -> Self {
+                    $structure {
+                        strategy: $spec.empty(),
+                    }
+                }
+            }
+
+            impl StrategyConstructor for $structure {
+                fn get_strategy(vec_positions: &[Position]) -> Result<Self, StrategyError> {
+                    Ok($structure {
+                        strategy: $spec.assemble(vec_positions)?,
+                    })
+                }
+            }
+
+            impl BreakEvenable for $structure {
+                fn get_break_even_points(&self) -> Result<&Vec<Positive>, StrategyError> {
+                    self.strategy.get_break_even_points()
+                }
+
+                fn update_break_even_points(&mut self) -> Result<(), StrategyError> {
+                    self.strategy.update_break_even_points()
+                }
+            }
+
+            impl Validable for $structure {
+                fn validate(&self) -> bool {
+                    self.strategy.validate() && $spec.arrange(&self.strategy.positions).is_ok()
+                }
+            }
+
+            impl Positionable for $structure {
+                fn get_positions(&self) -> Result<Vec<&Position>, PositionError> {
+                    self.strategy.get_positions()
+                }
+
+                fn get_position(
+                    &mut self,
+                    option_style: &OptionStyle,
+                    side: &Side,
+                    strike: &Positive,
+                ) -> Result<Vec<&mut Position>, PositionError> {
+                    self.strategy.get_position(option_style, side, strike)
+                }
+
+                fn modify_position(&mut self, position: &Position) -> Result<(), PositionError> {
+                    self.strategy.modify_position(position)
+                }
+            }
+
+            impl Strategable for $structure {
+                fn info(&self) -> Result<StrategyBasics, StrategyError> {
+                    self.strategy.info()
+                }
+            }
+
+            impl BasicAble for $structure {
+                fn get_title(&self) -> String {
+                    self.strategy.get_title()
+                }
+                fn get_option_basic_type(&self) -> HashSet<OptionBasicType<'_>> {
+                    self.strategy.get_option_basic_type()
+                }
+                fn get_implied_volatility(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+                    self.strategy.get_implied_volatility()
+                }
+                fn get_quantity(&self) -> HashMap<OptionBasicType<'_>, &Positive> {
+                    self.strategy.get_quantity()
+                }
+                fn one_option(&self) -> &Options {
+                    self.strategy.one_option()
+                }
+                fn one_option_mut(&mut self) -> &mut Options {
+                    self.strategy.one_option_mut()
+                }
+                fn set_expiration_date(
+                    &mut self,
+                    expiration_date: ExpirationDate,
+                ) -> Result<(), StrategyError> {
+                    self.strategy.set_expiration_date(expiration_date)
+                }
+                fn set_underlying_price(&mut self, price: &Positive) -> Result<(), StrategyError> {
+                    self.strategy.set_underlying_price(price)
+                }
+                fn set_implied_volatility(
+                    &mut self,
+                    volatility: &Positive,
+                ) -> Result<(), StrategyError> {
+                    self.strategy.set_implied_volatility(volatility)
+                }
+            }
+
+            impl Strategies for $structure {
+                fn get_volume(&mut self) -> Result<Positive, StrategyError> {
+                    self.strategy.get_volume()
+                }
+                fn get_max_profit(&self) -> Result<Positive, StrategyError> {
+                    self.strategy.get_max_profit()
+                }
+                fn get_max_loss(&self) -> Result<Positive, StrategyError> {
+                    self.strategy.get_max_loss()
+                }
+                fn get_total_cost(&self) -> Result<Positive, PositionError> {
+                    self.strategy.get_total_cost()
+                }
+                fn get_net_cost(&self) -> Result<Decimal, PositionError> {
+                    self.strategy.get_net_cost()
+                }
+                fn get_fees(&self) -> Result<Positive, StrategyError> {
+                    self.strategy.get_fees()
+                }
+                fn get_profit_area(&self) -> Result<Decimal, StrategyError> {
+                    self.strategy.get_profit_area()
+                }
+                fn get_profit_ratio(&self) -> Result<Decimal, StrategyError> {
+                    self.strategy.get_profit_ratio()
+                }
+            }
+
+            impl Optimizable for $structure {
+                type Strategy = $structure;
+
+                fn filter_combinations<'a>(
+                    &'a self,
+                    option_chain: &'a OptionChain,
+                    side: FindOptimalSide,
+                ) -> impl Iterator<Item = OptionDataGroup<'a>> {
+                    $spec
+                        .combinations(option_chain, side)
+                        .filter(move |options| {
+                            $spec
+                                .quoted_in(&self.strategy, option_chain, options)
+                                .is_ok()
+                        })
+                        .map(|options| options_group(&options))
+                }
+
+                fn find_optimal(
+                    &mut self,
+                    option_chain: &OptionChain,
+                    side: FindOptimalSide,
+                    criteria: OptimizationCriteria,
+                ) {
+                    if let Some(strategy) =
+                        $spec.find_optimal(&self.strategy, option_chain, side, &criteria)
+                    {
+                        self.strategy = strategy;
+                    }
+                }
+
+                fn are_valid_legs(&self, legs: &StrategyLegs) -> bool {
+                    $spec.has_quotes(&leg_options(legs))
+                }
+
+                fn create_strategy(
+                    &self,
+                    chain: &OptionChain,
+                    legs: &StrategyLegs,
+                ) -> Self::Strategy {
+                    let strategy = $spec
+                        .quoted_in(&self.strategy, chain, &leg_options(legs))
+                        .expect("Invalid legs for this strategy");
+                    $structure { strategy }
+                }
+            }
+
+            impl Profit for $structure {
+                fn calculate_profit_at(&self, price: &Positive) -> Result<Decimal, Box<dyn Error>> {
+                    self.strategy.calculate_profit_at(price)
+                }
+            }
+
+            impl ProbabilityAnalysis for $structure {
+                fn get_profit_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+                    self.strategy.get_profit_ranges()
+                }
+
+                fn get_loss_ranges(&self) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+                    self.strategy.get_loss_ranges()
+                }
+            }
+
+            impl Greeks for $structure {
+                fn get_options(&self) -> Result<Vec<&Options>, GreeksError> {
+                    self.strategy.get_options()
+                }
+            }
+
+            impl DeltaNeutrality for $structure {}
+
+            impl PnLCalculator for $structure {
+                fn calculate_pnl(
+                    &self,
+                    market_price: &Positive,
+                    expiration_date: ExpirationDate,
+                    implied_volatility: &Positive,
+                ) -> Result<PnL, Box<dyn Error>> {
+                    self.strategy
+                        .calculate_pnl(market_price, expiration_date, implied_volatility)
+                }
+
+                fn calculate_pnl_at_expiration(
+                    &self,
+                    underlying_price: &Positive,
+                ) -> Result<PnL, Box<dyn Error>> {
+                    self.strategy.calculate_pnl_at_expiration(underlying_price)
+                }
+            }
+        };
+    };
+}
+
+pub(super) use impl_structure;
+
+#[cfg(test)]
+mod tests_structures {
+    use super::*;
+    use crate::pricing::Profit;
+    use crate::strategies::base::{
+        BreakEvenable, Optimizable, Positionable, Strategable, Validable,
+    };
+    use crate::strategies::{
+        BoxSpread, CallBackSpread, CallBrokenWingButterfly, CallCondor, CallRatioSpread,
+        JadeLizard, PutBackSpread, PutBrokenWingButterfly, PutCondor, PutRatioSpread,
+        ReverseIronCondor, RiskReversal, Strap, StrategyConstructor, StrategyRequest, Strip,
+    };
+    use crate::{pos, spos};
+    use rust_decimal_macros::dec;
+
+    static THREE_STRIKES: StructureSpec = StructureSpec {
+        kind: StrategyType::JadeLizard,
+        name: "Three strikes",
+        description: "",
+        legs: &[
+            leg(0, OptionStyle::Put, Side::Short, false),
+            leg(1, OptionStyle::Call, Side::Short, false),
+            leg(2, OptionStyle::Call, Side::Long, false),
+        ],
+        ratio: QuantityRatio::One,
+        strikes: distinct_strikes,
+    };
+
+    /// Chain quoted 2% around the Black-Scholes price, with no quotes at 120.
+    fn chain() -> OptionChain {
+        let mut chain = OptionChain::new("TEST", pos!(100.0), "2030-01-01".to_string(), None, None);
+        for strike in (80..=120).step_by(5) {
+            let strike = pos!(strike as f64);
+            let price = |style| {
+                let option = Options::new(
+                    OptionType::European,
+                    Side::Long,
+                    "TEST".to_string(),
+                    strike,
+                    ExpirationDate::Days(pos!(30.0)),
+                    pos!(0.2),
+                    Positive::ONE,
+                    pos!(100.0),
+                    dec!(0.05),
+                    style,
+                    Positive::ZERO,
+                    None,
+                );
+                Positive::from(option.calculate_price_black_scholes().unwrap())
+            };
+            let (call, put) = (price(OptionStyle::Call), price(OptionStyle::Put));
+            let quoted = strike < pos!(120.0);
+            chain.add_option(
+                strike,
+                quoted.then_some(call * 0.98),
+                quoted.then_some(call * 1.02),
+                quoted.then_some(put * 0.98),
+                quoted.then_some(put * 1.02),
+                pos!(0.2),
+                None,
+                None,
+                None,
+                spos!(100.0),
+                Some(50),
+                None,
+            );
+        }
+        chain
+    }
+
+    fn jade_lizard() -> JadeLizard {
+        JadeLizard::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(105.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(2.0),
+            pos!(2.5),
+            pos!(4.0),
+            pos!(1.0),
+            pos!(0.1),
+            pos!(0.1),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_combinations_follow_side() {
+        let chain = chain();
+        let spec = &THREE_STRIKES;
+        // The chain skips the strike without quotes, leaving 8 strikes
+        assert_eq!(spec.combinations(&chain, FindOptimalSide::All).count(), 56);
+        assert!(
+            spec.combinations(&chain, FindOptimalSide::Upper)
+                .all(|group| group.iter().all(|o| o.strike_price >= pos!(100.0)))
+        );
+        assert!(
+            spec.combinations(&chain, FindOptimalSide::Center)
+                .all(|group| {
+                    group[0].strike_price <= pos!(100.0) && group[2].strike_price >= pos!(100.0)
+                })
+        );
+    }
+
+    #[test]
+    fn test_find_optimal_keeps_terms_and_shape() {
+        let chain = chain();
+        let mut strategy = jade_lizard();
+        strategy.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Area);
+
+        assert!(strategy.validate());
+        let positions = strategy.get_positions().unwrap();
+        assert!(positions.iter().all(|p| p.option.quantity == pos!(2.0)));
+        assert!(positions.iter().all(|p| p.open_fee == pos!(0.1)));
+        assert!(
+            positions
+                .iter()
+                .all(|p| p.option.strike_price < pos!(120.0))
+        );
+        // Legs trade at the ask when bought and at the bid when sold
+        let data = |strike: Positive| {
+            chain
+                .options
+                .iter()
+                .find(|o| o.strike_price == strike)
+                .unwrap()
+        };
+        let long_call = positions[2];
+        assert_eq!(
+            long_call.premium,
+            data(long_call.option.strike_price).call_ask.unwrap()
+        );
+        let short_put = positions[0];
+        assert_eq!(
+            short_put.premium,
+            data(short_put.option.strike_price).put_bid.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ratio_needs_bounded_payoff() {
+        let chain = chain();
+        let mut condor = CallCondor::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(90.0),
+            pos!(95.0),
+            pos!(105.0),
+            pos!(110.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(11.0),
+            pos!(7.0),
+            pos!(2.0),
+            pos!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        condor.find_optimal(&chain, FindOptimalSide::Center, OptimizationCriteria::Ratio);
+        assert!(condor.validate());
+        assert!(condor.get_max_loss().is_ok());
+
+        // A strip has unlimited upside, so only the area can rank it
+        let strip = Strip::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(3.0),
+            pos!(3.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap();
+        let mut by_ratio = strip.clone();
+        by_ratio.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Ratio);
+        assert_eq!(by_ratio.get_positions().unwrap()[0].premium, pos!(3.0));
+        let mut by_area = strip.clone();
+        by_area.find_optimal(&chain, FindOptimalSide::All, OptimizationCriteria::Area);
+        assert_ne!(by_area.get_positions().unwrap()[0].premium, pos!(3.0));
+    }
+
+    #[test]
+    fn test_filter_and_create_strategy() {
+        let chain = chain();
+        let strategy = jade_lizard();
+        let groups: Vec<_> = strategy
+            .filter_combinations(&chain, FindOptimalSide::Center)
+            .collect();
+        assert!(!groups.is_empty());
+        let OptionDataGroup::Three(first, second, third) = groups[0] else {
+            panic!("Jade lizards have three strikes");
+        };
+        let legs = StrategyLegs::ThreeLegs {
+            first,
+            second,
+            third,
+        };
+        assert!(strategy.are_valid_legs(&legs));
+        assert!(strategy.create_strategy(&chain, &legs).validate());
+    }
+
+    #[test]
+    fn test_strategy_request() {
+        let strategy = jade_lizard();
+        let request = StrategyRequest::new(
+            StrategyType::JadeLizard,
+            strategy
+                .get_positions()
+                .unwrap()
+                .into_iter()
+                .cloned()
+                .collect(),
+        );
+        let built = request.get_strategy().unwrap();
+        assert_eq!(built.type_name(), StrategyType::JadeLizard);
+        assert_eq!(
+            built.get_break_even_points().unwrap(),
+            strategy.get_break_even_points().unwrap()
+        );
+        let wrong = StrategyRequest::new(StrategyType::BoxSpread, request.positions.clone());
+        assert!(wrong.get_strategy().is_err());
+    }
+
+    /// Expected payoff of a structure at expiration; `None` where it is unbounded.
+    struct PayoffCase {
+        strategy: Box<dyn Strategable>,
+        break_even_points: Vec<Positive>,
+        max_profit: Option<Positive>,
+        max_loss: Option<Positive>,
+    }
+
+    fn case(
+        strategy: Result<impl Strategable + 'static, StrategyError>,
+        break_even_points: &[f64],
+        max_profit: Option<f64>,
+        max_loss: Option<f64>,
+    ) -> PayoffCase {
+        PayoffCase {
+            strategy: Box::new(strategy.unwrap()),
+            break_even_points: break_even_points.iter().map(|p| pos!(*p)).collect(),
+            max_profit: max_profit.map(|p| pos!(p)),
+            max_loss: max_loss.map(|p| pos!(p)),
+        }
+    }
+
+    fn days() -> ExpirationDate {
+        ExpirationDate::Days(pos!(30.0))
+    }
+
+    fn call_ratio_spread(
+        quantity: Positive,
+        ratio: Positive,
+    ) -> Result<CallRatioSpread, StrategyError> {
+        CallRatioSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            pos!(110.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            quantity,
+            ratio,
+            pos!(3.0),
+            pos!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn put_ratio_spread(
+        quantity: Positive,
+        ratio: Positive,
+    ) -> Result<PutRatioSpread, StrategyError> {
+        PutRatioSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            pos!(90.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            quantity,
+            ratio,
+            pos!(3.0),
+            pos!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn call_back_spread(
+        quantity: Positive,
+        ratio: Positive,
+    ) -> Result<CallBackSpread, StrategyError> {
+        CallBackSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            pos!(110.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            quantity,
+            ratio,
+            pos!(5.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn put_back_spread(
+        quantity: Positive,
+        ratio: Positive,
+    ) -> Result<PutBackSpread, StrategyError> {
+        PutBackSpread::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(100.0),
+            pos!(90.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            quantity,
+            ratio,
+            pos!(5.0),
+            pos!(2.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn call_broken_wing_butterfly(
+        upper_strike: Positive,
+    ) -> Result<CallBrokenWingButterfly, StrategyError> {
+        CallBrokenWingButterfly::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(100.0),
+            upper_strike,
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(6.5),
+            pos!(3.5),
+            pos!(0.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn put_broken_wing_butterfly(
+        lower_strike: Positive,
+    ) -> Result<PutBrokenWingButterfly, StrategyError> {
+        PutBrokenWingButterfly::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            lower_strike,
+            pos!(100.0),
+            pos!(105.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(0.5),
+            pos!(3.5),
+            pos!(6.5),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+    }
+
+    fn jade_lizard_with_call_premium(premium_short_call: Positive) -> JadeLizard {
+        JadeLizard::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(95.0),
+            pos!(105.0),
+            pos!(110.0),
+            days(),
+            pos!(0.2),
+            dec!(0.05),
+            Positive::ZERO,
+            pos!(1.0),
+            pos!(2.5),
+            premium_short_call,
+            pos!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+        )
+        .unwrap()
+    }
+
+    fn payoff_cases() -> Vec<PayoffCase> {
+        vec![
+            case(
+                call_ratio_spread(pos!(1.0), Positive::TWO),
+                &[101.0, 119.0],
+                Some(9.0),
+                None,
+            ),
+            case(
+                put_ratio_spread(pos!(1.0), Positive::TWO),
+                &[81.0, 99.0],
+                Some(9.0),
+                Some(81.0),
+            ),
+            case(
+                call_back_spread(pos!(1.0), Positive::TWO),
+                &[101.0, 119.0],
+                None,
+                Some(9.0),
+            ),
+            case(
+                put_back_spread(pos!(1.0), Positive::TWO),
+                &[81.0, 99.0],
+                Some(81.0),
+                Some(9.0),
+            ),
+            // Entered for no net cost: flat at zero beyond the narrow wing
+            case(
+                call_broken_wing_butterfly(pos!(110.0)),
+                &[105.0],
+                Some(5.0),
+                Some(5.0),
+            ),
+            case(
+                put_broken_wing_butterfly(pos!(90.0)),
+                &[95.0],
+                Some(5.0),
+                Some(5.0),
+            ),
+            case(
+                CallCondor::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(90.0),
+                    pos!(95.0),
+                    pos!(105.0),
+                    pos!(110.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(11.0),
+                    pos!(7.0),
+                    pos!(2.0),
+                    pos!(0.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[92.5, 107.5],
+                Some(2.5),
+                Some(2.5),
+            ),
+            case(
+                PutCondor::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(90.0),
+                    pos!(95.0),
+                    pos!(105.0),
+                    pos!(110.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(0.5),
+                    pos!(2.0),
+                    pos!(7.0),
+                    pos!(11.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[92.5, 107.5],
+                Some(2.5),
+                Some(2.5),
+            ),
+            case(
+                Ok(jade_lizard_with_call_premium(pos!(4.0))),
+                &[89.5],
+                Some(5.5),
+                Some(89.5),
+            ),
+            case(
+                ReverseIronCondor::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(85.0),
+                    pos!(95.0),
+                    pos!(105.0),
+                    pos!(115.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(0.5),
+                    pos!(2.0),
+                    pos!(2.0),
+                    pos!(0.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[92.0, 108.0],
+                Some(7.0),
+                Some(3.0),
+            ),
+            case(
+                RiskReversal::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(95.0),
+                    pos!(105.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(2.5),
+                    pos!(2.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[94.5],
+                None,
+                Some(94.5),
+            ),
+            case(
+                Strip::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(100.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(3.0),
+                    pos!(3.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[95.5, 109.0],
+                None,
+                Some(9.0),
+            ),
+            case(
+                Strap::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(100.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(3.0),
+                    pos!(3.0),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[91.0, 104.5],
+                None,
+                Some(9.0),
+            ),
+            // A debit of 9.8 for a box worth 10 at expiration
+            case(
+                BoxSpread::new(
+                    "TEST".to_string(),
+                    pos!(100.0),
+                    pos!(95.0),
+                    pos!(105.0),
+                    days(),
+                    pos!(0.2),
+                    dec!(0.05),
+                    Positive::ZERO,
+                    pos!(1.0),
+                    pos!(6.8),
+                    pos!(1.5),
+                    pos!(2.0),
+                    pos!(6.5),
+                    Positive::ZERO,
+                    Positive::ZERO,
+                ),
+                &[],
+                Some(0.2),
+                None,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_structure_payoffs_and_legs() {
+        for case in payoff_cases() {
+            let strategy = case.strategy;
+            let kind = strategy.type_name();
+            assert_eq!(
+                strategy.get_break_even_points().unwrap(),
+                &case.break_even_points,
+                "{kind:?}"
+            );
+            assert_eq!(strategy.get_max_profit().ok(), case.max_profit, "{kind:?}");
+            assert_eq!(strategy.get_max_loss().ok(), case.max_loss, "{kind:?}");
+
+            // The legs are recognised in any order, but not with a leg missing or flipped
+            let positions: Vec<Position> = strategy
+                .get_positions()
+                .unwrap()
+                .into_iter()
+                .cloned()
+                .collect();
+            let mut reversed = positions.clone();
+            reversed.reverse();
+            let rebuilt = StrategyRequest::new(kind.clone(), reversed)
+                .get_strategy()
+                .unwrap();
+            assert_eq!(rebuilt.type_name(), kind);
+            assert_eq!(
+                rebuilt.get_break_even_points().unwrap(),
+                &case.break_even_points,
+                "{kind:?}"
+            );
+            let missing = positions[..positions.len() - 1].to_vec();
+            assert!(
+                StrategyRequest::new(kind.clone(), missing)
+                    .get_strategy()
+                    .is_err(),
+                "{kind:?}"
+            );
+            let mut flipped = positions.clone();
+            let last = flipped.len() - 1;
+            flipped[last].option.side = match flipped[last].option.side {
+                Side::Long => Side::Short,
+                Side::Short => Side::Long,
+            };
+            assert!(
+                StrategyRequest::new(kind.clone(), flipped)
+                    .get_strategy()
+                    .is_err(),
+                "{kind:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ratio_spreads_scale_quantities() {
+        let (quantity, ratio) = (pos!(2.0), pos!(3.0));
+        let legs = |strategy: &dyn Strategable| -> Vec<(Side, Positive)> {
+            strategy
+                .get_positions()
+                .unwrap()
+                .iter()
+                .map(|p| (p.option.side, p.option.quantity))
+                .collect()
+        };
+        // Ratio spreads sell the larger number of options, back spreads buy it
+        let sold = [
+            legs(&call_ratio_spread(quantity, ratio).unwrap()),
+            legs(&put_ratio_spread(quantity, ratio).unwrap()),
+        ];
+        for legs in sold {
+            assert!(legs.contains(&(Side::Long, pos!(2.0))));
+            assert!(legs.contains(&(Side::Short, pos!(6.0))));
+        }
+        let bought = [
+            legs(&call_back_spread(quantity, ratio).unwrap()),
+            legs(&put_back_spread(quantity, ratio).unwrap()),
+        ];
+        for legs in bought {
+            assert!(legs.contains(&(Side::Short, pos!(2.0))));
+            assert!(legs.contains(&(Side::Long, pos!(6.0))));
+        }
+
+        // The ratio must be above one, whether given or read from the positions
+        assert!(call_ratio_spread(quantity, Positive::ONE).is_err());
+        assert!(put_ratio_spread(quantity, Positive::ONE).is_err());
+        assert!(call_back_spread(quantity, Positive::ONE).is_err());
+        assert!(put_back_spread(quantity, Positive::ONE).is_err());
+        let mut equal: Vec<Position> = call_ratio_spread(quantity, ratio)
+            .unwrap()
+            .get_positions()
+            .unwrap()
+            .into_iter()
+            .cloned()
+            .collect();
+        for position in equal.iter_mut() {
+            position.option.quantity = quantity;
+        }
+        assert!(CallRatioSpread::get_strategy(&equal).is_err());
+    }
+
+    #[test]
+    fn test_broken_wing_butterflies_reject_equal_wings() {
+        assert!(call_broken_wing_butterfly(pos!(105.0)).is_err());
+        assert!(put_broken_wing_butterfly(pos!(95.0)).is_err());
+
+        // The body trades twice the quantity of each wing
+        let strategy = call_broken_wing_butterfly(pos!(110.0)).unwrap();
+        let quantities: Vec<Positive> = strategy
+            .get_positions()
+            .unwrap()
+            .iter()
+            .map(|p| p.option.quantity)
+            .collect();
+        assert_eq!(quantities, vec![pos!(1.0), pos!(2.0), pos!(1.0)]);
+
+        // Moving the upper wing in to the width of the lower one is rejected as well
+        let mut positions: Vec<Position> = strategy
+            .get_positions()
+            .unwrap()
+            .into_iter()
+            .cloned()
+            .collect();
+        positions[2].option.strike_price = pos!(105.0);
+        assert!(CallBrokenWingButterfly::get_strategy(&positions).is_err());
+    }
+
+    #[test]
+    fn test_jade_lizard_upside_risk_depends_on_credit() {
+        // A credit of 5.5 against a call spread 5 wide leaves no upside risk
+        let covered = jade_lizard_with_call_premium(pos!(4.0));
+        for price in [110.0, 150.0, 200.0] {
+            assert_eq!(
+                covered.calculate_profit_at(&pos!(price)).unwrap(),
+                dec!(0.5)
+            );
+        }
+        // A credit of 3.5 does not cover the width of the call spread
+        let uncovered = jade_lizard_with_call_premium(pos!(2.0));
+        for price in [110.0, 150.0, 200.0] {
+            assert_eq!(
+                uncovered.calculate_profit_at(&pos!(price)).unwrap(),
+                dec!(-1.5)
+            );
+        }
+    }
+}
@@ -0,0 +1,297 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+//! Valuation shared by the calendar and diagonal spreads.
+//!
+//! These strategies sell a front-month option and buy a back-month option, so the
+//! payoff at the front-month expiration is not piecewise linear: the short leg is
+//! settled at its intrinsic value while the long leg still carries time value. The
+//! back leg is therefore priced with Black-Scholes on the time it has left, and break
+//! even points and profit extremes are searched numerically over a price grid with the
+//! helpers of [`super::payoff_scan`].
+
+use crate::chains::{OptionChain, OptionData};
+use crate::error::probability::ProbabilityError;
+use crate::model::ProfitLossRange;
+use crate::model::position::Position;
+use crate::pnl::PnLCalculator;
+use crate::pnl::utils::PnL;
+use crate::series::OptionSeries;
+use crate::strategies::Strategies;
+use crate::strategies::payoff_scan;
+use crate::strategies::utils::OptimizationCriteria;
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::error::Error;
+
+/// Time the back leg has left when the front leg expires.
+pub(super) fn remaining_expiration(
+    front: &Options,
+    back: &Options,
+) -> Result<ExpirationDate, Box<dyn Error>> {
+    let front_days = front.expiration_date.get_days()?;
+    let back_days = back.expiration_date.get_days()?;
+    if back_days <= front_days {
+        return Err(format!(
+            "Back leg must expire after the front leg ({back_days} <= {front_days} days)"
+        )
+        .into());
+    }
+    Ok(ExpirationDate::Days(back_days - front_days))
+}
+
+/// Expiration of the back leg once the front leg is moved to `front_expiration`,
+/// keeping the number of days between both legs unchanged.
+pub(super) fn shifted_back_expiration(
+    front: &Options,
+    back: &Options,
+    front_expiration: &ExpirationDate,
+) -> Result<ExpirationDate, Box<dyn Error>> {
+    let gap = remaining_expiration(front, back)?.get_days()?;
+    Ok(ExpirationDate::Days(front_expiration.get_days()? + gap))
+}
+
+/// Black-Scholes value of the whole back leg when the front leg expires with the
+/// underlying at `price`. The value carries the sign of the back leg's side.
+fn back_leg_value(
+    front: &Position,
+    back: &Position,
+    price: &Positive,
+) -> Result<Decimal, Box<dyn Error>> {
+    let mut option = back.option.clone();
+    option.expiration_date = remaining_expiration(&front.option, &back.option)?;
+    option.underlying_price = *price;
+    Ok(option.calculate_price_black_scholes()? * option.quantity)
+}
+
+/// Profit of the spread at the front-month expiration, closing the back leg at its
+/// theoretical value.
+pub(super) fn profit_at_front_expiry(
+    front: &Position,
+    back: &Position,
+    price: &Positive,
+) -> Result<Decimal, Box<dyn Error>> {
+    Ok(
+        front.pnl_at_expiration(&Some(price))? + back_leg_value(front, back, price)?
+            - back.total_cost()?
+            + back.premium_received()?,
+    )
+}
+
+/// Same valuation as [`profit_at_front_expiry`], returned as a [`PnL`] dated at the
+/// front-month expiration.
+pub(super) fn pnl_at_front_expiry(
+    front: &Position,
+    back: &Position,
+    price: &Positive,
+) -> Result<PnL, Box<dyn Error>> {
+    let initial_cost = back.total_cost()?;
+    let initial_income = back.premium_received()?;
+    let realized =
+        back_leg_value(front, back, price)? - initial_cost.to_dec() + initial_income.to_dec();
+    let back_pnl = PnL::new(
+        Some(realized),
+        Some(Decimal::ZERO),
+        initial_cost,
+        initial_income,
+        front.option.expiration_date.get_date()?,
+    );
+    Ok(front.calculate_pnl_at_expiration(price)? + back_pnl)
+}
+
+/// Prices from half the lowest to one and a half times the highest of the strikes and
+/// the underlying price. The strikes themselves are always part of the grid, since the
+/// profit of a time spread peaks around them.
+fn price_grid(front: &Options, back: &Options) -> Vec<Positive> {
+    let lowest = front
+        .strike_price
+        .min(back.strike_price)
+        .min(front.underlying_price);
+    let highest = front
+        .strike_price
+        .max(back.strike_price)
+        .max(front.underlying_price);
+    payoff_scan::price_grid(
+        lowest.to_dec() * dec!(0.5),
+        highest.to_dec() * dec!(1.5),
+        [front.strike_price, back.strike_price],
+    )
+}
+
+/// Prices at which the profit at the front-month expiration crosses zero, in
+/// ascending order and rounded to two decimals.
+pub(super) fn break_even_points(
+    front: &Position,
+    back: &Position,
+) -> Result<Vec<Positive>, Box<dyn Error>> {
+    payoff_scan::break_even_points(&price_grid(&front.option, &back.option), &|price| {
+        profit_at_front_expiry(front, back, price)
+    })
+}
+
+/// Highest and lowest profit at the front-month expiration over the price grid.
+pub(super) fn profit_extremes(
+    front: &Position,
+    back: &Position,
+) -> Result<(Decimal, Decimal), Box<dyn Error>> {
+    payoff_scan::profit_extremes(&price_grid(&front.option, &back.option), &|price| {
+        profit_at_front_expiry(front, back, price)
+    })
+}
+
+/// Area under the positive part of the profit curve at the front-month expiration,
+/// integrated over the price grid and scaled down by 100.
+pub(super) fn profit_area(front: &Position, back: &Position) -> Result<Decimal, Box<dyn Error>> {
+    payoff_scan::profit_area(&price_grid(&front.option, &back.option), &|price| {
+        profit_at_front_expiry(front, back, price)
+    })
+}
+
+/// Value of `strategy` under the optimization `criteria`, or `None` when it cannot be
+/// computed, for instance because the strategy has no profit.
+pub(super) fn optimization_value<S: Strategies>(
+    strategy: &S,
+    criteria: &OptimizationCriteria,
+) -> Option<Decimal> {
+    match criteria {
+        OptimizationCriteria::Ratio => strategy.get_profit_ratio().ok(),
+        OptimizationCriteria::Area => strategy.get_profit_area().ok(),
+    }
+}
+
+/// Price intervals delimited by the break-even points in which the spread is
+/// profitable (`profitable == true`) or losing, with the probability of the
+/// underlying finishing inside each of them at the front-month expiration.
+pub(super) fn probability_ranges(
+    front: &Position,
+    back: &Position,
+    break_even_points: &[Positive],
+    profitable: bool,
+) -> Result<Vec<ProfitLossRange>, ProbabilityError> {
+    payoff_scan::probability_ranges(
+        &front.option,
+        vec![
+            front.option.implied_volatility,
+            back.option.implied_volatility,
+        ],
+        break_even_points,
+        &|price| profit_at_front_expiry(front, back, price),
+        profitable,
+    )
+}
+
+/// Every pair of chains of `series` where the second expires after the first, as
+/// `(front expiration, front chain, back expiration, back chain)`.
+pub(super) fn expiration_pairs(
+    series: &OptionSeries,
+) -> Vec<(ExpirationDate, &OptionChain, ExpirationDate, &OptionChain)> {
+    let chains: Vec<(&ExpirationDate, &OptionChain)> = series.chains.iter().collect();
+    let mut pairs = Vec::new();
+    for (i, (front_expiration, front_chain)) in chains.iter().enumerate() {
+        for (back_expiration, back_chain) in &chains[(i + 1)..] {
+            if back_expiration > front_expiration {
+                pairs.push((
+                    **front_expiration,
+                    *front_chain,
+                    **back_expiration,
+                    *back_chain,
+                ));
+            }
+        }
+    }
+    pairs
+}
+
+/// Implied volatility quoted for `option`, or `None` when the chain carries none.
+pub(super) fn quoted_volatility(option: &OptionData) -> Option<Positive> {
+    (option.implied_volatility > Positive::ZERO).then_some(option.implied_volatility)
+}
+
+#[cfg(test)]
+mod tests_time_spread {
+    use super::*;
+    use crate::model::types::{OptionStyle, OptionType, Side};
+    use crate::pos;
+    use chrono::Utc;
+
+    fn position(side: Side, strike: Positive, days: Positive, premium: Positive) -> Position {
+        let option = Options::new(
+            OptionType::European,
+            side,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(days),
+            pos!(0.2),
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.05),
+            OptionStyle::Call,
+            Positive::ZERO,
+            None,
+        );
+        Position::new(
+            option,
+            premium,
+            Utc::now(),
+            Positive::ZERO,
+            Positive::ZERO,
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_remaining_expiration() {
+        let front = position(Side::Short, pos!(100.0), pos!(30.0), pos!(2.5));
+        let back = position(Side::Long, pos!(100.0), pos!(90.0), pos!(4.6));
+        let remaining = remaining_expiration(&front.option, &back.option).unwrap();
+        assert_eq!(remaining.get_days().unwrap(), pos!(60.0));
+        assert!(remaining_expiration(&back.option, &front.option).is_err());
+
+        let shifted = shifted_back_expiration(
+            &front.option,
+            &back.option,
+            &ExpirationDate::Days(pos!(10.0)),
+        )
+        .unwrap();
+        assert_eq!(shifted.get_days().unwrap(), pos!(70.0));
+    }
+
+    #[test]
+    fn test_profit_at_front_expiry_prices_back_leg() {
+        let front = position(Side::Short, pos!(100.0), pos!(30.0), pos!(2.5));
+        let back = position(Side::Long, pos!(100.0), pos!(90.0), pos!(4.6));
+
+        let mut remaining = back.option.clone();
+        remaining.expiration_date = ExpirationDate::Days(pos!(60.0));
+        let expected = remaining.calculate_price_black_scholes().unwrap() + dec!(2.5) - dec!(4.6);
+        let profit = profit_at_front_expiry(&front, &back, &pos!(100.0)).unwrap();
+        assert_eq!(profit, expected);
+
+        let pnl = pnl_at_front_expiry(&front, &back, &pos!(100.0)).unwrap();
+        assert_eq!(pnl.realized.unwrap(), profit);
+    }
+
+    #[test]
+    fn test_break_even_points_bracket_the_strike() {
+        let front = position(Side::Short, pos!(100.0), pos!(30.0), pos!(2.5));
+        let back = position(Side::Long, pos!(100.0), pos!(90.0), pos!(4.6));
+        let break_even_points = break_even_points(&front, &back).unwrap();
+        assert_eq!(break_even_points.len(), 2);
+        assert!(break_even_points[0] < pos!(100.0));
+        assert!(break_even_points[1] > pos!(100.0));
+        for point in break_even_points {
+            let profit = profit_at_front_expiry(&front, &back, &point).unwrap();
+            assert!(profit.abs() < dec!(0.01));
+        }
+
+        let (max, min) = profit_extremes(&front, &back).unwrap();
+        assert!(max >= profit_at_front_expiry(&front, &back, &pos!(100.0)).unwrap());
+        assert!(min < Decimal::ZERO);
+        assert!(min >= dec!(-2.1));
+    }
+}
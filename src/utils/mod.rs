@@ -196,6 +196,8 @@ mod csv;
 pub mod file;
 /// Module for time-related utilities.
 pub mod time;
+/// Sample statistics shared by the backtester and the risk measures.
+pub(crate) mod stats;
 
 /// This module contains traits and type definitions used throughout the library.  It provides
 /// functionality for defining and implementing common traits, as well as type aliases for
@@ -0,0 +1,60 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+/// Arithmetic mean, `None` for an empty sample.
+pub(crate) fn mean(values: &[Decimal]) -> Option<Decimal> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<Decimal>() / Decimal::from(values.len()))
+}
+
+/// Empirical quantile using linear interpolation between order statistics,
+/// `None` for an empty sample or a level outside `[0, 1]`.
+pub(crate) fn quantile(values: &[Decimal], level: Decimal) -> Option<Decimal> {
+    if values.is_empty() || level < Decimal::ZERO || level > Decimal::ONE {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort();
+    let rank = level * Decimal::from(sorted.len() - 1);
+    let lower = rank.floor();
+    let index = lower.to_usize()?;
+    let upper = (index + 1).min(sorted.len() - 1);
+    Some(sorted[index] + (sorted[upper] - sorted[index]) * (rank - lower))
+}
+
+#[cfg(test)]
+mod tests_stats {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_mean() {
+        assert_eq!(mean(&[dec!(1), dec!(2), dec!(6)]), Some(dec!(3)));
+        assert_eq!(mean(&[]), None);
+    }
+
+    #[test]
+    fn test_quantile_interpolates() {
+        let values = vec![dec!(1), dec!(2), dec!(3), dec!(4), dec!(5)];
+        assert_eq!(quantile(&values, dec!(0.5)), Some(dec!(3)));
+        assert_eq!(quantile(&values, dec!(0.25)), Some(dec!(2)));
+        assert_eq!(quantile(&values, dec!(0.1)), Some(dec!(1.4)));
+        assert_eq!(quantile(&values, Decimal::ZERO), Some(dec!(1)));
+        assert_eq!(quantile(&values, Decimal::ONE), Some(dec!(5)));
+    }
+
+    #[test]
+    fn test_quantile_rejects_levels_outside_unit_interval() {
+        let values = vec![dec!(1), dec!(2), dec!(3)];
+        assert_eq!(quantile(&values, dec!(1.5)), None);
+        assert_eq!(quantile(&values, dec!(-0.1)), None);
+        assert_eq!(quantile(&[], dec!(0.5)), None);
+    }
+}
@@ -0,0 +1,520 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::error::VolatilityError;
+use crate::simulation::WalkType;
+use crate::utils::time::TimeFrame;
+use crate::volatility::optimizer::nelder_mead;
+use crate::volatility::svi::{MAX_ITERATIONS, to_decimal, to_f64};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+/// Fewest returns accepted by [`GarchModel::fit`].
+const MIN_OBSERVATIONS: usize = 30;
+
+/// Upper bound on the persistence of fitted GARCH and GJR-GARCH models.
+const MAX_PERSISTENCE: f64 = 0.9999;
+
+/// Conditional variance specification of a [`GarchModel`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GarchVariant {
+    /// GARCH(1,1): `σ²ₜ = ω + α ε²ₜ₋₁ + β σ²ₜ₋₁`.
+    Garch,
+    /// GJR-GARCH(1,1), where negative shocks add `γ ε²ₜ₋₁`:
+    /// `σ²ₜ = ω + (α + γ 1{εₜ₋₁ < 0}) ε²ₜ₋₁ + β σ²ₜ₋₁`.
+    GjrGarch,
+    /// EGARCH(1,1) on the log variance, with `zₜ = εₜ / σₜ`:
+    /// `ln σ²ₜ = ω + α (|zₜ₋₁| - √(2/π)) + γ zₜ₋₁ + β ln σ²ₜ₋₁`.
+    Egarch,
+}
+
+impl Display for GarchVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GarchVariant::Garch => write!(f, "GARCH(1,1)"),
+            GarchVariant::GjrGarch => write!(f, "GJR-GARCH(1,1)"),
+            GarchVariant::Egarch => write!(f, "EGARCH(1,1)"),
+        }
+    }
+}
+
+/// A GARCH-family model fitted to a return series by maximum likelihood.
+///
+/// All quantities are per period of the fitted returns; [`GarchModel::volatility_term_structure`]
+/// and [`GarchModel::to_walk_type`] annualize them for a given [`TimeFrame`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarchModel {
+    /// Conditional variance specification.
+    pub variant: GarchVariant,
+    /// Mean return per period, removed from the returns before fitting.
+    pub mean: Decimal,
+    /// Constant term `ω`.
+    pub omega: Decimal,
+    /// Reaction to the last shock `α`.
+    pub alpha: Decimal,
+    /// Persistence of the last variance `β`.
+    pub beta: Decimal,
+    /// Asymmetry `γ`; zero for GARCH(1,1).
+    pub gamma: Decimal,
+    /// Gaussian log-likelihood at the fitted parameters.
+    pub log_likelihood: Decimal,
+    /// Conditional variance of the last return.
+    pub last_variance: Positive,
+    /// Last return minus the mean.
+    pub last_residual: Decimal,
+}
+
+/// Fitted parameters in f64, in the order `ω, α, β, γ`.
+type Coefficients = [f64; 4];
+
+/// Conditional variance after a residual `residual` drawn with variance `variance`.
+fn next_variance(
+    variant: GarchVariant,
+    [omega, alpha, beta, gamma]: Coefficients,
+    residual: f64,
+    variance: f64,
+) -> f64 {
+    match variant {
+        GarchVariant::Garch => omega + alpha * residual * residual + beta * variance,
+        GarchVariant::GjrGarch => {
+            let leverage = if residual < 0.0 { gamma } else { 0.0 };
+            omega + (alpha + leverage) * residual * residual + beta * variance
+        }
+        GarchVariant::Egarch => {
+            let z = residual / variance.sqrt();
+            (omega + alpha * (z.abs() - (2.0 / PI).sqrt()) + gamma * z + beta * variance.ln()).exp()
+        }
+    }
+}
+
+/// Runs the variance recursion over `residuals`, starting from their sample variance.
+///
+/// Returns the Gaussian log-likelihood and the variance of the last residual, or `None`
+/// if the variance stops being positive and finite.
+fn filter(
+    variant: GarchVariant,
+    coefficients: Coefficients,
+    residuals: &[f64],
+    initial_variance: f64,
+) -> Option<(f64, f64)> {
+    let mut variance = initial_variance;
+    let mut log_likelihood = 0.0;
+    for (i, &residual) in residuals.iter().enumerate() {
+        if i > 0 {
+            variance = next_variance(variant, coefficients, residuals[i - 1], variance);
+        }
+        if !variance.is_finite() || variance <= 0.0 {
+            return None;
+        }
+        log_likelihood -= 0.5 * ((2.0 * PI).ln() + variance.ln() + residual * residual / variance);
+    }
+    Some((log_likelihood, variance))
+}
+
+fn logistic(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+/// Maps unconstrained search coordinates to coefficients that keep the variance positive
+/// and, for GARCH and GJR-GARCH, stationary.
+fn unpack(variant: GarchVariant, x: &[f64]) -> Coefficients {
+    match variant {
+        GarchVariant::Garch => {
+            let persistence = MAX_PERSISTENCE * logistic(x[1]);
+            let share = logistic(x[2]);
+            [
+                x[0].exp(),
+                persistence * share,
+                persistence * (1.0 - share),
+                0.0,
+            ]
+        }
+        GarchVariant::GjrGarch => {
+            // α, γ/2 and β split the persistence
+            let persistence = MAX_PERSISTENCE * logistic(x[1]);
+            let weights = [x[2].exp(), x[3].exp(), 1.0];
+            let total: f64 = weights.iter().sum();
+            [
+                x[0].exp(),
+                persistence * weights[0] / total,
+                persistence * weights[2] / total,
+                2.0 * persistence * weights[1] / total,
+            ]
+        }
+        GarchVariant::Egarch => [x[0], x[1], MAX_PERSISTENCE * x[2].tanh(), x[3]],
+    }
+}
+
+impl GarchModel {
+    /// Fits a GARCH-family model to `returns` by Gaussian maximum likelihood.
+    ///
+    /// `returns` are per-period log returns, for example `calculate_log_returns` applied
+    /// to the closes of `read_ohlcv_from_zip` candles. The sample mean is removed first and
+    /// the recursion starts from the sample variance. The likelihood is maximized with a
+    /// Nelder-Mead search over a reparametrization that keeps GARCH and GJR-GARCH
+    /// stationary (`γ` is restricted to non-negative values) and EGARCH's `|β| < 1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if there are fewer than 30 returns, the
+    /// returns have no variance, or no parameters give a finite likelihood.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use optionstratlib::volatility::{GarchModel, GarchVariant};
+    /// use rust_decimal::Decimal;
+    ///
+    /// let returns: Vec<Decimal> = (0..250)
+    ///     .map(|i| Decimal::new(((i * 37) % 23) as i64 - 11, 3))
+    ///     .collect();
+    /// let model = GarchModel::fit(&returns, GarchVariant::Garch).unwrap();
+    /// assert!(model.persistence() < Decimal::ONE);
+    /// let forecast = model.forecast(10);
+    /// assert_eq!(forecast.len(), 10);
+    /// ```
+    pub fn fit(returns: &[Decimal], variant: GarchVariant) -> Result<Self, VolatilityError> {
+        if returns.len() < MIN_OBSERVATIONS {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!(
+                    "{variant} needs at least {MIN_OBSERVATIONS} returns, got {}",
+                    returns.len()
+                ),
+            });
+        }
+        let values: Vec<f64> = returns.iter().map(|&r| to_f64(r)).collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let residuals: Vec<f64> = values.iter().map(|r| r - mean).collect();
+        let sample_variance = residuals.iter().map(|e| e * e).sum::<f64>() / residuals.len() as f64;
+        if returns.iter().all(|&r| r == returns[0])
+            || !sample_variance.is_finite()
+            || sample_variance <= 0.0
+        {
+            return Err(VolatilityError::CalibrationError {
+                reason: "Returns have no variance".to_string(),
+            });
+        }
+
+        let start = match variant {
+            GarchVariant::Garch => vec![
+                (sample_variance * 0.05).ln(),
+                logit(0.95 / MAX_PERSISTENCE),
+                logit(0.1),
+            ],
+            GarchVariant::GjrGarch => vec![
+                (sample_variance * 0.05).ln(),
+                logit(0.95 / MAX_PERSISTENCE),
+                (0.05_f64 / 0.85).ln(),
+                (0.05_f64 / 0.85).ln(),
+            ],
+            GarchVariant::Egarch => vec![
+                sample_variance.ln() * 0.05,
+                0.1,
+                (0.95 / MAX_PERSISTENCE).atanh(),
+                -0.05,
+            ],
+        };
+        let objective = |x: &[f64]| {
+            filter(variant, unpack(variant, x), &residuals, sample_variance)
+                .map_or(f64::NAN, |(log_likelihood, _)| -log_likelihood)
+        };
+        // Restart once from the first result, which lets the simplex recover its shape
+        let (first, _) = nelder_mead(objective, &start, 0.5, MAX_ITERATIONS, 1e-10);
+        let (best, _) = nelder_mead(objective, &first, 0.1, MAX_ITERATIONS, 1e-10);
+        let coefficients = unpack(variant, &best);
+        let (log_likelihood, last_variance) =
+            filter(variant, coefficients, &residuals, sample_variance).ok_or_else(|| {
+                VolatilityError::CalibrationError {
+                    reason: format!("{variant} likelihood is not finite"),
+                }
+            })?;
+        let [omega, alpha, beta, gamma] = coefficients;
+
+        Ok(GarchModel {
+            variant,
+            mean: to_decimal(mean)?,
+            omega: to_decimal(omega)?,
+            alpha: to_decimal(alpha)?,
+            beta: to_decimal(beta)?,
+            gamma: to_decimal(gamma)?,
+            log_likelihood: to_decimal(log_likelihood)?,
+            last_variance: Positive::from(to_decimal(last_variance)?),
+            last_residual: to_decimal(residuals[residuals.len() - 1])?,
+        })
+    }
+
+    fn coefficients(&self) -> Coefficients {
+        [
+            to_f64(self.omega),
+            to_f64(self.alpha),
+            to_f64(self.beta),
+            to_f64(self.gamma),
+        ]
+    }
+
+    /// Rate at which variance shocks decay: `α + β` for GARCH, `α + γ/2 + β` for
+    /// GJR-GARCH (assuming symmetric shocks) and `β` for EGARCH.
+    pub fn persistence(&self) -> Decimal {
+        match self.variant {
+            GarchVariant::Garch => self.alpha + self.beta,
+            GarchVariant::GjrGarch => self.alpha + self.gamma / Decimal::TWO + self.beta,
+            GarchVariant::Egarch => self.beta,
+        }
+    }
+
+    /// Unconditional variance per period, or `None` if the model is not stationary.
+    ///
+    /// For EGARCH this is `exp(ω / (1 - β))`, the exponential of the mean log variance.
+    pub fn long_run_variance(&self) -> Option<Positive> {
+        let persistence = self.persistence();
+        if persistence >= Decimal::ONE {
+            return None;
+        }
+        let variance = match self.variant {
+            GarchVariant::Egarch => (to_f64(self.omega) / (1.0 - to_f64(persistence))).exp(),
+            _ => to_f64(self.omega) / (1.0 - to_f64(persistence)),
+        };
+        to_decimal(variance).ok().map(Positive::from)
+    }
+
+    /// Expected conditional variance for each of the next `horizon` periods.
+    ///
+    /// The first value follows from the last residual; later ones decay towards the
+    /// long-run variance at the rate given by [`GarchModel::persistence`]. EGARCH
+    /// forecasts iterate the log variance and ignore the convexity of the exponential.
+    pub fn forecast(&self, horizon: usize) -> Vec<Positive> {
+        let coefficients = self.coefficients();
+        let [omega, _, beta, _] = coefficients;
+        let persistence = to_f64(self.persistence());
+        let mut variance = next_variance(
+            self.variant,
+            coefficients,
+            to_f64(self.last_residual),
+            to_f64(self.last_variance.to_dec()),
+        );
+        let mut forecasts = Vec::with_capacity(horizon);
+        for _ in 0..horizon {
+            forecasts.push(Positive::from(
+                to_decimal(variance).unwrap_or(Decimal::ZERO),
+            ));
+            variance = match self.variant {
+                GarchVariant::Egarch => (omega + beta * variance.ln()).exp(),
+                _ => omega + persistence * variance,
+            };
+        }
+        forecasts
+    }
+
+    /// Annualized volatility term structure for maturities of `1..=horizon` periods.
+    ///
+    /// Each value is the square root of the average forecast variance up to that maturity,
+    /// scaled by the periods per year of `time_frame`, which is the volatility to use for an
+    /// option expiring after that many periods.
+    pub fn volatility_term_structure(
+        &self,
+        horizon: usize,
+        time_frame: TimeFrame,
+    ) -> Vec<Positive> {
+        let periods_per_year = time_frame.periods_per_year().to_dec();
+        let mut cumulative = Decimal::ZERO;
+        self.forecast(horizon)
+            .into_iter()
+            .enumerate()
+            .map(|(i, variance)| {
+                cumulative += variance.to_dec();
+                (Positive::from(cumulative * periods_per_year / Decimal::from(i + 1))).sqrt()
+            })
+            .collect()
+    }
+
+    /// Converts the model into `WalkType::Garch` parameters for simulation.
+    ///
+    /// The walk's `volatility` is the annualized long-run volatility, which also sets its
+    /// `ω`, and `drift` is the annualized mean return. GJR-GARCH maps to the symmetric
+    /// GARCH with the same persistence, `α + γ/2`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` for EGARCH, which has no GARCH(1,1)
+    /// equivalent, or if the model is not stationary.
+    pub fn to_walk_type(&self, time_frame: TimeFrame) -> Result<WalkType, VolatilityError> {
+        let alpha = match self.variant {
+            GarchVariant::Garch => self.alpha,
+            GarchVariant::GjrGarch => self.alpha + self.gamma / Decimal::TWO,
+            GarchVariant::Egarch => {
+                return Err(VolatilityError::CalibrationError {
+                    reason: "EGARCH cannot be expressed as a GARCH(1,1) walk".to_string(),
+                });
+            }
+        };
+        let long_run_variance =
+            self.long_run_variance()
+                .ok_or_else(|| VolatilityError::CalibrationError {
+                    reason: format!("{} is not stationary", self.variant),
+                })?;
+        let periods_per_year = time_frame.periods_per_year();
+        Ok(WalkType::Garch {
+            dt: Positive::ONE / periods_per_year,
+            drift: self.mean * periods_per_year.to_dec(),
+            volatility: (long_run_variance * periods_per_year).sqrt(),
+            alpha: Positive::from(alpha),
+            beta: Positive::from(self.beta),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_garch {
+    use super::*;
+    use crate::pos;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand_distr::{Distribution, StandardNormal};
+    use rust_decimal_macros::dec;
+
+    /// Simulates `n` returns from a GJR-GARCH process; `gamma = 0` gives GARCH(1,1).
+    fn simulate(n: usize, omega: f64, alpha: f64, beta: f64, gamma: f64) -> Vec<Decimal> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut variance = omega / (1.0 - alpha - beta - gamma / 2.0);
+        (0..n)
+            .map(|_| {
+                let z: f64 = StandardNormal.sample(&mut rng);
+                let residual = variance.sqrt() * z;
+                variance = next_variance(
+                    GarchVariant::GjrGarch,
+                    [omega, alpha, beta, gamma],
+                    residual,
+                    variance,
+                );
+                Decimal::from_f64_retain(0.0004 + residual).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_recovers_garch_parameters() {
+        let returns = simulate(4000, 4e-6, 0.08, 0.9, 0.0);
+        let model = GarchModel::fit(&returns, GarchVariant::Garch).unwrap();
+        assert!((model.alpha - dec!(0.08)).abs() < dec!(0.03), "{model:?}");
+        assert!((model.beta - dec!(0.9)).abs() < dec!(0.04), "{model:?}");
+        assert!((model.persistence() - dec!(0.98)).abs() < dec!(0.015));
+        assert!((model.mean - dec!(0.0004)).abs() < dec!(0.0005));
+        assert_eq!(model.gamma, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_asymmetric_models_detect_leverage() {
+        let returns = simulate(4000, 4e-6, 0.02, 0.9, 0.12);
+        let garch = GarchModel::fit(&returns, GarchVariant::Garch).unwrap();
+        let gjr = GarchModel::fit(&returns, GarchVariant::GjrGarch).unwrap();
+        let egarch = GarchModel::fit(&returns, GarchVariant::Egarch).unwrap();
+        assert!(gjr.gamma > gjr.alpha, "{gjr:?}");
+        assert!(gjr.log_likelihood > garch.log_likelihood);
+        // Negative shocks raise EGARCH variance through a negative gamma
+        assert!(egarch.gamma < Decimal::ZERO, "{egarch:?}");
+        assert!(egarch.beta.abs() < Decimal::ONE);
+        assert!(egarch.log_likelihood > garch.log_likelihood);
+    }
+
+    #[test]
+    fn test_forecast_converges_to_long_run_variance() {
+        let model = GarchModel {
+            variant: GarchVariant::Garch,
+            mean: Decimal::ZERO,
+            omega: dec!(0.000002),
+            alpha: dec!(0.1),
+            beta: dec!(0.85),
+            gamma: Decimal::ZERO,
+            log_likelihood: Decimal::ZERO,
+            last_variance: pos!(0.0001),
+            last_residual: dec!(0.02),
+        };
+        let long_run = model.long_run_variance().unwrap();
+        assert!((long_run.to_f64() - 0.00004).abs() < 1e-15);
+        let forecast = model.forecast(200);
+        // 0.000002 + 0.1 * 0.0004 + 0.85 * 0.0001
+        assert_eq!(forecast[0], pos!(0.000127));
+        assert!(forecast.windows(2).all(|pair| pair[1] < pair[0]));
+        // The gap shrinks by 0.95 per period
+        assert!((forecast[199].to_f64() - long_run.to_f64()).abs() < 1e-8);
+
+        let term_structure = model.volatility_term_structure(200, TimeFrame::Day);
+        assert_eq!(term_structure.len(), 200);
+        assert!(term_structure.windows(2).all(|pair| pair[1] < pair[0]));
+        let periods = TimeFrame::Day.periods_per_year();
+        assert_eq!(term_structure[0], (forecast[0] * periods).sqrt());
+    }
+
+    #[test]
+    fn test_egarch_forecast_and_walk_type() {
+        let model = GarchModel {
+            variant: GarchVariant::Egarch,
+            mean: Decimal::ZERO,
+            omega: dec!(-0.5),
+            alpha: dec!(0.1),
+            beta: dec!(0.95),
+            gamma: dec!(-0.05),
+            log_likelihood: Decimal::ZERO,
+            last_variance: pos!(0.0004),
+            last_residual: dec!(-0.01),
+        };
+        let long_run = model.long_run_variance().unwrap().to_f64();
+        assert!((long_run - (-10.0_f64).exp()).abs() < 1e-12);
+        let forecast = model.forecast(500);
+        assert!((forecast[499].to_f64() - long_run).abs() < 1e-8);
+        assert!(matches!(
+            model.to_walk_type(TimeFrame::Day),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_walk_type() {
+        let returns = simulate(2000, 4e-6, 0.02, 0.9, 0.12);
+        let model = GarchModel::fit(&returns, GarchVariant::GjrGarch).unwrap();
+        let periods = TimeFrame::Day.periods_per_year();
+        match model.to_walk_type(TimeFrame::Day).unwrap() {
+            WalkType::Garch {
+                dt,
+                drift,
+                volatility,
+                alpha,
+                beta,
+            } => {
+                assert_eq!(dt, Positive::ONE / periods);
+                assert_eq!(drift, model.mean * periods.to_dec());
+                assert_eq!(alpha.to_dec(), model.alpha + model.gamma / Decimal::TWO);
+                assert_eq!(beta.to_dec(), model.beta);
+                assert!(alpha + beta < Positive::ONE);
+                assert_eq!(
+                    volatility,
+                    (model.long_run_variance().unwrap() * periods).sqrt()
+                );
+            }
+            other => panic!("Expected Garch walk, got {other}"),
+        }
+    }
+
+    #[test]
+    fn test_fit_rejects_short_or_flat_series() {
+        let short = vec![dec!(0.01); 10];
+        assert!(matches!(
+            GarchModel::fit(&short, GarchVariant::Garch),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+        let flat = vec![dec!(0.01); 100];
+        assert!(matches!(
+            GarchModel::fit(&flat, GarchVariant::Egarch),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+    }
+}
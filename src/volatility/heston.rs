@@ -0,0 +1,388 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::chains::chain::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::curves::{Curve, Point2D};
+use crate::error::VolatilityError;
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::pricing::HestonParameters;
+use crate::pricing::heston::heston_call_f64;
+use crate::volatility::optimizer::nelder_mead;
+use crate::volatility::sabr::chain_quotes;
+use crate::volatility::svi::{MAX_ITERATIONS, forward_price, strike_grid, to_decimal, to_f64};
+use crate::volatility::{VolatilitySmile, solve_implied_volatility};
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use statrs::distribution::{ContinuousCDF, Normal};
+use std::collections::BTreeSet;
+
+/// Black-Scholes call price and vega on the forward, in f64.
+fn black_call(forward: f64, strike: f64, years: f64, discount: f64, volatility: f64) -> (f64, f64) {
+    let normal = Normal::new(0.0, 1.0).unwrap();
+    let deviation = volatility * years.sqrt();
+    let d1 = ((forward / strike).ln() + deviation * deviation / 2.0) / deviation;
+    let d2 = d1 - deviation;
+    let price = discount * (forward * normal.cdf(d1) - strike * normal.cdf(d2));
+    let vega = discount * forward * (-d1 * d1 / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+        * years.sqrt();
+    (price, vega)
+}
+
+/// A Heston smile for one expiration, implied by a set of model parameters.
+///
+/// Implements [`VolatilitySmile`] by pricing each strike with
+/// [`crate::pricing::heston_price`] and inverting the price to a Black-Scholes
+/// volatility.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HestonSmile {
+    /// Model parameters.
+    pub parameters: HestonParameters,
+    /// Spot price of the underlying.
+    pub underlying_price: Positive,
+    /// Continuously compounded risk-free rate.
+    pub risk_free_rate: Decimal,
+    /// Continuous dividend yield.
+    pub dividend_yield: Positive,
+    /// Days to expiration.
+    pub days: Positive,
+    /// Strikes covered by `smile`.
+    pub strike_range: (Positive, Positive),
+    /// Root mean square error in implied volatility against the calibration quotes.
+    pub rmse: Decimal,
+}
+
+impl HestonSmile {
+    /// Creates a smile from known parameters.
+    pub fn new(
+        parameters: HestonParameters,
+        underlying_price: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        days: Positive,
+        strike_range: (Positive, Positive),
+    ) -> Self {
+        HestonSmile {
+            parameters,
+            underlying_price,
+            risk_free_rate,
+            dividend_yield,
+            days,
+            strike_range,
+            rmse: Decimal::ZERO,
+        }
+    }
+
+    /// Calibrates `κ`, `θ`, `ξ`, `ρ` and `v₀` to an option chain.
+    ///
+    /// Quotes are built as for [`crate::volatility::SABRSmile::calibrate`]: the mid of
+    /// the bid and ask implied volatilities on the out-of-the-money side, or the chain's
+    /// implied volatility. The search minimizes vega-weighted price errors, a first-order
+    /// proxy for implied volatility errors that avoids inverting every model price, and
+    /// each error is scaled by the quote's volatility spread. The Feller condition is
+    /// not enforced; see [`HestonParameters::feller_condition`].
+    ///
+    /// A single expiration cannot separate the speed of mean reversion from the long-term
+    /// variance well, so several parameter sets may fit the smile equally.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if the expiration cannot be read or
+    /// fewer than five strikes can be quoted, and `VolatilityError::InvalidTime` if the
+    /// chain has expired.
+    pub fn calibrate(chain: &OptionChain) -> Result<Self, VolatilityError> {
+        let days = chain
+            .get_expiration()
+            .and_then(|expiration| expiration.get_days().ok())
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: format!("Invalid expiration date {}", chain.get_expiration_date()),
+            })?;
+        let years = days / DAYS_IN_A_YEAR;
+        if years == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: years,
+                reason: "Cannot calibrate Heston to an expired chain".to_string(),
+            });
+        }
+        let t = to_f64(years.to_dec());
+        let rate = chain.risk_free_rate.unwrap_or(Decimal::ZERO);
+        let dividend_yield = chain.dividend_yield.unwrap_or(Positive::ZERO);
+        let (spot, r, q) = (
+            to_f64(chain.underlying_price.to_dec()),
+            to_f64(rate),
+            to_f64(dividend_yield.to_dec()),
+        );
+        let forward = forward_price(chain.underlying_price, rate, dividend_yield, t);
+        let discount = (-r * t).exp();
+        let quotes = chain_quotes(chain, days, forward);
+        if quotes.len() < 5 {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!(
+                    "Heston needs at least 5 implied volatility quotes, got {}",
+                    quotes.len()
+                ),
+            });
+        }
+        // (strike, market call price, vega scaled by the quote's spread)
+        let targets: Vec<(f64, f64, f64)> = quotes
+            .iter()
+            .map(|quote| {
+                let (price, vega) =
+                    black_call(forward, quote.strike, t, discount, quote.volatility);
+                (quote.strike, price, vega.max(1e-8) * quote.spread)
+            })
+            .collect();
+
+        let unpack = |x: &[f64]| {
+            [
+                x[0].exp(),
+                x[1].exp(),
+                x[2].exp(),
+                0.999 * x[3].tanh(),
+                x[4].exp(),
+            ]
+        };
+        let objective = |x: &[f64]| {
+            let parameters = unpack(x);
+            targets
+                .iter()
+                .map(|&(strike, price, weight)| {
+                    let model = heston_call_f64(spot, strike, t, r, q, &parameters);
+                    ((model - price) / weight).powi(2)
+                })
+                .sum::<f64>()
+        };
+        let atm_variance = quotes
+            .iter()
+            .min_by(|a, b| {
+                (a.strike - forward)
+                    .abs()
+                    .total_cmp(&(b.strike - forward).abs())
+            })
+            .map(|quote| quote.volatility * quote.volatility)
+            .unwrap_or(0.04)
+            .ln();
+        let start = [2.0_f64.ln(), atm_variance, 0.5_f64.ln(), -0.5, atm_variance];
+        // Restart once from the first result, which lets the simplex recover its shape
+        let (first, _) = nelder_mead(objective, &start, 0.5, MAX_ITERATIONS, 1e-14);
+        let (best, _) = nelder_mead(objective, &first, 0.1, MAX_ITERATIONS, 1e-14);
+        let [kappa, theta, xi, rho, v0] = unpack(&best);
+        let parameters = HestonParameters::new(
+            Positive::from(to_decimal(kappa)?),
+            Positive::from(to_decimal(theta)?),
+            Positive::from(to_decimal(xi)?),
+            to_decimal(rho)?,
+            Positive::from(to_decimal(v0)?),
+        );
+
+        let strikes = quotes
+            .iter()
+            .map(|quote| to_decimal(quote.strike).map(Positive::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let strike_range = (
+            strikes
+                .iter()
+                .copied()
+                .fold(Positive::INFINITY, Positive::min),
+            strikes.iter().copied().fold(Positive::ZERO, Positive::max),
+        );
+        let mut smile = HestonSmile::new(
+            parameters,
+            chain.underlying_price,
+            rate,
+            dividend_yield,
+            days,
+            strike_range,
+        );
+        let squared_error = strikes
+            .iter()
+            .zip(&quotes)
+            .map(|(&strike, quote)| {
+                smile
+                    .implied_volatility(strike)
+                    .map(|iv| (to_f64(iv.to_dec()) - quote.volatility).powi(2))
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .iter()
+            .sum::<f64>()
+            / quotes.len() as f64;
+        smile.rmse = to_decimal(squared_error.sqrt())?;
+        Ok(smile)
+    }
+
+    /// Black-Scholes implied volatility of the Heston price at `strike`.
+    ///
+    /// The out-of-the-money option is inverted, where the price is most sensitive to
+    /// volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if the Heston price cannot be
+    /// computed, or the solver's error if it cannot be inverted.
+    pub fn implied_volatility(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        let years = to_f64((self.days / DAYS_IN_A_YEAR).to_dec());
+        let forward = forward_price(
+            self.underlying_price,
+            self.risk_free_rate,
+            self.dividend_yield,
+            years,
+        );
+        let style = if to_f64(strike.to_dec()) >= forward {
+            OptionStyle::Call
+        } else {
+            OptionStyle::Put
+        };
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            "HESTON".to_string(),
+            strike,
+            ExpirationDate::Days(self.days),
+            Positive::ONE,
+            Positive::ONE,
+            self.underlying_price,
+            self.risk_free_rate,
+            style,
+            self.dividend_yield,
+            None,
+        );
+        let price = crate::pricing::heston_price(&option, &self.parameters).map_err(|e| {
+            VolatilityError::CalibrationError {
+                reason: e.to_string(),
+            }
+        })?;
+        solve_implied_volatility(&option, price).map(|result| result.volatility)
+    }
+}
+
+impl VolatilitySmile for HestonSmile {
+    /// Samples the Heston-implied smile across `strike_range`.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = strike_grid(self.strike_range)
+            .into_iter()
+            .filter_map(|strike| {
+                self.implied_volatility(strike)
+                    .ok()
+                    .map(|iv| Point2D::new(strike.to_dec(), iv.to_dec()))
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests_heston {
+    use super::*;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::volatility::svi::GRID_POINTS;
+    use crate::{assert_decimal_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn parameters() -> HestonParameters {
+        HestonParameters::new(pos!(2.0), pos!(0.04), pos!(0.6), dec!(-0.6), pos!(0.05))
+    }
+
+    fn chain_from_heston(parameters: &HestonParameters, strikes: &[f64]) -> OptionChain {
+        let mut chain = OptionChain::new(
+            "TEST",
+            pos!(100.0),
+            get_x_days_formatted_pos(pos!(90.0)),
+            None,
+            None,
+        );
+        let days = chain.get_expiration().unwrap().get_days().unwrap();
+        let smile = HestonSmile::new(
+            *parameters,
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            days,
+            (pos!(75.0), pos!(125.0)),
+        );
+        for &strike in strikes {
+            let iv = smile.implied_volatility(pos!(strike)).unwrap();
+            chain.add_option(
+                pos!(strike),
+                None,
+                None,
+                None,
+                None,
+                iv,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        chain
+    }
+
+    #[test]
+    fn test_calibration_reproduces_smile() {
+        let strikes: Vec<f64> = (75..=125).step_by(5).map(f64::from).collect();
+        let chain = chain_from_heston(&parameters(), &strikes);
+        let smile = HestonSmile::calibrate(&chain).unwrap();
+        assert!(smile.rmse < dec!(0.001), "rmse {}", smile.rmse);
+        assert_eq!(smile.strike_range, (pos!(75.0), pos!(125.0)));
+        for option in chain.options.iter() {
+            let fitted = smile.implied_volatility(option.strike_price).unwrap();
+            assert_decimal_eq!(
+                fitted.to_dec(),
+                option.implied_volatility.to_dec(),
+                dec!(0.002)
+            );
+        }
+        // The skew comes from the correlation
+        assert!(smile.parameters.rho < dec!(-0.3));
+    }
+
+    #[test]
+    fn test_negative_correlation_gives_downward_skew() {
+        let smile = HestonSmile::new(
+            parameters(),
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            pos!(90.0),
+            (pos!(80.0), pos!(120.0)),
+        );
+        let low = smile.implied_volatility(pos!(80.0)).unwrap();
+        let atm = smile.implied_volatility(pos!(100.0)).unwrap();
+        let high = smile.implied_volatility(pos!(120.0)).unwrap();
+        assert!(low > atm && atm > high);
+    }
+
+    #[test]
+    fn test_smile_curve() {
+        let smile = HestonSmile::new(
+            parameters(),
+            pos!(100.0),
+            dec!(0.03),
+            pos!(0.01),
+            pos!(60.0),
+            (pos!(80.0), pos!(120.0)),
+        );
+        let curve = smile.smile();
+        assert_eq!(curve.points.len(), GRID_POINTS);
+        assert!(
+            curve
+                .points
+                .iter()
+                .all(|point| point.y > dec!(0.1) && point.y < dec!(0.4))
+        );
+    }
+
+    #[test]
+    fn test_calibration_needs_five_quotes() {
+        let chain = chain_from_heston(&parameters(), &[90.0, 100.0, 110.0]);
+        assert!(matches!(
+            HestonSmile::calibrate(&chain),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+    }
+}
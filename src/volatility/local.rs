@@ -0,0 +1,466 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::error::VolatilityError;
+use crate::series::OptionSeries;
+use crate::surfaces::{Point3D, Surface};
+use crate::volatility::SSVISurface;
+use crate::volatility::svi::{to_decimal, to_f64};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Columns of the grid built from an SSVI surface.
+const LOG_MONEYNESS_POINTS: usize = 41;
+
+/// Rows of the grid built from an SSVI surface.
+const MATURITY_POINTS: usize = 40;
+
+/// Bounds applied to local volatility where Dupire's formula breaks down.
+const MIN_LOCAL_VOLATILITY: f64 = 0.01;
+const MAX_LOCAL_VOLATILITY: f64 = 5.0;
+
+/// A Dupire local volatility surface `σ(K, T)` on a grid of log forward-moneyness
+/// `ln(K / F_T)` and maturity.
+///
+/// Between grid nodes the volatility is interpolated bilinearly; outside the grid it is
+/// held flat. Moneyness is measured against the forward of `underlying_price`, so the
+/// surface behaves as sticky-strike when the spot moves.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalVolatilitySurface {
+    /// Spot price the surface was built at.
+    pub underlying_price: Positive,
+    /// Risk-free rate used for forwards.
+    pub risk_free_rate: Decimal,
+    /// Dividend yield used for forwards.
+    pub dividend_yield: Positive,
+    /// Log forward-moneyness of the grid columns, ascending.
+    pub log_moneyness: Vec<Decimal>,
+    /// Maturities of the grid rows in days, ascending.
+    pub days: Vec<Positive>,
+    /// Local volatility by maturity (rows) and log forward-moneyness (columns).
+    pub volatilities: Vec<Vec<Positive>>,
+}
+
+/// The grid of a [`LocalVolatilitySurface`] in f64, for path simulation.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalVolatilityGrid {
+    log_spot: f64,
+    carry: f64,
+    log_moneyness: Vec<f64>,
+    years: Vec<f64>,
+    volatilities: Vec<Vec<f64>>,
+}
+
+/// Bracketing index and weight of `x` on an ascending axis, flat outside it.
+fn locate(axis: &[f64], x: f64) -> (usize, usize, f64) {
+    let last = axis.len() - 1;
+    if x <= axis[0] {
+        return (0, 0, 0.0);
+    }
+    if x >= axis[last] {
+        return (last, last, 0.0);
+    }
+    let upper = axis.partition_point(|node| *node <= x);
+    let lower = upper - 1;
+    (
+        lower,
+        upper,
+        (x - axis[lower]) / (axis[upper] - axis[lower]),
+    )
+}
+
+impl LocalVolatilityGrid {
+    /// Local volatility for an underlying at `price` after `years`.
+    pub(crate) fn volatility(&self, price: f64, years: f64) -> f64 {
+        let y = price.ln() - self.log_spot - self.carry * years;
+        let (t0, t1, wt) = locate(&self.years, years);
+        let (y0, y1, wy) = locate(&self.log_moneyness, y);
+        let row = |t: usize| self.volatilities[t][y0] * (1.0 - wy) + self.volatilities[t][y1] * wy;
+        row(t0) * (1.0 - wt) + row(t1) * wt
+    }
+}
+
+/// Dupire local variance from total implied variance `w(y, T)`, in Gatheral's form:
+///
+/// `σ² = ∂w/∂T / (1 - y/w ∂w/∂y + 1/4 (-1/4 - 1/w + y²/w²) (∂w/∂y)² + 1/2 ∂²w/∂y²)`
+fn dupire_volatility<F>(total_variance: F, y: f64, years: f64) -> Result<f64, VolatilityError>
+where
+    F: Fn(f64, f64) -> Result<f64, VolatilityError>,
+{
+    let (hy, ht) = (1e-3, years * 1e-2);
+    let w = total_variance(y, years)?;
+    let (w_up, w_down) = (
+        total_variance(y + hy, years)?,
+        total_variance(y - hy, years)?,
+    );
+    let w_later = total_variance(y, years + ht)?;
+    let w_earlier = total_variance(y, years - ht)?;
+    let dw_dt = (w_later - w_earlier) / (2.0 * ht);
+    let dw_dy = (w_up - w_down) / (2.0 * hy);
+    let d2w_dy2 = (w_up - 2.0 * w + w_down) / (hy * hy);
+    let denominator = 1.0 - y / w * dw_dy
+        + 0.25 * (-0.25 - 1.0 / w + y * y / (w * w)) * dw_dy * dw_dy
+        + 0.5 * d2w_dy2;
+    let variance = dw_dt / denominator;
+    Ok(
+        if variance.is_finite() && denominator > 0.0 && variance > 0.0 {
+            variance
+                .sqrt()
+                .clamp(MIN_LOCAL_VOLATILITY, MAX_LOCAL_VOLATILITY)
+        } else {
+            MIN_LOCAL_VOLATILITY
+        },
+    )
+}
+
+impl LocalVolatilitySurface {
+    /// Creates a surface from a grid of local volatilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if an axis is empty or not strictly
+    /// ascending, or `volatilities` does not have one row per maturity and one column
+    /// per moneyness.
+    pub fn new(
+        underlying_price: Positive,
+        risk_free_rate: Decimal,
+        dividend_yield: Positive,
+        log_moneyness: Vec<Decimal>,
+        days: Vec<Positive>,
+        volatilities: Vec<Vec<Positive>>,
+    ) -> Result<Self, VolatilityError> {
+        let invalid = |reason: &str| VolatilityError::CalibrationError {
+            reason: reason.to_string(),
+        };
+        if log_moneyness.is_empty() || days.is_empty() {
+            return Err(invalid("Local volatility grid has an empty axis"));
+        }
+        if log_moneyness.windows(2).any(|pair| pair[0] >= pair[1])
+            || days.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err(invalid("Local volatility grid axes must be ascending"));
+        }
+        if volatilities.len() != days.len()
+            || volatilities
+                .iter()
+                .any(|row| row.len() != log_moneyness.len())
+        {
+            return Err(invalid(
+                "Local volatility grid must have one row per maturity and one column per moneyness",
+            ));
+        }
+        Ok(LocalVolatilitySurface {
+            underlying_price,
+            risk_free_rate,
+            dividend_yield,
+            log_moneyness,
+            days,
+            volatilities,
+        })
+    }
+
+    /// Extracts local volatility from an SSVI implied volatility surface with Dupire's
+    /// formula.
+    ///
+    /// SSVI is smooth in strike and maturity, so its derivatives can be taken by finite
+    /// differences. The grid spans the surface's strike range, widened by 10% in
+    /// log-moneyness, and maturities up to its last expiration. Where calendar or
+    /// butterfly arbitrage makes the local variance negative the volatility is floored
+    /// at 1%, and it is capped at 500%.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` if the surface has no slices.
+    pub fn from_ssvi(surface: &SSVISurface) -> Result<Self, VolatilityError> {
+        let last_days = surface
+            .slices
+            .last()
+            .map(|slice| slice.days)
+            .ok_or_else(|| VolatilityError::InvalidTime {
+                time: Positive::ZERO,
+                reason: "The surface has no slices".to_string(),
+            })?;
+        let spot = to_f64(surface.underlying_price.to_dec());
+        let (low, high) = (
+            (to_f64(surface.strike_range.0.to_dec()) / spot).ln() - 0.1,
+            (to_f64(surface.strike_range.1.to_dec()) / spot).ln() + 0.1,
+        );
+        let log_moneyness: Vec<f64> = (0..LOG_MONEYNESS_POINTS)
+            .map(|i| low + (high - low) * i as f64 / (LOG_MONEYNESS_POINTS - 1) as f64)
+            .collect();
+        let days: Vec<Positive> = (1..=MATURITY_POINTS)
+            .map(|i| last_days * Decimal::from(i) / Decimal::from(MATURITY_POINTS))
+            .collect();
+        let total_variance = |y: f64, years: f64| {
+            let days = Positive::from(to_decimal(years)? * DAYS_IN_A_YEAR.to_dec());
+            surface.total_variance_f64(y, days)
+        };
+        let volatilities = days
+            .iter()
+            .map(|day| {
+                let years = to_f64((*day / DAYS_IN_A_YEAR).to_dec());
+                log_moneyness
+                    .iter()
+                    .map(|&y| {
+                        dupire_volatility(total_variance, y, years)
+                            .and_then(to_decimal)
+                            .map(Positive::from)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        LocalVolatilitySurface::new(
+            surface.underlying_price,
+            surface.risk_free_rate,
+            surface.dividend_yield,
+            log_moneyness
+                .into_iter()
+                .map(to_decimal)
+                .collect::<Result<Vec<_>, _>>()?,
+            days,
+            volatilities,
+        )
+    }
+
+    /// Fits an SSVI surface to `series` and extracts its local volatility.
+    ///
+    /// # Errors
+    ///
+    /// See [`SSVISurface::fit`] and [`LocalVolatilitySurface::from_ssvi`].
+    pub fn from_series(series: &OptionSeries) -> Result<Self, VolatilityError> {
+        LocalVolatilitySurface::from_ssvi(&SSVISurface::fit(series)?)
+    }
+
+    pub(crate) fn grid(&self) -> LocalVolatilityGrid {
+        LocalVolatilityGrid {
+            log_spot: to_f64(self.underlying_price.to_dec()).ln(),
+            carry: to_f64(self.risk_free_rate - self.dividend_yield.to_dec()),
+            log_moneyness: self.log_moneyness.iter().map(|&y| to_f64(y)).collect(),
+            years: self
+                .days
+                .iter()
+                .map(|day| to_f64((*day / DAYS_IN_A_YEAR).to_dec()))
+                .collect(),
+            volatilities: self
+                .volatilities
+                .iter()
+                .map(|row| row.iter().map(|v| v.to_f64()).collect())
+                .collect(),
+        }
+    }
+
+    /// Local volatility for the underlying at `price` after `days`.
+    pub fn local_volatility(&self, price: Positive, days: Positive) -> Positive {
+        let years = to_f64((days / DAYS_IN_A_YEAR).to_dec());
+        let volatility = self.grid().volatility(price.to_f64(), years);
+        Positive::from(to_decimal(volatility).unwrap_or(Decimal::ZERO))
+    }
+
+    /// Local volatility at the current spot and the first maturity of the grid.
+    pub fn atm_volatility(&self) -> Positive {
+        self.local_volatility(self.underlying_price, self.days[0])
+    }
+
+    /// Samples the grid as `(strike, days to expiration, local volatility)` points, with
+    /// the strike of each node at `F_T · e^y`.
+    pub fn surface(&self) -> Surface {
+        let grid = self.grid();
+        let points: BTreeSet<Point3D> = self
+            .days
+            .iter()
+            .zip(&grid.years)
+            .zip(&self.volatilities)
+            .flat_map(|((day, years), row)| {
+                let forward = (grid.log_spot + grid.carry * years).exp();
+                grid.log_moneyness
+                    .iter()
+                    .zip(row)
+                    .filter_map(move |(y, volatility)| {
+                        to_decimal(forward * y.exp())
+                            .ok()
+                            .map(|strike| Point3D::new(strike, day.to_dec(), volatility.to_dec()))
+                    })
+            })
+            .collect();
+        Surface::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests_local_volatility {
+    use super::*;
+    use crate::chains::chain::OptionChain;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::volatility::SSVISlice;
+    use crate::{ExpirationDate, pos};
+    use rust_decimal_macros::dec;
+
+    /// An SSVI surface with ATM volatilities `atm` at each maturity in days.
+    fn ssvi(rho: Decimal, eta: Decimal, atm: &[(f64, f64)]) -> SSVISurface {
+        SSVISurface {
+            symbol: "TEST".to_string(),
+            underlying_price: pos!(100.0),
+            risk_free_rate: Decimal::ZERO,
+            dividend_yield: Positive::ZERO,
+            rho,
+            eta,
+            gamma: dec!(0.5),
+            slices: atm
+                .iter()
+                .map(|&(days, vol)| SSVISlice {
+                    days: pos!(days),
+                    theta: pos!(vol * vol * days / 365.0),
+                })
+                .collect(),
+            strike_range: (pos!(80.0), pos!(120.0)),
+            rmse: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_flat_implied_volatility_gives_flat_local_volatility() {
+        let local = LocalVolatilitySurface::from_ssvi(&ssvi(
+            Decimal::ZERO,
+            dec!(1e-6),
+            &[(30.0, 0.2), (365.0, 0.2)],
+        ))
+        .unwrap();
+        assert_eq!(local.days.len(), MATURITY_POINTS);
+        assert_eq!(local.log_moneyness.len(), LOG_MONEYNESS_POINTS);
+        for row in &local.volatilities {
+            for volatility in row {
+                assert!((volatility.to_f64() - 0.2).abs() < 1e-3, "{volatility}");
+            }
+        }
+        assert!((local.atm_volatility().to_f64() - 0.2).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_term_structure_gives_forward_volatility() {
+        let local = LocalVolatilitySurface::from_ssvi(&ssvi(
+            Decimal::ZERO,
+            dec!(1e-6),
+            &[(30.0, 0.2), (365.0, 0.3)],
+        ))
+        .unwrap();
+        // Forward variance between the two expirations
+        let forward = ((0.09_f64 - 0.04 * 30.0 / 365.0) / (335.0 / 365.0)).sqrt();
+        let volatility = local.local_volatility(pos!(100.0), pos!(200.0));
+        assert!((volatility.to_f64() - forward).abs() < 2e-3, "{volatility}");
+        // Before the first expiration the ATM volatility is constant
+        let volatility = local.local_volatility(pos!(100.0), pos!(15.0));
+        assert!((volatility.to_f64() - 0.2).abs() < 2e-3, "{volatility}");
+    }
+
+    #[test]
+    fn test_skew_steepens_in_local_volatility() {
+        let surface = ssvi(dec!(-0.6), dec!(1.0), &[(30.0, 0.2), (365.0, 0.2)]);
+        let local = LocalVolatilitySurface::from_ssvi(&surface).unwrap();
+        let days = pos!(182.5);
+        let local_at = |strike: f64| local.local_volatility(pos!(strike), days).to_f64();
+        let implied_at = |strike: f64| {
+            surface
+                .implied_volatility(pos!(strike), days)
+                .unwrap()
+                .to_f64()
+        };
+        assert!(local_at(90.0) > local_at(100.0) && local_at(100.0) > local_at(110.0));
+        // Near the money the local skew is about twice the implied skew
+        let local_skew = local_at(95.0) - local_at(105.0);
+        let implied_skew = implied_at(95.0) - implied_at(105.0);
+        assert!(local_skew > 1.5 * implied_skew && local_skew < 2.5 * implied_skew);
+    }
+
+    #[test]
+    fn test_from_series() {
+        let mut series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        for (days, vol) in [(30.0, 0.25), (90.0, 0.22), (180.0, 0.2)] {
+            let mut chain = OptionChain::new(
+                "TEST",
+                pos!(100.0),
+                get_x_days_formatted_pos(pos!(days)),
+                None,
+                None,
+            );
+            for strike in (80..=120).step_by(5) {
+                let skew = 0.002 * (100.0 - strike as f64);
+                chain.add_option(
+                    pos!(strike as f64),
+                    None,
+                    None,
+                    None,
+                    None,
+                    pos!(vol + skew),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+            }
+            series
+                .chains
+                .insert(ExpirationDate::Days(pos!(days)), chain);
+        }
+        let local = LocalVolatilitySurface::from_series(&series).unwrap();
+        assert_eq!(*local.days.last().unwrap(), pos!(180.0));
+        assert!(
+            local
+                .volatilities
+                .iter()
+                .flatten()
+                .all(|v| v.to_f64() >= MIN_LOCAL_VOLATILITY && v.to_f64() <= 1.0)
+        );
+        assert_eq!(
+            local.surface().points.len(),
+            LOG_MONEYNESS_POINTS * MATURITY_POINTS
+        );
+    }
+
+    #[test]
+    fn test_grid_interpolation_and_validation() {
+        let local = LocalVolatilitySurface::new(
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            vec![dec!(-0.1), dec!(0.1)],
+            vec![pos!(30.0), pos!(90.0)],
+            vec![vec![pos!(0.3), pos!(0.1)], vec![pos!(0.5), pos!(0.3)]],
+        )
+        .unwrap();
+        let grid = local.grid();
+        assert!((grid.volatility(100.0, 30.0 / 365.0) - 0.2).abs() < 1e-12);
+        assert!((grid.volatility(100.0, 60.0 / 365.0) - 0.3).abs() < 1e-12);
+        // Flat outside the grid
+        assert!((grid.volatility(1000.0, 0.0) - 0.1).abs() < 1e-12);
+        assert!((grid.volatility(10.0, 5.0) - 0.5).abs() < 1e-12);
+
+        let mismatched = LocalVolatilitySurface::new(
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            vec![dec!(-0.1), dec!(0.1)],
+            vec![pos!(30.0)],
+            vec![vec![pos!(0.3)]],
+        );
+        assert!(matches!(
+            mismatched,
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+        let descending = LocalVolatilitySurface::new(
+            pos!(100.0),
+            Decimal::ZERO,
+            Positive::ZERO,
+            vec![dec!(0.1), dec!(-0.1)],
+            vec![pos!(30.0)],
+            vec![vec![pos!(0.3), pos!(0.3)]],
+        );
+        assert!(descending.is_err());
+    }
+}
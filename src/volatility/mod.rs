@@ -22,6 +22,9 @@
 //! - Implied Volatility
 //! - Uncertain Volatility Bounds
 //! - Volatility Surface Interpolation
+//! - Parametric Smiles and Surfaces (SVI / SSVI)
+//! - SABR Smiles with Bartlett Delta
+//! - Dupire Local Volatility Surfaces
 //!
 //! ## Usage Examples
 //!
@@ -88,6 +91,15 @@
 //! ```
 //!
 //!
+//! ### Realized Volatility from OHLCV Candles
+//!
+//! `range_volatility` estimates volatility from candle ranges with the Parkinson,
+//! Garman-Klass, Rogers-Satchell or Yang-Zhang estimators, or from closes.
+//! `resample_candles` aggregates candles to a coarser `TimeFrame`, and
+//! `realized_variance` sums squared intraday returns per period. `VolatilityCone`
+//! holds percentiles of rolling realized volatility per window length and compares an
+//! `OptionChain`'s at-the-money implied volatility against them.
+//!
 //! ## Mathematical Models
 //!
 //! ### GARCH(1,1)
@@ -106,6 +118,10 @@
 //! let garch_vol = garch_volatility(&returns, omega, alpha, beta);
 //! ```
 //!
+//! `GarchModel::fit` estimates GARCH(1,1), GJR-GARCH and EGARCH parameters from a
+//! return series by maximum likelihood. The fitted model forecasts the variance term
+//! structure and converts to `WalkType::Garch` parameters for simulation.
+//!
 //! ### Heston Stochastic Volatility
 //!
 //! Implements the Heston model:
@@ -127,6 +143,24 @@
 //! let heston_vol = simulate_heston_volatility(kappa, theta, xi, v0, dt, steps);
 //! ```
 //!
+//! European prices under the model come from `pricing::heston_price`.
+//! `HestonSmile::calibrate` fits `κ`, `θ`, `ξ`, `ρ` and `v₀` to an `OptionChain`, and
+//! `HestonSmile` implements `VolatilitySmile` with the model-implied volatilities.
+//!
+//! ### SVI and SSVI
+//!
+//! `SVISmile::fit` fits a raw SVI smile to one `OptionChain`, and `SSVISurface::fit`
+//! fits a surface SVI to all the expirations of an `OptionSeries`. Both give implied
+//! volatilities at any strike (and, for the surface, any maturity) and report butterfly
+//! and calendar arbitrage. `SVISmile` implements `VolatilitySmile`.
+//!
+//! ### SABR
+//!
+//! `SABRSmile::calibrate` fits `α`, `ρ` and `ν` for a fixed `β` to the bid/ask implied
+//! volatilities of an `OptionChain`. `SABRParameters` gives Hagan's lognormal and
+//! normal volatilities, and the SABR-consistent delta and vega that the `Greeks` trait
+//! exposes as `sabr_delta` and `sabr_vega`.
+//!
 //! ## Time Frame Handling
 //!
 //! The module includes utilities for converting between different time frames:
@@ -152,7 +186,7 @@
 //! ## Implementation Notes
 //!
 //! - All volatility calculations ensure non-negative results
-//! - Implied volatility uses Newton-Raphson method with bounds
+//! - Implied volatility uses Newton-Raphson with a Brent fallback inside a bracketing interval
 //! - Surface interpolation uses bilinear interpolation
 //! - Time scaling follows the square root of time rule
 //! - Numerical stability is ensured through bounds checking
@@ -162,12 +196,31 @@
 //! The implementations are based on standard financial mathematics literature:
 //! - Black-Scholes-Merton option pricing model
 //! - RiskMetrics™ Technical Document for EWMA
-//! - Heston (1993) stochastic volatility model
-//! - GARCH by Bollerslev (1986)
+//! - Heston (1993) stochastic volatility model; Lewis (2001) and Albrecher et al. (2007)
+//!   for its characteristic-function pricing
+//! - GARCH by Bollerslev (1986); GJR-GARCH by Glosten, Jagannathan and Runkle (1993);
+//!   EGARCH by Nelson (1991)
+//! - Gatheral and Jacquier (2014), Arbitrage-free SVI volatility surfaces
+//! - Dupire (1994), Pricing with a Smile; Gatheral (2006), The Volatility Surface
+//! - Parkinson (1980); Garman and Klass (1980); Rogers and Satchell (1991); Yang and
+//!   Zhang (2000) for range-based volatility
+//! - Hagan et al. (2002), Managing Smile Risk; Bartlett (2006), Hedging under SABR
 
+mod garch;
+mod heston;
+mod local;
+mod optimizer;
+mod realized;
+mod sabr;
+mod solver;
+mod svi;
 mod traits;
 mod utils;
 
+pub use solver::{
+    ImpliedVolatilityConfig, ImpliedVolatilityMethod, ImpliedVolatilityResult,
+    implied_volatility_with_config, solve_implied_volatility,
+};
 pub use utils::{
     adjust_volatility, annualized_volatility, calculate_iv, constant_volatility,
     de_annualized_volatility, ewma_volatility, garch_volatility, generate_ou_process,
@@ -175,4 +228,14 @@ pub use utils::{
     uncertain_volatility_bounds,
 };
 
+pub use garch::{GarchModel, GarchVariant};
+pub use heston::HestonSmile;
+pub(crate) use local::LocalVolatilityGrid;
+pub use local::LocalVolatilitySurface;
+pub use realized::{
+    IvConeComparison, RangeEstimator, RealizedVariance, VolatilityCone, VolatilityConeWindow,
+    range_volatility, realized_variance, resample_candles, rolling_range_volatility,
+};
+pub use sabr::{SABRParameters, SABRSmile, SABRVolatilityType};
+pub use svi::{ButterflyArbitrage, SSVISlice, SSVISurface, SVIParameters, SVISmile};
 pub use traits::{AtmIvProvider, VolatilitySmile};
@@ -0,0 +1,149 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+
+/// Minimizes `objective` with the Nelder-Mead simplex method.
+///
+/// The initial simplex is built around `start` by moving each coordinate by `step`.
+/// Non-finite objective values are treated as infinitely bad, so callers can reject
+/// invalid parameters by returning `f64::NAN` or `f64::INFINITY`. The search stops after
+/// `max_iterations` or when the spread of objective values in the simplex falls below
+/// `tolerance`.
+///
+/// # Returns
+///
+/// The best point found and its objective value.
+pub(crate) fn nelder_mead<F>(
+    objective: F,
+    start: &[f64],
+    step: f64,
+    max_iterations: usize,
+    tolerance: f64,
+) -> (Vec<f64>, f64)
+where
+    F: Fn(&[f64]) -> f64,
+{
+    let evaluate = |x: &[f64]| {
+        let value = objective(x);
+        if value.is_finite() {
+            value
+        } else {
+            f64::INFINITY
+        }
+    };
+    let n = start.len();
+    let mut simplex: Vec<(Vec<f64>, f64)> = Vec::with_capacity(n + 1);
+    simplex.push((start.to_vec(), evaluate(start)));
+    for i in 0..n {
+        let mut vertex = start.to_vec();
+        vertex[i] += step;
+        let value = evaluate(&vertex);
+        simplex.push((vertex, value));
+    }
+
+    for _ in 0..max_iterations {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, worst) = (simplex[0].1, simplex[n].1);
+        if best.is_finite() && (worst - best).abs() <= tolerance {
+            break;
+        }
+
+        let centroid: Vec<f64> = (0..n)
+            .map(|j| simplex[..n].iter().map(|(x, _)| x[j]).sum::<f64>() / n as f64)
+            .collect();
+        let towards = |coefficient: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&simplex[n].0)
+                .map(|(c, w)| c + coefficient * (w - c))
+                .collect()
+        };
+
+        let reflected = towards(-1.0);
+        let reflected_value = evaluate(&reflected);
+        if reflected_value < simplex[0].1 {
+            let expanded = towards(-2.0);
+            let expanded_value = evaluate(&expanded);
+            simplex[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value < simplex[n - 1].1 {
+            simplex[n] = (reflected, reflected_value);
+        } else {
+            let contracted = if reflected_value < simplex[n].1 {
+                towards(-0.5)
+            } else {
+                towards(0.5)
+            };
+            let contracted_value = evaluate(&contracted);
+            if contracted_value < simplex[n].1.min(reflected_value) {
+                simplex[n] = (contracted, contracted_value);
+            } else {
+                let best = simplex[0].0.clone();
+                for (vertex, value) in simplex.iter_mut().skip(1) {
+                    for (x, b) in vertex.iter_mut().zip(&best) {
+                        *x = b + 0.5 * (*x - b);
+                    }
+                    *value = evaluate(vertex);
+                }
+            }
+        }
+    }
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex.swap_remove(0)
+}
+
+#[cfg(test)]
+mod tests_optimizer {
+    use super::*;
+
+    #[test]
+    fn test_quadratic_minimum() {
+        let (x, value) = nelder_mead(
+            |x| (x[0] - 3.0).powi(2) + (x[1] + 1.0).powi(2),
+            &[0.0, 0.0],
+            1.0,
+            500,
+            1e-14,
+        );
+        assert!((x[0] - 3.0).abs() < 1e-5);
+        assert!((x[1] + 1.0).abs() < 1e-5);
+        assert!(value < 1e-10);
+    }
+
+    #[test]
+    fn test_rosenbrock() {
+        let (x, _) = nelder_mead(
+            |x| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2),
+            &[-1.2, 1.0],
+            0.5,
+            5000,
+            1e-16,
+        );
+        assert!((x[0] - 1.0).abs() < 1e-3);
+        assert!((x[1] - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_invalid_region_is_avoided() {
+        let (x, value) = nelder_mead(
+            |x| {
+                if x[0] <= 0.0 {
+                    f64::NAN
+                } else {
+                    (x[0].ln() - 1.0).powi(2)
+                }
+            },
+            &[1.0],
+            0.5,
+            500,
+            1e-14,
+        );
+        assert!((x[0] - std::f64::consts::E).abs() < 1e-4);
+        assert!(value.is_finite());
+    }
+}
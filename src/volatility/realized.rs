@@ -0,0 +1,763 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::Positive;
+use crate::chains::chain::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::error::VolatilityError;
+use crate::utils::OhlcvCandle;
+use crate::utils::stats::quantile;
+use crate::utils::time::TimeFrame;
+use crate::volatility::AtmIvProvider;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+
+/// Range-based and close-to-close estimators of volatility from OHLCV candles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RangeEstimator {
+    /// Sample standard deviation of close-to-close log returns.
+    CloseToClose,
+    /// Parkinson (1980), from the high-low range. Assumes no drift and no opening jumps.
+    Parkinson,
+    /// Garman and Klass (1980), from the high-low range and the open-close move.
+    GarmanKlass,
+    /// Rogers and Satchell (1991), unbiased in the presence of drift.
+    RogersSatchell,
+    /// Yang and Zhang (2000), combining overnight, open-close and Rogers-Satchell
+    /// variances. Handles both drift and opening jumps.
+    YangZhang,
+}
+
+impl RangeEstimator {
+    /// Fewest candles the estimator accepts.
+    fn min_candles(&self) -> usize {
+        match self {
+            RangeEstimator::CloseToClose | RangeEstimator::YangZhang => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// Log prices of a candle, checked for consistency.
+struct LogCandle {
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+}
+
+fn log_candles(candles: &[OhlcvCandle]) -> Result<Vec<LogCandle>, VolatilityError> {
+    candles
+        .iter()
+        .map(|candle| {
+            let prices = [candle.open, candle.high, candle.low, candle.close];
+            if prices.iter().any(|price| *price <= Decimal::ZERO) {
+                return Err(VolatilityError::MarketDataError {
+                    reason: format!("Candle on {} has a non-positive price", candle.date),
+                });
+            }
+            if candle.high < candle.open.max(candle.close)
+                || candle.low > candle.open.min(candle.close)
+            {
+                return Err(VolatilityError::MarketDataError {
+                    reason: format!("Candle on {} has an inconsistent range", candle.date),
+                });
+            }
+            let ln = |price: Decimal| price.to_f64().unwrap_or(f64::NAN).ln();
+            Ok(LogCandle {
+                open: ln(candle.open),
+                high: ln(candle.high),
+                low: ln(candle.low),
+                close: ln(candle.close),
+            })
+        })
+        .collect()
+}
+
+fn sample_variance(values: &[f64]) -> f64 {
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+fn rogers_satchell(c: &LogCandle) -> f64 {
+    (c.high - c.close) * (c.high - c.open) + (c.low - c.close) * (c.low - c.open)
+}
+
+/// Variance per period of `candles` under `estimator`.
+fn period_variance(candles: &[LogCandle], estimator: RangeEstimator) -> f64 {
+    let n = candles.len() as f64;
+    match estimator {
+        RangeEstimator::CloseToClose => {
+            let returns: Vec<f64> = candles
+                .windows(2)
+                .map(|p| p[1].close - p[0].close)
+                .collect();
+            sample_variance(&returns)
+        }
+        RangeEstimator::Parkinson => {
+            candles
+                .iter()
+                .map(|c| (c.high - c.low).powi(2))
+                .sum::<f64>()
+                / (4.0 * 2.0_f64.ln() * n)
+        }
+        RangeEstimator::GarmanKlass => {
+            candles
+                .iter()
+                .map(|c| {
+                    0.5 * (c.high - c.low).powi(2)
+                        - (2.0 * 2.0_f64.ln() - 1.0) * (c.close - c.open).powi(2)
+                })
+                .sum::<f64>()
+                / n
+        }
+        RangeEstimator::RogersSatchell => candles.iter().map(rogers_satchell).sum::<f64>() / n,
+        RangeEstimator::YangZhang => {
+            // The first candle only provides the close before the first overnight gap
+            let overnight: Vec<f64> = candles.windows(2).map(|p| p[1].open - p[0].close).collect();
+            let open_close: Vec<f64> = candles[1..].iter().map(|c| c.close - c.open).collect();
+            let m = overnight.len() as f64;
+            let rs = candles[1..].iter().map(rogers_satchell).sum::<f64>() / m;
+            let k = 0.34 / (1.34 + (m + 1.0) / (m - 1.0));
+            sample_variance(&overnight) + k * sample_variance(&open_close) + (1.0 - k) * rs
+        }
+    }
+}
+
+fn annualize(variance: f64, time_frame: TimeFrame) -> Result<Positive, VolatilityError> {
+    let annual = variance.max(0.0) * time_frame.periods_per_year().to_f64();
+    Decimal::from_f64(annual.sqrt())
+        .map(Positive::from)
+        .ok_or_else(|| VolatilityError::MarketDataError {
+            reason: format!("Volatility {annual} is not a finite number"),
+        })
+}
+
+/// Annualized volatility of `candles` estimated with `estimator`.
+///
+/// `time_frame` is the length of each candle and sets the annualization factor, so daily
+/// candles use `TimeFrame::Day`. Candles must be in chronological order.
+///
+/// # Errors
+///
+/// Returns `VolatilityError::MarketDataError` if a candle has a non-positive price or a
+/// range that does not contain its open and close, or if there are too few candles
+/// (three for close-to-close and Yang-Zhang, one otherwise).
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use optionstratlib::utils::OhlcvCandle;
+/// use optionstratlib::utils::time::TimeFrame;
+/// use optionstratlib::volatility::{RangeEstimator, range_volatility};
+/// use rust_decimal_macros::dec;
+///
+/// let candles: Vec<OhlcvCandle> = (1..=20)
+///     .map(|day| OhlcvCandle {
+///         date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+///         time: "00:00:00".to_string(),
+///         open: dec!(100.0),
+///         high: dec!(101.0),
+///         low: dec!(99.0),
+///         close: dec!(100.0),
+///         volume: 1_000,
+///     })
+///     .collect();
+/// let parkinson = range_volatility(&candles, RangeEstimator::Parkinson, TimeFrame::Day).unwrap();
+/// assert!(parkinson.to_f64() > 0.1 && parkinson.to_f64() < 0.3);
+/// ```
+pub fn range_volatility(
+    candles: &[OhlcvCandle],
+    estimator: RangeEstimator,
+    time_frame: TimeFrame,
+) -> Result<Positive, VolatilityError> {
+    if candles.len() < estimator.min_candles() {
+        return Err(VolatilityError::MarketDataError {
+            reason: format!(
+                "{estimator:?} needs at least {} candles, got {}",
+                estimator.min_candles(),
+                candles.len()
+            ),
+        });
+    }
+    annualize(
+        period_variance(&log_candles(candles)?, estimator),
+        time_frame,
+    )
+}
+
+/// Annualized volatility over each window of `window` consecutive candles.
+///
+/// # Errors
+///
+/// See [`range_volatility`]; windows shorter than the estimator needs are rejected.
+pub fn rolling_range_volatility(
+    candles: &[OhlcvCandle],
+    estimator: RangeEstimator,
+    window: usize,
+    time_frame: TimeFrame,
+) -> Result<Vec<Positive>, VolatilityError> {
+    if window < estimator.min_candles() {
+        return Err(VolatilityError::MarketDataError {
+            reason: format!(
+                "{estimator:?} needs windows of at least {} candles, got {window}",
+                estimator.min_candles()
+            ),
+        });
+    }
+    let logs = log_candles(candles)?;
+    logs.windows(window)
+        .map(|slice| annualize(period_variance(slice, estimator), time_frame))
+        .collect()
+}
+
+/// Start of the `time_frame` period that contains `candle`.
+fn period_start(
+    candle: &OhlcvCandle,
+    time_frame: TimeFrame,
+) -> Result<NaiveDateTime, VolatilityError> {
+    let date = candle.date;
+    let midnight = |date: NaiveDate| date.and_time(NaiveTime::MIN);
+    let time = || {
+        NaiveTime::parse_from_str(&candle.time, "%H:%M:%S")
+            .or_else(|_| NaiveTime::parse_from_str(&candle.time, "%H:%M"))
+            .map_err(|_| VolatilityError::MarketDataError {
+                reason: format!("Invalid candle time '{}' on {date}", candle.time),
+            })
+    };
+    let first_of = |month: u32| NaiveDate::from_ymd_opt(date.year(), month, 1).map(midnight);
+    let start = match time_frame {
+        TimeFrame::Second => Some(date.and_time(time()?)),
+        TimeFrame::Minute => time()?.with_second(0).map(|t| date.and_time(t)),
+        TimeFrame::Hour => time()?
+            .with_second(0)
+            .and_then(|t| t.with_minute(0))
+            .map(|t| date.and_time(t)),
+        TimeFrame::Day => Some(midnight(date)),
+        TimeFrame::Week => Some(
+            midnight(date) - chrono::Duration::days(date.weekday().num_days_from_monday() as i64),
+        ),
+        TimeFrame::Month => first_of(date.month()),
+        TimeFrame::Quarter => first_of((date.month() - 1) / 3 * 3 + 1),
+        TimeFrame::Year => first_of(1),
+        TimeFrame::Microsecond | TimeFrame::Millisecond | TimeFrame::Custom(_) => None,
+    };
+    start.ok_or_else(|| VolatilityError::MarketDataError {
+        reason: format!("Cannot group candles by {time_frame}"),
+    })
+}
+
+/// Groups chronological candles by `time_frame` period, keeping the start of each period.
+fn group_by_period(
+    candles: &[OhlcvCandle],
+    time_frame: TimeFrame,
+) -> Result<Vec<(NaiveDateTime, &[OhlcvCandle])>, VolatilityError> {
+    let mut groups: Vec<(NaiveDateTime, &[OhlcvCandle])> = Vec::new();
+    let mut first = 0;
+    let mut current: Option<NaiveDateTime> = None;
+    for (i, candle) in candles.iter().enumerate() {
+        let start = period_start(candle, time_frame)?;
+        match current {
+            Some(previous) if start < previous => {
+                return Err(VolatilityError::MarketDataError {
+                    reason: format!("Candles are not in chronological order at {start}"),
+                });
+            }
+            Some(previous) if start > previous => {
+                groups.push((previous, &candles[first..i]));
+                first = i;
+            }
+            _ => {}
+        }
+        current = Some(start);
+    }
+    if let Some(previous) = current {
+        groups.push((previous, &candles[first..]));
+    }
+    Ok(groups)
+}
+
+/// Aggregates chronological candles into candles of `time_frame`.
+///
+/// Each output candle opens with the first candle of its period, closes with the last,
+/// spans their highest high and lowest low, and adds up their volume. Its date and time
+/// mark the start of the period.
+///
+/// # Errors
+///
+/// Returns `VolatilityError::MarketDataError` if the candles are out of order, an
+/// intraday time cannot be parsed, or `time_frame` is finer than a second or custom.
+pub fn resample_candles(
+    candles: &[OhlcvCandle],
+    time_frame: TimeFrame,
+) -> Result<Vec<OhlcvCandle>, VolatilityError> {
+    Ok(group_by_period(candles, time_frame)?
+        .into_iter()
+        .map(|(start, group)| OhlcvCandle {
+            date: start.date(),
+            time: start.time().format("%H:%M:%S").to_string(),
+            open: group[0].open,
+            high: group.iter().map(|c| c.high).max().unwrap_or(group[0].high),
+            low: group.iter().map(|c| c.low).min().unwrap_or(group[0].low),
+            close: group[group.len() - 1].close,
+            volume: group.iter().map(|c| c.volume).sum(),
+        })
+        .collect())
+}
+
+/// Realized variance of one period, from intraday returns.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RealizedVariance {
+    /// Start of the period.
+    pub start: NaiveDateTime,
+    /// Length of the period.
+    pub period: TimeFrame,
+    /// Sum of squared log returns within the period.
+    pub variance: Positive,
+    /// Number of returns in the sum.
+    pub observations: usize,
+}
+
+impl RealizedVariance {
+    /// Annualized volatility implied by the period's realized variance.
+    pub fn annualized_volatility(&self) -> Positive {
+        (self.variance * self.period.periods_per_year()).sqrt()
+    }
+}
+
+/// Realized variance for each `period`, from candle closes resampled to `sampling`.
+///
+/// For example, minute candles with `sampling = TimeFrame::Hour` and
+/// `period = TimeFrame::Day` sum the squared hourly returns of each day. Returns across
+/// period boundaries, such as overnight gaps, are left out.
+///
+/// # Errors
+///
+/// See [`resample_candles`].
+pub fn realized_variance(
+    candles: &[OhlcvCandle],
+    sampling: TimeFrame,
+    period: TimeFrame,
+) -> Result<Vec<RealizedVariance>, VolatilityError> {
+    let sampled = resample_candles(candles, sampling)?;
+    let logs = log_candles(&sampled)?;
+    let mut offset = 0;
+    let mut variances = Vec::new();
+    for (start, group) in group_by_period(&sampled, period)? {
+        let closes = &logs[offset..offset + group.len()];
+        offset += group.len();
+        let variance: f64 = closes
+            .windows(2)
+            .map(|pair| (pair[1].close - pair[0].close).powi(2))
+            .sum();
+        variances.push(RealizedVariance {
+            start,
+            period,
+            variance: Positive::from(Decimal::from_f64(variance).unwrap_or(Decimal::ZERO)),
+            observations: group.len() - 1,
+        });
+    }
+    Ok(variances)
+}
+
+/// Distribution of realized volatility over one window length.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityConeWindow {
+    /// Window length in candles.
+    pub window: usize,
+    /// Lowest realized volatility.
+    pub minimum: Positive,
+    /// 25th percentile.
+    pub percentile_25: Positive,
+    /// Median.
+    pub median: Positive,
+    /// 75th percentile.
+    pub percentile_75: Positive,
+    /// Highest realized volatility.
+    pub maximum: Positive,
+    /// Realized volatility of the most recent window.
+    pub current: Positive,
+    /// Rolling realized volatilities, oldest first.
+    pub history: Vec<Positive>,
+}
+
+impl VolatilityConeWindow {
+    fn new(window: usize, history: Vec<Positive>) -> Option<Self> {
+        let values: Vec<Decimal> = history.iter().map(|v| v.to_dec()).collect();
+        let at = |level: Decimal| quantile(&values, level).map(Positive::from);
+        Some(VolatilityConeWindow {
+            window,
+            minimum: at(Decimal::ZERO)?,
+            percentile_25: at(Decimal::new(25, 2))?,
+            median: at(Decimal::new(5, 1))?,
+            percentile_75: at(Decimal::new(75, 2))?,
+            maximum: at(Decimal::ONE)?,
+            current: *history.last()?,
+            history,
+        })
+    }
+
+    /// Fraction of the historical windows whose realized volatility is at or below
+    /// `volatility`.
+    pub fn percentile_rank(&self, volatility: Positive) -> Decimal {
+        let below = self.history.iter().filter(|v| **v <= volatility).count();
+        Decimal::from(below) / Decimal::from(self.history.len().max(1))
+    }
+}
+
+/// Implied volatility of a chain set against the realized volatility cone.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IvConeComparison {
+    /// Cone window closest to the chain's time to expiration, in candles.
+    pub window: usize,
+    /// At-the-money implied volatility of the chain.
+    pub implied_volatility: Positive,
+    /// Fraction of historical realized volatilities at or below the implied volatility.
+    pub percentile_rank: Decimal,
+    /// Median realized volatility for the window.
+    pub realized_median: Positive,
+    /// Implied minus median realized volatility.
+    pub premium: Decimal,
+}
+
+/// Percentiles of rolling realized volatility for several window lengths.
+///
+/// Comparing an option's implied volatility with the cone for its time to expiration
+/// shows whether options are rich or cheap relative to realized history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolatilityCone {
+    /// Estimator used for every window.
+    pub estimator: RangeEstimator,
+    /// Length of each candle.
+    pub time_frame: TimeFrame,
+    /// One entry per window length, in the order requested.
+    pub windows: Vec<VolatilityConeWindow>,
+}
+
+impl VolatilityCone {
+    /// Builds the cone from `candles` for each length in `windows`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::MarketDataError` if a window is longer than the history
+    /// or shorter than the estimator needs, or the candles are invalid.
+    pub fn new(
+        candles: &[OhlcvCandle],
+        windows: &[usize],
+        estimator: RangeEstimator,
+        time_frame: TimeFrame,
+    ) -> Result<Self, VolatilityError> {
+        let windows = windows
+            .iter()
+            .map(|&window| {
+                let history = rolling_range_volatility(candles, estimator, window, time_frame)?;
+                VolatilityConeWindow::new(window, history).ok_or_else(|| {
+                    VolatilityError::MarketDataError {
+                        reason: format!(
+                            "Window of {window} candles is longer than the {} available",
+                            candles.len()
+                        ),
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(VolatilityCone {
+            estimator,
+            time_frame,
+            windows,
+        })
+    }
+
+    /// Window whose length is closest to `days` calendar days.
+    pub fn window_for_days(&self, days: Positive) -> Option<&VolatilityConeWindow> {
+        let periods = (days / DAYS_IN_A_YEAR * self.time_frame.periods_per_year()).to_dec();
+        self.windows
+            .iter()
+            .min_by_key(|entry| (Decimal::from(entry.window) - periods).abs())
+    }
+
+    /// Compares the at-the-money implied volatility of `chain` with the cone window
+    /// matching its time to expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::MarketDataError` if the cone is empty or the chain has
+    /// no expiration or at-the-money implied volatility.
+    pub fn compare(&self, chain: &OptionChain) -> Result<IvConeComparison, VolatilityError> {
+        let days = chain
+            .get_expiration()
+            .and_then(|expiration| expiration.get_days().ok())
+            .ok_or_else(|| VolatilityError::MarketDataError {
+                reason: format!("Invalid expiration date {}", chain.get_expiration_date()),
+            })?;
+        let implied_volatility = *chain
+            .atm_iv()
+            .map_err(|e| VolatilityError::MarketDataError {
+                reason: e.to_string(),
+            })?;
+        let entry = self
+            .window_for_days(days)
+            .ok_or_else(|| VolatilityError::MarketDataError {
+                reason: "Volatility cone has no windows".to_string(),
+            })?;
+        Ok(IvConeComparison {
+            window: entry.window,
+            implied_volatility,
+            percentile_rank: entry.percentile_rank(implied_volatility),
+            realized_median: entry.median,
+            premium: implied_volatility.to_dec() - entry.median.to_dec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests_realized {
+    use super::*;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::{assert_decimal_eq, pos};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand_distr::{Distribution, StandardNormal};
+    use rust_decimal_macros::dec;
+
+    fn candle(date: NaiveDate, time: &str, ohlc: [f64; 4]) -> OhlcvCandle {
+        let d = |v: f64| Decimal::from_f64(v).unwrap();
+        OhlcvCandle {
+            date,
+            time: time.to_string(),
+            open: d(ohlc[0]),
+            high: d(ohlc[1]),
+            low: d(ohlc[2]),
+            close: d(ohlc[3]),
+            volume: 10,
+        }
+    }
+
+    /// Daily candles built from a driftless geometric Brownian motion sampled 100 times
+    /// a day, with annualized volatility `sigma` and no overnight gap.
+    fn gbm_candles(days: usize, sigma: f64) -> Vec<OhlcvCandle> {
+        let mut rng = StdRng::seed_from_u64(11);
+        let step = sigma / (252.0_f64 * 100.0).sqrt();
+        let start = NaiveDate::from_ymd_opt(2020, 1, 1).unwrap();
+        let mut log_price = 100.0_f64.ln();
+        (0..days)
+            .map(|day| {
+                let open = log_price;
+                let (mut high, mut low) = (open, open);
+                for _ in 0..100 {
+                    let z: f64 = StandardNormal.sample(&mut rng);
+                    log_price += step * z;
+                    high = high.max(log_price);
+                    low = low.min(log_price);
+                }
+                candle(
+                    start + chrono::Duration::days(day as i64),
+                    "00:00:00",
+                    [open.exp(), high.exp(), low.exp(), log_price.exp()],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_estimators_recover_volatility() {
+        let candles = gbm_candles(1000, 0.25);
+        for estimator in [
+            RangeEstimator::CloseToClose,
+            RangeEstimator::Parkinson,
+            RangeEstimator::GarmanKlass,
+            RangeEstimator::RogersSatchell,
+            RangeEstimator::YangZhang,
+        ] {
+            let volatility = range_volatility(&candles, estimator, TimeFrame::Day).unwrap();
+            // Discrete sampling biases range estimators slightly downwards
+            assert!(
+                volatility > pos!(0.22) && volatility < pos!(0.27),
+                "{estimator:?}: {volatility}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_single_candle_formulas() {
+        let candles = [candle(
+            NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+            "",
+            [100.0, 110.0, 95.0, 105.0],
+        )];
+        let range = (110.0_f64 / 95.0).ln();
+        let body = (105.0_f64 / 100.0).ln();
+        let expected = |variance: f64| (variance * 252.0).sqrt();
+        let volatility = |estimator| {
+            range_volatility(&candles, estimator, TimeFrame::Day)
+                .unwrap()
+                .to_f64()
+        };
+        assert!(
+            (volatility(RangeEstimator::Parkinson)
+                - expected(range * range / (4.0 * 2.0_f64.ln())))
+            .abs()
+                < 1e-12
+        );
+        assert!(
+            (volatility(RangeEstimator::GarmanKlass)
+                - expected(0.5 * range * range - (2.0 * 2.0_f64.ln() - 1.0) * body * body))
+            .abs()
+                < 1e-12
+        );
+        let rs = (110.0_f64 / 105.0).ln() * (110.0_f64 / 100.0).ln()
+            + (95.0_f64 / 105.0).ln() * (95.0_f64 / 100.0).ln();
+        assert!((volatility(RangeEstimator::RogersSatchell) - expected(rs)).abs() < 1e-12);
+        assert!(range_volatility(&candles, RangeEstimator::YangZhang, TimeFrame::Day).is_err());
+    }
+
+    #[test]
+    fn test_yang_zhang_captures_overnight_gaps() {
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        // Flat sessions that gap 2% up and down overnight
+        let candles: Vec<OhlcvCandle> = (0..20)
+            .map(|day| {
+                let price = if day % 2 == 0 { 100.0 } else { 102.0 };
+                candle(
+                    start + chrono::Duration::days(day),
+                    "",
+                    [price, price + 0.5, price - 0.5, price],
+                )
+            })
+            .collect();
+        let rogers_satchell =
+            range_volatility(&candles, RangeEstimator::RogersSatchell, TimeFrame::Day).unwrap();
+        let yang_zhang =
+            range_volatility(&candles, RangeEstimator::YangZhang, TimeFrame::Day).unwrap();
+        assert!(yang_zhang > rogers_satchell * pos!(2.0));
+    }
+
+    #[test]
+    fn test_invalid_candles() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let bad_range = [candle(date, "", [100.0, 99.0, 95.0, 98.0])];
+        assert!(matches!(
+            range_volatility(&bad_range, RangeEstimator::Parkinson, TimeFrame::Day),
+            Err(VolatilityError::MarketDataError { .. })
+        ));
+        let good = [candle(date, "", [100.0, 101.0, 99.0, 100.0])];
+        assert!(
+            rolling_range_volatility(&good, RangeEstimator::CloseToClose, 2, TimeFrame::Day)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_resample_and_realized_variance() {
+        let day_one = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let day_two = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let minutes = [
+            (day_one, "09:30:00", [100.0, 101.0, 99.5, 100.5]),
+            (day_one, "09:59:00", [100.5, 102.0, 100.0, 101.0]),
+            (day_one, "10:15:00", [101.0, 101.5, 98.0, 99.0]),
+            (day_one, "11:00:00", [99.0, 99.5, 98.5, 99.2]),
+            (day_two, "09:30:00", [103.0, 104.0, 102.0, 103.5]),
+            (day_two, "10:30:00", [103.5, 104.5, 103.0, 104.0]),
+        ]
+        .map(|(date, time, ohlc)| candle(date, time, ohlc));
+
+        let hourly = resample_candles(&minutes, TimeFrame::Hour).unwrap();
+        assert_eq!(hourly.len(), 5);
+        assert_eq!(hourly[0].time, "09:00:00");
+        assert_eq!(hourly[0].open, dec!(100.0));
+        assert_eq!(hourly[0].high, dec!(102.0));
+        assert_eq!(hourly[0].low, dec!(99.5));
+        assert_eq!(hourly[0].close, dec!(101.0));
+        assert_eq!(hourly[0].volume, 20);
+
+        let daily = resample_candles(&minutes, TimeFrame::Day).unwrap();
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[1].close, dec!(104.0));
+        let weekly = resample_candles(&minutes, TimeFrame::Week).unwrap();
+        assert_eq!(weekly.len(), 1);
+        assert_eq!(weekly[0].date, day_one);
+
+        let realized = realized_variance(&minutes, TimeFrame::Hour, TimeFrame::Day).unwrap();
+        assert_eq!(realized.len(), 2);
+        assert_eq!(realized[0].observations, 2);
+        let expected = (99.0_f64 / 101.0).ln().powi(2) + (99.2_f64 / 99.0).ln().powi(2);
+        assert!((realized[0].variance.to_f64() - expected).abs() < 1e-12);
+        // The overnight gap is not part of the second day
+        let expected = (104.0_f64 / 103.5).ln().powi(2);
+        assert!((realized[1].variance.to_f64() - expected).abs() < 1e-12);
+        assert_eq!(
+            realized[1].annualized_volatility(),
+            (realized[1].variance * pos!(252.0)).sqrt()
+        );
+
+        let mut shuffled = minutes.to_vec();
+        shuffled.swap(0, 4);
+        assert!(resample_candles(&shuffled, TimeFrame::Hour).is_err());
+        assert!(resample_candles(&minutes, TimeFrame::Custom(pos!(100.0))).is_err());
+    }
+
+    #[test]
+    fn test_volatility_cone_and_iv_comparison() {
+        let candles = gbm_candles(300, 0.2);
+        let cone = VolatilityCone::new(
+            &candles,
+            &[10, 21, 63],
+            RangeEstimator::YangZhang,
+            TimeFrame::Day,
+        )
+        .unwrap();
+        assert_eq!(cone.windows.len(), 3);
+        for entry in &cone.windows {
+            assert_eq!(entry.history.len(), 300 - entry.window + 1);
+            assert!(entry.minimum <= entry.percentile_25);
+            assert!(entry.percentile_25 <= entry.median);
+            assert!(entry.median <= entry.percentile_75);
+            assert!(entry.percentile_75 <= entry.maximum);
+            assert_eq!(entry.current, *entry.history.last().unwrap());
+            assert_eq!(entry.percentile_rank(entry.maximum), Decimal::ONE);
+        }
+        // Longer windows have a narrower cone
+        let spread = |entry: &VolatilityConeWindow| entry.maximum - entry.minimum;
+        assert!(spread(&cone.windows[2]) < spread(&cone.windows[0]));
+
+        let mut chain = OptionChain::new(
+            "TEST",
+            pos!(100.0),
+            get_x_days_formatted_pos(pos!(30.0)),
+            None,
+            None,
+        );
+        chain.add_option(
+            pos!(100.0),
+            None,
+            None,
+            None,
+            None,
+            pos!(0.5),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let comparison = cone.compare(&chain).unwrap();
+        assert_eq!(comparison.window, 21);
+        assert_eq!(comparison.implied_volatility, pos!(0.5));
+        assert_eq!(comparison.percentile_rank, Decimal::ONE);
+        assert_decimal_eq!(
+            comparison.premium,
+            dec!(0.5) - cone.windows[1].median.to_dec(),
+            dec!(1e-12)
+        );
+        assert!(
+            VolatilityCone::new(&candles, &[400], RangeEstimator::Parkinson, TimeFrame::Day)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,801 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::chains::chain::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::curves::{Curve, Point2D};
+use crate::error::{GreeksError, VolatilityError};
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::volatility::optimizer::nelder_mead;
+use crate::volatility::svi::{MAX_ITERATIONS, forward_price, strike_grid, to_decimal, to_f64};
+use crate::volatility::{VolatilitySmile, solve_implied_volatility};
+use crate::{ExpirationDate, Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Bid/ask spread in volatility assumed for quotes that only carry a mid volatility.
+const DEFAULT_IV_SPREAD: f64 = 0.01;
+
+/// Relative bump used for finite-difference SABR Greeks.
+const BUMP: f64 = 1e-4;
+
+/// Quoting convention of a SABR implied volatility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SABRVolatilityType {
+    /// Black (lognormal) volatility, as used by Black-Scholes.
+    Lognormal,
+    /// Bachelier (normal) volatility, in price units per year.
+    Normal,
+}
+
+/// Parameters of the SABR stochastic volatility model (Hagan et al., 2002):
+///
+/// `dF = α F^β dW₁`, `dα = ν α dW₂`, `dW₁ dW₂ = ρ dt`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SABRParameters {
+    /// Initial volatility `α`.
+    pub alpha: Positive,
+    /// Backbone exponent `β`, between 0 (normal) and 1 (lognormal).
+    pub beta: Decimal,
+    /// Correlation `ρ` between the forward and its volatility.
+    pub rho: Decimal,
+    /// Volatility of volatility `ν`.
+    pub nu: Positive,
+}
+
+/// `z / x(z)` from Hagan's formulas, expanded around `z = 0`.
+fn z_over_x(z: f64, rho: f64) -> f64 {
+    if z.abs() < 1e-7 {
+        return 1.0 - rho * z / 2.0;
+    }
+    let x = (((1.0 - 2.0 * rho * z + z * z).sqrt() + z - rho) / (1.0 - rho)).ln();
+    z / x
+}
+
+impl SABRParameters {
+    /// Creates a new set of SABR parameters.
+    pub fn new(alpha: Positive, beta: Decimal, rho: Decimal, nu: Positive) -> Self {
+        SABRParameters {
+            alpha,
+            beta,
+            rho,
+            nu,
+        }
+    }
+
+    fn as_f64(&self) -> (f64, f64, f64, f64) {
+        (
+            to_f64(self.alpha.to_dec()),
+            to_f64(self.beta),
+            to_f64(self.rho),
+            to_f64(self.nu.to_dec()),
+        )
+    }
+
+    fn validate(&self) -> Result<(), VolatilityError> {
+        if self.beta < Decimal::ZERO || self.beta > Decimal::ONE {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!("SABR beta must be between 0 and 1, got {}", self.beta),
+            });
+        }
+        if self.rho <= -Decimal::ONE || self.rho >= Decimal::ONE {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!("SABR rho must be between -1 and 1, got {}", self.rho),
+            });
+        }
+        Ok(())
+    }
+
+    /// Hagan's lognormal implied volatility for an explicit `alpha`, so that Greeks can
+    /// bump it.
+    fn lognormal(&self, alpha: f64, forward: f64, strike: f64, years: f64) -> f64 {
+        let (_, beta, rho, nu) = self.as_f64();
+        let one_beta = 1.0 - beta;
+        let log_fk = (forward / strike).ln();
+        let fk_beta = (forward * strike).powf(one_beta / 2.0);
+        let z = nu / alpha * fk_beta * log_fk;
+        let denominator = fk_beta
+            * (1.0
+                + one_beta.powi(2) / 24.0 * log_fk.powi(2)
+                + one_beta.powi(4) / 1920.0 * log_fk.powi(4));
+        let correction = 1.0
+            + (one_beta.powi(2) / 24.0 * alpha * alpha / (fk_beta * fk_beta)
+                + rho * beta * nu * alpha / (4.0 * fk_beta)
+                + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu)
+                * years;
+        alpha / denominator * z_over_x(z, rho) * correction
+    }
+
+    /// Hagan's normal implied volatility.
+    fn normal(&self, forward: f64, strike: f64, years: f64) -> f64 {
+        let (alpha, beta, rho, nu) = self.as_f64();
+        let one_beta = 1.0 - beta;
+        let log_fk = (forward / strike).ln();
+        let fk_beta = (forward * strike).powf(one_beta / 2.0);
+        let z = nu / alpha * fk_beta * log_fk;
+        let numerator = 1.0 + log_fk.powi(2) / 24.0 + log_fk.powi(4) / 1920.0;
+        let denominator = 1.0
+            + one_beta.powi(2) / 24.0 * log_fk.powi(2)
+            + one_beta.powi(4) / 1920.0 * log_fk.powi(4);
+        let correction = 1.0
+            + (-beta * (2.0 - beta) * alpha * alpha / (24.0 * fk_beta * fk_beta)
+                + rho * alpha * nu * beta / (4.0 * fk_beta)
+                + (2.0 - 3.0 * rho * rho) / 24.0 * nu * nu)
+                * years;
+        alpha * (forward * strike).powf(beta / 2.0) * numerator / denominator
+            * z_over_x(z, rho)
+            * correction
+    }
+
+    /// Implied volatility of a strike for the given forward and time to expiration in
+    /// years, using Hagan's asymptotic formulas.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if the parameters are out of range,
+    /// and `VolatilityError::InvalidPrice` if the formula does not give a positive
+    /// volatility at `strike`.
+    pub fn implied_volatility(
+        &self,
+        forward: Positive,
+        strike: Positive,
+        years: Positive,
+        volatility_type: SABRVolatilityType,
+    ) -> Result<Positive, VolatilityError> {
+        self.validate()?;
+        if forward == Positive::ZERO || strike == Positive::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price: strike,
+                reason: "SABR needs a positive forward and strike".to_string(),
+            });
+        }
+        let (f, k, t) = (
+            to_f64(forward.to_dec()),
+            to_f64(strike.to_dec()),
+            to_f64(years.to_dec()),
+        );
+        let volatility = match volatility_type {
+            SABRVolatilityType::Lognormal => self.lognormal(self.as_f64().0, f, k, t),
+            SABRVolatilityType::Normal => self.normal(f, k, t),
+        };
+        if !volatility.is_finite() || volatility <= 0.0 {
+            return Err(VolatilityError::InvalidPrice {
+                price: strike,
+                reason: format!("SABR volatility {volatility} is not positive"),
+            });
+        }
+        Ok(Positive::from(to_decimal(volatility)?))
+    }
+
+    /// Forward and time to expiration of an option, in that order.
+    fn option_forward(option: &Options) -> Result<(f64, f64), GreeksError> {
+        let years = to_f64(option.expiration_date.get_years()?.to_dec());
+        let forward = forward_price(
+            option.underlying_price,
+            option.risk_free_rate,
+            option.dividend_yield,
+            years,
+        );
+        Ok((forward, years))
+    }
+
+    /// The option with its volatility taken from the SABR smile.
+    fn with_sabr_volatility(&self, option: &Options) -> Result<Options, GreeksError> {
+        let (forward, years) = Self::option_forward(option)?;
+        let volatility = self.lognormal(
+            self.as_f64().0,
+            forward,
+            to_f64(option.strike_price.to_dec()),
+            years,
+        );
+        if !volatility.is_finite() || volatility <= 0.0 {
+            return Err(GreeksError::StdError(format!(
+                "SABR volatility {volatility} is not positive at strike {}",
+                option.strike_price
+            )));
+        }
+        let mut priced = option.clone();
+        priced.implied_volatility = Positive::from(to_decimal(volatility)?);
+        Ok(priced)
+    }
+
+    /// Sensitivity of the SABR volatility at `strike` to `alpha`, by central differences.
+    fn volatility_alpha_sensitivity(&self, forward: f64, strike: f64, years: f64) -> f64 {
+        let alpha = self.as_f64().0;
+        let bump = alpha * BUMP;
+        (self.lognormal(alpha + bump, forward, strike, years)
+            - self.lognormal(alpha - bump, forward, strike, years))
+            / (2.0 * bump)
+    }
+
+    /// SABR-consistent delta of an option (Bartlett, 2006).
+    ///
+    /// The Black-Scholes delta at the SABR volatility is corrected by the vega times the
+    /// change of that volatility when the forward moves. Besides the move along the
+    /// smile, the forward move is accompanied by the expected change of `α` given the
+    /// correlation, `dα = ρν / F^β dF`. Like [`crate::greeks::delta`], the result is
+    /// signed by side and scaled by quantity. Expired options keep their Black-Scholes
+    /// delta.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the expiration cannot be read or the SABR volatility
+    /// is not positive at the option's strike.
+    pub fn bartlett_delta(&self, option: &Options) -> Result<Decimal, GreeksError> {
+        self.validate()?;
+        let (forward, years) = Self::option_forward(option)?;
+        if years == 0.0 {
+            return crate::greeks::delta(option);
+        }
+        let priced = self.with_sabr_volatility(option)?;
+        let (alpha, beta, rho, nu) = self.as_f64();
+        let strike = to_f64(option.strike_price.to_dec());
+        let bump = forward * BUMP;
+        let smile_slope = (self.lognormal(alpha, forward + bump, strike, years)
+            - self.lognormal(alpha, forward - bump, strike, years))
+            / (2.0 * bump);
+        let backbone = self.volatility_alpha_sensitivity(forward, strike, years) * rho * nu
+            / forward.powf(beta);
+        let forward_per_spot = forward / to_f64(option.underlying_price.to_dec());
+        let volatility_per_spot = (smile_slope + backbone) * forward_per_spot;
+
+        // `vega` is per volatility point and unsigned
+        let signed_vega = match option.side {
+            Side::Long => crate::greeks::vega(&priced)?,
+            Side::Short => -crate::greeks::vega(&priced)?,
+        };
+        Ok(crate::greeks::delta(&priced)?
+            + signed_vega * Decimal::ONE_HUNDRED * to_decimal(volatility_per_spot)?)
+    }
+
+    /// SABR vega of an option: the change in value when `α` moves enough to raise the
+    /// at-the-money volatility by one percentage point.
+    ///
+    /// Like [`crate::greeks::vega`], the result is scaled by quantity and not signed by
+    /// side. Expired options have zero vega.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `GreeksError` if the expiration cannot be read or the SABR volatility
+    /// is not positive at the option's strike.
+    pub fn vega(&self, option: &Options) -> Result<Decimal, GreeksError> {
+        self.validate()?;
+        let (forward, years) = Self::option_forward(option)?;
+        if years == 0.0 {
+            return Ok(Decimal::ZERO);
+        }
+        let priced = self.with_sabr_volatility(option)?;
+        let strike = to_f64(option.strike_price.to_dec());
+        let atm_sensitivity = self.volatility_alpha_sensitivity(forward, forward, years);
+        if atm_sensitivity == 0.0 {
+            return Err(GreeksError::StdError(
+                "ATM volatility does not depend on alpha".to_string(),
+            ));
+        }
+        let ratio = self.volatility_alpha_sensitivity(forward, strike, years) / atm_sensitivity;
+        Ok(crate::greeks::vega(&priced)? * to_decimal(ratio)?)
+    }
+}
+
+/// A single volatility quote used in SABR and Heston calibration.
+pub(super) struct VolatilityQuote {
+    pub(super) strike: f64,
+    pub(super) volatility: f64,
+    pub(super) spread: f64,
+}
+
+/// Volatility quotes of a chain. Where bid and ask prices are available for the
+/// out-of-the-money side, their implied volatilities give the mid and the spread;
+/// otherwise the quoted implied volatility is used with `DEFAULT_IV_SPREAD`.
+pub(super) fn chain_quotes(
+    chain: &OptionChain,
+    days: Positive,
+    forward: f64,
+) -> Vec<VolatilityQuote> {
+    let rate = chain.risk_free_rate.unwrap_or(Decimal::ZERO);
+    let dividend_yield = chain.dividend_yield.unwrap_or(Positive::ZERO);
+    let implied = |style: OptionStyle, strike: Positive, price: Positive| {
+        let option = Options::new(
+            OptionType::European,
+            Side::Long,
+            chain.symbol.clone(),
+            strike,
+            ExpirationDate::Days(days),
+            Positive::ONE,
+            Positive::ONE,
+            chain.underlying_price,
+            rate,
+            style,
+            dividend_yield,
+            None,
+        );
+        solve_implied_volatility(&option, price.to_dec())
+            .ok()
+            .map(|result| to_f64(result.volatility.to_dec()))
+    };
+    chain
+        .options
+        .iter()
+        .filter_map(|option| {
+            let strike = to_f64(option.strike_price.to_dec());
+            let (style, bid, ask) = if strike >= forward {
+                (OptionStyle::Call, option.call_bid, option.call_ask)
+            } else {
+                (OptionStyle::Put, option.put_bid, option.put_ask)
+            };
+            let from_prices = match (bid, ask) {
+                (Some(bid), Some(ask)) if bid > Positive::ZERO && ask > bid => implied(
+                    style,
+                    option.strike_price,
+                    bid,
+                )
+                .zip(implied(style, option.strike_price, ask)),
+                _ => None,
+            };
+            match from_prices {
+                Some((bid_iv, ask_iv)) => Some(VolatilityQuote {
+                    strike,
+                    volatility: (bid_iv + ask_iv) / 2.0,
+                    spread: (ask_iv - bid_iv).max(1e-4),
+                }),
+                None if option.implied_volatility > Positive::ZERO => Some(VolatilityQuote {
+                    strike,
+                    volatility: to_f64(option.implied_volatility.to_dec()),
+                    spread: DEFAULT_IV_SPREAD,
+                }),
+                None => None,
+            }
+        })
+        .collect()
+}
+
+/// A SABR smile calibrated to one expiration.
+///
+/// Implements [`VolatilitySmile`] and gives the lognormal or normal implied volatility
+/// at any strike.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SABRSmile {
+    /// Model parameters.
+    pub parameters: SABRParameters,
+    /// Forward price of the underlying for this expiration.
+    pub forward: Positive,
+    /// Time to expiration in years.
+    pub years: Positive,
+    /// Strikes covered by `smile`.
+    pub strike_range: (Positive, Positive),
+    /// Root mean square error in implied volatility against the calibration quotes.
+    pub rmse: Decimal,
+}
+
+impl SABRSmile {
+    /// Creates a smile from known parameters.
+    pub fn new(
+        parameters: SABRParameters,
+        forward: Positive,
+        years: Positive,
+        strike_range: (Positive, Positive),
+    ) -> Self {
+        SABRSmile {
+            parameters,
+            forward,
+            years,
+            strike_range,
+            rmse: Decimal::ZERO,
+        }
+    }
+
+    /// Calibrates `α`, `ρ` and `ν` to an option chain for a fixed `β`.
+    ///
+    /// Each strike contributes the mid of its bid and ask implied volatilities, weighted
+    /// by the inverse of the bid/ask volatility spread, so tight quotes are fitted more
+    /// closely. Strikes without a two-sided quote use the chain's implied volatility.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if `beta` is outside `[0, 1]`, the
+    /// expiration cannot be read, or fewer than three strikes can be quoted, and
+    /// `VolatilityError::InvalidTime` if the chain has expired.
+    pub fn calibrate(chain: &OptionChain, beta: Decimal) -> Result<Self, VolatilityError> {
+        if beta < Decimal::ZERO || beta > Decimal::ONE {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!("SABR beta must be between 0 and 1, got {beta}"),
+            });
+        }
+        let days = chain
+            .get_expiration()
+            .and_then(|expiration| expiration.get_days().ok())
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: format!("Invalid expiration date {}", chain.get_expiration_date()),
+            })?;
+        let years = days / DAYS_IN_A_YEAR;
+        if years == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: years,
+                reason: "Cannot calibrate SABR to an expired chain".to_string(),
+            });
+        }
+        let t = to_f64(years.to_dec());
+        let forward = forward_price(
+            chain.underlying_price,
+            chain.risk_free_rate.unwrap_or(Decimal::ZERO),
+            chain.dividend_yield.unwrap_or(Positive::ZERO),
+            t,
+        );
+        let quotes = chain_quotes(chain, days, forward);
+        if quotes.len() < 3 {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!(
+                    "SABR needs at least 3 implied volatility quotes, got {}",
+                    quotes.len()
+                ),
+            });
+        }
+
+        let beta_f64 = to_f64(beta);
+        let atm_volatility = quotes
+            .iter()
+            .min_by(|a, b| {
+                (a.strike - forward)
+                    .abs()
+                    .total_cmp(&(b.strike - forward).abs())
+            })
+            .map(|quote| quote.volatility)
+            .unwrap_or(0.2);
+        let template = SABRParameters::new(Positive::ONE, beta, Decimal::ZERO, Positive::ONE);
+        let unpack = |x: &[f64]| (x[0].exp(), 0.999 * x[1].tanh(), x[2].exp());
+        let objective = |x: &[f64]| {
+            let (alpha, rho, nu) = unpack(x);
+            let model = SABRParameters {
+                rho: Decimal::from_f64(rho).unwrap_or_default(),
+                nu: Positive::from(Decimal::from_f64(nu).unwrap_or_default()),
+                ..template
+            };
+            quotes
+                .iter()
+                .map(|quote| {
+                    let fitted = model.lognormal(alpha, forward, quote.strike, t);
+                    ((fitted - quote.volatility) / quote.spread).powi(2)
+                })
+                .sum::<f64>()
+        };
+        let alpha_guess = (atm_volatility * forward.powf(1.0 - beta_f64)).ln();
+        let (best, _) = [[alpha_guess, 0.0, 0.0], [alpha_guess, -0.5, -0.7]]
+            .iter()
+            .map(|start| nelder_mead(objective, start, 0.3, MAX_ITERATIONS, 1e-16))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: "SABR search did not start".to_string(),
+            })?;
+        let (alpha, rho, nu) = unpack(&best);
+        let parameters = SABRParameters::new(
+            Positive::from(to_decimal(alpha)?),
+            beta,
+            to_decimal(rho)?,
+            Positive::from(to_decimal(nu)?),
+        );
+        let squared_error = quotes
+            .iter()
+            .map(|quote| {
+                (parameters.lognormal(alpha, forward, quote.strike, t) - quote.volatility).powi(2)
+            })
+            .sum::<f64>()
+            / quotes.len() as f64;
+        let strikes = quotes
+            .iter()
+            .map(|quote| to_decimal(quote.strike).map(Positive::from))
+            .collect::<Result<Vec<_>, _>>()?;
+        let strike_range = (
+            strikes
+                .iter()
+                .copied()
+                .fold(Positive::INFINITY, Positive::min),
+            strikes.iter().copied().fold(Positive::ZERO, Positive::max),
+        );
+
+        Ok(SABRSmile {
+            parameters,
+            forward: Positive::from(to_decimal(forward)?),
+            years,
+            strike_range,
+            rmse: to_decimal(squared_error.sqrt())?,
+        })
+    }
+
+    /// Lognormal implied volatility at `strike`.
+    ///
+    /// # Errors
+    ///
+    /// See [`SABRParameters::implied_volatility`].
+    pub fn implied_volatility(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        self.parameters.implied_volatility(
+            self.forward,
+            strike,
+            self.years,
+            SABRVolatilityType::Lognormal,
+        )
+    }
+
+    /// Normal implied volatility at `strike`, in price units per year.
+    ///
+    /// # Errors
+    ///
+    /// See [`SABRParameters::implied_volatility`].
+    pub fn normal_volatility(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        self.parameters.implied_volatility(
+            self.forward,
+            strike,
+            self.years,
+            SABRVolatilityType::Normal,
+        )
+    }
+}
+
+impl VolatilitySmile for SABRSmile {
+    /// Samples the lognormal SABR smile across `strike_range`.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = strike_grid(self.strike_range)
+            .into_iter()
+            .filter_map(|strike| {
+                self.implied_volatility(strike)
+                    .ok()
+                    .map(|iv| Point2D::new(strike.to_dec(), iv.to_dec()))
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests_sabr {
+    use super::*;
+    use crate::greeks::{Greeks, delta, vega};
+    use crate::model::utils::create_sample_option;
+    use crate::strategies::ShortStrangle;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::volatility::svi::GRID_POINTS;
+    use crate::{assert_decimal_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn true_parameters() -> SABRParameters {
+        SABRParameters::new(pos!(2.0), dec!(0.5), dec!(-0.35), pos!(0.6))
+    }
+
+    fn chain_from_sabr(parameters: &SABRParameters, with_prices: bool) -> OptionChain {
+        let mut chain = OptionChain::new(
+            "TEST",
+            pos!(100.0),
+            get_x_days_formatted_pos(pos!(90.0)),
+            None,
+            None,
+        );
+        let days = chain.get_expiration().unwrap().get_days().unwrap();
+        let years = days / DAYS_IN_A_YEAR;
+        for strike in (75..=125).step_by(5) {
+            let strike = pos!(strike as f64);
+            let iv = parameters
+                .implied_volatility(pos!(100.0), strike, years, SABRVolatilityType::Lognormal)
+                .unwrap();
+            let (mut call_bid, mut call_ask, mut put_bid, mut put_ask) = (None, None, None, None);
+            let mut quoted_iv = iv;
+            if with_prices {
+                let price = |style: OptionStyle, volatility: Positive| {
+                    let option = Options::new(
+                        OptionType::European,
+                        Side::Long,
+                        "TEST".to_string(),
+                        strike,
+                        ExpirationDate::Days(days),
+                        volatility,
+                        Positive::ONE,
+                        pos!(100.0),
+                        Decimal::ZERO,
+                        style,
+                        Positive::ZERO,
+                        None,
+                    );
+                    Positive::from(option.calculate_price_black_scholes().unwrap())
+                };
+                // One volatility point wide around the model volatility
+                let (low, high) = (iv - pos!(0.005), iv + pos!(0.005));
+                call_bid = Some(price(OptionStyle::Call, low));
+                call_ask = Some(price(OptionStyle::Call, high));
+                put_bid = Some(price(OptionStyle::Put, low));
+                put_ask = Some(price(OptionStyle::Put, high));
+                quoted_iv = Positive::ZERO;
+            }
+            chain.add_option(
+                strike, call_bid, call_ask, put_bid, put_ask, quoted_iv, None, None, None, None,
+                None, None,
+            );
+        }
+        chain
+    }
+
+    #[test]
+    fn test_hagan_reduces_to_constant_volatility() {
+        // beta = 1 and no vol-of-vol: Black-Scholes with volatility alpha
+        let flat = SABRParameters::new(pos!(0.2), Decimal::ONE, Decimal::ZERO, pos!(1e-9));
+        for strike in [pos!(80.0), pos!(100.0), pos!(120.0)] {
+            let iv = flat
+                .implied_volatility(
+                    pos!(100.0),
+                    strike,
+                    Positive::ONE,
+                    SABRVolatilityType::Lognormal,
+                )
+                .unwrap();
+            assert_decimal_eq!(iv.to_dec(), dec!(0.2), dec!(1e-9));
+        }
+        // beta = 0 and no vol-of-vol: Bachelier with volatility alpha
+        let normal = SABRParameters::new(pos!(20.0), Decimal::ZERO, Decimal::ZERO, pos!(1e-9));
+        let iv = normal
+            .implied_volatility(
+                pos!(100.0),
+                pos!(100.0),
+                Positive::ONE,
+                SABRVolatilityType::Normal,
+            )
+            .unwrap();
+        assert_decimal_eq!(iv.to_dec(), dec!(20.0), dec!(1e-6));
+    }
+
+    #[test]
+    fn test_lognormal_and_normal_agree_at_the_money() {
+        let parameters = true_parameters();
+        let lognormal = parameters
+            .implied_volatility(
+                pos!(100.0),
+                pos!(100.0),
+                pos!(0.25),
+                SABRVolatilityType::Lognormal,
+            )
+            .unwrap();
+        let normal = parameters
+            .implied_volatility(
+                pos!(100.0),
+                pos!(100.0),
+                pos!(0.25),
+                SABRVolatilityType::Normal,
+            )
+            .unwrap();
+        // At the money, the normal volatility is close to F times the lognormal one
+        let ratio = normal.to_dec() / (lognormal.to_dec() * dec!(100.0));
+        assert_decimal_eq!(ratio, Decimal::ONE, dec!(0.01));
+        // Negative rho: downside skew
+        let low = parameters
+            .implied_volatility(
+                pos!(100.0),
+                pos!(80.0),
+                pos!(0.25),
+                SABRVolatilityType::Lognormal,
+            )
+            .unwrap();
+        assert!(low > lognormal);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let parameters = SABRParameters::new(pos!(0.2), dec!(1.5), Decimal::ZERO, pos!(0.3));
+        assert!(
+            parameters
+                .implied_volatility(
+                    pos!(100.0),
+                    pos!(100.0),
+                    Positive::ONE,
+                    SABRVolatilityType::Lognormal
+                )
+                .is_err()
+        );
+        let chain = chain_from_sabr(&true_parameters(), false);
+        assert!(matches!(
+            SABRSmile::calibrate(&chain, dec!(-0.1)),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_calibrate_to_implied_volatilities() {
+        let truth = true_parameters();
+        let chain = chain_from_sabr(&truth, false);
+        let smile = SABRSmile::calibrate(&chain, dec!(0.5)).unwrap();
+        assert!(smile.rmse < dec!(0.0005), "rmse {}", smile.rmse);
+        assert_decimal_eq!(smile.parameters.alpha.to_dec(), dec!(2.0), dec!(0.05));
+        assert_decimal_eq!(smile.parameters.rho, dec!(-0.35), dec!(0.05));
+        assert_decimal_eq!(smile.parameters.nu.to_dec(), dec!(0.6), dec!(0.05));
+        assert_eq!(smile.strike_range, (pos!(75.0), pos!(125.0)));
+
+        let curve = smile.smile();
+        assert_eq!(curve.points.len(), GRID_POINTS);
+    }
+
+    #[test]
+    fn test_calibrate_to_bid_ask_prices() {
+        let chain = chain_from_sabr(&true_parameters(), true);
+        let smile = SABRSmile::calibrate(&chain, dec!(0.5)).unwrap();
+        let days = chain.get_expiration().unwrap().get_days().unwrap();
+        let expected = true_parameters()
+            .implied_volatility(
+                pos!(100.0),
+                pos!(90.0),
+                days / DAYS_IN_A_YEAR,
+                SABRVolatilityType::Lognormal,
+            )
+            .unwrap();
+        let fitted = smile.implied_volatility(pos!(90.0)).unwrap();
+        assert_decimal_eq!(fitted.to_dec(), expected.to_dec(), dec!(0.001));
+    }
+
+    #[test]
+    fn test_bartlett_delta_without_vol_of_vol_is_black_scholes() {
+        let flat = SABRParameters::new(pos!(0.2), Decimal::ONE, dec!(-0.5), pos!(1e-9));
+        for side in [Side::Long, Side::Short] {
+            let option = create_sample_option(
+                OptionStyle::Call,
+                side,
+                pos!(100.0),
+                pos!(2.0),
+                pos!(105.0),
+                pos!(0.2),
+            );
+            let bartlett = flat.bartlett_delta(&option).unwrap();
+            assert_decimal_eq!(bartlett, delta(&option).unwrap(), dec!(1e-4));
+            let sabr_vega = flat.vega(&option).unwrap();
+            assert_decimal_eq!(sabr_vega, vega(&option).unwrap(), dec!(1e-4));
+        }
+    }
+
+    #[test]
+    fn test_bartlett_delta_with_negative_correlation() {
+        let skewed = SABRParameters::new(pos!(0.2), Decimal::ONE, dec!(-0.7), pos!(0.8));
+        let uncorrelated = SABRParameters::new(pos!(0.2), Decimal::ONE, Decimal::ZERO, pos!(0.8));
+        let option = create_sample_option(
+            OptionStyle::Call,
+            Side::Long,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(100.0),
+            pos!(0.2),
+        );
+        // Volatility falls when the forward rises, lowering the call delta
+        assert!(
+            skewed.bartlett_delta(&option).unwrap() < uncorrelated.bartlett_delta(&option).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_strategy_sabr_greeks() {
+        let strangle = ShortStrangle::new(
+            "TEST".to_string(),
+            pos!(100.0),
+            pos!(110.0),
+            pos!(90.0),
+            ExpirationDate::Days(pos!(30.0)),
+            pos!(0.2),
+            pos!(0.2),
+            Decimal::ZERO,
+            Positive::ZERO,
+            Positive::ONE,
+            pos!(1.0),
+            pos!(1.0),
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+            Positive::ZERO,
+        );
+        let flat = SABRParameters::new(pos!(0.2), Decimal::ONE, Decimal::ZERO, pos!(1e-9));
+        assert_decimal_eq!(
+            strangle.sabr_delta(&flat).unwrap(),
+            strangle.delta().unwrap(),
+            dec!(1e-4)
+        );
+        assert_decimal_eq!(
+            strangle.sabr_vega(&flat).unwrap(),
+            strangle.vega().unwrap(),
+            dec!(1e-4)
+        );
+    }
+}
@@ -0,0 +1,548 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::constants::{IV_TOLERANCE, MAX_ITERATIONS_IV};
+use crate::error::VolatilityError;
+use crate::model::types::{OptionStyle, OptionType, Side};
+use crate::{Options, Positive};
+use num_traits::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Lower bound of the volatility search interval.
+const MIN_SEARCH_VOLATILITY: f64 = 1e-6;
+
+/// Upper bound of the volatility search interval (500%).
+const MAX_SEARCH_VOLATILITY: f64 = 5.0;
+
+/// Default number of Newton–Raphson iterations before falling back to Brent's method.
+const MAX_NEWTON_ITERATIONS: u32 = 50;
+
+/// Root-finding method that produced an implied volatility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImpliedVolatilityMethod {
+    /// Newton–Raphson iterations using the analytic vega.
+    Newton,
+    /// Brent's method on a bracketing interval, used when Newton fails to converge.
+    Brent,
+}
+
+impl fmt::Display for ImpliedVolatilityMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImpliedVolatilityMethod::Newton => write!(f, "Newton-Raphson"),
+            ImpliedVolatilityMethod::Brent => write!(f, "Brent"),
+        }
+    }
+}
+
+/// Implied volatility together with the diagnostics of the solver run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpliedVolatilityResult {
+    /// The implied volatility.
+    pub volatility: Positive,
+    /// Total number of iterations, Newton and Brent combined.
+    pub iterations: u32,
+    /// Model price at `volatility` minus the market price, per contract.
+    pub price_error: Decimal,
+    /// Method that produced the final estimate.
+    pub method: ImpliedVolatilityMethod,
+}
+
+/// Settings of the implied volatility solver.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpliedVolatilityConfig {
+    /// Maximum number of Newton–Raphson iterations before switching to Brent's method.
+    pub max_newton_iterations: u32,
+    /// Maximum number of iterations in total.
+    pub max_iterations: u32,
+    /// Absolute price tolerance at which the solver stops.
+    pub price_tolerance: Decimal,
+}
+
+impl Default for ImpliedVolatilityConfig {
+    fn default() -> Self {
+        ImpliedVolatilityConfig {
+            max_newton_iterations: MAX_NEWTON_ITERATIONS,
+            max_iterations: MAX_ITERATIONS_IV,
+            price_tolerance: IV_TOLERANCE,
+        }
+    }
+}
+
+/// Pricing problem in volatility: the long price of a clone of the option.
+struct VolatilityProblem {
+    option: Options,
+    target: f64,
+    spot: f64,
+    strike: f64,
+    time: f64,
+    rate: f64,
+    dividend_yield: f64,
+}
+
+impl VolatilityProblem {
+    fn new(option: &Options, market_price: Decimal) -> Result<Self, VolatilityError> {
+        let target = market_price.abs().to_f64().unwrap_or(0.0);
+        if target <= 0.0 {
+            return Err(VolatilityError::OptionError {
+                reason: format!("Market price must be non-zero, got {market_price}"),
+            });
+        }
+        let time = option
+            .time_to_expiration()
+            .map_err(|e| VolatilityError::OptionError {
+                reason: e.to_string(),
+            })?
+            .to_f64();
+        if time <= 0.0 {
+            return Err(VolatilityError::OptionError {
+                reason: "Cannot imply a volatility from an expired option".to_string(),
+            });
+        }
+        let spot = option.underlying_price.to_f64();
+        let strike = option.strike_price.to_f64();
+        if spot <= 0.0 || strike <= 0.0 {
+            return Err(VolatilityError::OptionError {
+                reason: "Underlying and strike prices must be positive".to_string(),
+            });
+        }
+        let rate = option.risk_free_rate.to_f64().unwrap_or(0.0);
+        let dividend_yield = option.dividend_yield.to_f64();
+        let mut option = option.clone();
+        option.side = Side::Long;
+        option.quantity = Positive::ONE;
+        Ok(VolatilityProblem {
+            option,
+            target,
+            spot,
+            strike,
+            time,
+            rate,
+            dividend_yield,
+        })
+    }
+
+    /// Model price minus market price at volatility `sigma`.
+    fn objective(&mut self, sigma: f64) -> Result<f64, VolatilityError> {
+        self.option.implied_volatility = Positive(Decimal::from_f64(sigma).ok_or(
+            VolatilityError::OptionError {
+                reason: format!("Invalid volatility {sigma}"),
+            },
+        )?);
+        let price = self.option.calculate_price_black_scholes().map_err(|e| {
+            VolatilityError::OptionError {
+                reason: e.to_string(),
+            }
+        })?;
+        Ok(price.to_f64().unwrap_or(f64::NAN) - self.target)
+    }
+
+    fn forward(&self) -> f64 {
+        self.spot * ((self.rate - self.dividend_yield) * self.time).exp()
+    }
+
+    /// Black-Scholes-Merton vega per unit of volatility.
+    fn vega(&self, sigma: f64) -> f64 {
+        let vol_sqrt_time = sigma * self.time.sqrt();
+        let d1 = ((self.spot / self.strike).ln()
+            + (self.rate - self.dividend_yield + sigma * sigma / 2.0) * self.time)
+            / vol_sqrt_time;
+        self.spot * (-self.dividend_yield * self.time).exp() * (-d1 * d1 / 2.0).exp()
+            / (2.0 * PI).sqrt()
+            * self.time.sqrt()
+    }
+
+    /// No-arbitrage bounds of a European price, used to reject impossible quotes.
+    fn price_bounds(&self) -> Option<(f64, f64)> {
+        if self.option.option_type != OptionType::European {
+            return None;
+        }
+        let discounted_spot = self.spot * (-self.dividend_yield * self.time).exp();
+        let discounted_strike = self.strike * (-self.rate * self.time).exp();
+        Some(match self.option.option_style {
+            OptionStyle::Call => (
+                (discounted_spot - discounted_strike).max(0.0),
+                discounted_spot,
+            ),
+            OptionStyle::Put => (
+                (discounted_strike - discounted_spot).max(0.0),
+                discounted_strike,
+            ),
+        })
+    }
+
+    /// Initial guess in normalised coordinates.
+    ///
+    /// Following Jäckel, the price is normalised by the forward and the log-moneyness
+    /// `x = ln(F/K)`. The Corrado–Miller approximation is used where it is defined, which
+    /// covers quotes reasonably close to the money; otherwise the guess is the inflection
+    /// point `σ√T = √(2|x|)` of the normalised price, where vega is maximal and Newton's
+    /// method converges monotonically.
+    fn initial_guess(&self) -> f64 {
+        let forward = self.forward();
+        let x = (forward / self.strike).ln();
+        let sqrt_time = self.time.sqrt();
+        let discount = (-self.rate * self.time).exp();
+        let call_price = match self.option.option_style {
+            OptionStyle::Call => self.target,
+            // Put-call parity on the undiscounted forward prices
+            OptionStyle::Put => self.target + discount * (forward - self.strike),
+        };
+        let discounted_forward = forward * discount;
+        let discounted_strike = self.strike * discount;
+        let half_gap = (discounted_forward - discounted_strike) / 2.0;
+        let radicand =
+            (call_price - half_gap).powi(2) - (discounted_forward - discounted_strike).powi(2) / PI;
+        let corrado_miller = if radicand >= 0.0 {
+            (2.0 * PI).sqrt() / (discounted_forward + discounted_strike)
+                * (call_price - half_gap + radicand.sqrt())
+                / sqrt_time
+        } else {
+            f64::NAN
+        };
+        let inflection = (2.0 * x.abs()).sqrt() / sqrt_time;
+        let guess = if corrado_miller.is_finite() && corrado_miller > 0.0 {
+            corrado_miller
+        } else if inflection > 0.0 {
+            inflection
+        } else {
+            0.2
+        };
+        guess.clamp(0.01, MAX_SEARCH_VOLATILITY / 2.0)
+    }
+}
+
+/// Solves for the volatility that reproduces `market_price` with the default settings.
+///
+/// See [`implied_volatility_with_config`].
+pub fn solve_implied_volatility(
+    option: &Options,
+    market_price: Decimal,
+) -> Result<ImpliedVolatilityResult, VolatilityError> {
+    implied_volatility_with_config(option, market_price, &ImpliedVolatilityConfig::default())
+}
+
+/// Solves for the volatility at which the option's model price equals `market_price`.
+///
+/// The option is priced with `Options::calculate_price_black_scholes`, so the solver
+/// honours the dividend yield and every option type that pricer supports. The price is
+/// taken per contract and in absolute value, so short positions may quote negative prices.
+///
+/// # Algorithm
+///
+/// 1. For European options, quotes outside the no-arbitrage bounds are rejected.
+/// 2. Newton–Raphson iterations start from a normalised initial guess (see below) and
+///    use the analytic Black-Scholes-Merton vega. Every evaluation also tightens a
+///    bracketing interval.
+/// 3. If Newton stalls (tiny vega, a step outside the bracket, or too many iterations),
+///    Brent's method finishes the search inside the bracket.
+///
+/// The initial guess follows Jäckel's normalisation by forward and log-moneyness,
+/// using the Corrado–Miller approximation where it is defined and the inflection point
+/// of the normalised price elsewhere.
+///
+/// # Returns
+///
+/// The implied volatility with the number of iterations, the residual price error and
+/// the method that converged.
+///
+/// # Errors
+///
+/// * `VolatilityError::OptionError` for non-positive prices, expired options or pricing failures.
+/// * `VolatilityError::InvalidPrice` for quotes outside the no-arbitrage bounds.
+/// * `VolatilityError::NoConvergence` when no volatility in (0, 500%] matches the price
+///   within `config.max_iterations` iterations.
+pub fn implied_volatility_with_config(
+    option: &Options,
+    market_price: Decimal,
+    config: &ImpliedVolatilityConfig,
+) -> Result<ImpliedVolatilityResult, VolatilityError> {
+    let mut problem = VolatilityProblem::new(option, market_price)?;
+    let tolerance = config.price_tolerance.to_f64().unwrap_or(1e-5);
+
+    if let Some((lower, upper)) = problem.price_bounds()
+        && (problem.target <= lower || problem.target >= upper)
+    {
+        return Err(VolatilityError::InvalidPrice {
+            price: Positive(market_price.abs()),
+            reason: format!("Price is outside the no-arbitrage bounds ({lower:.6}, {upper:.6})"),
+        });
+    }
+
+    let finish = |sigma: f64, error: f64, iterations: u32, method| {
+        Ok(ImpliedVolatilityResult {
+            volatility: Positive(Decimal::from_f64(sigma).unwrap_or_default()),
+            iterations,
+            price_error: Decimal::from_f64(error).unwrap_or_default(),
+            method,
+        })
+    };
+
+    // Bracket [low, high] with objective(low) < 0 < objective(high), tightened as we go
+    let mut low = MIN_SEARCH_VOLATILITY;
+    let mut high = MAX_SEARCH_VOLATILITY;
+    let mut sigma = problem.initial_guess();
+    let mut iterations = 0;
+    let newton_budget = config.max_newton_iterations.min(config.max_iterations);
+
+    while iterations < newton_budget {
+        iterations += 1;
+        let error = problem.objective(sigma)?;
+        if error.abs() < tolerance {
+            return finish(sigma, error, iterations, ImpliedVolatilityMethod::Newton);
+        }
+        if error > 0.0 {
+            high = high.min(sigma);
+        } else {
+            low = low.max(sigma);
+        }
+        let vega = problem.vega(sigma);
+        if !vega.is_finite() || vega < 1e-12 {
+            break;
+        }
+        let next = sigma - error / vega;
+        if !next.is_finite() || next <= low || next >= high {
+            break;
+        }
+        if (next - sigma).abs() < 1e-12 {
+            let error = problem.objective(next)?;
+            if error.abs() < tolerance {
+                return finish(next, error, iterations, ImpliedVolatilityMethod::Newton);
+            }
+            break;
+        }
+        sigma = next;
+    }
+
+    brent(
+        &mut problem,
+        low,
+        high,
+        tolerance,
+        iterations,
+        config.max_iterations,
+    )
+    .and_then(|(sigma, error, iterations)| {
+        finish(sigma, error, iterations, ImpliedVolatilityMethod::Brent)
+    })
+}
+
+/// Brent's method on `[low, high]`, continuing the iteration count of the Newton phase.
+fn brent(
+    problem: &mut VolatilityProblem,
+    low: f64,
+    high: f64,
+    tolerance: f64,
+    mut iterations: u32,
+    max_iterations: u32,
+) -> Result<(f64, f64, u32), VolatilityError> {
+    let (mut a, mut b) = (low, high);
+    let mut fa = problem.objective(a)?;
+    let mut fb = problem.objective(b)?;
+    if fa.abs() < tolerance {
+        return Ok((a, fa, iterations));
+    }
+    if fb.abs() < tolerance {
+        return Ok((b, fb, iterations));
+    }
+    if fa * fb > 0.0 {
+        return Err(VolatilityError::NoConvergence {
+            iterations,
+            last_volatility: Positive(
+                Decimal::from_f64(if fa.abs() < fb.abs() { a } else { b }).unwrap_or_default(),
+            ),
+        });
+    }
+
+    let (mut c, mut fc) = (a, fa);
+    let mut d = b - a;
+    let mut e = d;
+    while iterations < max_iterations {
+        iterations += 1;
+        if fb * fc > 0.0 {
+            c = a;
+            fc = fa;
+            d = b - a;
+            e = d;
+        }
+        if fc.abs() < fb.abs() {
+            a = b;
+            b = c;
+            c = a;
+            fa = fb;
+            fb = fc;
+            fc = fa;
+        }
+        let step_tolerance = 2.0 * f64::EPSILON * b.abs() + 1e-12;
+        let midpoint = (c - b) / 2.0;
+        if fb.abs() < tolerance || midpoint.abs() <= step_tolerance {
+            return Ok((b, fb, iterations));
+        }
+        if e.abs() >= step_tolerance && fa.abs() > fb.abs() {
+            // Inverse quadratic interpolation, or the secant method with two points
+            let s = fb / fa;
+            let (mut p, mut q) = if a == c {
+                (2.0 * midpoint * s, 1.0 - s)
+            } else {
+                let q = fa / fc;
+                let r = fb / fc;
+                (
+                    s * (2.0 * midpoint * q * (q - r) - (b - a) * (r - 1.0)),
+                    (q - 1.0) * (r - 1.0) * (s - 1.0),
+                )
+            };
+            if p > 0.0 {
+                q = -q;
+            } else {
+                p = -p;
+            }
+            if 2.0 * p < (3.0 * midpoint * q - (step_tolerance * q).abs()).min((e * q).abs()) {
+                e = d;
+                d = p / q;
+            } else {
+                d = midpoint;
+                e = d;
+            }
+        } else {
+            d = midpoint;
+            e = d;
+        }
+        a = b;
+        fa = fb;
+        b += if d.abs() > step_tolerance {
+            d
+        } else {
+            step_tolerance.copysign(midpoint)
+        };
+        fb = problem.objective(b)?;
+    }
+    Err(VolatilityError::NoConvergence {
+        iterations,
+        last_volatility: Positive(Decimal::from_f64(b).unwrap_or_default()),
+    })
+}
+
+#[cfg(test)]
+mod tests_implied_volatility_solver {
+    use super::*;
+    use crate::model::ExpirationDate;
+    use crate::model::utils::create_sample_option;
+    use crate::{assert_pos_relative_eq, pos};
+    use rust_decimal_macros::dec;
+
+    fn option_with_dividend(style: OptionStyle, strike: Positive, iv: Positive) -> Options {
+        Options::new(
+            OptionType::European,
+            Side::Long,
+            "TEST".to_string(),
+            strike,
+            ExpirationDate::Days(pos!(90.0)),
+            iv,
+            Positive::ONE,
+            pos!(100.0),
+            dec!(0.04),
+            style,
+            pos!(0.02),
+            None,
+        )
+    }
+
+    #[test]
+    fn test_round_trip_call_and_put() {
+        for style in [OptionStyle::Call, OptionStyle::Put] {
+            for strike in [pos!(80.0), pos!(100.0), pos!(125.0)] {
+                let option = option_with_dividend(style, strike, pos!(0.3));
+                let price = option.calculate_price_black_scholes().unwrap();
+                let result = solve_implied_volatility(&option, price).unwrap();
+                assert_pos_relative_eq!(result.volatility, pos!(0.3), pos!(1e-4));
+                assert!(result.price_error.abs() < IV_TOLERANCE);
+                assert!(result.iterations > 0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_option_negative_price() {
+        let mut option = option_with_dividend(OptionStyle::Call, pos!(105.0), pos!(0.22));
+        option.side = Side::Short;
+        option.quantity = pos!(3.0);
+        let price = option.calculate_price_black_scholes().unwrap();
+        assert!(price < Decimal::ZERO);
+        let result = solve_implied_volatility(&option, price).unwrap();
+        assert_pos_relative_eq!(result.volatility, pos!(0.22), pos!(1e-4));
+    }
+
+    #[test]
+    fn test_near_the_money_uses_newton() {
+        let option = create_sample_option(
+            OptionStyle::Call,
+            Side::Long,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(100.0),
+            pos!(0.2),
+        );
+        let price = option.calculate_price_black_scholes().unwrap();
+        let result = solve_implied_volatility(&option, price).unwrap();
+        assert_eq!(result.method, ImpliedVolatilityMethod::Newton);
+        assert!(result.iterations <= 10);
+    }
+
+    #[test]
+    fn test_deep_out_of_the_money_converges() {
+        let option = option_with_dividend(OptionStyle::Call, pos!(250.0), pos!(0.8));
+        let price = option.calculate_price_black_scholes().unwrap();
+        let result = solve_implied_volatility(&option, price).unwrap();
+        assert_pos_relative_eq!(result.volatility, pos!(0.8), pos!(1e-3));
+    }
+
+    #[test]
+    fn test_brent_fallback_without_newton() {
+        let option = option_with_dividend(OptionStyle::Put, pos!(90.0), pos!(0.45));
+        let price = option.calculate_price_black_scholes().unwrap();
+        let config = ImpliedVolatilityConfig {
+            max_newton_iterations: 0,
+            ..Default::default()
+        };
+        let result = implied_volatility_with_config(&option, price, &config).unwrap();
+        assert_eq!(result.method, ImpliedVolatilityMethod::Brent);
+        assert_pos_relative_eq!(result.volatility, pos!(0.45), pos!(1e-3));
+    }
+
+    #[test]
+    fn test_price_outside_bounds() {
+        let option = option_with_dividend(OptionStyle::Call, pos!(100.0), pos!(0.2));
+        // A call can never be worth more than the discounted underlying
+        let result = solve_implied_volatility(&option, dec!(150.0));
+        assert!(matches!(result, Err(VolatilityError::InvalidPrice { .. })));
+        // Nor less than its discounted intrinsic value
+        let itm = option_with_dividend(OptionStyle::Call, pos!(50.0), pos!(0.2));
+        let result = solve_implied_volatility(&itm, dec!(10.0));
+        assert!(matches!(result, Err(VolatilityError::InvalidPrice { .. })));
+    }
+
+    #[test]
+    fn test_zero_price_and_expired_option() {
+        let option = option_with_dividend(OptionStyle::Call, pos!(100.0), pos!(0.2));
+        let result = solve_implied_volatility(&option, Decimal::ZERO);
+        assert!(matches!(result, Err(VolatilityError::OptionError { .. })));
+
+        let mut expired = option.clone();
+        expired.expiration_date = ExpirationDate::Days(Positive::ZERO);
+        let result = solve_implied_volatility(&expired, dec!(1.0));
+        assert!(matches!(result, Err(VolatilityError::OptionError { .. })));
+    }
+
+    #[test]
+    fn test_method_display() {
+        assert_eq!(
+            ImpliedVolatilityMethod::Newton.to_string(),
+            "Newton-Raphson"
+        );
+        assert_eq!(ImpliedVolatilityMethod::Brent.to_string(), "Brent");
+    }
+}
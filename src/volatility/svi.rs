@@ -0,0 +1,1061 @@
+/******************************************************************************
+   Author: Joaquín Béjar García
+   Email: jb@taunais.com
+   Date: 17/10/26
+******************************************************************************/
+use crate::chains::chain::OptionChain;
+use crate::constants::DAYS_IN_A_YEAR;
+use crate::curves::{Curve, Point2D};
+use crate::error::VolatilityError;
+use crate::series::{CalendarArbitrage, OptionSeries};
+use crate::surfaces::{Point3D, Surface};
+use crate::volatility::VolatilitySmile;
+use crate::volatility::optimizer::nelder_mead;
+use crate::{Options, Positive};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// Number of points used to sample smiles and to scan for arbitrage.
+pub(super) const GRID_POINTS: usize = 101;
+
+/// Iterations allowed to each Nelder-Mead search.
+pub(super) const MAX_ITERATIONS: usize = 2000;
+
+pub(super) fn to_f64(value: Decimal) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+pub(super) fn to_decimal(value: f64) -> Result<Decimal, VolatilityError> {
+    Decimal::from_f64(value).ok_or_else(|| VolatilityError::CalibrationError {
+        reason: format!("Value {value} cannot be represented as a decimal"),
+    })
+}
+
+/// Forward price for a maturity of `years`.
+pub(super) fn forward_price(
+    spot: Positive,
+    rate: Decimal,
+    dividend_yield: Positive,
+    years: f64,
+) -> f64 {
+    to_f64(spot.to_dec()) * ((to_f64(rate) - to_f64(dividend_yield.to_dec())) * years).exp()
+}
+
+/// Quoted `(log-moneyness, total variance)` pairs of a chain.
+fn chain_total_variances(chain: &OptionChain, forward: f64, years: f64) -> Vec<(f64, f64)> {
+    chain
+        .options
+        .iter()
+        .filter(|option| option.implied_volatility > Positive::ZERO)
+        .map(|option| {
+            let iv = to_f64(option.implied_volatility.to_dec());
+            (
+                (to_f64(option.strike_price.to_dec()) / forward).ln(),
+                iv * iv * years,
+            )
+        })
+        .collect()
+}
+
+/// Range of quoted strikes of a chain.
+fn chain_strike_range(chain: &OptionChain) -> Option<(Positive, Positive)> {
+    let mut strikes = chain
+        .options
+        .iter()
+        .filter(|option| option.implied_volatility > Positive::ZERO)
+        .map(|option| option.strike_price);
+    let first = strikes.next()?;
+    let last = strikes.next_back().unwrap_or(first);
+    Some((first, last))
+}
+
+/// Evenly spaced strikes covering `range`.
+pub(super) fn strike_grid(range: (Positive, Positive)) -> Vec<Positive> {
+    let (low, high) = (range.0.to_dec(), range.1.to_dec());
+    let steps = Decimal::from(GRID_POINTS - 1);
+    (0..GRID_POINTS)
+        .map(|i| Positive::from(low + (high - low) * Decimal::from(i) / steps))
+        .collect()
+}
+
+/// Solves the 3x3 linear system `matrix * x = rhs` by Gaussian elimination.
+fn solve_3x3(mut matrix: [[f64; 3]; 3], mut rhs: [f64; 3]) -> Option<[f64; 3]> {
+    for col in 0..3 {
+        let pivot =
+            (col..3).max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))?;
+        if matrix[pivot][col].abs() < 1e-14 {
+            return None;
+        }
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+        for row in col + 1..3 {
+            let pivot_row = matrix[col];
+            let factor = matrix[row][col] / pivot_row[col];
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+            rhs[row] -= factor * rhs[col];
+        }
+    }
+    let mut x = [0.0; 3];
+    for row in (0..3).rev() {
+        let tail: f64 = (row + 1..3).map(|k| matrix[row][k] * x[k]).sum();
+        x[row] = (rhs[row] - tail) / matrix[row][row];
+    }
+    Some(x)
+}
+
+/// Parameters of the raw SVI parameterization of total implied variance:
+///
+/// `w(k) = a + b * (rho * (k - m) + sqrt((k - m)² + sigma²))`
+///
+/// where `k = ln(K / F)` is the log-moneyness against the forward.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SVIParameters {
+    /// Vertical level of the smile.
+    pub a: Decimal,
+    /// Slope of the wings.
+    pub b: Decimal,
+    /// Skew, between -1 and 1.
+    pub rho: Decimal,
+    /// Horizontal shift of the smile.
+    pub m: Decimal,
+    /// Curvature around the minimum.
+    pub sigma: Decimal,
+}
+
+impl SVIParameters {
+    /// Creates a new set of raw SVI parameters.
+    pub fn new(a: Decimal, b: Decimal, rho: Decimal, m: Decimal, sigma: Decimal) -> Self {
+        SVIParameters {
+            a,
+            b,
+            rho,
+            m,
+            sigma,
+        }
+    }
+
+    fn as_f64(&self) -> [f64; 5] {
+        [
+            to_f64(self.a),
+            to_f64(self.b),
+            to_f64(self.rho),
+            to_f64(self.m),
+            to_f64(self.sigma),
+        ]
+    }
+
+    /// Total implied variance at log-moneyness `k`.
+    pub fn total_variance(&self, k: Decimal) -> Decimal {
+        Decimal::from_f64(svi_total_variance(&self.as_f64(), to_f64(k))).unwrap_or(Decimal::ZERO)
+    }
+}
+
+fn svi_total_variance(p: &[f64; 5], k: f64) -> f64 {
+    let [a, b, rho, m, sigma] = *p;
+    let y = k - m;
+    a + b * (rho * y + (y * y + sigma * sigma).sqrt())
+}
+
+/// Gatheral's butterfly density condition `g(k)`; negative values mean that a butterfly
+/// spread centred at `k` has a negative price.
+fn svi_density(p: &[f64; 5], k: f64) -> f64 {
+    let [_, b, rho, m, sigma] = *p;
+    let y = k - m;
+    let z = (y * y + sigma * sigma).sqrt();
+    let w = svi_total_variance(p, k);
+    let w1 = b * (rho + y / z);
+    let w2 = b * sigma * sigma / (z * z * z);
+    (1.0 - k * w1 / (2.0 * w)).powi(2) - w1 * w1 / 4.0 * (1.0 / w + 0.25) + w2 / 2.0
+}
+
+/// Best raw SVI slice for fixed `m` and `sigma`, with `a`, `b` and `rho` obtained by
+/// constrained linear least squares. Returns the parameters and the squared error.
+fn svi_inner_fit(points: &[(f64, f64)], m: f64, sigma: f64) -> Option<([f64; 5], f64)> {
+    let features: Vec<(f64, f64, f64)> = points
+        .iter()
+        .map(|&(k, w)| {
+            let y = k - m;
+            (y, (y * y + sigma * sigma).sqrt(), w)
+        })
+        .collect();
+    let mut matrix = [[0.0; 3]; 3];
+    let mut rhs = [0.0; 3];
+    for &(y, z, w) in &features {
+        let row = [1.0, y, z];
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix[i][j] += row[i] * row[j];
+            }
+            rhs[i] += row[i] * w;
+        }
+    }
+    let [_, d, c] = solve_3x3(matrix, rhs)?;
+    // w = a + d * y + c * z with b = c and rho = d / c
+    let c = c.max(0.0);
+    let d = d.clamp(-0.999 * c, 0.999 * c);
+    let n = features.len() as f64;
+    let mut a = features
+        .iter()
+        .map(|&(y, z, w)| w - d * y - c * z)
+        .sum::<f64>()
+        / n;
+    let rho = if c > 0.0 { d / c } else { 0.0 };
+    // Total variance must stay non-negative at its minimum
+    a = a.max(-c * sigma * (1.0 - rho * rho).sqrt());
+    let parameters = [a, c, rho, m, sigma];
+    let error = points
+        .iter()
+        .map(|&(k, w)| (svi_total_variance(&parameters, k) - w).powi(2))
+        .sum();
+    Some((parameters, error))
+}
+
+/// A strike where the density implied by a smile is negative.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ButterflyArbitrage {
+    /// Strike at which the violation was found.
+    pub strike: Positive,
+    /// Log-moneyness of the strike against the forward.
+    pub log_moneyness: Decimal,
+    /// Value of the density condition `g(k)`, negative or undefined for a violation.
+    pub density: Decimal,
+}
+
+/// A raw SVI smile for a single expiration.
+///
+/// Implements [`VolatilitySmile`] and gives the implied volatility at any strike, so it
+/// can replace a linearly interpolated chain smile when pricing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SVISmile {
+    /// Raw SVI parameters.
+    pub parameters: SVIParameters,
+    /// Forward price of the underlying for this expiration.
+    pub forward: Positive,
+    /// Time to expiration in years.
+    pub years: Positive,
+    /// Strikes covered by `smile` and by the arbitrage checks.
+    pub strike_range: (Positive, Positive),
+    /// Root mean square error in implied volatility against the fitted quotes.
+    pub rmse: Decimal,
+}
+
+impl SVISmile {
+    /// Creates a smile from known parameters.
+    pub fn new(
+        parameters: SVIParameters,
+        forward: Positive,
+        years: Positive,
+        strike_range: (Positive, Positive),
+    ) -> Self {
+        SVISmile {
+            parameters,
+            forward,
+            years,
+            strike_range,
+            rmse: Decimal::ZERO,
+        }
+    }
+
+    /// Fits a raw SVI smile to the implied volatilities of an option chain.
+    ///
+    /// The forward is derived from the chain's underlying price, risk-free rate and
+    /// dividend yield. `m` and `sigma` are searched with Nelder-Mead while `a`, `b` and
+    /// `rho` are solved by least squares for each candidate, which keeps the fit stable.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if the chain has fewer than five
+    /// strikes with implied volatility or its expiration cannot be read, and
+    /// `VolatilityError::InvalidTime` if the chain has already expired.
+    pub fn fit(chain: &OptionChain) -> Result<Self, VolatilityError> {
+        let days = chain
+            .get_expiration()
+            .and_then(|expiration| expiration.get_days().ok())
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: format!("Invalid expiration date {}", chain.get_expiration_date()),
+            })?;
+        let years = days / DAYS_IN_A_YEAR;
+        if years == Positive::ZERO {
+            return Err(VolatilityError::InvalidTime {
+                time: years,
+                reason: "Cannot fit a smile to an expired chain".to_string(),
+            });
+        }
+        let forward = forward_price(
+            chain.underlying_price,
+            chain.risk_free_rate.unwrap_or(Decimal::ZERO),
+            chain.dividend_yield.unwrap_or(Positive::ZERO),
+            to_f64(years.to_dec()),
+        );
+        let points = chain_total_variances(chain, forward, to_f64(years.to_dec()));
+        let strike_range =
+            chain_strike_range(chain).ok_or_else(|| VolatilityError::CalibrationError {
+                reason: "No implied volatility quotes in the chain".to_string(),
+            })?;
+        Self::fit_points(
+            &points,
+            Positive::from(to_decimal(forward)?),
+            years,
+            strike_range,
+        )
+    }
+
+    fn fit_points(
+        points: &[(f64, f64)],
+        forward: Positive,
+        years: Positive,
+        strike_range: (Positive, Positive),
+    ) -> Result<Self, VolatilityError> {
+        if points.len() < 5 {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!(
+                    "SVI needs at least 5 implied volatility quotes, got {}",
+                    points.len()
+                ),
+            });
+        }
+        let lowest = points
+            .iter()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|&(k, _)| k)
+            .unwrap_or(0.0);
+        let objective = |x: &[f64]| {
+            svi_inner_fit(points, x[0], x[1].exp())
+                .map(|(_, error)| error)
+                .unwrap_or(f64::INFINITY)
+        };
+        let (best, _) = [(lowest, 0.1), (0.0, 0.3), (lowest, 0.02)]
+            .iter()
+            .map(|&(m, sigma)| {
+                nelder_mead(objective, &[m, f64::ln(sigma)], 0.1, MAX_ITERATIONS, 1e-18)
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: "SVI search did not start".to_string(),
+            })?;
+        let (parameters, _) = svi_inner_fit(points, best[0], best[1].exp()).ok_or_else(|| {
+            VolatilityError::CalibrationError {
+                reason: "Degenerate quotes: SVI least squares is singular".to_string(),
+            }
+        })?;
+
+        let t = to_f64(years.to_dec());
+        let squared_iv_error = points
+            .iter()
+            .map(|&(k, w)| {
+                let fitted = svi_total_variance(&parameters, k).max(0.0);
+                ((fitted / t).sqrt() - (w / t).sqrt()).powi(2)
+            })
+            .sum::<f64>()
+            / points.len() as f64;
+
+        let [a, b, rho, m, sigma] = parameters;
+        Ok(SVISmile {
+            parameters: SVIParameters::new(
+                to_decimal(a)?,
+                to_decimal(b)?,
+                to_decimal(rho)?,
+                to_decimal(m)?,
+                to_decimal(sigma)?,
+            ),
+            forward,
+            years,
+            strike_range,
+            rmse: to_decimal(squared_iv_error.sqrt())?,
+        })
+    }
+
+    /// Log-moneyness of `strike` against the forward.
+    pub fn log_moneyness(&self, strike: Positive) -> Decimal {
+        to_decimal((to_f64(strike.to_dec()) / to_f64(self.forward.to_dec())).ln())
+            .unwrap_or(Decimal::ZERO)
+    }
+
+    /// Total implied variance `σ²T` at `strike`.
+    pub fn total_variance(&self, strike: Positive) -> Decimal {
+        self.parameters.total_variance(self.log_moneyness(strike))
+    }
+
+    /// Implied volatility at `strike`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidPrice` if the smile gives a non-positive total
+    /// variance at `strike`.
+    pub fn implied_volatility(&self, strike: Positive) -> Result<Positive, VolatilityError> {
+        let variance = self.total_variance(strike);
+        if variance <= Decimal::ZERO {
+            return Err(VolatilityError::InvalidPrice {
+                price: strike,
+                reason: "SVI total variance is not positive at this strike".to_string(),
+            });
+        }
+        let volatility = (to_f64(variance) / to_f64(self.years.to_dec())).sqrt();
+        Ok(Positive::from(to_decimal(volatility)?))
+    }
+
+    /// Scans the strike range for butterfly arbitrage.
+    ///
+    /// # Returns
+    ///
+    /// The strikes where the implied density is negative, empty if the smile is free of
+    /// butterfly arbitrage over `strike_range`.
+    pub fn butterfly_arbitrage(&self) -> Vec<ButterflyArbitrage> {
+        let parameters = self.parameters.as_f64();
+        strike_grid(self.strike_range)
+            .into_iter()
+            .filter_map(|strike| {
+                let k = self.log_moneyness(strike);
+                let w = svi_total_variance(&parameters, to_f64(k));
+                let density = svi_density(&parameters, to_f64(k));
+                (w <= 0.0 || density.is_nan() || density < -1e-12).then(|| ButterflyArbitrage {
+                    strike,
+                    log_moneyness: k,
+                    density: Decimal::from_f64(density).unwrap_or(Decimal::MIN),
+                })
+            })
+            .collect()
+    }
+}
+
+impl VolatilitySmile for SVISmile {
+    /// Samples the fitted smile across `strike_range`.
+    fn smile(&self) -> Curve {
+        let points: BTreeSet<Point2D> = strike_grid(self.strike_range)
+            .into_iter()
+            .filter_map(|strike| {
+                self.implied_volatility(strike)
+                    .ok()
+                    .map(|iv| Point2D::new(strike.to_dec(), iv.to_dec()))
+            })
+            .collect();
+        Curve::new(points)
+    }
+}
+
+/// ATM total variance of one expiration of an [`SSVISurface`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SSVISlice {
+    /// Days to expiration.
+    pub days: Positive,
+    /// At-the-money total implied variance `θ`.
+    pub theta: Positive,
+}
+
+/// Surface SVI (Gatheral and Jacquier, 2014) fitted across the expirations of an
+/// [`OptionSeries`].
+///
+/// Total variance is `w(k, θ) = θ/2 * (1 + ρφk + sqrt((φk + ρ)² + 1 - ρ²))` with the
+/// power-law `φ(θ) = η / (θ^γ (1 + θ)^(1 - γ))`. The fit keeps `η(1 + |ρ|) <= 2` and
+/// `γ <= 1/2`, and makes `θ` non-decreasing in maturity, which rules out butterfly and
+/// calendar arbitrage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SSVISurface {
+    /// Ticker of the underlying.
+    pub symbol: String,
+    /// Spot price of the underlying.
+    pub underlying_price: Positive,
+    /// Risk-free rate used for forwards.
+    pub risk_free_rate: Decimal,
+    /// Dividend yield used for forwards.
+    pub dividend_yield: Positive,
+    /// Correlation parameter `ρ`.
+    pub rho: Decimal,
+    /// Curvature level `η`.
+    pub eta: Decimal,
+    /// Curvature decay `γ`.
+    pub gamma: Decimal,
+    /// ATM total variance per expiration, ordered by maturity.
+    pub slices: Vec<SSVISlice>,
+    /// Strikes covered by the surface and by the arbitrage checks.
+    pub strike_range: (Positive, Positive),
+    /// Root mean square error in implied volatility against the fitted quotes.
+    pub rmse: Decimal,
+}
+
+fn ssvi_phi(theta: f64, eta: f64, gamma: f64) -> f64 {
+    eta / (theta.powf(gamma) * (1.0 + theta).powf(1.0 - gamma))
+}
+
+fn ssvi_total_variance(k: f64, theta: f64, rho: f64, eta: f64, gamma: f64) -> f64 {
+    let phi = ssvi_phi(theta, eta, gamma);
+    theta / 2.0 * (1.0 + rho * phi * k + ((phi * k + rho).powi(2) + 1.0 - rho * rho).sqrt())
+}
+
+/// Maps unconstrained search variables to `(ρ, η, γ)` inside the no-arbitrage region.
+fn ssvi_parameters(x: &[f64]) -> (f64, f64, f64) {
+    let logistic = |v: f64| 1.0 / (1.0 + (-v).exp());
+    let rho = 0.999 * x[0].tanh();
+    let eta = 2.0 / (1.0 + rho.abs()) * logistic(x[1]);
+    let gamma = 0.5 * logistic(x[2]);
+    (rho, eta, gamma)
+}
+
+/// Linear interpolation of `(x, y)` points at `x = 0`, flat beyond the ends.
+fn interpolate_at_zero(points: &[(f64, f64)]) -> Option<f64> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (first, last) = (sorted.first()?, sorted.last()?);
+    if first.0 >= 0.0 {
+        return Some(first.1);
+    }
+    if last.0 <= 0.0 {
+        return Some(last.1);
+    }
+    sorted.windows(2).find_map(|pair| {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        (x0 <= 0.0 && x1 >= 0.0).then(|| y0 + (y1 - y0) * (0.0 - x0) / (x1 - x0))
+    })
+}
+
+impl SSVISurface {
+    /// Fits an SSVI surface to every chain of an option series.
+    ///
+    /// `θ` for each expiration is the market ATM total variance, interpolated at the
+    /// forward and made non-decreasing in maturity. `ρ`, `η` and `γ` are then fitted
+    /// to all quotes at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::CalibrationError` if the series has no unexpired chain
+    /// with implied volatility quotes, or fewer than three quotes in total.
+    pub fn fit(series: &OptionSeries) -> Result<Self, VolatilityError> {
+        let rate = series.risk_free_rate.unwrap_or(Decimal::ZERO);
+        let dividend_yield = series.dividend_yield.unwrap_or(Positive::ZERO);
+        let mut fitted_slices = Vec::new();
+        let mut strike_range: Option<(Positive, Positive)> = None;
+        for (expiration, chain) in &series.chains {
+            let days = expiration
+                .get_days()
+                .map_err(|e| VolatilityError::CalibrationError {
+                    reason: e.to_string(),
+                })?;
+            if days == Positive::ZERO {
+                continue;
+            }
+            let years = to_f64((days / DAYS_IN_A_YEAR).to_dec());
+            let forward = forward_price(series.underlying_price, rate, dividend_yield, years);
+            let points = chain_total_variances(chain, forward, years);
+            let Some(theta) = interpolate_at_zero(&points) else {
+                continue;
+            };
+            if let Some((low, high)) = chain_strike_range(chain) {
+                strike_range = Some(match strike_range {
+                    Some((min, max)) => (min.min(low), max.max(high)),
+                    None => (low, high),
+                });
+            }
+            fitted_slices.push((days, years, theta, points));
+        }
+        fitted_slices.sort_by_key(|(days, ..)| *days);
+
+        // A running maximum of the ATM total variance removes calendar arbitrage
+        let mut running = 0.0_f64;
+        let mut thetas = Vec::with_capacity(fitted_slices.len());
+        let mut quotes: Vec<(f64, f64, f64, usize)> = Vec::new();
+        for (index, (_, years, theta, points)) in fitted_slices.iter().enumerate() {
+            running = running.max(*theta);
+            thetas.push(running);
+            quotes.extend(points.iter().map(|&(k, w)| (k, w, *years, index)));
+        }
+        if quotes.len() < 3 {
+            return Err(VolatilityError::CalibrationError {
+                reason: format!(
+                    "SSVI needs at least 3 implied volatility quotes, got {}",
+                    quotes.len()
+                ),
+            });
+        }
+        let strike_range =
+            strike_range.unwrap_or((series.underlying_price, series.underlying_price));
+        let slices = fitted_slices
+            .iter()
+            .zip(&thetas)
+            .map(|((days, ..), theta)| {
+                Ok(SSVISlice {
+                    days: *days,
+                    theta: Positive::from(to_decimal(*theta)?),
+                })
+            })
+            .collect::<Result<Vec<_>, VolatilityError>>()?;
+
+        let objective = |x: &[f64]| {
+            let (rho, eta, gamma) = ssvi_parameters(x);
+            quotes
+                .iter()
+                .map(|&(k, w, _, index)| {
+                    (ssvi_total_variance(k, thetas[index], rho, eta, gamma) - w).powi(2)
+                })
+                .sum::<f64>()
+        };
+        let (best, _) = [[0.0, 0.0, 0.0], [-1.0, 1.0, 0.0], [0.5, -1.0, 1.0]]
+            .iter()
+            .map(|start| nelder_mead(objective, start, 0.5, MAX_ITERATIONS, 1e-20))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .ok_or_else(|| VolatilityError::CalibrationError {
+                reason: "SSVI search did not start".to_string(),
+            })?;
+        let (rho, eta, gamma) = ssvi_parameters(&best);
+        let squared_iv_error = quotes
+            .iter()
+            .map(|&(k, w, years, index)| {
+                let fitted = ssvi_total_variance(k, thetas[index], rho, eta, gamma).max(0.0);
+                ((fitted / years).sqrt() - (w / years).sqrt()).powi(2)
+            })
+            .sum::<f64>()
+            / quotes.len() as f64;
+
+        Ok(SSVISurface {
+            symbol: series.symbol.clone(),
+            underlying_price: series.underlying_price,
+            risk_free_rate: rate,
+            dividend_yield,
+            rho: to_decimal(rho)?,
+            eta: to_decimal(eta)?,
+            gamma: to_decimal(gamma)?,
+            slices,
+            strike_range,
+            rmse: to_decimal(squared_iv_error.sqrt())?,
+        })
+    }
+
+    /// ATM total variance at `days`, linear in time between the fitted expirations
+    /// and with constant ATM volatility outside them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` if `days` is zero or the surface has no
+    /// slices.
+    pub fn theta(&self, days: Positive) -> Result<Positive, VolatilityError> {
+        let invalid = |reason: &str| VolatilityError::InvalidTime {
+            time: days,
+            reason: reason.to_string(),
+        };
+        if days == Positive::ZERO {
+            return Err(invalid("SSVI is not defined at zero days"));
+        }
+        let (first, last) = match (self.slices.first(), self.slices.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Err(invalid("The surface has no slices")),
+        };
+        if days <= first.days {
+            return Ok(first.theta * days / first.days);
+        }
+        if days >= last.days {
+            return Ok(last.theta * days / last.days);
+        }
+        self.slices
+            .windows(2)
+            .find(|pair| days >= pair[0].days && days <= pair[1].days)
+            .map(|pair| {
+                let (near, far) = (pair[0], pair[1]);
+                let weight = ((days - near.days) / (far.days - near.days)).to_dec();
+                Positive::from(near.theta.to_dec() + (far.theta - near.theta).to_dec() * weight)
+            })
+            .ok_or_else(|| invalid("Could not bracket the maturity"))
+    }
+
+    /// Total variance at log forward-moneyness `k` and `days`.
+    pub(super) fn total_variance_f64(
+        &self,
+        k: f64,
+        days: Positive,
+    ) -> Result<f64, VolatilityError> {
+        let theta = to_f64(self.theta(days)?.to_dec());
+        Ok(ssvi_total_variance(
+            k,
+            theta,
+            to_f64(self.rho),
+            to_f64(self.eta),
+            to_f64(self.gamma),
+        ))
+    }
+
+    /// Forward price at `days`.
+    pub fn forward(&self, days: Positive) -> Positive {
+        let years = to_f64((days / DAYS_IN_A_YEAR).to_dec());
+        let forward = forward_price(
+            self.underlying_price,
+            self.risk_free_rate,
+            self.dividend_yield,
+            years,
+        );
+        Positive::from(Decimal::from_f64(forward).unwrap_or(self.underlying_price.to_dec()))
+    }
+
+    /// Raw SVI smile of the surface at `days`.
+    ///
+    /// Every SSVI slice is an SVI smile with `a = θ(1 - ρ²)/2`, `b = θφ/2`, `m = -ρ/φ`
+    /// and `σ = sqrt(1 - ρ²)/φ`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` if `days` is zero or the surface has no
+    /// slices.
+    pub fn smile_at(&self, days: Positive) -> Result<SVISmile, VolatilityError> {
+        let theta = to_f64(self.theta(days)?.to_dec());
+        let (rho, eta, gamma) = (to_f64(self.rho), to_f64(self.eta), to_f64(self.gamma));
+        let phi = ssvi_phi(theta, eta, gamma);
+        let parameters = SVIParameters::new(
+            to_decimal(theta / 2.0 * (1.0 - rho * rho))?,
+            to_decimal(theta * phi / 2.0)?,
+            self.rho,
+            to_decimal(-rho / phi)?,
+            to_decimal((1.0 - rho * rho).sqrt() / phi)?,
+        );
+        Ok(SVISmile::new(
+            parameters,
+            self.forward(days),
+            days / DAYS_IN_A_YEAR,
+            self.strike_range,
+        ))
+    }
+
+    /// Implied volatility at any strike and maturity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` if `days` is zero or the surface has no
+    /// slices.
+    pub fn implied_volatility(
+        &self,
+        strike: Positive,
+        days: Positive,
+    ) -> Result<Positive, VolatilityError> {
+        self.smile_at(days)?.implied_volatility(strike)
+    }
+
+    /// Sets the implied volatility of an option from the surface, using its strike and
+    /// time to expiration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `VolatilityError::InvalidTime` if the option has expired or its
+    /// expiration cannot be read.
+    pub fn apply_to(&self, option: &mut Options) -> Result<(), VolatilityError> {
+        let days = option
+            .expiration_date
+            .get_days()
+            .map_err(|e| VolatilityError::OptionError {
+                reason: e.to_string(),
+            })?;
+        option.implied_volatility = self.implied_volatility(option.strike_price, days)?;
+        Ok(())
+    }
+
+    /// Checks that total variance is non-decreasing in maturity at every strike of the
+    /// strike range, between consecutive fitted expirations.
+    pub fn calendar_arbitrage(&self) -> Vec<CalendarArbitrage> {
+        let mut violations = Vec::new();
+        for pair in self.slices.windows(2) {
+            let (Ok(near), Ok(far)) = (self.smile_at(pair[0].days), self.smile_at(pair[1].days))
+            else {
+                continue;
+            };
+            for strike in strike_grid(self.strike_range) {
+                let near_total_variance = near.total_variance(strike);
+                let far_total_variance = far.total_variance(strike);
+                if far_total_variance < near_total_variance - Decimal::new(1, 12) {
+                    violations.push(CalendarArbitrage {
+                        strike,
+                        near_days: pair[0].days,
+                        far_days: pair[1].days,
+                        near_total_variance,
+                        far_total_variance,
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// Checks every fitted expiration for butterfly arbitrage.
+    pub fn butterfly_arbitrage(&self) -> Vec<(Positive, ButterflyArbitrage)> {
+        self.slices
+            .iter()
+            .filter_map(|slice| {
+                self.smile_at(slice.days)
+                    .ok()
+                    .map(|smile| (slice.days, smile))
+            })
+            .flat_map(|(days, smile)| {
+                smile
+                    .butterfly_arbitrage()
+                    .into_iter()
+                    .map(move |violation| (days, violation))
+            })
+            .collect()
+    }
+
+    /// Samples the surface as `(strike, days to expiration, implied volatility)` points
+    /// over the strike range and the fitted expirations.
+    pub fn surface(&self) -> Surface {
+        let points: BTreeSet<Point3D> = self
+            .slices
+            .iter()
+            .filter_map(|slice| {
+                self.smile_at(slice.days)
+                    .ok()
+                    .map(|smile| (slice.days, smile))
+            })
+            .flat_map(|(days, smile)| {
+                strike_grid(self.strike_range)
+                    .into_iter()
+                    .filter_map(move |strike| {
+                        smile
+                            .implied_volatility(strike)
+                            .ok()
+                            .map(|iv| Point3D::new(strike.to_dec(), days.to_dec(), iv.to_dec()))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        Surface::new(points)
+    }
+}
+
+#[cfg(test)]
+mod tests_svi {
+    use super::*;
+    use crate::utils::time::get_x_days_formatted_pos;
+    use crate::{ExpirationDate, pos};
+    use rust_decimal::MathematicalOps;
+    use rust_decimal_macros::dec;
+
+    fn true_smile() -> SVIParameters {
+        SVIParameters::new(dec!(0.004), dec!(0.05), dec!(-0.4), dec!(0.02), dec!(0.15))
+    }
+
+    fn chain_from_svi(parameters: &SVIParameters, days: Positive) -> OptionChain {
+        let date = get_x_days_formatted_pos(days);
+        let mut chain = OptionChain::new("TEST", pos!(100.0), date, None, None);
+        let years = to_f64((chain.get_expiration().unwrap().get_days().unwrap() / 365.0).to_dec());
+        for strike in (70..=130).step_by(5) {
+            let k = (strike as f64 / 100.0).ln();
+            let w = svi_total_variance(&parameters.as_f64(), k);
+            chain.add_option(
+                pos!(strike as f64),
+                None,
+                None,
+                None,
+                None,
+                pos!((w / years).sqrt()),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        chain
+    }
+
+    #[test]
+    fn test_svi_fit_recovers_smile() {
+        let chain = chain_from_svi(&true_smile(), pos!(60.0));
+        let smile = SVISmile::fit(&chain).unwrap();
+        assert!(smile.rmse < dec!(0.0005), "rmse {}", smile.rmse);
+        for option in &chain.options {
+            let fitted = smile.implied_volatility(option.strike_price).unwrap();
+            assert!((fitted.to_dec() - option.implied_volatility.to_dec()).abs() < dec!(0.001));
+        }
+        // Between quoted strikes
+        let years = smile.years.to_dec();
+        let w = true_smile().total_variance(smile.log_moneyness(pos!(97.5)));
+        let expected = (w / years).sqrt().unwrap();
+        let fitted = smile.implied_volatility(pos!(97.5)).unwrap().to_dec();
+        assert!((fitted - expected).abs() < dec!(0.001));
+        assert_eq!(smile.strike_range, (pos!(70.0), pos!(130.0)));
+        assert!(smile.butterfly_arbitrage().is_empty());
+    }
+
+    #[test]
+    fn test_svi_smile_curve() {
+        let smile = SVISmile::fit(&chain_from_svi(&true_smile(), pos!(30.0))).unwrap();
+        let curve = smile.smile();
+        assert_eq!(curve.points.len(), GRID_POINTS);
+        assert_eq!(curve.x_range, (dec!(70), dec!(130)));
+        // Negative rho: the smile is skewed towards low strikes
+        let first = curve.points.iter().next().unwrap();
+        let last = curve.points.iter().last().unwrap();
+        assert!(first.y > last.y);
+    }
+
+    #[test]
+    fn test_svi_requires_quotes() {
+        let date = get_x_days_formatted_pos(pos!(30.0));
+        let mut chain = OptionChain::new("TEST", pos!(100.0), date, None, None);
+        for strike in [95.0, 100.0, 105.0] {
+            chain.add_option(
+                pos!(strike),
+                None,
+                None,
+                None,
+                None,
+                pos!(0.2),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+        }
+        assert!(matches!(
+            SVISmile::fit(&chain),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_butterfly_arbitrage_detected() {
+        // Axel Vogt's example of an SVI slice with butterfly arbitrage
+        let parameters = SVIParameters::new(
+            dec!(-0.0410),
+            dec!(0.1331),
+            dec!(0.3060),
+            dec!(0.3586),
+            dec!(0.4153),
+        );
+        let smile = SVISmile::new(
+            parameters,
+            pos!(100.0),
+            pos!(1.0),
+            (pos!(25.0), pos!(400.0)),
+        );
+        let violations = smile.butterfly_arbitrage();
+        assert!(!violations.is_empty());
+        assert!(violations.iter().all(|v| v.density < Decimal::ZERO));
+
+        let clean = SVISmile::new(
+            true_smile(),
+            pos!(100.0),
+            pos!(1.0),
+            (pos!(25.0), pos!(400.0)),
+        );
+        assert!(clean.butterfly_arbitrage().is_empty());
+    }
+
+    fn series_from_ssvi(rho: f64, eta: f64, gamma: f64, atm: &[(f64, f64)]) -> OptionSeries {
+        let mut series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        for &(days, atm_vol) in atm {
+            let years = days / 365.0;
+            let theta = atm_vol * atm_vol * years;
+            let mut chain = OptionChain::new(
+                "TEST",
+                pos!(100.0),
+                get_x_days_formatted_pos(pos!(days)),
+                None,
+                None,
+            );
+            for strike in (80..=120).step_by(5) {
+                let k = (strike as f64 / 100.0).ln();
+                let w = ssvi_total_variance(k, theta, rho, eta, gamma);
+                chain.add_option(
+                    pos!(strike as f64),
+                    None,
+                    None,
+                    None,
+                    None,
+                    pos!((w / years).sqrt()),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+            }
+            series
+                .chains
+                .insert(ExpirationDate::Days(pos!(days)), chain);
+        }
+        series
+    }
+
+    #[test]
+    fn test_ssvi_fit_recovers_surface() {
+        let series = series_from_ssvi(-0.5, 1.2, 0.4, &[(30.0, 0.25), (60.0, 0.23), (90.0, 0.22)]);
+        let surface = SSVISurface::fit(&series).unwrap();
+        assert!(surface.rmse < dec!(0.001), "rmse {}", surface.rmse);
+        assert_eq!(surface.slices.len(), 3);
+        assert!((surface.rho - dec!(-0.5)).abs() < dec!(0.05));
+
+        let atm = surface.implied_volatility(pos!(100.0), pos!(60.0)).unwrap();
+        assert!((atm.to_dec() - dec!(0.23)).abs() < dec!(0.001));
+        // Between expirations the ATM total variance is interpolated linearly
+        let theta = surface.theta(pos!(45.0)).unwrap().to_dec();
+        let expected = (dec!(0.0625) * dec!(30) + dec!(0.0529) * dec!(60)) / dec!(2) / dec!(365);
+        assert!((theta - expected).abs() < dec!(1e-9));
+
+        assert!(surface.calendar_arbitrage().is_empty());
+        assert!(surface.butterfly_arbitrage().is_empty());
+        assert_eq!(surface.surface().points.len(), 3 * GRID_POINTS);
+    }
+
+    #[test]
+    fn test_ssvi_smile_matches_surface() {
+        let series = series_from_ssvi(-0.3, 1.0, 0.3, &[(30.0, 0.2), (90.0, 0.2)]);
+        let surface = SSVISurface::fit(&series).unwrap();
+        let smile = surface.smile_at(pos!(45.0)).unwrap();
+        for strike in [pos!(85.0), pos!(100.0), pos!(115.0)] {
+            assert_eq!(
+                smile.implied_volatility(strike).unwrap(),
+                surface.implied_volatility(strike, pos!(45.0)).unwrap()
+            );
+        }
+        let mut option = crate::model::utils::create_sample_option(
+            crate::model::types::OptionStyle::Call,
+            crate::model::types::Side::Long,
+            pos!(100.0),
+            Positive::ONE,
+            pos!(110.0),
+            pos!(0.5),
+        );
+        surface.apply_to(&mut option).unwrap();
+        assert_eq!(
+            option.implied_volatility,
+            surface.implied_volatility(pos!(110.0), pos!(30.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_ssvi_removes_calendar_arbitrage() {
+        // ATM total variance falls from 30 to 60 days in the quotes
+        let series = series_from_ssvi(-0.3, 1.0, 0.3, &[(30.0, 0.40), (60.0, 0.20)]);
+        assert!(!series.calendar_arbitrage().unwrap().is_empty());
+        let surface = SSVISurface::fit(&series).unwrap();
+        assert!(surface.slices[1].theta >= surface.slices[0].theta);
+        assert!(surface.calendar_arbitrage().is_empty());
+    }
+
+    #[test]
+    fn test_ssvi_requires_quotes() {
+        let series = OptionSeries::new("TEST".to_string(), pos!(100.0));
+        assert!(matches!(
+            SSVISurface::fit(&series),
+            Err(VolatilityError::CalibrationError { .. })
+        ));
+        assert!(surface_without_slices().theta(pos!(30.0)).is_err());
+    }
+
+    fn surface_without_slices() -> SSVISurface {
+        SSVISurface {
+            symbol: "TEST".to_string(),
+            underlying_price: pos!(100.0),
+            risk_free_rate: Decimal::ZERO,
+            dividend_yield: Positive::ZERO,
+            rho: Decimal::ZERO,
+            eta: Decimal::ONE,
+            gamma: dec!(0.5),
+            slices: Vec::new(),
+            strike_range: (pos!(90.0), pos!(110.0)),
+            rmse: Decimal::ZERO,
+        }
+    }
+}
@@ -7,11 +7,11 @@ use crate::constants::{MAX_VOLATILITY, MIN_VOLATILITY};
 use crate::error::VolatilityError;
 use crate::model::decimal::decimal_normal_sample;
 use crate::utils::time::TimeFrame;
+use crate::volatility::{ImpliedVolatilityConfig, implied_volatility_with_config};
 use crate::{ExpirationDate, OptionStyle, OptionType, Options, Side};
 use crate::{Positive, pos};
 use num_traits::{FromPrimitive, ToPrimitive};
 use rand::random;
-use rayon::prelude::*;
 use rust_decimal::{Decimal, MathematicalOps};
 use std::error::Error;
 
@@ -86,59 +86,38 @@ pub fn ewma_volatility(
 /// Calculates the implied volatility of an option given its market price.
 ///
 /// This function uses the Newton-Raphson method to iteratively approximate the implied
-/// volatility that corresponds to the observed market price of the option. The implied
-/// volatility is updated within the `Options` struct provided as a mutable reference.
+/// volatility that corresponds to the observed market price of the option, falling back
+/// to Brent's method when Newton does not converge. The implied volatility is updated
+/// within the `Options` struct provided as a mutable reference.
 ///
 /// # Parameters
 /// - `market_price`: The observed market price of the option.
 /// - `options`: A mutable reference to an `Options` struct, which should contain the necessary
 ///   methods and fields such as `implied_volatility`, `calculate_price_black_scholes()`, and `vega()`.
-/// - `max_iterations`: The maximum number of iterations allowed for the Newton-Raphson method.
+/// - `max_iterations`: The maximum number of iterations allowed for the Newton-Raphson method
+///   before switching to Brent's method.
 ///
 /// # Returns
 /// The function returns the estimated implied volatility of the option.
 ///
 /// # Remarks
-/// - If the price difference between the calculated and market price is within the tolerated threshold (`TOLERANCE`),
-///   the current implied volatility is returned.
-/// - The function ensures that the implied volatility stays positive.
+/// - The solver stops once the model price is within `IV_TOLERANCE` of the market price.
+/// - Use [`solve_implied_volatility`](crate::volatility::solve_implied_volatility) to also
+///   obtain the convergence diagnostics.
 ///
 pub fn implied_volatility(
     market_price: Positive,
     options: &mut Options,
     max_iterations: i64,
 ) -> Result<Positive, Box<dyn Error>> {
-    let base_option = options.clone();
-    let iterations = 100 * max_iterations;
-    let result = (1..iterations)
-        .into_par_iter()
-        .map(|i| {
-            let mut option = base_option.clone();
-            let iv = Positive::from(i as f64 / iterations as f64);
-            option.implied_volatility = iv;
-
-            match option.calculate_price_black_scholes() {
-                Ok(price) => {
-                    let diff = (price - market_price.to_dec()).abs();
-                    Some((iv, diff))
-                }
-                Err(_) => None,
-            }
-        })
-        .filter_map(|x| x) // Remove errors
-        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-
-    match result {
-        Some((best_iv, _)) => {
-            let iv = best_iv.clamp(MIN_VOLATILITY, MAX_VOLATILITY);
-            if iv == Positive::from(1f64 / iterations as f64) {
-                Err("Implied volatility not found".into())
-            } else {
-                Ok(iv)
-            }
-        }
-        None => Err("No valid volatility found".into()),
-    }
+    let config = ImpliedVolatilityConfig {
+        max_newton_iterations: u32::try_from(max_iterations.max(0)).unwrap_or(u32::MAX),
+        ..Default::default()
+    };
+    let result = implied_volatility_with_config(options, market_price.to_dec(), &config)?;
+    let iv = result.volatility.clamp(MIN_VOLATILITY, MAX_VOLATILITY);
+    options.implied_volatility = iv;
+    Ok(iv)
 }
 
 /// Calculates the implied volatility (IV) of an option given its parameters.
@@ -744,7 +723,7 @@ mod tests_implied_volatility {
 
         let iv = result.unwrap();
         assert!(iv >= MIN_VOLATILITY && iv <= MAX_VOLATILITY);
-        assert_pos_relative_eq!(iv, pos!(0.437), pos!(1e-3));
+        assert_pos_relative_eq!(iv, pos!(0.43745), pos!(1e-4));
     }
 
     #[test]